@@ -0,0 +1,396 @@
+//! Export of timelines to `bevy_animation`, enabled with the
+//! `animation` feature.
+//!
+//! [`AnimationClipExporter`] scrubs a timeline and records the
+//! listed fields into an [`AnimationClip`], so animations authored
+//! with MotionGfx can play on a regular [`AnimationPlayer`]:
+//!
+//! ```ignore
+//! let clip = AnimationClipExporter::new(60.0)
+//!     .with_transform(cube, AnimationTargetId::from_name(&name))
+//!     .export(&mut manager, world, &timeline_id)
+//!     .unwrap();
+//!
+//! let (graph, node) = add_clip_graph(clip, &mut clips, &mut graphs);
+//! ```
+//!
+//! The exported entities still need an [`AnimationTarget`] pointing
+//! at the player, as with any other clip.
+//!
+//! The other way around, [`node_weight`] and [`node_speed`] let a
+//! timeline orchestrate the clips playing on an [`AnimationPlayer`],
+//! e.g. blending from an imported walk into a run:
+//!
+//! ```ignore
+//! player.play(walk).repeat();
+//! player.play(run).repeat().set_weight(0.0);
+//!
+//! let blend = animation::crossfade(
+//!     &mut builder,
+//!     character,
+//!     walk,
+//!     run,
+//!     ms(500),
+//! );
+//! let track = track::delay(s(2), blend);
+//! ```
+//!
+//! Nodes only have a weight and speed while they are playing, so
+//! they must be played before the timeline reaches them.
+//!
+//! [`AnimationTarget`]: bevy_animation::AnimationTarget
+
+use core::time::Duration;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use bevy_animation::animation_curves::{
+    AnimatableCurve, AnimatableProperty, AnimatedField,
+    AnimationCompatibleCurve,
+};
+use bevy_animation::graph::{AnimationGraph, AnimationNodeIndex};
+use bevy_animation::{
+    ActiveAnimation, AnimationClip, AnimationPlayer,
+    AnimationTargetId, animated_field,
+};
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::component::Mutable;
+use bevy_ecs::prelude::*;
+use bevy_math::StableInterpolate;
+use bevy_math::curve::{Interval, SampleAutoCurve};
+use bevy_platform::collections::HashMap;
+use bevy_platform::sync::{LazyLock, Mutex};
+use bevy_transform::components::Transform;
+use motiongfx::field::VirtualField;
+use motiongfx::prelude::*;
+
+use crate::manager::{MotionGfxManager, TimelineId};
+use crate::world::BevyTimelineBuilder;
+
+/// Records fields of entities into an [`AnimationClip`] by sampling
+/// a timeline at a fixed rate.
+pub struct AnimationClipExporter {
+    fps: f32,
+    tracks: Vec<Box<dyn ExportTrack>>,
+}
+
+impl AnimationClipExporter {
+    /// Creates an exporter sampling at `fps` samples per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is not positive and finite.
+    pub fn new(fps: f32) -> Self {
+        assert!(
+            fps > 0.0 && fps.is_finite(),
+            "sample rate must be positive and finite, got {fps}"
+        );
+
+        Self {
+            fps,
+            tracks: Vec::new(),
+        }
+    }
+
+    /// Records the translation, rotation and scale of `entity` under
+    /// `target`.
+    pub fn with_transform(
+        self,
+        entity: Entity,
+        target: AnimationTargetId,
+    ) -> Self {
+        self.with_field(
+            entity,
+            target,
+            path!(<Transform>::translation),
+            animated_field!(Transform::translation),
+        )
+        .with_field(
+            entity,
+            target,
+            path!(<Transform>::rotation),
+            animated_field!(Transform::rotation),
+        )
+        .with_field(
+            entity,
+            target,
+            path!(<Transform>::scale),
+            animated_field!(Transform::scale),
+        )
+    }
+
+    /// Records `field` of `entity` under `target`, played back
+    /// through `property`, usually made with [`animated_field!`].
+    pub fn with_field<C, P>(
+        mut self,
+        entity: Entity,
+        target: AnimationTargetId,
+        field: FieldAccessor<C, P::Property>,
+        property: P,
+    ) -> Self
+    where
+        C: Component<Mutability = Mutable>,
+        P: AnimatableProperty + Clone,
+        P::Property: StableInterpolate + Clone,
+        SampleAutoCurve<P::Property>:
+            AnimationCompatibleCurve<P::Property>,
+    {
+        self.tracks.push(Box::new(FieldTrack {
+            entity,
+            target,
+            field,
+            property,
+            samples: None,
+        }));
+        self
+    }
+
+    /// Scrubs the target track of the timeline from start to end and
+    /// builds a clip from the recorded fields.
+    ///
+    /// The timeline is scrubbed back to its original target time
+    /// afterwards. Fields missing on any sample are left out of the
+    /// clip. Returns [`None`] if the timeline does not exist.
+    pub fn export(
+        &self,
+        manager: &mut MotionGfxManager,
+        world: &mut World,
+        id: &TimelineId,
+    ) -> Option<AnimationClip> {
+        let timeline = manager.get_timeline(id)?;
+        let duration =
+            timeline.tracks()[timeline.target_index()].duration();
+        let original_time = timeline.target_time();
+
+        let mut recorders = self
+            .tracks
+            .iter()
+            .map(|track| track.recorder())
+            .collect::<Vec<_>>();
+
+        let step = Duration::from_secs_f32(self.fps.recip());
+        let mut time = Duration::ZERO;
+        loop {
+            manager.scrub_timeline(world, id, time);
+            for recorder in recorders.iter_mut() {
+                recorder.record(world);
+            }
+
+            if time >= duration {
+                break;
+            }
+            time = time.saturating_add(step).min(duration);
+        }
+
+        manager.scrub_timeline(world, id, original_time);
+
+        let mut clip = AnimationClip::default();
+        for recorder in recorders {
+            recorder.finish(&mut clip, duration.as_secs_f32());
+        }
+        Some(clip)
+    }
+}
+
+/// Adds `clip` to `clips` along with a single clip graph playing it.
+pub fn add_clip_graph(
+    clip: AnimationClip,
+    clips: &mut Assets<AnimationClip>,
+    graphs: &mut Assets<AnimationGraph>,
+) -> (Handle<AnimationGraph>, AnimationNodeIndex) {
+    let (graph, node) = AnimationGraph::from_clip(clips.add(clip));
+    (graphs.add(graph), node)
+}
+
+/// A parameter of the [`ActiveAnimation`] of a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum NodeParam {
+    Weight,
+    Speed,
+}
+
+/// Node fields of an [`AnimationPlayer`], shared so that every action
+/// on a node forms a single sequence.
+#[expect(clippy::type_complexity)]
+static NODE_FIELDS: LazyLock<
+    Mutex<
+        HashMap<
+            (AnimationNodeIndex, NodeParam),
+            VirtualField<AnimationPlayer, f32>,
+        >,
+    >,
+> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+fn node_field(
+    node: AnimationNodeIndex,
+    param: NodeParam,
+) -> VirtualField<AnimationPlayer, f32> {
+    type Get = fn(&ActiveAnimation) -> f32;
+    type Set = fn(&mut ActiveAnimation, f32);
+
+    let (get, set): (Get, Set) = match param {
+        NodeParam::Weight => {
+            (ActiveAnimation::weight, |a, weight| {
+                a.set_weight(weight);
+            })
+        }
+        NodeParam::Speed => (ActiveAnimation::speed, |a, speed| {
+            a.set_speed(speed);
+        }),
+    };
+
+    NODE_FIELDS
+        .lock()
+        .unwrap_or_else(|poison| poison.into_inner())
+        .entry((node, param))
+        .or_insert_with(|| {
+            VirtualField::new(
+                path!(<AnimationPlayer>),
+                move |player: &AnimationPlayer| {
+                    player.animation(node).map_or(0.0, get)
+                },
+                move |player: &mut AnimationPlayer, value: f32| {
+                    if let Some(active) = player.animation_mut(node) {
+                        set(active, value);
+                    }
+                },
+            )
+        })
+        .clone()
+}
+
+/// The weight of the playing `node` of an [`AnimationPlayer`].
+///
+/// Reads as `0.0` and ignores writes while `node` is not playing.
+pub fn node_weight(
+    node: AnimationNodeIndex,
+) -> VirtualField<AnimationPlayer, f32> {
+    node_field(node, NodeParam::Weight)
+}
+
+/// The playback speed of the playing `node` of an
+/// [`AnimationPlayer`].
+///
+/// Reads as `0.0` and ignores writes while `node` is not playing.
+pub fn node_speed(
+    node: AnimationNodeIndex,
+) -> VirtualField<AnimationPlayer, f32> {
+    node_field(node, NodeParam::Speed)
+}
+
+/// Blends the [`AnimationPlayer`] of `player` from `from` to `to`,
+/// fading the weight of `from` to `0.0` and of `to` to `1.0` over
+/// `duration`.
+pub fn crossfade(
+    builder: &mut BevyTimelineBuilder<'_>,
+    player: Entity,
+    from: AnimationNodeIndex,
+    to: AnimationNodeIndex,
+    duration: Duration,
+) -> TrackFragment {
+    [
+        builder
+            .act_to(player, node_weight(from), 0.0)
+            .play(duration),
+        builder.act_to(player, node_weight(to), 1.0).play(duration),
+    ]
+    .ord_all()
+}
+
+/// A field to export, type erased so fields of any type can share
+/// an exporter.
+trait ExportTrack: Send + Sync {
+    /// An empty copy of this track to record samples into.
+    fn recorder(&self) -> Box<dyn ExportTrack>;
+
+    fn record(&mut self, world: &World);
+
+    fn finish(self: Box<Self>, clip: &mut AnimationClip, end: f32);
+}
+
+struct FieldTrack<C, P: AnimatableProperty> {
+    entity: Entity,
+    target: AnimationTargetId,
+    field: FieldAccessor<C, P::Property>,
+    property: P,
+    /// `None` once the field was missing on a sample.
+    samples: Option<Vec<P::Property>>,
+}
+
+impl<C, P> ExportTrack for FieldTrack<C, P>
+where
+    C: Component<Mutability = Mutable>,
+    P: AnimatableProperty + Clone,
+    P::Property: StableInterpolate + Clone,
+    SampleAutoCurve<P::Property>:
+        AnimationCompatibleCurve<P::Property>,
+{
+    fn recorder(&self) -> Box<dyn ExportTrack> {
+        Box::new(Self {
+            entity: self.entity,
+            target: self.target,
+            field: self.field,
+            property: self.property.clone(),
+            samples: Some(Vec::new()),
+        })
+    }
+
+    fn record(&mut self, world: &World) {
+        let value = world
+            .get::<C>(self.entity)
+            .map(|c| self.field.accessor.get_ref(c).clone());
+
+        match (&mut self.samples, value) {
+            (Some(samples), Some(value)) => samples.push(value),
+            _ => self.samples = None,
+        }
+    }
+
+    fn finish(self: Box<Self>, clip: &mut AnimationClip, end: f32) {
+        let Some(samples) = self.samples else {
+            return;
+        };
+        // A zero length timeline holds its only sample.
+        let samples = match samples.as_slice() {
+            [value] => alloc::vec![value.clone(), value.clone()],
+            _ => samples,
+        };
+        let Ok(domain) = Interval::new(0.0, end.max(f32::EPSILON))
+        else {
+            return;
+        };
+        let Ok(curve) = SampleAutoCurve::new(domain, samples) else {
+            return;
+        };
+
+        clip.add_curve_to_target(
+            self.target,
+            AnimatableCurve::new(self.property, curve),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_fields_follow_the_active_animation() {
+        let node = AnimationNodeIndex::new(1);
+        let weight = node_weight(node);
+        let mut player = AnimationPlayer::default();
+
+        weight.conversion().set(&mut player, 0.5);
+        assert_eq!(weight.conversion().get(&player), 0.0);
+
+        player.play(node);
+        weight.conversion().set(&mut player, 0.5);
+        node_speed(node).conversion().set(&mut player, 2.0);
+        assert_eq!(weight.conversion().get(&player), 0.5);
+        assert_eq!(player.animation(node).unwrap().speed(), 2.0);
+        assert_eq!(
+            node_weight(node).conversion().id(),
+            weight.conversion().id()
+        );
+    }
+}
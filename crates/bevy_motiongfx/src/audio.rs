@@ -0,0 +1,442 @@
+//! Audio cues synced to a timeline, enabled with the `audio` feature.
+//!
+//! [`AudioCues`] sits next to the [`TimelineId`] of a timeline and
+//! schedules audio sources at timestamps of its tracks:
+//!
+//! ```ignore
+//! app.add_plugins(AudioCuePlugin);
+//!
+//! commands.spawn((
+//!     motiongfx.add_timeline(timeline),
+//!     RealtimePlayer::new().with_playing(true),
+//!     AudioCues::new()
+//!         .with_cue(AudioCue::new(0, s(1), voiceover))
+//!         .with_cue(AudioCue::new(1, Duration::ZERO, music)),
+//! ));
+//! ```
+//!
+//! Cues only sound while the timeline plays forward. They pause with
+//! the timeline, stop when it jumps, and restart at the matching
+//! offset once it plays again, so seeking into the middle of a cue
+//! picks the audio up mid-clip.
+
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use bevy_app::prelude::*;
+use bevy_asset::Handle;
+use bevy_audio::prelude::*;
+use bevy_ecs::prelude::*;
+
+use crate::MotionGfxSystems;
+use crate::manager::{MotionGfxManager, TimelineId};
+
+/// Plays the [`AudioCues`] of every timeline.
+pub struct AudioCuePlugin;
+
+impl Plugin for AudioCuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            sync_audio_cues.after(MotionGfxSystems::Sample),
+        );
+    }
+}
+
+/// Audio sources scheduled on the timeline of this entity.
+#[derive(Component, Debug, Clone)]
+pub struct AudioCues {
+    cues: Vec<AudioCue>,
+    /// The largest forward step still treated as playback, anything
+    /// larger is a seek.
+    pub seek_threshold: Duration,
+    /// The `(track, time)` of the timeline on the last sync.
+    last: Option<(usize, Duration)>,
+}
+
+impl AudioCues {
+    pub const DEFAULT_SEEK_THRESHOLD: Duration =
+        Duration::from_millis(250);
+
+    pub fn new() -> Self {
+        Self {
+            cues: Vec::new(),
+            seek_threshold: Self::DEFAULT_SEEK_THRESHOLD,
+            last: None,
+        }
+    }
+
+    pub fn with_cue(mut self, cue: AudioCue) -> Self {
+        self.cues.push(cue);
+        self
+    }
+
+    /// Builder method for setting [`Self::seek_threshold`].
+    pub fn with_seek_threshold(
+        mut self,
+        threshold: Duration,
+    ) -> Self {
+        self.seek_threshold = threshold;
+        self
+    }
+
+    pub fn push(&mut self, cue: AudioCue) {
+        self.cues.push(cue);
+    }
+
+    pub fn cues(&self) -> &[AudioCue] {
+        &self.cues
+    }
+
+    /// Records the `(track, time)` of the timeline, returning how far
+    /// it played forward since the last sync, or `None` on jumps,
+    /// including any step backwards.
+    fn advance(
+        &mut self,
+        track: usize,
+        time: Duration,
+    ) -> Option<Duration> {
+        let (last_track, last_time) =
+            self.last.replace((track, time))?;
+        if last_track != track {
+            return None;
+        }
+
+        time.checked_sub(last_time)
+            .filter(|&step| step <= self.seek_threshold)
+    }
+}
+
+impl Default for AudioCues {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// An audio source starting at [`Self::start`] of a track.
+#[derive(Debug, Clone)]
+pub struct AudioCue {
+    /// The index of the track the cue belongs to.
+    pub track: usize,
+    /// Start time within the track.
+    pub start: Duration,
+    pub source: Handle<AudioSource>,
+    /// Settings of the spawned player, the mode and start position
+    /// are managed by the cue.
+    pub settings: PlaybackSettings,
+    /// Cuts the cue off after this long, otherwise it plays until
+    /// the source ends.
+    pub length: Option<Duration>,
+    /// The entity playing the cue.
+    player: Option<Entity>,
+}
+
+impl AudioCue {
+    pub fn new(
+        track: usize,
+        start: Duration,
+        source: Handle<AudioSource>,
+    ) -> Self {
+        Self {
+            track,
+            start,
+            source,
+            settings: PlaybackSettings::ONCE,
+            length: None,
+            player: None,
+        }
+    }
+
+    /// Builder method for setting [`Self::settings`].
+    pub fn with_settings(
+        mut self,
+        settings: PlaybackSettings,
+    ) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Builder method for setting [`Self::length`].
+    pub fn with_length(mut self, length: Duration) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    /// The offset into the cue at `time` of `track`, if it is within
+    /// the cue.
+    fn offset(
+        &self,
+        track: usize,
+        time: Duration,
+    ) -> Option<Duration> {
+        if track != self.track {
+            return None;
+        }
+
+        let offset = time.checked_sub(self.start)?;
+        match self.length {
+            Some(length) if offset >= length => None,
+            _ => Some(offset),
+        }
+    }
+
+    /// What the player of the cue does at `time` of `track`, after
+    /// the timeline moved forward by `step`, see
+    /// [`AudioCues::advance`].
+    fn action(
+        &self,
+        track: usize,
+        time: Duration,
+        step: Option<Duration>,
+    ) -> Option<CueAction> {
+        let playing = self.player.is_some();
+
+        match (self.offset(track, time), step) {
+            (None, _) | (_, None) => {
+                playing.then_some(CueAction::Stop)
+            }
+            (Some(offset), Some(step)) => {
+                match (playing, step.is_zero()) {
+                    // Only start on forward playback.
+                    (false, false) => Some(CueAction::Start(offset)),
+                    (false, true) => None,
+                    (true, false) => Some(CueAction::Play),
+                    (true, true) => Some(CueAction::Pause),
+                }
+            }
+        }
+    }
+}
+
+/// A change to the player of an [`AudioCue`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CueAction {
+    /// Spawns a player starting this far into the cue.
+    Start(Duration),
+    /// Despawns the player.
+    Stop,
+    /// Resumes the player if it is paused.
+    Play,
+    Pause,
+}
+
+fn sync_audio_cues(
+    mut commands: Commands,
+    motiongfx: Res<MotionGfxManager>,
+    mut q_cues: Query<(&TimelineId, &mut AudioCues)>,
+    q_sinks: Query<&AudioSink>,
+) {
+    for (id, mut cues) in q_cues.iter_mut() {
+        let Some(timeline) = motiongfx.get_timeline(id) else {
+            continue;
+        };
+        let track = timeline.curr_index();
+        let time = timeline.curr_time();
+        let step = cues.advance(track, time);
+
+        for cue in cues.cues.iter_mut() {
+            match cue.action(track, time, step) {
+                Some(CueAction::Start(offset)) => {
+                    let settings = PlaybackSettings {
+                        mode: PlaybackMode::Once,
+                        ..cue.settings
+                    }
+                    .with_start_position(
+                        offset.mul_f64(cue.settings.speed as f64),
+                    );
+
+                    cue.player = Some(
+                        commands
+                            .spawn((
+                                AudioPlayer::new(cue.source.clone()),
+                                settings,
+                            ))
+                            .id(),
+                    );
+                }
+                Some(CueAction::Stop) => {
+                    if let Some(player) = cue.player.take() {
+                        commands.entity(player).try_despawn();
+                    }
+                }
+                Some(CueAction::Play) => {
+                    if let Some(sink) =
+                        cue.player.and_then(|p| q_sinks.get(p).ok())
+                        && sink.is_paused()
+                    {
+                        sink.play();
+                    }
+                }
+                Some(CueAction::Pause) => {
+                    if let Some(sink) =
+                        cue.player.and_then(|p| q_sinks.get(p).ok())
+                    {
+                        sink.pause();
+                    }
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use motiongfx::prelude::*;
+    use motiongfx::time::{ms, s};
+
+    use super::*;
+
+    #[derive(Component)]
+    struct Angle {
+        radians: f32,
+    }
+
+    /// Two tracks of 2s, with a cue on the first one from 0.5s to
+    /// 1.5s.
+    fn setup() -> (App, Entity) {
+        let mut app = App::new();
+        app.init_resource::<MotionGfxManager>()
+            .add_systems(Update, sync_audio_cues);
+
+        let subject =
+            app.world_mut().spawn(Angle { radians: 0.0 }).id();
+        let mut motiongfx =
+            app.world_mut().resource_mut::<MotionGfxManager>();
+        let mut b = motiongfx.create_builder();
+        let tracks = [1.0, 0.0].map(|to| {
+            b.act_to(subject, path!(<Angle>::radians), to)
+                .play(s(2))
+                .compile()
+        });
+        b.add_tracks(tracks);
+        let timeline = b.compile();
+        let id = motiongfx.add_timeline(timeline);
+
+        let cue = AudioCue::new(0, ms(500), Handle::default())
+            .with_length(s(1));
+        let entity = app
+            .world_mut()
+            .spawn((id, AudioCues::new().with_cue(cue)))
+            .id();
+        app.world_mut().resource_scope(
+            |world, mut motiongfx: Mut<MotionGfxManager>| {
+                motiongfx.load_pending_timelines(world);
+            },
+        );
+
+        (app, entity)
+    }
+
+    /// Seeks to `time` of `track` and syncs the cues.
+    fn sync(
+        app: &mut App,
+        entity: Entity,
+        track: usize,
+        time: Duration,
+    ) {
+        let id = *app.world().get::<TimelineId>(entity).unwrap();
+        app.world_mut().resource_scope(
+            |world, mut motiongfx: Mut<MotionGfxManager>| {
+                motiongfx
+                    .get_timeline_mut(&id)
+                    .unwrap()
+                    .set_target_track(track)
+                    .set_target_time(time);
+                motiongfx.sample_timelines(world);
+            },
+        );
+        app.update();
+    }
+
+    /// The start positions of the spawned players.
+    fn players(app: &mut App) -> Vec<Option<Duration>> {
+        app.world_mut()
+            .query_filtered::<&PlaybackSettings, With<AudioPlayer>>()
+            .iter(app.world())
+            .map(|settings| settings.start_position)
+            .collect()
+    }
+
+    /// Plays forward into the cue, 0.6s in.
+    fn start(app: &mut App, entity: Entity) {
+        sync(app, entity, 0, s(1));
+        sync(app, entity, 0, ms(1100));
+        assert_eq!(players(app), [Some(ms(600))]);
+    }
+
+    #[test]
+    fn cues_start_mid_clip_on_forward_playback() {
+        let (mut app, entity) = setup();
+
+        sync(&mut app, entity, 0, Duration::ZERO);
+        // Seeking into the cue waits for playback to start it.
+        sync(&mut app, entity, 0, s(1));
+        assert!(players(&mut app).is_empty());
+
+        sync(&mut app, entity, 0, ms(1100));
+        assert_eq!(players(&mut app), [Some(ms(600))]);
+    }
+
+    #[test]
+    fn seeking_backwards_stops_the_cue() {
+        let (mut app, entity) = setup();
+        start(&mut app, entity);
+
+        sync(&mut app, entity, 0, ms(900));
+        assert!(players(&mut app).is_empty());
+
+        sync(&mut app, entity, 0, s(1));
+        assert_eq!(players(&mut app), [Some(ms(500))]);
+    }
+
+    #[test]
+    fn cues_stop_past_their_length() {
+        let (mut app, entity) = setup();
+        start(&mut app, entity);
+
+        // Playing on keeps the same player.
+        sync(&mut app, entity, 0, ms(1300));
+        assert_eq!(players(&mut app), [Some(ms(600))]);
+
+        sync(&mut app, entity, 0, ms(1500));
+        assert!(players(&mut app).is_empty());
+        sync(&mut app, entity, 0, ms(1600));
+        assert!(players(&mut app).is_empty());
+    }
+
+    #[test]
+    fn changing_tracks_stops_the_cue() {
+        let (mut app, entity) = setup();
+        start(&mut app, entity);
+
+        sync(&mut app, entity, 1, ms(1100));
+        assert!(players(&mut app).is_empty());
+        // The cue belongs to the first track only.
+        sync(&mut app, entity, 1, ms(1200));
+        assert!(players(&mut app).is_empty());
+    }
+
+    #[test]
+    fn players_pause_and_resume_with_the_timeline() {
+        let mut cue = AudioCue::new(0, ms(500), Handle::default());
+        let (paused, playing) = (Some(Duration::ZERO), Some(ms(16)));
+
+        assert_eq!(cue.action(0, s(1), paused), None);
+        assert_eq!(
+            cue.action(0, s(1), playing),
+            Some(CueAction::Start(ms(500)))
+        );
+
+        cue.player = Some(Entity::PLACEHOLDER);
+        assert_eq!(
+            cue.action(0, s(1), paused),
+            Some(CueAction::Pause)
+        );
+        assert_eq!(
+            cue.action(0, s(1), playing),
+            Some(CueAction::Play)
+        );
+        assert_eq!(cue.action(0, s(1), None), Some(CueAction::Stop));
+    }
+}
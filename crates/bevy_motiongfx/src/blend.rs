@@ -0,0 +1,140 @@
+//! Weighted blending between timelines that animate the same fields.
+//!
+//! Timelines without a [`BlendWeight`] overwrite the fields they
+//! animate. Timelines with one are sampled afterwards, in the order
+//! they were added, and only blend their weight of each value over
+//! what the earlier timelines wrote. Crossfading from a looping idle
+//! motion into a highlight is a matter of fading in the weight of the
+//! highlight:
+//!
+//! ```ignore
+//! commands.spawn((
+//!     motiongfx.add_timeline(highlight),
+//!     RealtimePlayer::new().with_playing(true),
+//!     BlendWeight::new(0.0).with_fade(1.0, ms(300)),
+//! ));
+//! ```
+//!
+//! Blending only happens on frames the blended timeline samples, so
+//! the timelines underneath should keep playing during the fade.
+
+use core::time::Duration;
+
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_time::prelude::*;
+
+use crate::MotionGfxSystems;
+use crate::manager::{MotionGfxManager, TimelineId};
+
+pub struct BlendPlugin;
+
+impl Plugin for BlendPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (fade_blend_weights, sync_blend_weights)
+                .chain()
+                .in_set(MotionGfxSystems::Controller),
+        );
+    }
+}
+
+/// Blends the timeline of this entity over the timelines without a
+/// weight instead of overwriting them.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct BlendWeight {
+    /// `0.0` leaves the fields untouched, `1.0` overwrites them.
+    pub weight: f32,
+    fade: Option<Fade>,
+}
+
+impl BlendWeight {
+    pub fn new(weight: f32) -> Self {
+        Self { weight, fade: None }
+    }
+
+    /// Builder method for [`Self::fade_to`].
+    pub fn with_fade(
+        mut self,
+        target: f32,
+        duration: Duration,
+    ) -> Self {
+        self.fade_to(target, duration);
+        self
+    }
+
+    /// Linearly moves [`Self::weight`] to `target` over `duration`,
+    /// replacing any fade in progress.
+    pub fn fade_to(&mut self, target: f32, duration: Duration) {
+        self.fade = Some(Fade {
+            from: self.weight,
+            to: target,
+            duration,
+            elapsed: Duration::ZERO,
+        });
+    }
+
+    #[inline]
+    pub fn is_fading(&self) -> bool {
+        self.fade.is_some()
+    }
+}
+
+impl Default for BlendWeight {
+    fn default() -> Self {
+        Self::new(1.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Fade {
+    from: f32,
+    to: f32,
+    duration: Duration,
+    elapsed: Duration,
+}
+
+fn fade_blend_weights(
+    mut q_weights: Query<&mut BlendWeight>,
+    time: Res<Time>,
+) {
+    for mut blend in
+        q_weights.iter_mut().filter(|blend| blend.is_fading())
+    {
+        let Some(mut fade) = blend.fade else {
+            continue;
+        };
+
+        fade.elapsed += time.delta();
+        if fade.elapsed >= fade.duration {
+            blend.weight = fade.to;
+            blend.fade = None;
+        } else {
+            let t = fade.elapsed.as_secs_f32()
+                / fade.duration.as_secs_f32();
+            blend.weight = fade.from + (fade.to - fade.from) * t;
+            blend.fade = Some(fade);
+        }
+    }
+}
+
+fn sync_blend_weights(
+    mut motiongfx: ResMut<MotionGfxManager>,
+    q_weights: Query<
+        (&TimelineId, &BlendWeight),
+        Changed<BlendWeight>,
+    >,
+    q_timelines: Query<&TimelineId>,
+    mut removed: RemovedComponents<BlendWeight>,
+) {
+    for (id, blend) in q_weights.iter() {
+        motiongfx.set_blend_weight(id, Some(blend.weight));
+    }
+
+    for entity in removed.read() {
+        if let Ok(id) = q_timelines.get(entity) {
+            motiongfx.set_blend_weight(id, None);
+        }
+    }
+}
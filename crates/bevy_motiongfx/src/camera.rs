@@ -0,0 +1,502 @@
+//! Reusable camera moves, enabled with the `camera` feature.
+//!
+//! Every move takes the camera entity and a duration and returns a
+//! [`TrackFragment`], so moves order like any other action:
+//!
+//! ```ignore
+//! let track = [
+//!     camera::frame(&mut builder, cam, Vec3::ZERO, 6.0, s(1)),
+//!     camera::orbit(&mut builder, cam, Vec3::ZERO, FRAC_PI_2, s(2)),
+//!     [
+//!         camera::dolly(&mut builder, cam, 2.0, s(1)),
+//!         camera::focus_pull(&mut builder, cam, FRAC_PI_6, s(1)),
+//!     ]
+//!     .ord_all(),
+//! ]
+//! .ord_chain();
+//! ```
+//!
+//! The projection is animated through virtual fields, e.g. [`fov`]
+//! and [`ortho_scale`], so zooms can be authored with any action,
+//! such as a dolly zoom paired with [`dolly`]:
+//!
+//! ```ignore
+//! let vertigo = [
+//!     camera::dolly(&mut builder, cam, -4.0, s(2)),
+//!     builder.act_to(cam, camera::fov(), FRAC_PI_2).play(s(2)),
+//! ]
+//! .ord_all();
+//! ```
+//!
+//! [`dolly`], [`orbit`] and [`frame`] write the whole [`Transform`]
+//! of the camera, so they should be chained rather than overlapped.
+//! [`shake`] instead offsets the camera through a [`CameraShake`]
+//! on top of any move.
+
+use core::time::Duration;
+
+use bevy_app::prelude::*;
+use bevy_camera::Projection;
+use bevy_ecs::prelude::*;
+use bevy_math::{Quat, Vec3, ops};
+use bevy_platform::sync::LazyLock;
+use bevy_transform::components::Transform;
+use motiongfx::field::VirtualField;
+use motiongfx::prelude::*;
+
+use crate::MotionGfxSystems;
+use crate::world::BevyTimelineBuilder;
+
+/// The field of view of a perspective [`Projection`], other
+/// projections read as `0.0` and ignore writes.
+static FOV: LazyLock<VirtualField<Projection, f32>> =
+    LazyLock::new(|| {
+        VirtualField::new(
+            path!(<Projection>),
+            |projection: &Projection| match projection {
+                Projection::Perspective(p) => p.fov,
+                _ => 0.0,
+            },
+            |projection: &mut Projection, fov: f32| {
+                if let Projection::Perspective(p) = projection {
+                    p.fov = fov;
+                }
+            },
+        )
+    });
+
+/// The scale of an orthographic [`Projection`], other projections
+/// read as `1.0` and ignore writes.
+static SCALE: LazyLock<VirtualField<Projection, f32>> =
+    LazyLock::new(|| {
+        VirtualField::new(
+            path!(<Projection>),
+            |projection: &Projection| match projection {
+                Projection::Orthographic(o) => o.scale,
+                _ => 1.0,
+            },
+            |projection: &mut Projection, scale: f32| {
+                if let Projection::Orthographic(o) = projection {
+                    o.scale = scale;
+                }
+            },
+        )
+    });
+
+/// The near clipping plane of a [`Projection`], custom projections
+/// read as `0.0` and ignore writes.
+static NEAR: LazyLock<VirtualField<Projection, f32>> =
+    LazyLock::new(|| {
+        VirtualField::new(
+            path!(<Projection>),
+            |projection: &Projection| match projection {
+                Projection::Perspective(p) => p.near,
+                Projection::Orthographic(o) => o.near,
+                _ => 0.0,
+            },
+            |projection: &mut Projection, near: f32| match projection
+            {
+                Projection::Perspective(p) => p.near = near,
+                Projection::Orthographic(o) => o.near = near,
+                _ => {}
+            },
+        )
+    });
+
+/// The far clipping plane of a [`Projection`], custom projections
+/// read as `0.0` and ignore writes.
+static FAR: LazyLock<VirtualField<Projection, f32>> =
+    LazyLock::new(|| {
+        VirtualField::new(
+            path!(<Projection>),
+            |projection: &Projection| match projection {
+                Projection::Perspective(p) => p.far,
+                Projection::Orthographic(o) => o.far,
+                _ => 0.0,
+            },
+            |projection: &mut Projection, far: f32| match projection {
+                Projection::Perspective(p) => p.far = far,
+                Projection::Orthographic(o) => o.far = far,
+                _ => {}
+            },
+        )
+    });
+
+/// The vertical field of view of a perspective [`Projection`] in
+/// radians, for use with any action.
+///
+/// Other projections read as `0.0` and ignore writes.
+pub fn fov() -> VirtualField<Projection, f32> {
+    FOV.clone()
+}
+
+/// The scale of an orthographic [`Projection`].
+///
+/// Other projections read as `1.0` and ignore writes.
+pub fn ortho_scale() -> VirtualField<Projection, f32> {
+    SCALE.clone()
+}
+
+/// The near clipping plane of a perspective or orthographic
+/// [`Projection`].
+pub fn near() -> VirtualField<Projection, f32> {
+    NEAR.clone()
+}
+
+/// The far clipping plane of a perspective or orthographic
+/// [`Projection`].
+pub fn far() -> VirtualField<Projection, f32> {
+    FAR.clone()
+}
+
+/// Moves the camera `distance` along its forward direction, or
+/// backward if negative.
+pub fn dolly(
+    builder: &mut BevyTimelineBuilder<'_>,
+    camera: Entity,
+    distance: f32,
+    duration: Duration,
+) -> TrackFragment {
+    builder
+        .act(camera, path!(<Transform>), move |tf: &Transform| {
+            let forward = tf.forward();
+            tf.with_translation(tf.translation + forward * distance)
+        })
+        .play(duration)
+}
+
+/// Orbits the camera `angle` radians around the vertical axis
+/// through `center`, counterclockwise when seen from above, while
+/// keeping it pointed at `center`.
+///
+/// The camera keeps its distance and height relative to `center`
+/// along the whole arc.
+pub fn orbit(
+    builder: &mut BevyTimelineBuilder<'_>,
+    camera: Entity,
+    center: Vec3,
+    angle: f32,
+    duration: Duration,
+) -> TrackFragment {
+    let field = VirtualField::new(
+        path!(<Transform>),
+        move |tf: &Transform| Orbit::read(tf, center),
+        move |tf: &mut Transform, orbit: Orbit| {
+            orbit.write(tf, center)
+        },
+    );
+
+    builder
+        .act_builder(camera, field, move |orbit: &Orbit| Orbit {
+            yaw: orbit.yaw + angle,
+            ..*orbit
+        })
+        .with_interp(Orbit::interp)
+        .play(duration)
+}
+
+/// Changes the vertical field of view of a perspective camera to
+/// `fov` radians.
+pub fn focus_pull(
+    builder: &mut BevyTimelineBuilder<'_>,
+    camera: Entity,
+    fov: f32,
+    duration: Duration,
+) -> TrackFragment {
+    builder.act_to(camera, FOV.clone(), fov).play(duration)
+}
+
+/// Changes the scale of an orthographic camera to `scale`, zooming
+/// in below `1.0` and out above it.
+pub fn zoom(
+    builder: &mut BevyTimelineBuilder<'_>,
+    camera: Entity,
+    scale: f32,
+    duration: Duration,
+) -> TrackFragment {
+    builder.act_to(camera, SCALE.clone(), scale).play(duration)
+}
+
+/// Moves the camera to `distance` away from `point`, approaching
+/// from its current position, and points it at `point`.
+pub fn frame(
+    builder: &mut BevyTimelineBuilder<'_>,
+    camera: Entity,
+    point: Vec3,
+    distance: f32,
+    duration: Duration,
+) -> TrackFragment {
+    builder
+        .act(camera, path!(<Transform>), move |tf: &Transform| {
+            let back = (tf.translation - point)
+                .try_normalize()
+                .unwrap_or(*tf.back());
+
+            tf.with_translation(point + back * distance)
+                .looking_at(point, Vec3::Y)
+        })
+        .play(duration)
+}
+
+/// Shakes the camera with `trauma` decaying to zero over `duration`.
+///
+/// Needs a [`CameraShake`] on the camera and the
+/// [`CameraShakePlugin`]. Delay it to an impact to sync the two:
+///
+/// ```ignore
+/// let impact = track::delay(
+///     ms(800),
+///     camera::shake(&mut builder, cam, 1.0, ms(600)),
+/// );
+/// ```
+pub fn shake(
+    builder: &mut BevyTimelineBuilder<'_>,
+    camera: Entity,
+    trauma: f32,
+    duration: Duration,
+) -> TrackFragment {
+    let secs = duration.as_secs_f32();
+
+    [
+        builder
+            .act_fn(camera, path!(<CameraShake>::trauma), move |t| {
+                trauma * (1.0 - t)
+            })
+            .play(duration),
+        builder
+            .act_fn(camera, path!(<CameraShake>::time), move |t| {
+                t * secs
+            })
+            .play(duration),
+    ]
+    .ord_all()
+}
+
+/// Applies the [`CameraShake`] of every camera.
+pub struct CameraShakePlugin;
+
+impl Plugin for CameraShakePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PreUpdate, remove_camera_shake).add_systems(
+            PostUpdate,
+            apply_camera_shake
+                .after(MotionGfxSystems::Sample)
+                .before(MotionGfxSystems::Export),
+        );
+    }
+}
+
+/// Noise offsetting the [`Transform`] of this entity on top of
+/// whatever animates it, scaled by the square of [`Self::trauma`].
+///
+/// The offset is added after sampling and removed again at the start
+/// of the next frame, so it never fights the authored camera move.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+#[require(Transform)]
+pub struct CameraShake {
+    /// Shake intensity in \[0.0..=1.0\], animated by [`shake`].
+    pub trauma: f32,
+    /// Time driving the noise in seconds, animated by [`shake`].
+    pub time: f32,
+    /// The largest translation offset along each local axis.
+    pub max_offset: Vec3,
+    /// The largest roll around the local forward axis, in radians.
+    pub max_roll: f32,
+    /// Roughly how many bumps per second.
+    pub frequency: f32,
+    /// Use different seeds to decorrelate cameras.
+    pub seed: u32,
+    /// The offset applied this frame.
+    applied: (Vec3, Quat),
+}
+
+impl CameraShake {
+    pub const fn new(max_offset: Vec3, max_roll: f32) -> Self {
+        Self {
+            trauma: 0.0,
+            time: 0.0,
+            max_offset,
+            max_roll,
+            frequency: 15.0,
+            seed: 0,
+            applied: (Vec3::ZERO, Quat::IDENTITY),
+        }
+    }
+
+    /// Builder method for setting [`Self::frequency`].
+    pub const fn with_frequency(mut self, frequency: f32) -> Self {
+        self.frequency = frequency;
+        self
+    }
+
+    /// Builder method for setting [`Self::seed`].
+    pub const fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The local translation and rotation offset at the current
+    /// [`Self::trauma`] and [`Self::time`].
+    pub fn offset(&self) -> (Vec3, Quat) {
+        let trauma = self.trauma.clamp(0.0, 1.0);
+        let amount = trauma * trauma;
+        if amount == 0.0 {
+            return (Vec3::ZERO, Quat::IDENTITY);
+        }
+
+        let x = self.time * self.frequency;
+        let noise = |i: u32| {
+            Wiggle::new(amount, self.frequency)
+                .with_seed(self.seed.wrapping_add(i))
+                .sample(x)
+        };
+
+        (
+            self.max_offset * Vec3::new(noise(0), noise(1), noise(2)),
+            Quat::from_rotation_z(self.max_roll * noise(3)),
+        )
+    }
+}
+
+impl Default for CameraShake {
+    fn default() -> Self {
+        Self::new(Vec3::new(0.3, 0.3, 0.0), 0.05)
+    }
+}
+
+fn remove_camera_shake(
+    mut q_shakes: Query<(&mut Transform, &mut CameraShake)>,
+) {
+    for (mut tf, mut shake) in q_shakes.iter_mut() {
+        let (offset, roll) = shake.applied;
+        if offset == Vec3::ZERO && roll == Quat::IDENTITY {
+            continue;
+        }
+
+        tf.rotation *= roll.inverse();
+        let rotation = tf.rotation;
+        tf.translation -= rotation * offset;
+        shake.applied = (Vec3::ZERO, Quat::IDENTITY);
+    }
+}
+
+fn apply_camera_shake(
+    mut q_shakes: Query<(&mut Transform, &mut CameraShake)>,
+) {
+    for (mut tf, mut shake) in q_shakes.iter_mut() {
+        let (offset, roll) = shake.offset();
+        if offset == Vec3::ZERO && roll == Quat::IDENTITY {
+            continue;
+        }
+
+        let rotation = tf.rotation;
+        tf.translation += rotation * offset;
+        tf.rotation *= roll;
+        shake.applied = (offset, roll);
+    }
+}
+
+/// Spherical coordinates of a camera around a center point.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Orbit {
+    yaw: f32,
+    pitch: f32,
+    radius: f32,
+}
+
+impl Orbit {
+    fn read(tf: &Transform, center: Vec3) -> Self {
+        let offset = tf.translation - center;
+        let radius = offset.length();
+        if radius <= f32::EPSILON {
+            return Self {
+                yaw: 0.0,
+                pitch: 0.0,
+                radius: 0.0,
+            };
+        }
+
+        Self {
+            yaw: ops::atan2(offset.x, offset.z),
+            pitch: ops::asin((offset.y / radius).clamp(-1.0, 1.0)),
+            radius,
+        }
+    }
+
+    fn write(self, tf: &mut Transform, center: Vec3) {
+        let horizontal = ops::cos(self.pitch) * self.radius;
+        tf.translation = center
+            + Vec3::new(
+                ops::sin(self.yaw) * horizontal,
+                ops::sin(self.pitch) * self.radius,
+                ops::cos(self.yaw) * horizontal,
+            );
+
+        if self.radius > f32::EPSILON {
+            tf.look_at(center, Vec3::Y);
+        }
+    }
+
+    fn interp(a: &Self, b: &Self, t: f32) -> Self {
+        Self {
+            yaw: a.yaw + (b.yaw - a.yaw) * t,
+            pitch: a.pitch + (b.pitch - a.pitch) * t,
+            radius: a.radius + (b.radius - a.radius) * t,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::FRAC_PI_2;
+
+    use super::*;
+
+    #[test]
+    fn orbit_round_trips_and_stays_on_the_sphere() {
+        let center = Vec3::new(1.0, 0.0, 0.0);
+        let mut tf = Transform::from_xyz(1.0, 2.0, 4.0);
+
+        let orbit = Orbit::read(&tf, center);
+        let quarter = Orbit {
+            yaw: orbit.yaw + FRAC_PI_2,
+            ..orbit
+        };
+        quarter.write(&mut tf, center);
+
+        assert!(
+            tf.translation
+                .abs_diff_eq(Vec3::new(5.0, 2.0, 0.0), 1e-5)
+        );
+        assert!(
+            (Orbit::read(&tf, center).radius - orbit.radius).abs()
+                < 1e-5
+        );
+    }
+
+    #[test]
+    fn projection_fields_match_the_variant() {
+        let mut ortho = Projection::Orthographic(
+            bevy_camera::OrthographicProjection::default_2d(),
+        );
+        ortho_scale().conversion().set(&mut ortho, 0.5);
+        far().conversion().set(&mut ortho, 20.0);
+        fov().conversion().set(&mut ortho, 1.0);
+
+        assert_eq!(ortho_scale().conversion().get(&ortho), 0.5);
+        assert_eq!(far().conversion().get(&ortho), 20.0);
+        assert_eq!(fov().conversion().get(&ortho), 0.0);
+    }
+
+    #[test]
+    fn shake_scales_with_trauma() {
+        let mut shake = CameraShake::new(Vec3::ONE, 0.1);
+        shake.time = 0.37;
+        assert_eq!(shake.offset(), (Vec3::ZERO, Quat::IDENTITY));
+
+        shake.trauma = 0.5;
+        let (offset, _) = shake.offset();
+        assert_ne!(offset, Vec3::ZERO);
+        assert!(offset.abs().max_element() <= 0.25);
+    }
+}
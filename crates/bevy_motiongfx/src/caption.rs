@@ -0,0 +1,406 @@
+//! Subtitles synced to a timeline, enabled with the `ui` feature.
+//!
+//! [`Captions`] sits next to the [`TimelineId`] of a timeline and
+//! writes the caption under the playhead into a [`Text`] entity:
+//!
+//! ```ignore
+//! app.add_plugins(CaptionPlugin);
+//!
+//! let subtitle = commands.spawn(Text::default()).id();
+//! commands.spawn((
+//!     motiongfx.add_timeline(timeline),
+//!     RealtimePlayer::new().with_playing(true),
+//!     Captions::new(subtitle)
+//!         .with_caption(Caption::new(0, s(1)..s(3), "Hello!"))
+//!         .with_captions(parse_srt(1, include_str!("outro.srt"))?),
+//! ));
+//! ```
+//!
+//! The text follows the playhead in either direction and is emptied
+//! between captions. Captions of a track can be written back out
+//! with [`write_srt`].
+
+use core::fmt::{self, Write};
+use core::ops::Range;
+use core::time::Duration;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_ui::widget::Text;
+
+use crate::MotionGfxSystems;
+use crate::manager::{MotionGfxManager, TimelineId};
+
+/// Shows the [`Captions`] of every timeline.
+pub struct CaptionPlugin;
+
+impl Plugin for CaptionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            sync_captions.after(MotionGfxSystems::Sample),
+        );
+    }
+}
+
+/// Timed text shown on the timeline of this entity.
+#[derive(Component, Debug, Clone)]
+pub struct Captions {
+    /// The [`Text`] entity the captions are written into.
+    pub target: Entity,
+    captions: Vec<Caption>,
+    /// The index of the caption currently shown.
+    shown: Option<usize>,
+}
+
+impl Captions {
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target,
+            captions: Vec::new(),
+            shown: None,
+        }
+    }
+
+    pub fn with_caption(mut self, caption: Caption) -> Self {
+        self.captions.push(caption);
+        self
+    }
+
+    pub fn with_captions(
+        mut self,
+        captions: impl IntoIterator<Item = Caption>,
+    ) -> Self {
+        self.captions.extend(captions);
+        self
+    }
+
+    pub fn push(&mut self, caption: Caption) {
+        self.captions.push(caption);
+    }
+
+    pub fn captions(&self) -> &[Caption] {
+        &self.captions
+    }
+
+    /// The caption under `time` of `track`, the first one added if
+    /// several overlap.
+    pub fn caption_at(
+        &self,
+        track: usize,
+        time: Duration,
+    ) -> Option<&Caption> {
+        self.index_at(track, time).map(|i| &self.captions[i])
+    }
+
+    fn index_at(
+        &self,
+        track: usize,
+        time: Duration,
+    ) -> Option<usize> {
+        self.captions
+            .iter()
+            .position(|caption| caption.contains(track, time))
+    }
+}
+
+/// Text shown during [`Self::span`] of a track.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Caption {
+    /// The index of the track the caption belongs to.
+    pub track: usize,
+    /// Start and end time within the track.
+    pub span: Range<Duration>,
+    pub text: String,
+}
+
+impl Caption {
+    pub fn new(
+        track: usize,
+        span: Range<Duration>,
+        text: impl Into<String>,
+    ) -> Self {
+        Self {
+            track,
+            span,
+            text: text.into(),
+        }
+    }
+
+    /// Returns `true` if the caption is shown at `time` of `track`.
+    pub fn contains(&self, track: usize, time: Duration) -> bool {
+        self.track == track && self.span.contains(&time)
+    }
+}
+
+fn sync_captions(
+    motiongfx: Res<MotionGfxManager>,
+    mut q_captions: Query<(&TimelineId, &mut Captions)>,
+    mut q_texts: Query<&mut Text>,
+) {
+    for (id, mut captions) in q_captions.iter_mut() {
+        let Some(timeline) = motiongfx.get_timeline(id) else {
+            continue;
+        };
+        let shown = captions
+            .index_at(timeline.curr_index(), timeline.curr_time());
+        if shown == captions.shown {
+            continue;
+        }
+
+        let Ok(mut text) = q_texts.get_mut(captions.target) else {
+            continue;
+        };
+        text.0 = shown
+            .map(|i| captions.captions[i].text.clone())
+            .unwrap_or_default();
+        captions.shown = shown;
+    }
+}
+
+/// Reasons SubRip (`.srt`) subtitles can fail to parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SrtError {
+    /// The entry starting at this line has no `start --> end` line.
+    MissingTiming(usize),
+    /// A timestamp is not in the `HH:MM:SS,mmm` format.
+    InvalidTimestamp(String),
+}
+
+impl fmt::Display for SrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingTiming(line) => {
+                write!(
+                    f,
+                    "missing timing for the entry at line {line}"
+                )
+            }
+            Self::InvalidTimestamp(stamp) => {
+                write!(f, "invalid timestamp `{stamp}`")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SrtError {}
+
+/// Parses SubRip (`.srt`) subtitles into captions of `track`.
+pub fn parse_srt(
+    track: usize,
+    source: &str,
+) -> Result<Vec<Caption>, SrtError> {
+    let mut captions = Vec::new();
+    let mut lines = source
+        .lines()
+        .map(|line| line.trim_end_matches('\r'))
+        .enumerate()
+        .peekable();
+
+    loop {
+        // Skip the blank lines between entries.
+        while lines
+            .next_if(|(_, line)| line.trim().is_empty())
+            .is_some()
+        {}
+        let Some((start_line, first)) = lines.next() else {
+            break;
+        };
+
+        // The counter line is optional.
+        let timing = if first.contains("-->") {
+            first
+        } else {
+            match lines.next() {
+                Some((_, line)) if line.contains("-->") => line,
+                _ => {
+                    return Err(SrtError::MissingTiming(
+                        start_line + 1,
+                    ));
+                }
+            }
+        };
+
+        let (start, end) =
+            timing.split_once("-->").unwrap_or_default();
+        // Anything after the end time is positioning, left out.
+        let end = end.split_whitespace().next().unwrap_or_default();
+        let span = parse_timestamp(start)?..parse_timestamp(end)?;
+
+        let mut text = String::new();
+        while let Some((_, line)) =
+            lines.next_if(|(_, line)| !line.trim().is_empty())
+        {
+            if !text.is_empty() {
+                text.push('\n');
+            }
+            text.push_str(line);
+        }
+
+        captions.push(Caption::new(track, span, text));
+    }
+
+    Ok(captions)
+}
+
+/// Writes `captions` as SubRip (`.srt`) subtitles, in the order
+/// given. The track of each caption is left out.
+pub fn write_srt<'a>(
+    captions: impl IntoIterator<Item = &'a Caption>,
+) -> String {
+    let mut srt = String::new();
+
+    for (i, caption) in captions.into_iter().enumerate() {
+        if i > 0 {
+            srt.push('\n');
+        }
+        // Writing into a `String` never fails.
+        let _ = write!(
+            srt,
+            "{}\n{} --> {}\n{}\n",
+            i + 1,
+            Timestamp(caption.span.start),
+            Timestamp(caption.span.end),
+            caption.text
+        );
+    }
+
+    srt
+}
+
+/// Parses a `HH:MM:SS,mmm` timestamp, also accepting a `.` as the
+/// decimal separator and fractions of 1 to 9 digits.
+fn parse_timestamp(stamp: &str) -> Result<Duration, SrtError> {
+    let stamp = stamp.trim();
+    let invalid = || SrtError::InvalidTimestamp(stamp.to_string());
+
+    let (clock, fraction) =
+        stamp.split_once([',', '.']).ok_or_else(invalid)?;
+    let parts = clock
+        .split(':')
+        .map(|part| part.parse::<u64>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|_| invalid())?;
+    let [hours, minutes, seconds] = parts[..] else {
+        return Err(invalid());
+    };
+    let secs = hours
+        .checked_mul(3600)
+        .zip(minutes.checked_mul(60))
+        .and_then(|(hours, minutes)| hours.checked_add(minutes))
+        .and_then(|secs| secs.checked_add(seconds))
+        .ok_or_else(invalid)?;
+
+    // `.5` is half a second, not 5 milliseconds.
+    if !(1..=9).contains(&fraction.len())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return Err(invalid());
+    }
+    let nanos = fraction.parse::<u32>().map_err(|_| invalid())?
+        * 10u32.pow(9 - fraction.len() as u32);
+
+    Ok(Duration::new(secs, nanos))
+}
+
+/// Displays a [`Duration`] as a `HH:MM:SS,mmm` timestamp.
+struct Timestamp(Duration);
+
+impl fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.0.as_secs();
+        write!(
+            f,
+            "{:02}:{:02}:{:02},{:03}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            self.0.subsec_millis()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use motiongfx::time::{ms, s};
+
+    use super::*;
+
+    const SRT: &str = "1\r\n\
+        00:00:01,000 --> 00:00:03,500\r\n\
+        Meet the cube.\r\n\
+        \r\n\
+        2\r\n\
+        00:01:02,250 --> 00:01:04,000 X1:0\r\n\
+        It spins,\r\n\
+        and it scales.\r\n";
+
+    #[test]
+    fn srt_round_trips() {
+        let captions = parse_srt(2, SRT).unwrap();
+
+        assert_eq!(
+            captions,
+            [
+                Caption::new(2, s(1)..ms(3500), "Meet the cube."),
+                Caption::new(
+                    2,
+                    ms(62_250)..s(64),
+                    "It spins,\nand it scales."
+                ),
+            ]
+        );
+        assert_eq!(parse_srt(2, &write_srt(&captions)), Ok(captions));
+    }
+
+    #[test]
+    fn invalid_srt_is_an_error() {
+        assert_eq!(
+            parse_srt(0, "1\nHello"),
+            Err(SrtError::MissingTiming(1))
+        );
+        assert_eq!(
+            parse_srt(0, "00:01 --> 00:00:02,000\nHello"),
+            Err(SrtError::InvalidTimestamp("00:01".to_string()))
+        );
+    }
+
+    #[test]
+    fn timestamps_scale_the_fraction_by_its_digits() {
+        assert_eq!(parse_timestamp("00:00:01,500"), Ok(ms(1500)));
+        assert_eq!(parse_timestamp("00:00:01.5"), Ok(ms(1500)));
+        assert_eq!(parse_timestamp("01:02:03,04"), Ok(ms(3_723_040)));
+        for stamp in
+            ["00:00:01,", "00:00:01,+5", "00:00:01,1234567890"]
+        {
+            assert_eq!(
+                parse_timestamp(stamp),
+                Err(SrtError::InvalidTimestamp(stamp.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn overflowing_timestamps_are_errors() {
+        let stamp = alloc::format!("{}:00:00,000", u64::MAX / 1000);
+        assert_eq!(
+            parse_timestamp(&stamp),
+            Err(SrtError::InvalidTimestamp(stamp.clone()))
+        );
+    }
+
+    #[test]
+    fn caption_at_follows_the_track() {
+        let captions = Captions::new(Entity::PLACEHOLDER)
+            .with_caption(Caption::new(0, s(1)..s(2), "a"))
+            .with_caption(Caption::new(1, s(1)..s(2), "b"));
+
+        assert_eq!(
+            captions.caption_at(1, ms(1500)).unwrap().text,
+            "b"
+        );
+        assert_eq!(captions.caption_at(0, s(2)), None);
+    }
+}
@@ -0,0 +1,181 @@
+//! Fluent control of [`RealtimePlayer`]s through [`Commands`].
+//!
+//! ```ignore
+//! fn skip_intro(
+//!     mut commands: Commands,
+//!     q_intros: Query<Entity, With<Intro>>,
+//! ) {
+//!     for entity in q_intros.iter() {
+//!         commands
+//!             .timeline(entity)
+//!             .play()
+//!             .set_speed(2.0)
+//!             .seek(ms(1500));
+//!     }
+//! }
+//! ```
+//!
+//! Each call triggers a matching event, such as [`PlayTimeline`] or
+//! [`SeekTimeline`], which can also be triggered directly. The events
+//! only update the [`RealtimePlayer`] of the entity, which applies
+//! them to its timeline in [`PostUpdate`]. Entities without a
+//! [`RealtimePlayer`] are ignored.
+
+use core::time::Duration;
+
+use alloc::string::String;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+
+use crate::controller::{RealtimePlayer, SeekTarget};
+
+pub struct TimelineCommandsPlugin;
+
+impl Plugin for TimelineCommandsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(play_timeline)
+            .add_observer(pause_timeline)
+            .add_observer(set_timeline_speed)
+            .add_observer(seek_timeline);
+    }
+}
+
+/// Starts playing the [`RealtimePlayer`] of `entity`.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct PlayTimeline {
+    pub entity: Entity,
+}
+
+/// Pauses the [`RealtimePlayer`] of `entity`.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct PauseTimeline {
+    pub entity: Entity,
+}
+
+/// Sets the [`RealtimePlayer::time_scale`] of `entity`.
+#[derive(EntityEvent, Debug, Clone, Copy)]
+pub struct SetTimelineSpeed {
+    pub entity: Entity,
+    pub speed: f64,
+}
+
+/// Makes the [`RealtimePlayer`] of `entity` jump to `target`.
+#[derive(EntityEvent, Debug, Clone)]
+pub struct SeekTimeline {
+    pub entity: Entity,
+    pub target: SeekTarget,
+}
+
+/// Adds [`Self::timeline`] to [`Commands`].
+pub trait TimelineCommandsExt<'w> {
+    /// Returns the [`TimelineCommands`] for the timeline of
+    /// `entity`.
+    fn timeline(
+        &mut self,
+        entity: Entity,
+    ) -> TimelineCommands<'w, '_>;
+}
+
+impl<'w> TimelineCommandsExt<'w> for Commands<'w, '_> {
+    fn timeline(
+        &mut self,
+        entity: Entity,
+    ) -> TimelineCommands<'w, '_> {
+        TimelineCommands {
+            entity,
+            commands: self.reborrow(),
+        }
+    }
+}
+
+/// Controls the timeline of an entity, see the [module
+/// docs](self).
+pub struct TimelineCommands<'w, 's> {
+    entity: Entity,
+    commands: Commands<'w, 's>,
+}
+
+impl TimelineCommands<'_, '_> {
+    /// Triggers [`PlayTimeline`].
+    pub fn play(&mut self) -> &mut Self {
+        let entity = self.entity;
+        self.commands.trigger(PlayTimeline { entity });
+        self
+    }
+
+    /// Triggers [`PauseTimeline`].
+    pub fn pause(&mut self) -> &mut Self {
+        let entity = self.entity;
+        self.commands.trigger(PauseTimeline { entity });
+        self
+    }
+
+    /// Triggers [`SetTimelineSpeed`].
+    pub fn set_speed(&mut self, speed: f64) -> &mut Self {
+        let entity = self.entity;
+        self.commands.trigger(SetTimelineSpeed { entity, speed });
+        self
+    }
+
+    /// Triggers [`SeekTimeline`] to `time` within the current track.
+    pub fn seek(&mut self, time: Duration) -> &mut Self {
+        self.seek_to(SeekTarget::Time(time))
+    }
+
+    /// Triggers [`SeekTimeline`] to the track labeled `label`.
+    pub fn seek_label(
+        &mut self,
+        label: impl Into<String>,
+    ) -> &mut Self {
+        self.seek_to(SeekTarget::Label(label.into()))
+    }
+
+    /// Triggers [`SeekTimeline`] to `target`.
+    pub fn seek_to(&mut self, target: SeekTarget) -> &mut Self {
+        let entity = self.entity;
+        self.commands.trigger(SeekTimeline { entity, target });
+        self
+    }
+
+    /// The entity of the controlled timeline.
+    #[inline]
+    pub fn id(&self) -> Entity {
+        self.entity
+    }
+}
+
+fn play_timeline(
+    play: On<PlayTimeline>,
+    mut q_players: Query<&mut RealtimePlayer>,
+) {
+    if let Ok(mut player) = q_players.get_mut(play.entity) {
+        player.set_playing(true);
+    }
+}
+
+fn pause_timeline(
+    pause: On<PauseTimeline>,
+    mut q_players: Query<&mut RealtimePlayer>,
+) {
+    if let Ok(mut player) = q_players.get_mut(pause.entity) {
+        player.set_playing(false);
+    }
+}
+
+fn set_timeline_speed(
+    speed: On<SetTimelineSpeed>,
+    mut q_players: Query<&mut RealtimePlayer>,
+) {
+    if let Ok(mut player) = q_players.get_mut(speed.entity) {
+        player.set_time_scale(speed.speed);
+    }
+}
+
+fn seek_timeline(
+    seek: On<SeekTimeline>,
+    mut q_players: Query<&mut RealtimePlayer>,
+) {
+    if let Ok(mut player) = q_players.get_mut(seek.entity) {
+        player.seek_to(seek.target.clone());
+    }
+}
@@ -0,0 +1,161 @@
+//! Shims for the `motiongfx_core` 0.1 API, enabled with the
+//! `compat-0.1` feature.
+//!
+//! Every item here is deprecated and forwards to the
+//! [`TimelineBuilder`] based API, so existing projects keep compiling
+//! while they are migrated piece by piece. [`MIGRATIONS`] lists each
+//! old call next to its replacement, and [`migration_guide`] renders
+//! that list as a markdown table.
+
+use core::fmt::Write;
+use core::time::Duration;
+
+use alloc::string::String;
+use bevy_ecs::prelude::*;
+use bevy_ecs::system::EntityCommands;
+use motiongfx::prelude::*;
+use motiongfx::track;
+
+use crate::controller::RealtimePlayer;
+use crate::manager::MotionGfxManager;
+use crate::world::BevyTimeline;
+
+/// An old API call and its replacement.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Migration {
+    /// The `motiongfx_core` 0.1 call.
+    pub old: &'static str,
+    /// The equivalent in the current API.
+    pub new: &'static str,
+}
+
+/// Every call covered by this module, in the order they appear in
+/// [`migration_guide`].
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        old: "commands.play_motion(timeline)",
+        new: "commands.spawn((motiongfx.add_timeline(timeline), \
+              RealtimePlayer::new().with_playing(true)))",
+    },
+    Migration {
+        old: "chain(&[a, b])",
+        new: "[a, b].ord_chain()",
+    },
+    Migration {
+        old: "all(&[a, b])",
+        new: "[a, b].ord_all()",
+    },
+    Migration {
+        old: "any(&[a, b])",
+        new: "[a, b].ord_any()",
+    },
+    Migration {
+        old: "flow(0.5, &[a, b])",
+        new: "[a, b].ord_flow(ms(500))",
+    },
+    Migration {
+        old: "delay(0.5, a)",
+        new: "track::delay(ms(500), a)",
+    },
+];
+
+/// Renders [`MIGRATIONS`] as a markdown table.
+pub fn migration_guide() -> String {
+    let mut guide =
+        String::from("| 0.1 | Current |\n| --- | --- |\n");
+
+    for Migration { old, new } in MIGRATIONS {
+        // Writing into a `String` cannot fail.
+        let _ = writeln!(guide, "| `{old}` | `{new}` |");
+    }
+
+    guide
+}
+
+/// Run all [`TrackFragment`]s one after another.
+#[deprecated(
+    since = "0.3.0",
+    note = "use `TrackOrdering::ord_chain`"
+)]
+pub fn chain(
+    fragments: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    track::chain(fragments)
+}
+
+/// Run all [`TrackFragment`]s concurrently and wait for all of them
+/// to finish.
+#[deprecated(since = "0.3.0", note = "use `TrackOrdering::ord_all`")]
+pub fn all(
+    fragments: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    track::all(fragments)
+}
+
+/// Run all [`TrackFragment`]s concurrently and wait for any of them
+/// to finish.
+#[deprecated(since = "0.3.0", note = "use `TrackOrdering::ord_any`")]
+pub fn any(
+    fragments: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    track::any(fragments)
+}
+
+/// Run one [`TrackFragment`] after another with a fixed delay in
+/// seconds.
+#[deprecated(since = "0.3.0", note = "use `TrackOrdering::ord_flow`")]
+pub fn flow(
+    delay: f32,
+    fragments: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    track::flow(secs(delay), fragments)
+}
+
+/// Run a [`TrackFragment`] after a fixed delay in seconds.
+#[deprecated(since = "0.3.0", note = "use `track::delay`")]
+pub fn delay(delay: f32, fragment: TrackFragment) -> TrackFragment {
+    track::delay(secs(delay), fragment)
+}
+
+/// Seconds as a [`Duration`], treating negative and non-finite values
+/// as zero.
+fn secs(secs: f32) -> Duration {
+    Duration::try_from_secs_f32(secs).unwrap_or_default()
+}
+
+/// Extension for spawning a playing timeline in one call.
+pub trait PlayMotionExt {
+    /// Spawns an entity playing `timeline` in realtime.
+    #[deprecated(
+        since = "0.3.0",
+        note = "spawn `MotionGfxManager::add_timeline` with a \
+                `RealtimePlayer`"
+    )]
+    fn play_motion(
+        &mut self,
+        timeline: BevyTimeline,
+    ) -> EntityCommands<'_>;
+}
+
+impl PlayMotionExt for Commands<'_, '_> {
+    fn play_motion(
+        &mut self,
+        timeline: BevyTimeline,
+    ) -> EntityCommands<'_> {
+        let mut entity =
+            self.spawn(RealtimePlayer::new().with_playing(true));
+        let id = entity.id();
+
+        entity.commands().queue(move |world: &mut World| {
+            let timeline_id = world
+                .resource_mut::<MotionGfxManager>()
+                .add_timeline(timeline);
+
+            if let Ok(mut entity) = world.get_entity_mut(id) {
+                entity.insert(timeline_id);
+            }
+        });
+
+        entity
+    }
+}
@@ -1,45 +1,162 @@
 use core::time::Duration;
 
+use alloc::string::String;
+use alloc::vec::Vec;
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
+use bevy_ecs::schedule::ScheduleLabel;
 use bevy_time::prelude::*;
+use bevy_time::{TimeSystems, TimeUpdateStrategy};
 
 use crate::MotionGfxSystems;
 use crate::manager::{MotionGfxManager, TimelineId};
+use crate::world::BevyTimeline;
 
 pub struct ControllerPlugin;
 
 impl Plugin for ControllerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            PostUpdate,
-            (
-                fixed_rate_player_update,
-                realtime_player_update,
-                passive_player_update,
+        app.init_resource::<GlobalTimeScale>()
+            .add_systems(
+                PostUpdate,
+                (
+                    (
+                        fixed_rate_player_update,
+                        realtime_player_update,
+                        passive_player_update,
+                    ),
+                    update_timeline_progress,
+                )
+                    .chain()
+                    .in_set(MotionGfxSystems::Controller),
             )
-                .in_set(MotionGfxSystems::Controller),
-        );
+            .init_schedule(ExportFrame)
+            .add_systems(
+                PostUpdate,
+                run_export_frames.in_set(MotionGfxSystems::Export),
+            )
+            .add_systems(
+                First,
+                render_mode_update.before(TimeSystems),
+            )
+            .add_systems(FixedUpdate, fixed_timestep_player_update);
     }
 }
 
+/// Steps [`Time`] by the next frame of the [`RenderMode`], handing
+/// control back to the clock once the resource is removed.
+fn render_mode_update(
+    mode: Option<ResMut<RenderMode>>,
+    mut strategy: ResMut<TimeUpdateStrategy>,
+    mut active: Local<bool>,
+) {
+    let Some(mut mode) = mode else {
+        if *active {
+            *strategy = TimeUpdateStrategy::Automatic;
+            *active = false;
+        }
+        return;
+    };
+
+    let prev = mode.elapsed();
+    mode.frame += 1;
+    *strategy = TimeUpdateStrategy::ManualDuration(
+        mode.elapsed().saturating_sub(prev),
+    );
+    *active = true;
+}
+
 fn realtime_player_update(
     mut motiongfx: ResMut<MotionGfxManager>,
-    q_timelines: Query<(&TimelineId, &RealtimePlayer)>,
+    mut q_timelines: Query<(&TimelineId, &mut RealtimePlayer)>,
     time: Res<Time>,
+    global_scale: Res<GlobalTimeScale>,
+) {
+    for (id, mut player) in q_timelines.iter_mut() {
+        // Only touch the player when seeking, to keep it unchanged.
+        if player.seek.is_some()
+            && let Some(seek) = player.seek.take()
+            && let Some(timeline) = motiongfx.get_timeline_mut(id)
+        {
+            match seek {
+                SeekTarget::Time(time) => {
+                    timeline.set_target_time(time);
+                }
+                SeekTarget::Label(label) => {
+                    timeline.seek_label(&label);
+                }
+            }
+        }
+        if player.step.is_some()
+            && let Some(step) = player.step.take()
+            && let Some(timeline) = motiongfx.get_timeline_mut(id)
+        {
+            let time = step_frames(
+                timeline.target_time(),
+                step.fps,
+                step.frames,
+            );
+            timeline.set_target_time(time);
+        }
+        if player.fixed_timestep {
+            continue;
+        }
+
+        advance_player(
+            &mut motiongfx,
+            id,
+            &player,
+            time.delta(),
+            global_scale.0,
+        );
+    }
+}
+
+/// Advances the [`RealtimePlayer::fixed_timestep`] players.
+fn fixed_timestep_player_update(
+    mut motiongfx: ResMut<MotionGfxManager>,
+    q_timelines: Query<(&TimelineId, &RealtimePlayer)>,
+    time: Res<Time<Fixed>>,
+    global_scale: Res<GlobalTimeScale>,
 ) {
     for (id, player) in
-        q_timelines.iter().filter(|(_, p)| p.is_playing)
+        q_timelines.iter().filter(|(_, p)| p.fixed_timestep)
     {
-        if let Some(timeline) = motiongfx.get_timeline_mut(id) {
-            // Magnitude sets the step, sign picks the direction.
-            let delta = time.delta().mul_f64(player.time_scale.abs());
+        advance_player(
+            &mut motiongfx,
+            id,
+            player,
+            time.delta(),
+            global_scale.0,
+        );
+    }
+}
 
-            if player.time_scale > 0.0 {
-                timeline.advance_time(delta);
-            } else if player.time_scale < 0.0 {
-                timeline.rewind_time(delta);
-            }
+fn advance_player(
+    motiongfx: &mut MotionGfxManager,
+    id: &TimelineId,
+    player: &RealtimePlayer,
+    delta: Duration,
+    global_scale: f64,
+) {
+    if !player.is_playing || player.frame_advance.is_some() {
+        return;
+    }
+
+    let time_scale = if player.ignore_global_time_scale {
+        player.time_scale
+    } else {
+        player.time_scale * global_scale
+    };
+
+    if let Some(timeline) = motiongfx.get_timeline_mut(id) {
+        // Magnitude sets the step, sign picks the direction.
+        let delta = delta.mul_f64(time_scale.abs());
+
+        if time_scale > 0.0 {
+            timeline.advance_time(delta);
+        } else if time_scale < 0.0 {
+            timeline.rewind_time(delta);
         }
     }
 }
@@ -59,6 +176,33 @@ fn fixed_rate_player_update(
     }
 }
 
+/// Runs [`ExportFrame`] once for every playing [`FixedRatePlayer`],
+/// in [`Entity`] order.
+pub(crate) fn run_export_frames(world: &mut World) {
+    let mut frames = world
+        .query::<(Entity, &TimelineId, &FixedRatePlayer)>()
+        .iter(world)
+        .filter(|(_, _, player)| player.is_playing)
+        .map(|(entity, &timeline, player)| CurrentExportFrame {
+            entity,
+            timeline,
+            frame: player.curr_frame,
+            time: player.frame_time(),
+        })
+        .collect::<Vec<_>>();
+    if frames.is_empty() {
+        return;
+    }
+    frames.sort_by_key(|frame| frame.entity);
+
+    for frame in frames {
+        world.insert_resource(frame);
+        // Initialized by the plugin, only missing if removed.
+        let _ = world.try_run_schedule(ExportFrame);
+    }
+    world.remove_resource::<CurrentExportFrame>();
+}
+
 fn passive_player_update(
     mut motiongfx: ResMut<MotionGfxManager>,
     passive_players: Query<
@@ -87,6 +231,19 @@ pub struct RealtimePlayer {
     /// The time scale of the player. Set this to negative
     /// to play backwards.
     pub time_scale: f64,
+    /// Holds the player paused on frames of this rate, only moving
+    /// through [`Self::step_forward`] and [`Self::step_backward`].
+    pub frame_advance: Option<u16>,
+    /// Advances in [`FixedUpdate`] by [`Time<Fixed>`] instead of by
+    /// the frame delta, keeping the timeline in lockstep with
+    /// gameplay and physics.
+    pub fixed_timestep: bool,
+    /// Opts out of the [`GlobalTimeScale`].
+    pub ignore_global_time_scale: bool,
+    /// Where to jump to on the next update.
+    seek: Option<SeekTarget>,
+    /// Frames to step on the next update.
+    step: Option<FrameStep>,
 }
 
 impl RealtimePlayer {
@@ -96,6 +253,11 @@ impl RealtimePlayer {
         Self {
             is_playing: false,
             time_scale: 1.0,
+            frame_advance: None,
+            fixed_timestep: false,
+            ignore_global_time_scale: false,
+            seek: None,
+            step: None,
         }
     }
 
@@ -131,6 +293,139 @@ impl RealtimePlayer {
         self.time_scale = time_scale;
         self
     }
+
+    /// Jumps to `time` within the current track on the next update.
+    pub fn seek(&mut self, time: Duration) -> &mut Self {
+        self.seek = Some(SeekTarget::Time(time));
+        self
+    }
+
+    /// Jumps to the start of the track labeled `label` on the next
+    /// update, see [`Timeline::seek_label`]. Unknown labels are
+    /// ignored.
+    ///
+    /// [`Timeline::seek_label`]: motiongfx::timeline::Timeline::seek_label
+    pub fn seek_label(
+        &mut self,
+        label: impl Into<String>,
+    ) -> &mut Self {
+        self.seek = Some(SeekTarget::Label(label.into()));
+        self
+    }
+
+    /// Jumps to `target` on the next update.
+    pub fn seek_to(&mut self, target: SeekTarget) -> &mut Self {
+        self.seek = Some(target);
+        self
+    }
+
+    /// Builder method for setting [`Self::frame_advance`].
+    #[inline]
+    #[must_use]
+    pub const fn with_frame_advance(mut self, fps: u16) -> Self {
+        self.frame_advance = Some(fps);
+        self
+    }
+
+    /// Builder method for setting [`Self::fixed_timestep`].
+    #[inline]
+    #[must_use]
+    pub const fn with_fixed_timestep(mut self, fixed: bool) -> Self {
+        self.fixed_timestep = fixed;
+        self
+    }
+
+    /// Builder method for setting
+    /// [`Self::ignore_global_time_scale`].
+    #[inline]
+    #[must_use]
+    pub const fn with_ignore_global_time_scale(
+        mut self,
+        ignore: bool,
+    ) -> Self {
+        self.ignore_global_time_scale = ignore;
+        self
+    }
+
+    /// Setter method for setting [`Self::frame_advance`].
+    #[inline]
+    pub const fn set_frame_advance(
+        &mut self,
+        fps: Option<u16>,
+    ) -> &mut Self {
+        self.frame_advance = fps;
+        self
+    }
+
+    /// Pauses and moves to the next frame of `fps` on the next
+    /// update. The time snaps to the frame grid of `fps` first.
+    pub fn step_forward(&mut self, fps: u16) -> &mut Self {
+        self.queue_step(fps, 1)
+    }
+
+    /// Pauses and moves to the previous frame of `fps` on the next
+    /// update, stopping at the first frame.
+    pub fn step_backward(&mut self, fps: u16) -> &mut Self {
+        self.queue_step(fps, -1)
+    }
+
+    /// Accumulates steps of the same rate until the next update.
+    fn queue_step(&mut self, fps: u16, frames: i64) -> &mut Self {
+        self.is_playing = false;
+        self.step = Some(match self.step {
+            Some(step) if step.fps == fps => FrameStep {
+                fps,
+                frames: step.frames + frames,
+            },
+            _ => FrameStep { fps, frames },
+        });
+        self
+    }
+}
+
+/// A point of a [`Timeline`] to jump to.
+///
+/// [`Timeline`]: motiongfx::timeline::Timeline
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SeekTarget {
+    /// A time within the current track.
+    Time(Duration),
+    /// The start of a labeled track, see
+    /// [`RealtimePlayer::seek_label`].
+    Label(String),
+}
+
+/// Multiplies the [`RealtimePlayer::time_scale`] of every player,
+/// e.g. `0.25` for slow motion while debugging, `4.0` to fast
+/// forward through a rehearsal or `0.0` to pause them all.
+///
+/// Players with [`RealtimePlayer::ignore_global_time_scale`] set
+/// keep their own pace.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct GlobalTimeScale(pub f64);
+
+impl Default for GlobalTimeScale {
+    fn default() -> Self {
+        Self(1.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FrameStep {
+    fps: u16,
+    frames: i64,
+}
+
+/// Moves `time` by `frames` on the frame grid of `fps`, starting
+/// from its nearest frame.
+fn step_frames(time: Duration, fps: u16, frames: i64) -> Duration {
+    if fps == 0 {
+        return time;
+    }
+
+    let frame = (time.as_secs_f64() * fps as f64).round() as i64;
+    let frame = frame.saturating_add(frames).max(0) as u64;
+    Duration::from_secs(frame) / fps as u32
 }
 
 impl Default for RealtimePlayer {
@@ -212,6 +507,83 @@ impl FixedRatePlayer {
     }
 }
 
+/// Renders frame by frame: while present, [`Time`] advances by
+/// exactly `1 / fps` per app update instead of following the clock.
+///
+/// Every time based system then sees the same sequence of frames on
+/// each run, including the [`RealtimePlayer`]s, so recordings are
+/// reproducible no matter how long each frame takes to render.
+/// Frame times are derived from the frame count, so they never drift.
+///
+/// ```ignore
+/// app.insert_resource(RenderMode::new(60));
+/// ```
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RenderMode {
+    fps: u16,
+    frame: u64,
+}
+
+impl RenderMode {
+    /// # Panics
+    ///
+    /// Panics if `fps` is zero.
+    #[inline]
+    #[must_use]
+    pub const fn new(fps: u16) -> Self {
+        assert!(fps > 0, "render mode needs a non-zero fps");
+        Self { fps, frame: 0 }
+    }
+
+    #[inline]
+    pub const fn fps(&self) -> u16 {
+        self.fps
+    }
+
+    /// Number of frames rendered so far.
+    #[inline]
+    pub const fn frame(&self) -> u64 {
+        self.frame
+    }
+
+    /// The exact time of [`Self::frame`].
+    #[inline]
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_secs(self.frame) / self.fps as u32
+    }
+}
+
+/// Schedule run for every frame recorded by a [`FixedRatePlayer`] or
+/// an export, for per-frame work such as stamping frame numbers, watermarks or
+/// stepping an external simulation.
+///
+/// For each playing player, in [`Entity`] order, it runs once the
+/// frame is sampled and before transforms are propagated, so any
+/// changes are part of the rendered frame and its readback. The frame
+/// is available as the [`CurrentExportFrame`] resource:
+///
+/// ```ignore
+/// app.add_systems(ExportFrame, |frame: Res<CurrentExportFrame>| {
+///     info!("exporting frame {}", frame.frame);
+/// });
+/// ```
+#[derive(ScheduleLabel, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ExportFrame;
+
+/// The frame being exported, only present while [`ExportFrame`]
+/// runs.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct CurrentExportFrame {
+    /// The entity of the [`FixedRatePlayer`] or exported timeline.
+    pub entity: Entity,
+    pub timeline: TimelineId,
+    /// See [`FixedRatePlayer::curr_frame`].
+    pub frame: u64,
+    /// See [`FixedRatePlayer::frame_time`].
+    pub time: Duration,
+}
+
 #[derive(Default, Component)]
 pub struct PassivePlayer {
     time: Duration,
@@ -243,9 +615,90 @@ impl PassivePlayer {
     }
 }
 
+/// Normalized playback progress of the timeline of this entity, for
+/// progress bars, chapter dots and the like to bind to.
+///
+/// Follows the target of the timeline, updated at the end of
+/// [`MotionGfxSystems::Controller`].
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct TimelineProgress {
+    /// The index of the current track.
+    pub track: usize,
+    pub track_count: usize,
+    /// Progress through the current track, in `0.0..=1.0`.
+    pub track_progress: f32,
+    /// Progress through every track, weighted by their durations, in
+    /// `0.0..=1.0`.
+    pub progress: f32,
+}
+
+impl TimelineProgress {
+    pub fn new(timeline: &BevyTimeline) -> Self {
+        let durations = timeline
+            .tracks()
+            .iter()
+            .map(|track| track.duration())
+            .collect::<Vec<_>>();
+
+        Self::from_durations(
+            &durations,
+            timeline.target_index(),
+            timeline.target_time(),
+        )
+    }
+
+    fn from_durations(
+        durations: &[Duration],
+        track: usize,
+        time: Duration,
+    ) -> Self {
+        // Empty tracks are over as soon as they start.
+        let ratio = |time: Duration, duration: Duration| {
+            if duration.is_zero() {
+                1.0
+            } else {
+                (time.as_secs_f64() / duration.as_secs_f64()).min(1.0)
+                    as f32
+            }
+        };
+
+        let sum = |durations: &[Duration]| {
+            durations
+                .iter()
+                .copied()
+                .fold(Duration::ZERO, Duration::saturating_add)
+        };
+        let elapsed =
+            sum(durations.get(..track).unwrap_or(durations))
+                .saturating_add(time);
+        let total = sum(durations);
+        let duration =
+            durations.get(track).copied().unwrap_or_default();
+
+        Self {
+            track,
+            track_count: durations.len(),
+            track_progress: ratio(time, duration),
+            progress: ratio(elapsed, total),
+        }
+    }
+}
+
+fn update_timeline_progress(
+    motiongfx: Res<MotionGfxManager>,
+    mut q_progress: Query<(&TimelineId, &mut TimelineProgress)>,
+) {
+    for (id, mut progress) in q_progress.iter_mut() {
+        if let Some(timeline) = motiongfx.get_timeline(id) {
+            // Leave change detection quiet while paused.
+            progress.set_if_neq(TimelineProgress::new(timeline));
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use motiongfx::time::{cs, ns, s};
+    use motiongfx::time::{cs, ms, ns, s};
 
     use super::*;
 
@@ -258,6 +711,14 @@ mod tests {
         .frame_time()
     }
 
+    #[test]
+    fn step_frames_snaps_to_the_frame_grid() {
+        assert_eq!(step_frames(Duration::ZERO, 30, 3), cs(10));
+        assert_eq!(step_frames(ms(1001), 30, -1), at(30, 29));
+        assert_eq!(step_frames(ms(20), 30, -2), Duration::ZERO);
+        assert_eq!(step_frames(ms(20), 0, 1), ms(20));
+    }
+
     #[test]
     fn frame_time_is_exact_where_the_rate_divides() {
         assert_eq!(at(30, 0), Duration::ZERO);
@@ -267,6 +728,39 @@ mod tests {
         assert_eq!(at(60, 90), cs(150));
     }
 
+    #[test]
+    fn progress_is_weighted_by_track_duration() {
+        let durations = [s(1), Duration::ZERO, s(3)];
+
+        let progress =
+            TimelineProgress::from_durations(&durations, 2, s(1));
+        assert_eq!(progress.track_count, 3);
+        assert_eq!(progress.track_progress, 1.0 / 3.0);
+        assert_eq!(progress.progress, 0.5);
+
+        let empty = TimelineProgress::from_durations(
+            &durations,
+            1,
+            Duration::ZERO,
+        );
+        assert_eq!(empty.track_progress, 1.0);
+        assert_eq!(empty.progress, 0.25);
+    }
+
+    #[test]
+    fn progress_saturates_on_endless_tracks() {
+        let durations = [Duration::MAX, Duration::MAX];
+
+        let progress =
+            TimelineProgress::from_durations(&durations, 1, s(1));
+        assert_eq!(progress.progress, 1.0);
+
+        // Out of range tracks count as over.
+        let past = TimelineProgress::from_durations(&[s(1)], 1, s(1));
+        assert_eq!(past.track_progress, 1.0);
+        assert_eq!(past.progress, 1.0);
+    }
+
     /// The whole point of deriving from the counter: no accumulated
     /// error, so a long render lands on exact second boundaries.
     #[test]
@@ -289,6 +783,23 @@ mod tests {
         }
     }
 
+    /// The per-frame steps handed to `Time` add up to the exact frame
+    /// times, even where `1 / fps` is not representable.
+    #[test]
+    fn render_mode_steps_do_not_drift() {
+        let mut mode = RenderMode::new(3);
+        let mut elapsed = Duration::ZERO;
+
+        for _ in 0..3_000 {
+            let prev = mode.elapsed();
+            mode.frame += 1;
+            elapsed += mode.elapsed() - prev;
+        }
+
+        assert_eq!(mode.frame(), 3_000);
+        assert_eq!(elapsed, s(1_000));
+    }
+
     /// A rate of zero frames per second advances no time, rather than
     /// dividing by zero or standing in some invented rate.
     #[test]
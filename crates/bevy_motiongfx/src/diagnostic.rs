@@ -0,0 +1,130 @@
+//! `bevy_diagnostic` integration, enabled with the `diagnostic`
+//! feature.
+//!
+//! [`MotionGfxDiagnosticsPlugin`] records the [`SampleStats`] of the
+//! [`MotionGfxManager`] every frame, along with the number of actions
+//! sampled by each pipeline under
+//! `motiongfx/pipeline/<kind>/<types>`:
+//!
+//! ```ignore
+//! app.add_plugins((
+//!     MotionGfxDiagnosticsPlugin,
+//!     LogDiagnosticsPlugin::default(),
+//! ));
+//! ```
+//!
+//! [`SampleStats`]: crate::manager::SampleStats
+
+use alloc::format;
+use bevy_app::prelude::*;
+use bevy_diagnostic::{
+    Diagnostic, DiagnosticMeasurement, DiagnosticPath,
+    DiagnosticsStore, RegisterDiagnostic,
+};
+use bevy_ecs::prelude::*;
+use bevy_platform::collections::HashMap;
+use bevy_platform::time::Instant;
+use motiongfx::prelude::*;
+
+use crate::MotionGfxSystems;
+use crate::manager::MotionGfxManager;
+
+/// Records the counters of the [`MotionGfxManager`] as diagnostics.
+pub struct MotionGfxDiagnosticsPlugin;
+
+impl MotionGfxDiagnosticsPlugin {
+    /// Time spent baking timelines.
+    pub const BAKE_TIME: DiagnosticPath =
+        DiagnosticPath::const_new("motiongfx/bake_time");
+    /// Time spent queuing and sampling timelines.
+    pub const SAMPLE_TIME: DiagnosticPath =
+        DiagnosticPath::const_new("motiongfx/sample_time");
+    /// Number of actions queued for sampling.
+    pub const QUEUED_ACTIONS: DiagnosticPath =
+        DiagnosticPath::const_new("motiongfx/queued_actions");
+    /// Number of actions sampled by a registered pipeline.
+    pub const SAMPLED_ACTIONS: DiagnosticPath =
+        DiagnosticPath::const_new("motiongfx/sampled_actions");
+}
+
+impl Plugin for MotionGfxDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(
+            Diagnostic::new(Self::BAKE_TIME).with_suffix("ms"),
+        )
+        .register_diagnostic(
+            Diagnostic::new(Self::SAMPLE_TIME).with_suffix("ms"),
+        )
+        .register_diagnostic(Diagnostic::new(Self::QUEUED_ACTIONS))
+        .register_diagnostic(Diagnostic::new(Self::SAMPLED_ACTIONS))
+        .add_systems(
+            PostUpdate,
+            record_diagnostics.after(MotionGfxSystems::Sample),
+        );
+    }
+}
+
+/// Uses the store directly, as pipeline diagnostics are only
+/// registered once the pipeline first samples.
+fn record_diagnostics(
+    motiongfx: Res<MotionGfxManager>,
+    mut store: ResMut<DiagnosticsStore>,
+    mut pipeline_paths: Local<HashMap<PipelineKey, DiagnosticPath>>,
+) {
+    let stats = motiongfx.stats();
+    let time = Instant::now();
+
+    let measurements = [
+        (
+            MotionGfxDiagnosticsPlugin::BAKE_TIME,
+            stats.bake_time.as_secs_f64() * 1000.0,
+        ),
+        (
+            MotionGfxDiagnosticsPlugin::SAMPLE_TIME,
+            stats.sample_time.as_secs_f64() * 1000.0,
+        ),
+        (
+            MotionGfxDiagnosticsPlugin::QUEUED_ACTIONS,
+            stats.queued as f64,
+        ),
+        (
+            MotionGfxDiagnosticsPlugin::SAMPLED_ACTIONS,
+            stats.sampled as f64,
+        ),
+    ];
+    for (path, value) in measurements.iter() {
+        measure(&mut store, path, time, *value);
+    }
+
+    for (key, &count) in stats.pipelines.iter() {
+        let path = pipeline_paths.entry(*key).or_insert_with(|| {
+            let name = motiongfx
+                .registry()
+                .pipeline
+                .name(key)
+                .unwrap_or("unknown");
+            let path = DiagnosticPath::new(format!(
+                "motiongfx/pipeline/{}/{name}",
+                key.kind_name(),
+            ));
+            store.add(Diagnostic::new(path.clone()));
+            path
+        });
+
+        measure(&mut store, path, time, count as f64);
+    }
+}
+
+fn measure(
+    store: &mut DiagnosticsStore,
+    path: &DiagnosticPath,
+    time: Instant,
+    value: f64,
+) {
+    if let Some(diagnostic) = store.get_mut(path)
+        && diagnostic.is_enabled
+    {
+        diagnostic
+            .add_measurement(DiagnosticMeasurement { time, value });
+    }
+}
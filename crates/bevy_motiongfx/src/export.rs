@@ -0,0 +1,916 @@
+//! Video export, enabled with the `export` feature.
+//!
+//! Inserting [`ExportSettings`] renders a timeline frame by frame and
+//! either pipes the frames into `ffmpeg` or writes them as a PNG
+//! sequence next to a `manifest.json`:
+//!
+//! ```ignore
+//! app.add_plugins(ExportPlugin);
+//!
+//! commands.insert_resource(ExportSettings::new(
+//!     timeline_entity,
+//!     60,
+//!     ExportOutput::ffmpeg("out.mp4"),
+//! ));
+//! ```
+//!
+//! While exporting, [`Time`] runs in [`RenderMode`] and the timeline
+//! is set to the exact time of every frame, so the output does not
+//! depend on how long frames take to render. The timeline entity
+//! should not have a player of its own. [`ExportFrame`] runs for
+//! every exported frame as well, and [`ExportFinished`] is triggered
+//! once the last frame is written.
+//!
+//! [`ExportOutput::Frames`] leaves the frames to the app instead,
+//! triggering an [`ExportedFrame`] for each of them. It needs no
+//! filesystem nor `ffmpeg`, so it is the one to use on the web, e.g.
+//! to draw the frames onto an `OffscreenCanvas` or to encode them
+//! with WebCodecs.
+//!
+//! [`DeckSettings`] instead exports handouts of a presentation, one
+//! slide per track, captured at the end of the track:
+//!
+//! ```ignore
+//! commands.insert_resource(DeckSettings::new(
+//!     timeline_entity,
+//!     DeckOutput::pdf("slides.pdf"),
+//! ));
+//! ```
+//!
+//! [`Time`]: bevy_time::Time
+
+use core::fmt;
+use core::time::Duration;
+
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_render::view::screenshot::{Screenshot, ScreenshotCaptured};
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, Command, ExitStatus, Stdio};
+
+use crate::MotionGfxSystems;
+use crate::controller::{
+    CurrentExportFrame, ExportFrame, RenderMode, run_export_frames,
+};
+use crate::manager::{MotionGfxManager, TimelineId};
+
+/// Exports timelines described by [`ExportSettings`].
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (
+                (
+                    begin_export
+                        .run_if(resource_added::<ExportSettings>),
+                    drive_export,
+                )
+                    .chain()
+                    .in_set(MotionGfxSystems::Controller),
+                capture_export_frame
+                    .in_set(MotionGfxSystems::Export)
+                    .after(run_export_frames),
+                (
+                    begin_deck.run_if(resource_added::<DeckSettings>),
+                    drive_deck,
+                )
+                    .chain()
+                    .in_set(MotionGfxSystems::Controller),
+                capture_deck_slide.in_set(MotionGfxSystems::Export),
+            ),
+        );
+    }
+}
+
+/// Describes an export, insert it as a resource to start exporting.
+///
+/// The resource is removed once the export finishes.
+#[derive(Resource, Debug, Clone)]
+pub struct ExportSettings {
+    /// The entity with the [`TimelineId`] to export. Its target track
+    /// is exported from start to end.
+    pub timeline: Entity,
+    /// Frames per second of the output.
+    pub fps: u16,
+    pub output: ExportOutput,
+    /// The window to capture, the primary window if [`None`].
+    pub window: Option<Entity>,
+}
+
+impl ExportSettings {
+    /// # Panics
+    ///
+    /// Panics if `fps` is zero.
+    pub fn new(
+        timeline: Entity,
+        fps: u16,
+        output: ExportOutput,
+    ) -> Self {
+        assert!(fps > 0, "export needs a non-zero fps");
+
+        Self {
+            timeline,
+            fps,
+            output,
+            window: None,
+        }
+    }
+
+    /// Builder method for setting [`Self::window`].
+    pub fn with_window(mut self, window: Entity) -> Self {
+        self.window = Some(window);
+        self
+    }
+}
+
+/// Where the frames of an export go.
+#[derive(Debug, Clone)]
+pub enum ExportOutput {
+    /// Pipes raw RGBA frames into an `ffmpeg` process encoding `path`.
+    Ffmpeg {
+        path: PathBuf,
+        /// Output arguments passed to `ffmpeg` before `path`.
+        args: Vec<String>,
+    },
+    /// Writes `frame_00000.png` onwards into `dir` along with a
+    /// `manifest.json` holding the fps, frame count, duration and
+    /// resolution.
+    ImageSequence { dir: PathBuf },
+    /// Triggers an [`ExportedFrame`] for every frame.
+    Frames,
+}
+
+impl ExportOutput {
+    /// An H.264 video at `path` through `ffmpeg`, which must be on
+    /// the `PATH`.
+    pub fn ffmpeg(path: impl Into<PathBuf>) -> Self {
+        Self::Ffmpeg {
+            path: path.into(),
+            args: ["-c:v", "libx264", "-pix_fmt", "yuv420p"]
+                .map(String::from)
+                .to_vec(),
+        }
+    }
+
+    /// A PNG sequence in `dir`, which is created if missing.
+    pub fn image_sequence(dir: impl Into<PathBuf>) -> Self {
+        Self::ImageSequence { dir: dir.into() }
+    }
+}
+
+/// Describes a slide deck export, insert it as a resource to start
+/// exporting.
+///
+/// The resource is removed once the export finishes, with the
+/// number of slides written as the [`ExportFinished::frames`].
+#[derive(Resource, Debug, Clone)]
+pub struct DeckSettings {
+    /// The entity with the [`TimelineId`] to export. Each of its
+    /// tracks is a slide, including the checkpoints.
+    pub timeline: Entity,
+    pub output: DeckOutput,
+    /// The window to capture, the primary window if [`None`].
+    pub window: Option<Entity>,
+}
+
+impl DeckSettings {
+    pub fn new(timeline: Entity, output: DeckOutput) -> Self {
+        Self {
+            timeline,
+            output,
+            window: None,
+        }
+    }
+
+    /// Builder method for setting [`Self::window`].
+    pub fn with_window(mut self, window: Entity) -> Self {
+        self.window = Some(window);
+        self
+    }
+}
+
+/// Where the slides of a deck export go.
+#[derive(Debug, Clone)]
+pub enum DeckOutput {
+    /// A PDF at `path` with a page per slide, sized one point per
+    /// pixel.
+    Pdf { path: PathBuf },
+    /// Writes `slide_001.png` onwards into `dir`.
+    Images { dir: PathBuf },
+    /// Triggers an [`ExportedFrame`] for every slide.
+    Frames,
+}
+
+impl DeckOutput {
+    pub fn pdf(path: impl Into<PathBuf>) -> Self {
+        Self::Pdf { path: path.into() }
+    }
+
+    /// Numbered PNGs in `dir`, which is created if missing.
+    pub fn images(dir: impl Into<PathBuf>) -> Self {
+        Self::Images { dir: dir.into() }
+    }
+}
+
+/// A frame of an export to [`ExportOutput::Frames`] or
+/// [`DeckOutput::Frames`], triggered in order.
+///
+/// The pixels are tightly packed rows of 8-bit sRGB RGBA, the layout
+/// of a web `ImageData`.
+#[derive(Event, Debug, Clone)]
+pub struct ExportedFrame {
+    /// The index of the frame, or of the slide.
+    pub frame: u64,
+    pub width: u32,
+    pub height: u32,
+    pub rgba: Vec<u8>,
+}
+
+/// Triggered once an export stops, successfully or not.
+#[derive(Event, Debug)]
+pub struct ExportFinished {
+    /// The number of frames written.
+    pub frames: u64,
+    pub result: Result<(), ExportError>,
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    /// The exported entity has no live timeline.
+    MissingTimeline,
+    Io(std::io::Error),
+    /// A captured frame could not be converted to RGBA.
+    Image(String),
+    /// `ffmpeg` exited with an error.
+    Ffmpeg(ExitStatus),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingTimeline => write!(f, "timeline not found"),
+            Self::Io(e) => write!(f, "io error: {e}"),
+            Self::Image(e) => write!(f, "unsupported frame: {e}"),
+            Self::Ffmpeg(status) => {
+                write!(f, "ffmpeg failed with {status}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for ExportError {}
+
+impl From<std::io::Error> for ExportError {
+    fn from(e: std::io::Error) -> Self {
+        Self::Io(e)
+    }
+}
+
+/// Progress of the running export.
+#[derive(Resource)]
+struct ExportState {
+    timeline: TimelineId,
+    frame_count: u64,
+    /// The next frame to sample and capture.
+    next_frame: u64,
+    /// The next frame to write, captures can arrive out of order.
+    next_write: u64,
+    pending: BTreeMap<u64, Image>,
+    writer: FrameWriter,
+}
+
+fn begin_export(
+    mut commands: Commands,
+    settings: Res<ExportSettings>,
+    motiongfx: Res<MotionGfxManager>,
+    q_timelines: Query<&TimelineId>,
+) {
+    let timeline = q_timelines
+        .get(settings.timeline)
+        .ok()
+        .and_then(|id| Some((*id, motiongfx.get_timeline(id)?)));
+    let Some((id, timeline)) = timeline else {
+        finish_export(
+            &mut commands,
+            0,
+            Err(ExportError::MissingTimeline),
+        );
+        return;
+    };
+
+    let duration =
+        timeline.tracks()[timeline.target_index()].duration();
+    // Both ends included.
+    let frame_count = (duration.as_nanos() * settings.fps as u128
+        / 1_000_000_000) as u64
+        + 1;
+
+    commands.insert_resource(RenderMode::new(settings.fps));
+    commands.insert_resource(ExportState {
+        timeline: id,
+        frame_count,
+        next_frame: 0,
+        next_write: 0,
+        pending: BTreeMap::new(),
+        writer: FrameWriter::new(&settings),
+    });
+}
+
+fn drive_export(
+    mut motiongfx: ResMut<MotionGfxManager>,
+    state: Option<Res<ExportState>>,
+    settings: Option<Res<ExportSettings>>,
+) {
+    let (Some(state), Some(settings)) = (state, settings) else {
+        return;
+    };
+    if state.next_frame >= state.frame_count {
+        return;
+    }
+
+    if let Some(timeline) =
+        motiongfx.get_timeline_mut(&state.timeline)
+    {
+        timeline.set_target_time(frame_time(
+            state.next_frame,
+            settings.fps,
+        ));
+    }
+}
+
+/// Runs [`ExportFrame`] for the sampled frame, then captures it.
+fn capture_export_frame(world: &mut World) {
+    let (Some(state), Some(settings)) = (
+        world.get_resource::<ExportState>(),
+        world.get_resource::<ExportSettings>(),
+    ) else {
+        return;
+    };
+    if state.next_frame >= state.frame_count {
+        return;
+    }
+
+    let frame = state.next_frame;
+    let screenshot = match settings.window {
+        Some(window) => Screenshot::window(window),
+        None => Screenshot::primary_window(),
+    };
+    world.insert_resource(CurrentExportFrame {
+        entity: settings.timeline,
+        timeline: state.timeline,
+        frame,
+        time: frame_time(frame, settings.fps),
+    });
+    let _ = world.try_run_schedule(ExportFrame);
+    world.remove_resource::<CurrentExportFrame>();
+
+    world.resource_mut::<ExportState>().next_frame += 1;
+    world.spawn(screenshot).observe(
+        move |captured: On<ScreenshotCaptured>,
+              mut commands: Commands,
+              state: Option<ResMut<ExportState>>| {
+            if let Some(mut state) = state {
+                state.pending.insert(frame, captured.image.clone());
+                write_pending(&mut commands, &mut state);
+            }
+        },
+    );
+}
+
+/// Writes the captured frames that are next in line, finishing the
+/// export after the last one or on the first error.
+fn write_pending(commands: &mut Commands, state: &mut ExportState) {
+    let written = loop {
+        let Some(image) = state.pending.remove(&state.next_write)
+        else {
+            return;
+        };
+        if let Err(e) =
+            state.writer.write(commands, state.next_write, image)
+        {
+            break Err(e);
+        }
+
+        state.next_write += 1;
+        if state.next_write == state.frame_count {
+            break state.writer.finish(state.frame_count);
+        }
+    };
+
+    finish_export(commands, state.next_write, written);
+}
+
+fn finish_export(
+    commands: &mut Commands,
+    frames: u64,
+    result: Result<(), ExportError>,
+) {
+    commands.remove_resource::<ExportState>();
+    commands.remove_resource::<ExportSettings>();
+    commands.remove_resource::<RenderMode>();
+    commands.trigger(ExportFinished { frames, result });
+}
+
+fn frame_time(frame: u64, fps: u16) -> Duration {
+    Duration::from_secs(frame) / fps as u32
+}
+
+/// Progress of the running deck export.
+#[derive(Resource)]
+struct DeckState {
+    timeline: TimelineId,
+    slide_count: usize,
+    /// The next slide to seek to and capture.
+    next_slide: usize,
+    /// The next slide to write, captures can arrive out of order.
+    next_write: usize,
+    pending: BTreeMap<usize, Image>,
+    writer: DeckWriter,
+}
+
+fn begin_deck(
+    mut commands: Commands,
+    settings: Res<DeckSettings>,
+    motiongfx: Res<MotionGfxManager>,
+    q_timelines: Query<&TimelineId>,
+) {
+    let timeline = q_timelines
+        .get(settings.timeline)
+        .ok()
+        .and_then(|id| Some((*id, motiongfx.get_timeline(id)?)));
+    let Some((id, timeline)) = timeline else {
+        finish_deck(
+            &mut commands,
+            0,
+            Err(ExportError::MissingTimeline),
+        );
+        return;
+    };
+
+    commands.insert_resource(DeckState {
+        timeline: id,
+        slide_count: timeline.tracks().len(),
+        next_slide: 0,
+        next_write: 0,
+        pending: BTreeMap::new(),
+        writer: DeckWriter::new(&settings.output),
+    });
+}
+
+fn drive_deck(
+    mut motiongfx: ResMut<MotionGfxManager>,
+    state: Option<Res<DeckState>>,
+) {
+    let Some(state) = state else {
+        return;
+    };
+    if state.next_slide >= state.slide_count {
+        return;
+    }
+
+    if let Some(timeline) =
+        motiongfx.get_timeline_mut(&state.timeline)
+    {
+        timeline.set_target_track(state.next_slide);
+        // Clamped to the end of the slide.
+        timeline.set_target_time(Duration::MAX);
+    }
+}
+
+/// Captures the slide sampled this frame.
+fn capture_deck_slide(
+    mut commands: Commands,
+    settings: Option<Res<DeckSettings>>,
+    state: Option<ResMut<DeckState>>,
+) {
+    let (Some(settings), Some(mut state)) = (settings, state) else {
+        return;
+    };
+    if state.next_slide >= state.slide_count {
+        return;
+    }
+
+    let slide = state.next_slide;
+    state.next_slide += 1;
+    let screenshot = match settings.window {
+        Some(window) => Screenshot::window(window),
+        None => Screenshot::primary_window(),
+    };
+    commands.spawn(screenshot).observe(
+        move |captured: On<ScreenshotCaptured>,
+              mut commands: Commands,
+              state: Option<ResMut<DeckState>>| {
+            if let Some(mut state) = state {
+                state.pending.insert(slide, captured.image.clone());
+                write_pending_slides(&mut commands, &mut state);
+            }
+        },
+    );
+}
+
+/// Writes the captured slides that are next in line, finishing the
+/// export after the last one or on the first error.
+fn write_pending_slides(
+    commands: &mut Commands,
+    state: &mut DeckState,
+) {
+    let written = loop {
+        let Some(image) = state.pending.remove(&state.next_write)
+        else {
+            return;
+        };
+        if let Err(e) =
+            state.writer.write(commands, state.next_write, image)
+        {
+            break Err(e);
+        }
+
+        state.next_write += 1;
+        if state.next_write == state.slide_count {
+            break state.writer.finish();
+        }
+    };
+
+    finish_deck(commands, state.next_write, written);
+}
+
+fn finish_deck(
+    commands: &mut Commands,
+    slides: usize,
+    result: Result<(), ExportError>,
+) {
+    commands.remove_resource::<DeckState>();
+    commands.remove_resource::<DeckSettings>();
+    commands.trigger(ExportFinished {
+        frames: slides as u64,
+        result,
+    });
+}
+
+enum DeckWriter {
+    Pdf {
+        path: PathBuf,
+        /// Written all at once as the page count goes up front.
+        pages: Vec<PdfPage>,
+    },
+    Images {
+        dir: PathBuf,
+    },
+    Frames,
+}
+
+impl DeckWriter {
+    fn new(output: &DeckOutput) -> Self {
+        match output {
+            DeckOutput::Pdf { path } => Self::Pdf {
+                path: path.clone(),
+                pages: Vec::new(),
+            },
+            DeckOutput::Images { dir } => {
+                Self::Images { dir: dir.clone() }
+            }
+            DeckOutput::Frames => Self::Frames,
+        }
+    }
+
+    fn write(
+        &mut self,
+        commands: &mut Commands,
+        slide: usize,
+        image: Image,
+    ) -> Result<(), ExportError> {
+        let image = image
+            .try_into_dynamic()
+            .map_err(|e| ExportError::Image(e.to_string()))?;
+
+        match self {
+            Self::Pdf { pages, .. } => {
+                pages.push(PdfPage {
+                    width: image.width(),
+                    height: image.height(),
+                    rgb: image.to_rgb8().into_raw(),
+                });
+            }
+            Self::Images { dir } => {
+                if slide == 0 {
+                    std::fs::create_dir_all(&*dir)?;
+                }
+                let path =
+                    dir.join(format!("slide_{:03}.png", slide + 1));
+                image
+                    .to_rgba8()
+                    .save(path)
+                    .map_err(|e| ExportError::Image(e.to_string()))?;
+            }
+            Self::Frames => {
+                commands.trigger(ExportedFrame {
+                    frame: slide as u64,
+                    width: image.width(),
+                    height: image.height(),
+                    rgba: image.to_rgba8().into_raw(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), ExportError> {
+        if let Self::Pdf { path, pages } = self {
+            std::fs::write(path, pdf_bytes(pages))?;
+        }
+
+        Ok(())
+    }
+}
+
+struct PdfPage {
+    width: u32,
+    height: u32,
+    rgb: Vec<u8>,
+}
+
+/// Encodes `pages` as a PDF, each filled by its uncompressed image.
+fn pdf_bytes(pages: &[PdfPage]) -> Vec<u8> {
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = Vec::new();
+    let mut object = |pdf: &mut Vec<u8>, body: &[u8]| {
+        offsets.push(pdf.len());
+        pdf.extend(format!("{} 0 obj\n", offsets.len()).bytes());
+        pdf.extend_from_slice(body);
+        pdf.extend_from_slice(b"\nendobj\n");
+    };
+
+    // Objects 1 and 2, then a page, its content and its image for
+    // every page starting at object 3.
+    let kids = (0..pages.len())
+        .map(|i| format!("{} 0 R", 3 + i * 3))
+        .collect::<Vec<_>>()
+        .join(" ");
+    object(&mut pdf, b"<< /Type /Catalog /Pages 2 0 R >>");
+    object(
+        &mut pdf,
+        format!(
+            "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+            pages.len()
+        )
+        .as_bytes(),
+    );
+
+    for (i, page) in pages.iter().enumerate() {
+        let (w, h) = (page.width, page.height);
+        let content = 4 + i * 3;
+        let image = content + 1;
+
+        object(
+            &mut pdf,
+            format!(
+                "<< /Type /Page /Parent 2 0 R \
+                 /MediaBox [0 0 {w} {h}] \
+                 /Resources << /XObject << /Im0 {image} 0 R >> >> \
+                 /Contents {content} 0 R >>"
+            )
+            .as_bytes(),
+        );
+
+        let draw = format!("q {w} 0 0 {h} 0 0 cm /Im0 Do Q");
+        object(
+            &mut pdf,
+            format!(
+                "<< /Length {} >>\nstream\n{draw}\nendstream",
+                draw.len()
+            )
+            .as_bytes(),
+        );
+
+        let mut body = format!(
+            "<< /Type /XObject /Subtype /Image /Width {w} \
+             /Height {h} /ColorSpace /DeviceRGB \
+             /BitsPerComponent 8 /Length {} >>\nstream\n",
+            page.rgb.len()
+        )
+        .into_bytes();
+        body.extend_from_slice(&page.rgb);
+        body.extend_from_slice(b"\nendstream");
+        object(&mut pdf, &body);
+    }
+
+    let xref = pdf.len();
+    pdf.extend(
+        format!(
+            "xref\n0 {}\n0000000000 65535 f \n",
+            offsets.len() + 1
+        )
+        .bytes(),
+    );
+    for offset in &offsets {
+        pdf.extend(format!("{offset:010} 00000 n \n").bytes());
+    }
+    pdf.extend(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\n\
+             startxref\n{xref}\n%%EOF\n",
+            offsets.len() + 1
+        )
+        .bytes(),
+    );
+
+    pdf
+}
+
+enum FrameWriter {
+    Ffmpeg {
+        path: PathBuf,
+        args: Vec<String>,
+        fps: u16,
+        /// Spawned on the first frame, once the resolution is known.
+        process: Option<(Child, ChildStdin)>,
+    },
+    ImageSequence {
+        dir: PathBuf,
+        fps: u16,
+        size: Option<(u32, u32)>,
+    },
+    Frames,
+}
+
+impl FrameWriter {
+    fn new(settings: &ExportSettings) -> Self {
+        match &settings.output {
+            ExportOutput::Ffmpeg { path, args } => Self::Ffmpeg {
+                path: path.clone(),
+                args: args.clone(),
+                fps: settings.fps,
+                process: None,
+            },
+            ExportOutput::ImageSequence { dir } => {
+                Self::ImageSequence {
+                    dir: dir.clone(),
+                    fps: settings.fps,
+                    size: None,
+                }
+            }
+            ExportOutput::Frames => Self::Frames,
+        }
+    }
+
+    fn write(
+        &mut self,
+        commands: &mut Commands,
+        frame: u64,
+        image: Image,
+    ) -> Result<(), ExportError> {
+        let (width, height) = (image.width(), image.height());
+        let rgba = image
+            .try_into_dynamic()
+            .map_err(|e| ExportError::Image(e.to_string()))?
+            .to_rgba8();
+
+        match self {
+            Self::Ffmpeg {
+                path,
+                args,
+                fps,
+                process,
+            } => {
+                if process.is_none() {
+                    let mut child = Command::new("ffmpeg")
+                        .args(["-y", "-f", "rawvideo", "-pix_fmt"])
+                        .args([
+                            "rgba",
+                            "-s",
+                            &format!("{width}x{height}"),
+                        ])
+                        .args(["-r", &fps.to_string(), "-i", "-"])
+                        .args(args.iter())
+                        .arg(path.as_os_str())
+                        .stdin(Stdio::piped())
+                        .stdout(Stdio::null())
+                        .stderr(Stdio::null())
+                        .spawn()?;
+                    let stdin =
+                        child.stdin.take().ok_or_else(|| {
+                            std::io::Error::other(
+                                "ffmpeg stdin is closed",
+                            )
+                        })?;
+                    *process = Some((child, stdin));
+                }
+                if let Some((_, stdin)) = process {
+                    stdin.write_all(rgba.as_raw())?;
+                }
+            }
+            Self::ImageSequence { dir, size, .. } => {
+                if size.is_none() {
+                    std::fs::create_dir_all(&*dir)?;
+                    *size = Some((width, height));
+                }
+                rgba.save(dir.join(format!("frame_{frame:05}.png")))
+                    .map_err(|e| ExportError::Image(e.to_string()))?;
+            }
+            Self::Frames => {
+                commands.trigger(ExportedFrame {
+                    frame,
+                    width,
+                    height,
+                    rgba: rgba.into_raw(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(
+        &mut self,
+        frame_count: u64,
+    ) -> Result<(), ExportError> {
+        match self {
+            Self::Ffmpeg { process, .. } => {
+                let Some((mut child, stdin)) = process.take() else {
+                    return Ok(());
+                };
+                // Closing stdin ends the stream.
+                drop(stdin);
+                let status = child.wait()?;
+                if !status.success() {
+                    return Err(ExportError::Ffmpeg(status));
+                }
+            }
+            Self::ImageSequence { dir, fps, size } => {
+                let (width, height) = size.unwrap_or_default();
+                let duration = frame_time(frame_count - 1, *fps);
+                let manifest = format!(
+                    "{{\n  \"fps\": {fps},\n  \"frames\": {frame_count},\n  \
+                     \"duration\": {},\n  \"width\": {width},\n  \
+                     \"height\": {height},\n  \
+                     \"pattern\": \"frame_%05d.png\"\n}}\n",
+                    duration.as_secs_f64(),
+                );
+                std::fs::write(dir.join("manifest.json"), manifest)?;
+            }
+            Self::Frames => {}
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for FrameWriter {
+    fn drop(&mut self) {
+        // Don't leave a stray encoder behind on errors.
+        if let Self::Ffmpeg {
+            process: Some((child, _)),
+            ..
+        } = self
+        {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdf_xref_points_at_the_objects() {
+        let page = || PdfPage {
+            width: 2,
+            height: 1,
+            rgb: [100, 0, 0, 0, 0, 100].to_vec(),
+        };
+        let pdf = pdf_bytes(&[page(), page()]);
+        let text = String::from_utf8(pdf).unwrap();
+
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("/Kids [3 0 R 6 0 R] /Count 2"));
+
+        let startxref = text.rfind("startxref\n").unwrap();
+        let xref = text[startxref + 10..]
+            .lines()
+            .next()
+            .and_then(|line| line.parse::<usize>().ok())
+            .unwrap();
+        assert!(text[xref..].starts_with("xref\n0 9\n"));
+
+        // Entries are 20 bytes each, after the free entry.
+        let entries = xref + "xref\n0 9\n".len() + 20;
+        for id in 1..=8 {
+            let entry = &text[entries + (id - 1) * 20..][..10];
+            let offset = entry.parse::<usize>().unwrap();
+            assert!(
+                text[offset..].starts_with(&format!("{id} 0 obj"))
+            );
+        }
+    }
+}
@@ -0,0 +1,372 @@
+//! Import of glTF node animations, enabled with the `gltf` feature.
+//!
+//! [`load_gltf_animations`] reads the translation, rotation and scale
+//! channels of a `.glb` file, and [`GltfAnimation::act`] turns them
+//! into a [`TrackFragment`] on the scene entities, so imported
+//! animations can be sequenced and retimed like any other action:
+//!
+//! ```ignore
+//! let animations = load_gltf_animations(include_bytes!("walk.glb"))?;
+//!
+//! // Map glTF nodes to the entities of the spawned scene, e.g. by
+//! // their `Name`.
+//! let walk = animations[0].act(&mut builder, |channel| {
+//!     nodes.get(channel.node_name.as_deref()?).copied()
+//! });
+//! let track = [walk, wave].ord_chain();
+//! ```
+
+use core::time::Duration;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use bevy_ecs::prelude::*;
+use bevy_math::{Quat, Vec3};
+use bevy_transform::components::Transform;
+use gltf::animation::util::ReadOutputs;
+use gltf::animation::{Channel, Interpolation as GltfInterpolation};
+use gltf::buffer::Source;
+use gltf::{Buffer, Gltf};
+use motiongfx::prelude::*;
+use motiongfx::track::delay;
+
+use crate::interpolation::Bevy;
+use crate::world::BevyTimelineBuilder;
+
+pub use gltf::Error as GltfError;
+
+/// A named set of node animation channels.
+#[derive(Debug, Clone)]
+pub struct GltfAnimation {
+    pub name: Option<String>,
+    pub channels: Vec<GltfChannel>,
+}
+
+impl GltfAnimation {
+    /// The time of the last keyframe of any channel.
+    pub fn duration(&self) -> Duration {
+        self.channels
+            .iter()
+            .map(GltfChannel::duration)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Plays every channel concurrently on the entity returned by
+    /// `entity`, leaving out channels without one.
+    pub fn act(
+        &self,
+        builder: &mut BevyTimelineBuilder,
+        mut entity: impl FnMut(&GltfChannel) -> Option<Entity>,
+    ) -> TrackFragment {
+        self.channels
+            .iter()
+            .filter_map(|channel| {
+                Some(channel.act(builder, entity(channel)?))
+            })
+            .ord_all()
+    }
+}
+
+/// The keyframes of a single [`Transform`] field of a glTF node.
+#[derive(Debug, Clone)]
+pub struct GltfChannel {
+    /// The index of the node in the document.
+    pub node: usize,
+    pub node_name: Option<String>,
+    /// Holds each value until the next keyframe instead of
+    /// interpolating linearly.
+    pub step: bool,
+    /// Keyframe times in seconds.
+    pub times: Vec<f32>,
+    pub values: GltfValues,
+}
+
+/// The keyframe values of a [`GltfChannel`].
+#[derive(Debug, Clone)]
+pub enum GltfValues {
+    Translation(Vec<Vec3>),
+    Rotation(Vec<Quat>),
+    Scale(Vec<Vec3>),
+}
+
+impl GltfChannel {
+    /// The time of the last keyframe.
+    pub fn duration(&self) -> Duration {
+        self.times.last().map_or(Duration::ZERO, |&time| secs(time))
+    }
+
+    /// Plays the keyframes on the [`Transform`] of `entity`.
+    ///
+    /// The first value is set right away, so the field holds it
+    /// until the first keyframe as in glTF.
+    pub fn act(
+        &self,
+        builder: &mut BevyTimelineBuilder,
+        entity: Entity,
+    ) -> TrackFragment {
+        match &self.values {
+            GltfValues::Translation(values) => self.act_keys(
+                builder,
+                entity,
+                path!(<Transform>::translation),
+                values,
+            ),
+            GltfValues::Rotation(values) => self.act_keys(
+                builder,
+                entity,
+                path!(<Transform>::rotation),
+                values,
+            ),
+            GltfValues::Scale(values) => self.act_keys(
+                builder,
+                entity,
+                path!(<Transform>::scale),
+                values,
+            ),
+        }
+    }
+
+    fn act_keys<T>(
+        &self,
+        builder: &mut BevyTimelineBuilder,
+        entity: Entity,
+        field: FieldAccessor<Transform, T>,
+        values: &[T],
+    ) -> TrackFragment
+    where
+        T: Interpolation<Bevy> + Copy + ThreadSafe,
+    {
+        let (Some(&start), Some((&first, values))) =
+            (self.times.first(), values.split_first())
+        else {
+            return TrackFragment::new();
+        };
+
+        let hold = builder
+            .act_step(entity, field, move |_| first)
+            .play(Duration::ZERO);
+
+        let mut prev = start;
+        let keys = self.times[1..]
+            .iter()
+            .zip(values)
+            .map(|(&time, &value)| {
+                let duration = secs(time - prev);
+                prev = time;
+
+                if self.step {
+                    builder.act_step(entity, field, move |_| value)
+                } else {
+                    builder
+                        .act_builder(entity, field, move |_| value)
+                        .with_interpolation::<Bevy>()
+                }
+                .play(duration)
+            })
+            .collect::<Vec<_>>();
+
+        [hold, delay(secs(start), keys.ord_chain())].ord_chain()
+    }
+}
+
+/// Reads the node animations of a `.glb` file.
+///
+/// Only the embedded binary buffer is read, channels stored in
+/// external or data URI buffers are left out, as are morph target
+/// weights. Cubic spline channels are sampled linearly between their
+/// keyframe values.
+pub fn load_gltf_animations(
+    bytes: &[u8],
+) -> Result<Vec<GltfAnimation>, GltfError> {
+    let gltf = Gltf::from_slice(bytes)?;
+    let blob = gltf.blob.as_deref();
+    let buffer = |buffer: Buffer| match buffer.source() {
+        Source::Bin => blob,
+        Source::Uri(_) => None,
+    };
+
+    Ok(gltf
+        .animations()
+        .map(|animation| GltfAnimation {
+            name: animation.name().map(ToString::to_string),
+            channels: animation
+                .channels()
+                .filter_map(|channel| read_channel(&channel, buffer))
+                .collect(),
+        })
+        .collect())
+}
+
+fn read_channel<'a, 's>(
+    channel: &Channel<'a>,
+    buffer: impl Clone + Fn(Buffer<'a>) -> Option<&'s [u8]>,
+) -> Option<GltfChannel> {
+    let reader = channel.reader(buffer);
+    let times = reader.read_inputs()?.collect::<Vec<_>>();
+    let interpolation = channel.sampler().interpolation();
+
+    let values = match reader.read_outputs()? {
+        ReadOutputs::Translations(v) => GltfValues::Translation(
+            keyframe_values(v.map(Vec3::from), interpolation),
+        ),
+        ReadOutputs::Rotations(v) => {
+            GltfValues::Rotation(keyframe_values(
+                v.into_f32().map(Quat::from_array),
+                interpolation,
+            ))
+        }
+        ReadOutputs::Scales(v) => GltfValues::Scale(keyframe_values(
+            v.map(Vec3::from),
+            interpolation,
+        )),
+        ReadOutputs::MorphTargetWeights(_) => return None,
+    };
+
+    let node = channel.target().node();
+    Some(GltfChannel {
+        node: node.index(),
+        node_name: node.name().map(ToString::to_string),
+        step: interpolation == GltfInterpolation::Step,
+        times,
+        values,
+    })
+}
+
+/// Cubic splines store an in tangent, the value and an out tangent
+/// per keyframe, only the value is kept.
+fn keyframe_values<T>(
+    values: impl Iterator<Item = T>,
+    interpolation: GltfInterpolation,
+) -> Vec<T> {
+    match interpolation {
+        GltfInterpolation::CubicSpline => {
+            values.skip(1).step_by(3).collect()
+        }
+        _ => values.collect(),
+    }
+}
+
+fn secs(secs: f32) -> Duration {
+    // `max` also maps NaN to zero.
+    Duration::try_from_secs_f32(secs.max(0.0))
+        .unwrap_or(Duration::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use motiongfx::time::{ms, s};
+
+    use crate::manager::MotionGfxManager;
+
+    use super::*;
+
+    fn channel(step: bool) -> GltfChannel {
+        GltfChannel {
+            node: 0,
+            node_name: None,
+            step,
+            times: alloc::vec![1.0, 2.0, 3.0],
+            values: GltfValues::Translation(alloc::vec![
+                Vec3::X,
+                Vec3::X * 3.0,
+                Vec3::X * 5.0,
+            ]),
+        }
+    }
+
+    /// Plays `channel` on a default [`Transform`], returning the
+    /// duration of its track and the translation at each time.
+    fn play(
+        channel: &GltfChannel,
+        times: &[Duration],
+    ) -> (Duration, Vec<Vec3>) {
+        let mut world = World::new();
+        let entity = world.spawn(Transform::default()).id();
+
+        let mut motiongfx = MotionGfxManager::default();
+        let mut b = motiongfx.create_builder();
+        let track = channel.act(&mut b, entity).compile();
+        let duration = track.duration();
+        b.add_tracks(track);
+        let timeline = b.compile();
+        let id = motiongfx.add_timeline(timeline);
+        motiongfx.load_pending_timelines(&world);
+
+        let translations = times
+            .iter()
+            .map(|&time| {
+                motiongfx
+                    .get_timeline_mut(&id)
+                    .unwrap()
+                    .set_target_time(time);
+                motiongfx.sample_timelines(&mut world);
+                world.get::<Transform>(entity).unwrap().translation
+            })
+            .collect();
+
+        (duration, translations)
+    }
+
+    #[test]
+    fn cubic_splines_keep_only_the_values() {
+        // In tangent, value and out tangent per keyframe.
+        let values = [0, 1, 2, 10, 11, 12];
+        assert_eq!(
+            keyframe_values(
+                values.into_iter(),
+                GltfInterpolation::CubicSpline
+            ),
+            [1, 11]
+        );
+        for interpolation in
+            [GltfInterpolation::Linear, GltfInterpolation::Step]
+        {
+            assert_eq!(
+                keyframe_values(values.into_iter(), interpolation),
+                values
+            );
+        }
+    }
+
+    #[test]
+    fn linear_keys_hold_the_first_value_until_it_starts() {
+        let times = [ms(500), ms(1500), s(2), s(3)];
+        let (duration, translations) = play(&channel(false), &times);
+
+        // Delayed by the first keyframe time.
+        assert_eq!(duration, s(3));
+        assert_eq!(
+            translations,
+            [Vec3::X, Vec3::X * 2.0, Vec3::X * 3.0, Vec3::X * 5.0]
+        );
+    }
+
+    #[test]
+    fn step_keys_hold_each_value_until_the_next() {
+        let times = [ms(500), ms(1500), s(2), ms(2500), s(3)];
+        let (duration, translations) = play(&channel(true), &times);
+
+        assert_eq!(duration, s(3));
+        assert_eq!(
+            translations,
+            [
+                Vec3::X,
+                Vec3::X,
+                Vec3::X * 3.0,
+                Vec3::X * 3.0,
+                Vec3::X * 5.0
+            ]
+        );
+    }
+
+    #[test]
+    fn empty_channels_play_nothing() {
+        let mut empty = channel(false);
+        empty.times.clear();
+        let (duration, translations) = play(&empty, &[s(1)]);
+
+        assert_eq!(duration, Duration::ZERO);
+        assert_eq!(translations, [Vec3::ZERO]);
+    }
+}
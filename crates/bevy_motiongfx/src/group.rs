@@ -0,0 +1,178 @@
+//! Timelines advancing in lockstep under a shared clock.
+//!
+//! A [`TimelineGroup`] owns a clock that every timeline entity
+//! [`InTimelineGroup`] of it follows. Character animation, a camera
+//! move and UI callouts can then be authored as separate timelines
+//! and still play, pause and seek together:
+//!
+//! ```ignore
+//! let group = commands
+//!     .spawn(TimelineGroup::new().with_playing(true))
+//!     .id();
+//!
+//! for timeline in [character, camera, callouts] {
+//!     commands.spawn((
+//!         motiongfx.add_timeline(timeline),
+//!         InTimelineGroup(group),
+//!     ));
+//! }
+//! ```
+//!
+//! Members are set to the time of the group every update rather than
+//! advanced by it, so they never drift apart. The time applies to the
+//! current track of each member, which should not have a player of
+//! its own.
+
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_time::prelude::*;
+
+use crate::MotionGfxSystems;
+use crate::controller::GlobalTimeScale;
+use crate::manager::{MotionGfxManager, TimelineId};
+
+pub struct TimelineGroupPlugin;
+
+impl Plugin for TimelineGroupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            timeline_group_update
+                .in_set(MotionGfxSystems::Controller),
+        );
+    }
+}
+
+/// The shared clock of the timelines [`InTimelineGroup`] of this
+/// entity.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct TimelineGroup {
+    /// Determines if the group is currently playing.
+    pub is_playing: bool,
+    /// The time scale of the group. Set this to negative to play
+    /// backwards.
+    pub time_scale: f64,
+    time: Duration,
+}
+
+impl TimelineGroup {
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            is_playing: false,
+            time_scale: 1.0,
+            time: Duration::ZERO,
+        }
+    }
+
+    /// Builder method for setting [`Self::is_playing`].
+    #[inline]
+    #[must_use]
+    pub const fn with_playing(mut self, playing: bool) -> Self {
+        self.is_playing = playing;
+        self
+    }
+
+    /// Builder method for setting [`Self::time_scale`].
+    #[inline]
+    #[must_use]
+    pub const fn with_time_scale(mut self, time_scale: f64) -> Self {
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// Setter method for setting [`Self::is_playing`].
+    #[inline]
+    pub const fn set_playing(&mut self, playing: bool) -> &mut Self {
+        self.is_playing = playing;
+        self
+    }
+
+    /// Setter method for setting [`Self::time_scale`].
+    #[inline]
+    pub const fn set_time_scale(
+        &mut self,
+        time_scale: f64,
+    ) -> &mut Self {
+        self.time_scale = time_scale;
+        self
+    }
+
+    /// Moves every member to `time` on the next update.
+    #[inline]
+    pub const fn seek(&mut self, time: Duration) -> &mut Self {
+        self.time = time;
+        self
+    }
+
+    /// The shared time of the members.
+    #[inline]
+    pub const fn time(&self) -> Duration {
+        self.time
+    }
+}
+
+impl Default for TimelineGroup {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Makes the timeline of this entity follow the [`TimelineGroup`]
+/// of the given entity.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[relationship(relationship_target = TimelineGroupMembers)]
+pub struct InTimelineGroup(pub Entity);
+
+/// The entities [`InTimelineGroup`] of this [`TimelineGroup`].
+#[derive(Component, Debug, Default)]
+#[relationship_target(relationship = InTimelineGroup)]
+pub struct TimelineGroupMembers(Vec<Entity>);
+
+fn timeline_group_update(
+    mut motiongfx: ResMut<MotionGfxManager>,
+    mut q_groups: Query<(&mut TimelineGroup, &TimelineGroupMembers)>,
+    q_timelines: Query<&TimelineId>,
+    time: Res<Time>,
+    global_scale: Res<GlobalTimeScale>,
+) {
+    for (mut group, members) in q_groups.iter_mut() {
+        let ids = members
+            .iter()
+            .filter_map(|entity| q_timelines.get(entity).ok());
+
+        if group.is_playing {
+            let time_scale = group.time_scale * global_scale.0;
+            let delta = time.delta().mul_f64(time_scale.abs());
+            // Stop at the end of the longest member, so rewinding
+            // starts moving them right away.
+            let end = ids
+                .clone()
+                .filter_map(|id| motiongfx.get_timeline(id))
+                .map(|timeline| timeline.curr_track().duration())
+                .max()
+                .unwrap_or_default();
+
+            let time = if time_scale >= 0.0 {
+                group.time.saturating_add(delta)
+            } else {
+                group.time.saturating_sub(delta)
+            };
+            group.time = time.min(end);
+        }
+
+        if !group.is_changed() {
+            continue;
+        }
+
+        for id in ids {
+            if let Some(timeline) = motiongfx.get_timeline_mut(id) {
+                timeline.set_target_time(group.time);
+            }
+        }
+    }
+}
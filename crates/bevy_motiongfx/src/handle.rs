@@ -0,0 +1,64 @@
+//! Handle swaps, enabled with the `asset` feature.
+//!
+//! An asset handle has nothing to interpolate, so [`HandleSwapExt`]
+//! replaces the whole handle component, e.g. `Mesh3d` or
+//! `MeshMaterial3d<M>`, at a single point in time instead:
+//!
+//! ```ignore
+//! let track = [
+//!     builder.act_swap_handle::<Mesh3d>(shape, sphere),
+//!     track::delay(
+//!         s(1),
+//!         builder.act_swap_handle::<MeshMaterial3d<_>>(shape, red),
+//!     ),
+//! ]
+//! .ord_chain();
+//! ```
+//!
+//! Scrubbing back before a swap restores the handle the entity had
+//! before it.
+
+use core::time::Duration;
+
+use bevy_asset::{AsAssetId, Handle};
+use bevy_ecs::component::{Component, Mutable};
+use bevy_ecs::entity::Entity;
+use motiongfx::prelude::*;
+
+use crate::world::BevyTimelineBuilder;
+
+/// Instant swaps of asset handle components.
+pub trait HandleSwapExt {
+    /// Replaces the `C` handle component of `target` with one
+    /// pointing to `handle`, taking no time on the track.
+    fn act_swap_handle<C>(
+        &mut self,
+        target: Entity,
+        handle: Handle<C::Asset>,
+    ) -> TrackFragment
+    where
+        C: AsAssetId
+            + Component<Mutability = Mutable>
+            + From<Handle<C::Asset>>
+            + Clone;
+}
+
+impl HandleSwapExt for BevyTimelineBuilder<'_> {
+    fn act_swap_handle<C>(
+        &mut self,
+        target: Entity,
+        handle: Handle<C::Asset>,
+    ) -> TrackFragment
+    where
+        C: AsAssetId
+            + Component<Mutability = Mutable>
+            + From<Handle<C::Asset>>
+            + Clone,
+    {
+        let component = C::from(handle);
+        self.act_step(target, path!(<C>), move |_: &C| {
+            component.clone()
+        })
+        .play(Duration::ZERO)
+    }
+}
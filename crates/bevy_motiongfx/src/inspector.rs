@@ -0,0 +1,79 @@
+//! On-screen [`TimelineReport`]s, enabled with the `ui` feature.
+//!
+//! A [`TimelineInspector`] turns its `Text` into the report of a
+//! timeline, refreshed after every sample:
+//!
+//! ```ignore
+//! app.add_plugins(TimelineInspectorPlugin);
+//!
+//! let timeline = commands
+//!     .spawn((motiongfx.add_timeline(timeline), RealtimePlayer::new()))
+//!     .id();
+//! commands.spawn((Text::default(), TimelineInspector(timeline)));
+//! ```
+//!
+//! Missing accessors and pipelines are listed first, as they are the
+//! usual reason for an animation that silently does nothing.
+
+use core::fmt::Write;
+
+use alloc::string::String;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_ui::widget::Text;
+
+use crate::MotionGfxSystems;
+use crate::manager::{MotionGfxManager, TimelineId};
+
+/// Updates the `Text` of every [`TimelineInspector`].
+pub struct TimelineInspectorPlugin;
+
+impl Plugin for TimelineInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            update_inspectors.after(MotionGfxSystems::Sample),
+        );
+    }
+}
+
+/// Displays the [`TimelineReport`] of the timeline on the target
+/// entity in the `Text` of this entity.
+///
+/// [`TimelineReport`]: motiongfx::inspect::TimelineReport
+#[derive(Component, Debug, Clone, Copy)]
+#[require(Text)]
+pub struct TimelineInspector(pub Entity);
+
+fn update_inspectors(
+    motiongfx: Res<MotionGfxManager>,
+    q_timelines: Query<&TimelineId>,
+    mut q_inspectors: Query<(&TimelineInspector, &mut Text)>,
+) {
+    for (inspector, mut text) in q_inspectors.iter_mut() {
+        let timeline = q_timelines
+            .get(inspector.0)
+            .ok()
+            .and_then(|id| motiongfx.get_timeline(id));
+
+        let mut content = String::new();
+        match timeline {
+            Some(timeline) => {
+                let report = timeline.inspect(motiongfx.registry());
+                for issue in report.issues() {
+                    let _ = writeln!(content, "warning: {issue}");
+                }
+                let _ = write!(content, "{report}");
+            }
+            None => {
+                let _ =
+                    write!(content, "no timeline on {}", inspector.0);
+            }
+        }
+
+        // Avoid re-layouting the text when nothing changed.
+        if text.0 != content {
+            text.0 = content;
+        }
+    }
+}
@@ -103,3 +103,115 @@ pub mod transform {
         }
     }
 }
+
+#[cfg(feature = "ui")]
+pub mod ui {
+    use core::mem::discriminant;
+
+    use bevy_color::Color;
+    use bevy_ui::prelude::*;
+
+    use super::*;
+
+    /// The tuple variant constructor of a [`Val`] unit.
+    type ValUnit = fn(f32) -> Val;
+
+    /// Splits a [`Val`] into its unit and value, [`None`] for
+    /// [`Val::Auto`].
+    fn split_val(val: &Val) -> Option<(ValUnit, f32)> {
+        match *val {
+            Val::Auto => None,
+            Val::Px(v) => Some((Val::Px, v)),
+            Val::Percent(v) => Some((Val::Percent, v)),
+            Val::Vw(v) => Some((Val::Vw, v)),
+            Val::Vh(v) => Some((Val::Vh, v)),
+            Val::VMin(v) => Some((Val::VMin, v)),
+            Val::VMax(v) => Some((Val::VMax, v)),
+        }
+    }
+
+    impl Interpolation<Bevy> for Val {
+        /// Interpolates the values of two [`Val`]s of the same unit.
+        ///
+        /// A zero length is the same in every unit, so it takes the
+        /// unit of the other side, e.g. `Px(0.0)` to `Percent(50.0)`
+        /// grows smoothly in percent. Other unit mixes, and
+        /// [`Val::Auto`], can't be resolved without the layout and
+        /// switch over at the end instead.
+        fn interp(a: &Self, b: &Self, t: f32) -> Self {
+            let lerp = |x: f32, y: f32| (y - x) * t + x;
+
+            match (split_val(a), split_val(b)) {
+                (Some((unit, x)), Some((_, y)))
+                    if discriminant(a) == discriminant(b)
+                        || y == 0.0 =>
+                {
+                    unit(lerp(x, y))
+                }
+                (Some((_, 0.0)), Some((unit, y))) => {
+                    unit(lerp(0.0, y))
+                }
+                _ if t < 1.0 => *a,
+                _ => *b,
+            }
+        }
+    }
+
+    impl Interpolation<Bevy> for UiRect {
+        fn interp(a: &Self, b: &Self, t: f32) -> Self {
+            Self {
+                left: Val::interp(&a.left, &b.left, t),
+                right: Val::interp(&a.right, &b.right, t),
+                top: Val::interp(&a.top, &b.top, t),
+                bottom: Val::interp(&a.bottom, &b.bottom, t),
+            }
+        }
+    }
+
+    impl Interpolation<Bevy> for BorderRadius {
+        fn interp(a: &Self, b: &Self, t: f32) -> Self {
+            Self {
+                top_left: Val::interp(&a.top_left, &b.top_left, t),
+                top_right: Val::interp(&a.top_right, &b.top_right, t),
+                bottom_right: Val::interp(
+                    &a.bottom_right,
+                    &b.bottom_right,
+                    t,
+                ),
+                bottom_left: Val::interp(
+                    &a.bottom_left,
+                    &b.bottom_left,
+                    t,
+                ),
+            }
+        }
+    }
+
+    impl Interpolation<Bevy> for BackgroundColor {
+        #[inline]
+        fn interp(a: &Self, b: &Self, t: f32) -> Self {
+            Self(Color::interp(&a.0, &b.0, t))
+        }
+    }
+
+    impl Interpolation<Bevy> for BorderColor {
+        fn interp(a: &Self, b: &Self, t: f32) -> Self {
+            Self {
+                top: Color::interp(&a.top, &b.top, t),
+                right: Color::interp(&a.right, &b.right, t),
+                bottom: Color::interp(&a.bottom, &b.bottom, t),
+                left: Color::interp(&a.left, &b.left, t),
+            }
+        }
+    }
+
+    impl Interpolation<Bevy> for Outline {
+        fn interp(a: &Self, b: &Self, t: f32) -> Self {
+            Self {
+                width: Val::interp(&a.width, &b.width, t),
+                offset: Val::interp(&a.offset, &b.offset, t),
+                color: Color::interp(&a.color, &b.color, t),
+            }
+        }
+    }
+}
@@ -1,27 +1,190 @@
 #![doc = include_str!("../README.md")]
 #![no_std]
 
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 use bevy_app::prelude::*;
 use bevy_ecs::prelude::*;
 
+use crate::blend::BlendPlugin;
+use crate::command::TimelineCommandsPlugin;
 use crate::controller::ControllerPlugin;
+use crate::group::TimelineGroupPlugin;
 use crate::manager::MotionGfxManagerPlugin;
 
+#[cfg(feature = "animation")]
+pub mod animation;
+#[cfg(feature = "audio")]
+pub mod audio;
+pub mod blend;
+#[cfg(feature = "camera")]
+pub mod camera;
+#[cfg(feature = "ui")]
+pub mod caption;
+pub mod command;
+#[cfg(feature = "compat-0.1")]
+pub mod compat;
 pub mod controller;
+#[cfg(feature = "diagnostic")]
+pub mod diagnostic;
+#[cfg(feature = "export")]
+pub mod export;
+#[cfg(feature = "gltf")]
+pub mod gltf;
+pub mod group;
+#[cfg(feature = "asset")]
+pub mod handle;
+#[cfg(feature = "ui")]
+pub mod inspector;
 pub mod interpolation;
 pub mod manager;
+pub mod modifier;
+#[cfg(feature = "morph")]
+pub mod morph;
+#[cfg(feature = "gizmos")]
+pub mod motion_path;
+#[cfg(feature = "gizmos")]
+pub mod motion_trail;
+#[cfg(feature = "color")]
+pub mod palette;
+#[cfg(feature = "presenter")]
+pub mod presenter;
+#[cfg(feature = "transform")]
+pub mod skeleton;
+pub mod spawn;
+#[cfg(feature = "sprite")]
+pub mod sprite;
+#[cfg(feature = "text")]
+pub mod text;
+#[cfg(feature = "transform")]
+pub mod transition;
+#[cfg(feature = "visibility")]
+pub mod visibility;
 pub mod world;
 
 pub mod prelude {
     pub use motiongfx::prelude::*;
 
-    pub use crate::controller::{FixedRatePlayer, RealtimePlayer};
-    pub use crate::manager::{MotionGfxManager, TimelineId};
-    pub use crate::world::{BevyTimeline, BevyTimelineBuilder};
+    #[cfg(feature = "animation")]
+    pub use crate::animation::{
+        AnimationClipExporter, add_clip_graph,
+    };
+    #[cfg(feature = "audio")]
+    pub use crate::audio::{AudioCue, AudioCuePlugin, AudioCues};
+    pub use crate::blend::BlendWeight;
+    #[cfg(feature = "camera")]
+    pub use crate::camera::{CameraShake, CameraShakePlugin};
+    #[cfg(feature = "ui")]
+    pub use crate::caption::{Caption, CaptionPlugin, Captions};
+    pub use crate::command::{
+        PauseTimeline, PlayTimeline, SeekTimeline, SetTimelineSpeed,
+        TimelineCommandsExt,
+    };
+    pub use crate::controller::{
+        CurrentExportFrame, ExportFrame, FixedRatePlayer,
+        GlobalTimeScale, RealtimePlayer, RenderMode, SeekTarget,
+        TimelineProgress,
+    };
+    #[cfg(feature = "diagnostic")]
+    pub use crate::diagnostic::MotionGfxDiagnosticsPlugin;
+    #[cfg(feature = "export")]
+    pub use crate::export::{
+        DeckOutput, DeckSettings, ExportFinished, ExportOutput,
+        ExportPlugin, ExportSettings, ExportedFrame,
+    };
+    #[cfg(feature = "gltf")]
+    pub use crate::gltf::{
+        GltfAnimation, GltfChannel, GltfValues, load_gltf_animations,
+    };
+    pub use crate::group::{
+        InTimelineGroup, TimelineGroup, TimelineGroupMembers,
+    };
+    #[cfg(feature = "asset")]
+    pub use crate::handle::HandleSwapExt;
+    #[cfg(feature = "ui")]
+    pub use crate::inspector::{
+        TimelineInspector, TimelineInspectorPlugin,
+    };
+    pub use crate::manager::{
+        DespawnPolicy, MotionGfxManager, RebakePlugin, TimelineId,
+    };
+    pub use crate::modifier::pixel_snap;
+    #[cfg(feature = "morph")]
+    pub use crate::morph::MorphExt;
+    #[cfg(feature = "gizmos")]
+    pub use crate::motion_path::{MotionPath, MotionPathPlugin};
+    #[cfg(feature = "gizmos")]
+    pub use crate::motion_trail::{MotionTrail, MotionTrailPlugin};
+    #[cfg(feature = "color")]
+    pub use crate::palette::{ColorKey, ColorPalette, PaletteSwap};
+    #[cfg(all(feature = "presenter", feature = "ui"))]
+    pub use crate::presenter::SlideCounter;
+    #[cfg(feature = "presenter")]
+    pub use crate::presenter::{
+        Presenter, PresenterKeys, PresenterPlugin, SlideCommand,
+    };
+    #[cfg(feature = "asset")]
+    pub use crate::register_material_fields;
+    #[cfg(feature = "transform")]
+    pub use crate::skeleton::{Pose, SkeletonExt};
+    pub use crate::spawn::{SpawnCue, SpawnCuePlugin, SpawnCues};
+    #[cfg(feature = "sprite")]
+    pub use crate::sprite::FlipbookExt;
+    #[cfg(feature = "text2d")]
+    pub use crate::text::glyph::{
+        Glyph, SplitText, SplitTextPlugin, stagger_glyphs,
+    };
+    #[cfg(feature = "text")]
+    pub use crate::text::{
+        RevealUnit, Typewriter, TypewriterCursor, TypewriterPlugin,
+    };
+    #[cfg(feature = "visibility")]
+    pub use crate::visibility::VisibilityExt;
+    #[cfg(feature = "asset")]
+    pub use crate::world::AssetOf;
+    pub use crate::world::{
+        BevyTimeline, BevyTimelineBuilder, BoneOf, Crowd, CrowdOf,
+        ResourceId, TimelineTemplate,
+    };
 }
 
 pub use motiongfx;
 
+/// Registers fields of a custom material `M` for animation, both on
+/// the asset itself and through the handle component of an entity.
+///
+/// Works for any asset with a handle component implementing
+/// `AsAssetId`, e.g. `MeshMaterial3d<M>` for a `Material` or
+/// `MeshMaterial2d<M>` for a `Material2d`.
+///
+/// ```ignore
+/// register_material_fields!(
+///     app,
+///     MeshMaterial3d<MyMaterial>,
+///     (uniform_a, color)
+/// );
+/// ```
+#[cfg(feature = "asset")]
+#[macro_export]
+macro_rules! register_material_fields {
+    (
+        $app:expr,
+        $handle:ident < $material:ty >,
+        ($($field:ident),* $(,)?)
+    ) => {{
+        let mut manager = $app
+            .world_mut()
+            .resource_mut::<$crate::manager::MotionGfxManager>();
+        $(
+            manager.register_asset_field::<$handle<$material>, _>(
+                $crate::motiongfx::path!(<$material>::$field),
+            );
+        )*
+    }};
+}
+
 pub struct BevyMotionGfxPlugin;
 
 impl Plugin for BevyMotionGfxPlugin {
@@ -30,16 +193,23 @@ impl Plugin for BevyMotionGfxPlugin {
             PostUpdate,
             (
                 MotionGfxSystems::Controller,
-                #[cfg(not(feature = "transform"))]
                 MotionGfxSystems::Sample,
+                #[cfg(not(feature = "transform"))]
+                MotionGfxSystems::Export,
                 #[cfg(feature = "transform")]
-                MotionGfxSystems::Sample.before(
+                MotionGfxSystems::Export.before(
                     bevy_transform::TransformSystems::Propagate,
                 ),
             )
                 .chain(),
         );
-        app.add_plugins((MotionGfxManagerPlugin, ControllerPlugin));
+        app.add_plugins((
+            MotionGfxManagerPlugin,
+            ControllerPlugin,
+            BlendPlugin,
+            TimelineCommandsPlugin,
+            TimelineGroupPlugin,
+        ));
     }
 }
 
@@ -49,4 +219,6 @@ pub enum MotionGfxSystems {
     Controller,
     /// Sample keyframes and applies the value.
     Sample,
+    /// Runs the [`ExportFrame`](controller::ExportFrame) schedule.
+    Export,
 }
@@ -1,23 +1,59 @@
+use core::any::TypeId;
+use core::marker::PhantomData;
 use core::ops::{Deref, DerefMut};
+use core::time::Duration;
+
+use alloc::string::String;
+use alloc::vec::Vec;
 
 use bevy_app::prelude::*;
+use bevy_ecs::component::Mutable;
+use bevy_ecs::lifecycle::HookContext;
 use bevy_ecs::prelude::*;
-use bevy_platform::collections::HashMap;
+use bevy_ecs::world::DeferredWorld;
+use bevy_platform::collections::{HashMap, HashSet};
+use bevy_platform::time::Instant;
+use motiongfx::action::UntypedSubjectId;
+use motiongfx::definition::{DefinitionError, TimelineDef};
+use motiongfx::graph::ResolveError;
 use motiongfx::prelude::*;
+use motiongfx::script::ScriptRegistry;
 
 use crate::MotionGfxSystems;
 use crate::controller::FixedRatePlayer;
 use crate::controller::RealtimePlayer;
 use crate::prelude::BevyTimelineBuilder;
-use crate::world::{BevyTimeline, BevyWorld};
+#[cfg(feature = "asset")]
+use crate::world::AssetOf;
+use crate::world::{
+    BevyTimeline, BevyWorld, BoneCache, BoneOf, ResourceId,
+    TimelineTemplate, clear_bone_cache,
+};
 
 pub struct MotionGfxManagerPlugin;
 
 impl Plugin for MotionGfxManagerPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<MotionGfxManager>().add_systems(
+        app.init_resource::<MotionGfxManager>()
+            .init_resource::<BoneCache>()
+            .add_observer(clear_bone_cache::<Insert>)
+            .add_observer(clear_bone_cache::<Replace>);
+        #[cfg(feature = "ui")]
+        app.world_mut()
+            .resource_mut::<MotionGfxManager>()
+            .register_ui_fields();
+        #[cfg(feature = "visibility")]
+        app.world_mut()
+            .resource_mut::<MotionGfxManager>()
+            .register_component_field(path!(
+                <bevy_camera::visibility::Visibility>
+            ));
+
+        app.add_systems(
             PostUpdate,
             (
+                sync_despawn_policies
+                    .in_set(MotionGfxSystems::Controller),
                 sample_timelines.in_set(MotionGfxSystems::Sample),
                 (
                     complete_timelines::<RealtimePlayer>,
@@ -29,6 +65,50 @@ impl Plugin for MotionGfxManagerPlugin {
     }
 }
 
+/// Re-bakes timelines that have not started playing whenever the `S`
+/// component of an animated entity changes, so moving an entity after
+/// its timeline was baked does not snap it back to the stale start.
+///
+/// ```ignore
+/// app.add_plugins(RebakePlugin::<Transform>::default());
+/// ```
+pub struct RebakePlugin<S>(PhantomData<S>);
+
+impl<S> Default for RebakePlugin<S> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<S: Component> Plugin for RebakePlugin<S> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            rebake_changed::<S>
+                .after(MotionGfxSystems::Controller)
+                .before(MotionGfxSystems::Sample),
+        );
+    }
+}
+
+fn rebake_changed<S: Component>(
+    world: &mut World,
+    q_changed: &mut QueryState<Entity, Changed<S>>,
+) {
+    let entities = q_changed.iter(world).collect::<Vec<_>>();
+    if entities.is_empty() {
+        return;
+    }
+
+    world.resource_scope::<MotionGfxManager, _>(
+        |world, mut motiongfx| {
+            for entity in entities {
+                motiongfx.rebake_component::<S>(world, entity);
+            }
+        },
+    );
+}
+
 // TODO: Optimize samplers into parallel operations.
 // This could be deferred into motiongfx::pipeline?
 // See also https://github.com/voxell-tech/motiongfx/issues/72
@@ -74,6 +154,66 @@ fn complete_timelines<T>(
     }
 }
 
+/// What the timeline of this entity does with actions targeting a
+/// despawned entity, either directly or through [`BoneOf`].
+///
+/// Without the component, or with [`Self::SkipSilently`], nothing is
+/// checked and the actions of despawned entities sample into nothing.
+#[derive(
+    Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash,
+)]
+pub enum DespawnPolicy {
+    #[default]
+    SkipSilently,
+    /// Skips the actions, logging a warning the first time each
+    /// entity is found missing. Only logs with the `std` feature.
+    WarnOnce,
+    /// Removes the actions from the timeline as soon as their entity
+    /// is found missing, see
+    /// [`MotionGfxManager::purge_despawned`].
+    RemoveActions,
+}
+
+/// Marks the entities animated by a timeline with a
+/// [`DespawnPolicy`], so that only despawning one of them makes the
+/// [`MotionGfxManager`] look for despawned subjects.
+#[derive(Component)]
+#[component(on_despawn = flag_despawned_subjects)]
+struct DespawnWatch;
+
+/// Set when an entity with a [`DespawnWatch`] is despawned.
+#[derive(Resource, Default)]
+struct SubjectsDespawned(bool);
+
+fn flag_despawned_subjects(mut world: DeferredWorld, _: HookContext) {
+    if let Some(mut despawned) =
+        world.get_resource_mut::<SubjectsDespawned>()
+    {
+        despawned.0 = true;
+    }
+}
+
+fn sync_despawn_policies(
+    mut motiongfx: ResMut<MotionGfxManager>,
+    q_policies: Query<
+        (&TimelineId, &DespawnPolicy),
+        Changed<DespawnPolicy>,
+    >,
+    q_timelines: Query<&TimelineId>,
+    mut removed: RemovedComponents<DespawnPolicy>,
+) {
+    for (id, policy) in q_policies.iter() {
+        motiongfx.set_despawn_policy(id, *policy);
+    }
+
+    for entity in removed.read() {
+        if let Ok(id) = q_timelines.get(entity) {
+            motiongfx
+                .set_despawn_policy(id, DespawnPolicy::default());
+        }
+    }
+}
+
 /// Resources that the [`motiongfx`] framework operates on.
 #[derive(Resource)]
 pub struct MotionGfxManager {
@@ -81,6 +221,24 @@ pub struct MotionGfxManager {
     pending_timelines: HashMap<TimelineId, MutDetect<BevyTimeline>>,
     timelines: HashMap<TimelineId, MutDetect<BevyTimeline>>,
     registry: Registry,
+    /// Fields by name, for [`Self::compile_definition`].
+    scripts: ScriptRegistry<BevyWorld, Entity>,
+    /// Timelines blended over the others, see [`BlendWeight`].
+    ///
+    /// [`BlendWeight`]: crate::blend::BlendWeight
+    blend_weights: HashMap<TimelineId, f32>,
+    /// See [`DespawnPolicy`], timelines without one skip silently.
+    despawn_policies: HashMap<TimelineId, DespawnPolicy>,
+    /// Despawned entities already warned about, by timeline.
+    warned: HashSet<(TimelineId, Entity)>,
+    /// Timelines with a despawn policy whose subjects carry a
+    /// [`DespawnWatch`].
+    watched: HashSet<TimelineId>,
+    /// Watched timelines to check for despawned subjects the next
+    /// time they are sampled.
+    unchecked: HashSet<TimelineId>,
+    stats: SampleStats,
+    lazy_bake: bool,
 }
 
 impl Default for MotionGfxManager {
@@ -90,6 +248,14 @@ impl Default for MotionGfxManager {
             pending_timelines: Default::default(),
             timelines: Default::default(),
             registry: Default::default(),
+            scripts: Default::default(),
+            blend_weights: Default::default(),
+            despawn_policies: Default::default(),
+            warned: Default::default(),
+            watched: Default::default(),
+            unchecked: Default::default(),
+            stats: Default::default(),
+            lazy_bake: false,
         }
     }
 }
@@ -99,6 +265,330 @@ impl MotionGfxManager {
         TimelineBuilder::new(&mut self.registry)
     }
 
+    #[inline]
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Bake the tracks of each timeline as it reaches them instead of
+    /// all at once when the timeline is loaded, which spreads the
+    /// cost of long timelines over playback. Every track still starts
+    /// from the world as it was when the timeline was loaded.
+    ///
+    /// See [`Timeline::bake_active_tracks`] and
+    /// [`Self::prebake_timeline`].
+    pub fn set_lazy_bake(&mut self, lazy: bool) -> &mut Self {
+        self.lazy_bake = lazy;
+        self
+    }
+
+    #[inline]
+    pub fn lazy_bake(&self) -> bool {
+        self.lazy_bake
+    }
+
+    /// Bakes every track of a timeline that is not baked yet, e.g.
+    /// during a loading screen when [`Self::lazy_bake`] is on.
+    ///
+    /// Returns `false` if the timeline does not exist.
+    pub fn prebake_timeline(
+        &mut self,
+        world: &World,
+        id: &TimelineId,
+    ) -> bool {
+        let world = BevyWorld::from_ref(world);
+        if let Some(mut timeline) = self.pending_timelines.remove(id)
+        {
+            timeline.snapshot_tracks(&self.registry, world);
+            self.timelines.insert(*id, timeline);
+        }
+        let Some(timeline) = self.timelines.get_mut(id) else {
+            return false;
+        };
+
+        timeline.bake_remaining_tracks(&self.registry, world);
+        true
+    }
+
+    /// Blends the timeline over the timelines without a weight when
+    /// `Some`, overwriting like them when `None`. Usually set through
+    /// the [`BlendWeight`] component.
+    ///
+    /// [`BlendWeight`]: crate::blend::BlendWeight
+    pub fn set_blend_weight(
+        &mut self,
+        id: &TimelineId,
+        weight: Option<f32>,
+    ) {
+        match weight {
+            Some(weight) => self.blend_weights.insert(*id, weight),
+            None => self.blend_weights.remove(id),
+        };
+        // Resample with the new weight.
+        if let Some(timeline) = self.get_timeline_mut(id) {
+            timeline.set_mutated();
+        }
+    }
+
+    #[inline]
+    pub fn blend_weight(&self, id: &TimelineId) -> Option<f32> {
+        self.blend_weights.get(id).copied()
+    }
+
+    /// Usually set through the [`DespawnPolicy`] component.
+    ///
+    /// Subjects are checked when the timeline is next sampled, and
+    /// then only after one of them is despawned. Set the policy again
+    /// after changing the subjects of the timeline outside of the
+    /// manager, e.g. through [`Self::get_timeline_mut`].
+    pub fn set_despawn_policy(
+        &mut self,
+        id: &TimelineId,
+        policy: DespawnPolicy,
+    ) {
+        self.watched.remove(id);
+        match policy {
+            DespawnPolicy::SkipSilently => {
+                self.despawn_policies.remove(id)
+            }
+            policy => self.despawn_policies.insert(*id, policy),
+        };
+    }
+
+    #[inline]
+    pub fn despawn_policy(&self, id: &TimelineId) -> DespawnPolicy {
+        self.despawn_policies.get(id).copied().unwrap_or_default()
+    }
+
+    /// Removes every action of the timeline targeting a despawned
+    /// entity, directly or through [`BoneOf`], re-baking what is left
+    /// of their sequences.
+    ///
+    /// Returns the number of removed actions, `0` if the timeline
+    /// does not exist or shares its actions, see
+    /// [`Timeline::remove_subjects`].
+    pub fn purge_despawned(
+        &mut self,
+        world: &World,
+        id: &TimelineId,
+    ) -> usize {
+        let Some(timeline) = self
+            .timelines
+            .get_mut(id)
+            .or_else(|| self.pending_timelines.get_mut(id))
+        else {
+            return 0;
+        };
+
+        purge_despawned(timeline, &mut self.registry, world)
+    }
+
+    /// Moves the actions of every `(old, new)` entity pair of the
+    /// timeline over to `new`, re-baking them from it, e.g. to replay
+    /// an effect on a freshly spawned prefab instance.
+    ///
+    /// Returns the number of retargeted entities, see
+    /// [`Timeline::retarget_all`].
+    pub fn retarget(
+        &mut self,
+        world: &World,
+        id: &TimelineId,
+        pairs: impl IntoIterator<Item = (Entity, Entity)>,
+    ) -> usize {
+        let Some(timeline) = self
+            .timelines
+            .get_mut(id)
+            .or_else(|| self.pending_timelines.get_mut(id))
+        else {
+            return 0;
+        };
+
+        // The new subjects are not watched yet.
+        self.watched.remove(id);
+        timeline.retarget_all(
+            &self.registry,
+            BevyWorld::from_ref(world),
+            pairs,
+        )
+    }
+
+    /// Counters of the last [`Self::load_pending_timelines`] and
+    /// [`Self::sample_timelines`].
+    #[inline]
+    pub fn stats(&self) -> &SampleStats {
+        &self.stats
+    }
+
+    /// Registers a field of the `S` [`Component`] ahead of time.
+    ///
+    /// Acting on an [`Entity`] registers the field as well, this is
+    /// only needed for fields that are animated by timelines built
+    /// outside of [`Self::create_builder`].
+    pub fn register_component_field<S, T>(
+        &mut self,
+        field: impl ActionField<S, T>,
+    ) -> &mut Self
+    where
+        S: Component<Mutability = Mutable>,
+    {
+        field.register::<BevyWorld, Entity>(&mut self.registry);
+        self
+    }
+
+    /// Registers an [`f32`] field of the `S` [`Component`] under
+    /// `name`, so that [`TimelineDef`]s can animate it.
+    pub fn register_named_field<S>(
+        &mut self,
+        name: impl Into<String>,
+        field: impl ActionField<S, f32> + Clone + ThreadSafe,
+    ) -> &mut Self
+    where
+        S: Component<Mutability = Mutable>,
+    {
+        self.register_component_field(field.clone());
+        self.scripts.register_field(name, field);
+        self
+    }
+
+    /// The fields registered with [`Self::register_named_field`].
+    #[inline]
+    pub fn scripts(&self) -> &ScriptRegistry<BevyWorld, Entity> {
+        &self.scripts
+    }
+
+    /// Mutable access to the named fields, e.g. to register eases.
+    #[inline]
+    pub fn scripts_mut(
+        &mut self,
+    ) -> &mut ScriptRegistry<BevyWorld, Entity> {
+        &mut self.scripts
+    }
+
+    /// Compiles `def` into a timeline, binding every target to the
+    /// entity with the matching [`Name`].
+    ///
+    /// ```ignore
+    /// fn load(
+    ///     mut motiongfx: ResMut<MotionGfxManager>,
+    ///     q_names: Query<(Entity, &Name)>,
+    /// ) {
+    ///     let def = TimelineDef::from_ron(SOURCE).unwrap();
+    ///     let timeline = motiongfx
+    ///         .compile_definition(&def, q_names.iter())
+    ///         .unwrap();
+    ///     motiongfx.add_timeline(timeline);
+    /// }
+    /// ```
+    pub fn compile_definition<'a>(
+        &mut self,
+        def: &TimelineDef,
+        names: impl IntoIterator<Item = (Entity, &'a Name)>,
+    ) -> Result<BevyTimeline, DefinitionError> {
+        let entities = names
+            .into_iter()
+            .map(|(entity, name)| (name.as_str(), entity))
+            .collect::<HashMap<_, _>>();
+
+        def.compile(&self.scripts, &mut self.registry, |name| {
+            entities.get(name).copied()
+        })
+    }
+
+    /// Binds the placeholder names of `template` to entities and
+    /// compiles it into an independent timeline.
+    ///
+    /// The template is left untouched, so a library of effects can be
+    /// instantiated on as many targets as needed:
+    ///
+    /// ```ignore
+    /// let mut pop_in = TimelineTemplate::new();
+    /// let grow = pop_in
+    ///     .act("target", path!(<Transform>::scale), |_| Vec3::ONE)
+    ///     .with_ease(ease::back::ease_out)
+    ///     .play(ms(300));
+    /// pop_in.add_track(grow);
+    ///
+    /// let bindings = HashMap::from([("target", button)]);
+    /// let timeline = motiongfx.instantiate(&pop_in, &bindings)?;
+    /// commands.spawn((
+    ///     motiongfx.add_timeline(timeline),
+    ///     RealtimePlayer::new().with_playing(true),
+    /// ));
+    /// ```
+    pub fn instantiate(
+        &mut self,
+        template: &TimelineTemplate,
+        bindings: &HashMap<&str, Entity>,
+    ) -> Result<BevyTimeline, ResolveError> {
+        template.resolve(&mut self.registry, |name| {
+            bindings.get(name).copied()
+        })
+    }
+
+    /// Registers a field of the `S` [`Resource`] ahead of time.
+    ///
+    /// Acting on [`ResourceId`] registers the field as well, this is
+    /// only needed for fields that are animated by timelines built
+    /// outside of [`Self::create_builder`].
+    pub fn register_resource_field<S, T>(
+        &mut self,
+        field: impl ActionField<S, T>,
+    ) -> &mut Self
+    where
+        S: Resource,
+    {
+        field.register::<BevyWorld, ResourceId>(&mut self.registry);
+        self
+    }
+
+    /// Registers a field of the asset referenced by the `C` handle
+    /// component, for both [`UntypedAssetId`] and [`AssetOf<C>`]
+    /// subjects. See [`register_material_fields!`].
+    ///
+    /// [`UntypedAssetId`]: bevy_asset::UntypedAssetId
+    /// [`register_material_fields!`]: crate::register_material_fields
+    #[cfg(feature = "asset")]
+    pub fn register_asset_field<C, T>(
+        &mut self,
+        field: impl ActionField<C::Asset, T> + Clone,
+    ) -> &mut Self
+    where
+        C: bevy_asset::AsAssetId,
+    {
+        field
+            .clone()
+            .register::<BevyWorld, bevy_asset::UntypedAssetId>(
+                &mut self.registry,
+            );
+        field.register::<BevyWorld, AssetOf<C>>(&mut self.registry);
+        self
+    }
+
+    /// Registers the commonly animated `bevy_ui` fields.
+    #[cfg(feature = "ui")]
+    fn register_ui_fields(&mut self) {
+        use bevy_ui::prelude::*;
+
+        self.register_component_field(path!(<Node>::width))
+            .register_component_field(path!(<Node>::height))
+            .register_component_field(path!(<Node>::left))
+            .register_component_field(path!(<Node>::right))
+            .register_component_field(path!(<Node>::top))
+            .register_component_field(path!(<Node>::bottom))
+            .register_component_field(path!(<Node>::margin))
+            .register_component_field(path!(<Node>::padding))
+            .register_component_field(path!(<Node>::border))
+            .register_component_field(path!(<Node>::border_radius))
+            .register_component_field(path!(<BackgroundColor>::0))
+            .register_component_field(path!(<BorderColor>::top))
+            .register_component_field(path!(<BorderColor>::right))
+            .register_component_field(path!(<BorderColor>::bottom))
+            .register_component_field(path!(<BorderColor>::left))
+            .register_component_field(path!(<Outline>::width))
+            .register_component_field(path!(<Outline>::offset))
+            .register_component_field(path!(<Outline>::color));
+    }
+
     pub fn add_timeline(
         &mut self,
         timeline: BevyTimeline,
@@ -114,6 +604,11 @@ impl MotionGfxManager {
         &mut self,
         id: &TimelineId,
     ) -> Option<BevyTimeline> {
+        self.blend_weights.remove(id);
+        self.despawn_policies.remove(id);
+        self.warned.retain(|(timeline, _)| timeline != id);
+        self.watched.remove(id);
+        self.unchecked.remove(id);
         self.timelines
             .remove(id)
             .or_else(|| self.pending_timelines.remove(id))
@@ -140,29 +635,290 @@ impl MotionGfxManager {
     }
 
     pub fn load_pending_timelines(&mut self, world: &World) {
+        if self.pending_timelines.is_empty() {
+            self.stats.bake_time = Duration::ZERO;
+            return;
+        }
+
+        let start = Instant::now();
         for (id, mut timeline) in self.pending_timelines.drain() {
-            timeline.bake_actions(
-                &self.registry,
-                BevyWorld::from_ref(world),
-            );
+            let world = BevyWorld::from_ref(world);
+            if self.lazy_bake {
+                // Later tracks bake from the world as it is now, not
+                // as the earlier tracks leave it.
+                timeline.snapshot_tracks(&self.registry, world);
+                timeline.bake_active_tracks(&self.registry, world);
+            } else {
+                timeline.bake_actions(&self.registry, world);
+            }
             self.timelines.insert(id, timeline);
         }
+        self.stats.bake_time = start.elapsed();
     }
 
     pub fn sample_timelines(&mut self, world: &mut World) {
-        for timeline in
-            self.timelines.values_mut().filter(|t| t.mutated())
-        {
+        let start = Instant::now();
+        let mut bake_time = Duration::ZERO;
+        self.stats.reset_samples();
+
+        let despawned = core::mem::take(
+            &mut world.get_resource_or_init::<SubjectsDespawned>().0,
+        );
+        if despawned {
+            self.unchecked.extend(self.watched.iter().copied());
+        }
+
+        // Blended timelines go last, in the order they were added.
+        let mut ids = self
+            .timelines
+            .iter()
+            .filter(|(_, t)| t.mutated())
+            .map(|(id, _)| *id)
+            .collect::<Vec<_>>();
+        ids.sort_by_key(|id| {
+            (self.blend_weights.contains_key(id), id.0)
+        });
+
+        for id in ids {
+            let Some(timeline) = self.timelines.get_mut(&id) else {
+                continue;
+            };
+            if self.lazy_bake {
+                let bake_start = Instant::now();
+                timeline.bake_active_tracks(
+                    &self.registry,
+                    BevyWorld::from_ref(world),
+                );
+                bake_time += bake_start.elapsed();
+            }
+
+            let mut policy = self.despawn_policies.get(&id).copied();
+            if policy.is_some() && self.watched.insert(id) {
+                watch_subjects(timeline, world);
+                self.unchecked.insert(id);
+            }
+            if !self.unchecked.remove(&id) {
+                policy = None;
+            }
+
+            match policy {
+                Some(DespawnPolicy::WarnOnce) => {
+                    for entity in despawned_subjects(timeline, world)
+                    {
+                        if self.warned.insert((id, entity)) {
+                            #[cfg(feature = "std")]
+                            bevy_log::warn!(
+                                "Timeline {id:?} animates the \
+                                 despawned entity {entity}."
+                            );
+                        }
+                    }
+                }
+                Some(DespawnPolicy::RemoveActions) => {
+                    purge_despawned(
+                        timeline,
+                        &mut self.registry,
+                        world,
+                    );
+                }
+                _ => {}
+            }
+
             timeline.queue_actions();
-            timeline.sample_queued_actions(
+            timeline.blend_queued_actions(
                 &self.registry,
                 BevyWorld::from_mut(world),
+                self.blend_weights.get(&id).copied().unwrap_or(1.0),
             );
             timeline.reset();
+
+            self.stats.queued += timeline.queue_cache().len();
+            for (key, count) in timeline.sample_queue() {
+                if !self.registry.pipeline.contains(key) {
+                    continue;
+                }
+                self.stats.sampled += count;
+                *self.stats.pipelines.entry(*key).or_default() +=
+                    count;
+            }
+        }
+
+        self.stats.bake_time += bake_time;
+        self.stats.sample_time =
+            start.elapsed().saturating_sub(bake_time);
+    }
+
+    /// Re-bakes the actions animating fields of the `S` component of
+    /// `entity` from its current value, in every loaded timeline that
+    /// is still at the start of its first track.
+    ///
+    /// See [`Timeline::rebake_actions`].
+    pub fn rebake_component<S: Component>(
+        &mut self,
+        world: &World,
+        entity: Entity,
+    ) {
+        for timeline in self.timelines.values_mut() {
+            if timeline.curr_index() != 0
+                || !timeline.curr_time().is_zero()
+            {
+                continue;
+            }
+            let Some(&uid) = timeline.action_table().get_uid(&entity)
+            else {
+                continue;
+            };
+            let subject_id = UntypedSubjectId::new::<Entity>(uid);
+
+            // The subject already holds the new start, resampling is
+            // not needed.
+            let mutated = timeline.mutated();
+            timeline.rebake_actions(
+                &self.registry,
+                BevyWorld::from_ref(world),
+                |key| {
+                    *key.subject_id() == subject_id
+                        && key.field().source_id()
+                            == TypeId::of::<S>()
+                },
+            );
+            if !mutated {
+                timeline.reset();
+            }
+        }
+    }
+
+    /// Moves the timeline to `time` and samples it into `world` right
+    /// away, baking it first if it is still pending.
+    ///
+    /// Returns `false` if the timeline does not exist.
+    #[cfg(feature = "animation")]
+    pub(crate) fn scrub_timeline(
+        &mut self,
+        world: &mut World,
+        id: &TimelineId,
+        time: core::time::Duration,
+    ) -> bool {
+        if self.pending_timelines.contains_key(id) {
+            self.load_pending_timelines(world);
+        }
+        let Some(timeline) = self.timelines.get_mut(id) else {
+            return false;
+        };
+
+        timeline.set_target_time(time);
+        timeline.bake_active_tracks(
+            &self.registry,
+            BevyWorld::from_ref(world),
+        );
+        timeline.queue_actions();
+        timeline.sample_queued_actions(
+            &self.registry,
+            BevyWorld::from_mut(world),
+        );
+        true
+    }
+}
+
+/// Counters of the [`MotionGfxManager`], see
+/// [`MotionGfxManager::stats`].
+#[derive(Debug, Default, Clone)]
+pub struct SampleStats {
+    /// Time spent baking newly added timelines, and tracks reached
+    /// with [`MotionGfxManager::lazy_bake`] on.
+    pub bake_time: Duration,
+    /// Time spent queuing and sampling timelines.
+    pub sample_time: Duration,
+    /// Number of actions queued for sampling.
+    pub queued: usize,
+    /// Number of queued actions that went through a registered
+    /// pipeline.
+    pub sampled: usize,
+    /// Number of actions sampled by each pipeline, kept at zero for
+    /// pipelines that sampled before but not this time.
+    pub pipelines: HashMap<PipelineKey, usize>,
+}
+
+impl SampleStats {
+    fn reset_samples(&mut self) {
+        self.queued = 0;
+        self.sampled = 0;
+        for count in self.pipelines.values_mut() {
+            *count = 0;
+        }
+    }
+}
+
+/// The despawned entities targeted by `timeline`, directly or as the
+/// root of a [`BoneOf`].
+fn despawned_subjects(
+    timeline: &BevyTimeline,
+    world: &World,
+) -> Vec<Entity> {
+    let mut despawned = subject_entities(timeline)
+        .filter(|&entity| world.get_entity(entity).is_err())
+        .collect::<Vec<_>>();
+    despawned.sort_unstable();
+    despawned.dedup();
+    despawned
+}
+
+/// Adds a [`DespawnWatch`] to the live entities targeted by
+/// `timeline`.
+fn watch_subjects(timeline: &BevyTimeline, world: &mut World) {
+    for entity in subject_entities(timeline) {
+        if let Ok(mut entity) = world.get_entity_mut(entity)
+            && !entity.contains::<DespawnWatch>()
+        {
+            entity.insert(DespawnWatch);
         }
     }
 }
 
+/// The entities targeted by every sequence of `timeline`, directly
+/// or as the root of a [`BoneOf`].
+fn subject_entities(
+    timeline: &BevyTimeline,
+) -> impl Iterator<Item = Entity> + '_ {
+    let table = timeline.action_table();
+    let bindings = timeline.subject_bindings();
+    timeline
+        .tracks()
+        .iter()
+        .flat_map(|track| track.sequences_spans())
+        .filter_map(|(key, _)| {
+            let subject_id = key.subject_id();
+            let uid = subject_id.uid();
+            if subject_id.type_id() == TypeId::of::<Entity>() {
+                bindings.resolve::<Entity>(table, &uid).copied()
+            } else if subject_id.type_id() == TypeId::of::<BoneOf>() {
+                bindings
+                    .resolve::<BoneOf>(table, &uid)
+                    .map(|bone| bone.root)
+            } else {
+                None
+            }
+        })
+}
+
+fn purge_despawned(
+    timeline: &mut BevyTimeline,
+    registry: &mut Registry,
+    world: &World,
+) -> usize {
+    let despawned =
+        |entity: Entity| world.get_entity(entity).is_err();
+    let bevy_world = BevyWorld::from_ref(world);
+
+    timeline.remove_subjects(registry, bevy_world, |e: &Entity| {
+        despawned(*e)
+    }) + timeline.remove_subjects(
+        registry,
+        bevy_world,
+        |bone: &BoneOf| despawned(bone.root),
+    )
+}
+
 pub struct MutDetect<T> {
     inner: T,
     mutated: bool,
@@ -180,6 +936,11 @@ impl<T> MutDetect<T> {
         self.mutated
     }
 
+    /// Set mutation detection flag to `true`.
+    pub fn set_mutated(&mut self) {
+        self.mutated = true
+    }
+
     /// Reset mutation detection flag to `false`.
     pub fn reset(&mut self) {
         self.mutated = false
@@ -204,3 +965,157 @@ impl<T> DerefMut for MutDetect<T> {
         &mut self.inner
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use motiongfx::time::s;
+
+    use super::*;
+
+    #[derive(Component)]
+    struct Angle {
+        radians: f32,
+    }
+
+    #[test]
+    fn templates_instantiate_per_binding() {
+        let mut world = World::new();
+        let a = world.spawn(Angle { radians: 0.0 }).id();
+        let b = world.spawn(Angle { radians: 0.0 }).id();
+
+        let mut template = TimelineTemplate::new();
+        let spin = template
+            .act("target", path!(<Angle>::radians), |r| r + 1.0)
+            .play(s(1));
+        template.add_track(spin);
+
+        let mut motiongfx = MotionGfxManager::default();
+        assert_eq!(
+            motiongfx.instantiate(&template, &HashMap::new()).err(),
+            Some(ResolveError::UnboundSubject("target".into()))
+        );
+
+        let timeline = motiongfx
+            .instantiate(&template, &HashMap::from([("target", a)]))
+            .unwrap();
+        let id = motiongfx.add_timeline(timeline);
+        motiongfx.load_pending_timelines(&world);
+        motiongfx
+            .get_timeline_mut(&id)
+            .unwrap()
+            .set_target_time(s(1));
+        motiongfx.sample_timelines(&mut world);
+
+        assert_eq!(world.get::<Angle>(a).unwrap().radians, 1.0);
+        assert_eq!(world.get::<Angle>(b).unwrap().radians, 0.0);
+    }
+
+    #[test]
+    fn lazy_bake_starts_from_the_loaded_world() {
+        let play = |lazy: bool| {
+            let mut world = World::new();
+            let entity = world.spawn(Angle { radians: 0.0 }).id();
+
+            let mut motiongfx = MotionGfxManager::default();
+            motiongfx.set_lazy_bake(lazy);
+            let mut b = motiongfx.create_builder();
+            let tracks = [0, 1].map(|_| {
+                b.act(entity, path!(<Angle>::radians), |r| r + 1.0)
+                    .play(s(1))
+                    .compile()
+            });
+            b.add_tracks(tracks);
+            let timeline = b.compile();
+            let id = motiongfx.add_timeline(timeline);
+            motiongfx.load_pending_timelines(&world);
+
+            let mut values = Vec::new();
+            for index in 0..2 {
+                let timeline =
+                    motiongfx.get_timeline_mut(&id).unwrap();
+                timeline.set_target_track(index);
+                timeline.set_target_time(s(1));
+                motiongfx.sample_timelines(&mut world);
+                values.push(
+                    world.get::<Angle>(entity).unwrap().radians,
+                );
+            }
+            values
+        };
+
+        assert_eq!(play(true), play(false));
+        assert_eq!(play(true), [1.0, 1.0]);
+    }
+
+    #[test]
+    fn subjects_are_only_checked_after_a_despawn() {
+        let mut world = World::new();
+        let kept = world.spawn(Angle { radians: 0.0 }).id();
+        let gone = world.spawn(Angle { radians: 0.0 }).id();
+
+        let mut motiongfx = MotionGfxManager::default();
+        let mut b = motiongfx.create_builder();
+        let track = [kept, gone]
+            .map(|entity| {
+                b.act_to(entity, path!(<Angle>::radians), 1.0)
+                    .play(s(1))
+            })
+            .ord_all();
+        b.add_tracks(track.compile());
+        let timeline = b.compile();
+        let id = motiongfx.add_timeline(timeline);
+        motiongfx
+            .set_despawn_policy(&id, DespawnPolicy::RemoveActions);
+        motiongfx.load_pending_timelines(&world);
+
+        let sample = |motiongfx: &mut MotionGfxManager,
+                      world: &mut World| {
+            motiongfx.get_timeline_mut(&id).unwrap().set_mutated();
+            motiongfx.sample_timelines(world);
+        };
+
+        // The first sample watches the subjects, later ones skip the
+        // check until a subject is despawned.
+        sample(&mut motiongfx, &mut world);
+        assert!(world.get::<DespawnWatch>(gone).is_some());
+        assert!(motiongfx.unchecked.is_empty());
+        sample(&mut motiongfx, &mut world);
+        assert!(!world.resource::<SubjectsDespawned>().0);
+
+        world.despawn(gone);
+        assert!(world.resource::<SubjectsDespawned>().0);
+        sample(&mut motiongfx, &mut world);
+        let timeline = motiongfx.get_timeline(&id).unwrap();
+        assert_eq!(timeline.action_table().get_uid(&gone), None);
+        assert!(timeline.action_table().get_uid(&kept).is_some());
+    }
+
+    #[test]
+    fn despawned_actions_are_purged() {
+        let mut world = World::new();
+        let kept = world.spawn(Angle { radians: 0.0 }).id();
+        let gone = world.spawn(Angle { radians: 0.0 }).id();
+
+        let mut motiongfx = MotionGfxManager::default();
+        let mut b = motiongfx.create_builder();
+        let track = [kept, gone]
+            .map(|entity| {
+                b.act_to(entity, path!(<Angle>::radians), 1.0)
+                    .play(s(1))
+            })
+            .ord_all();
+        b.add_tracks(track.compile());
+        let timeline = b.compile();
+        let id = motiongfx.add_timeline(timeline);
+        motiongfx.load_pending_timelines(&world);
+
+        world.despawn(gone);
+        let timeline = motiongfx.get_timeline(&id).unwrap();
+        assert_eq!(despawned_subjects(timeline, &world), [gone]);
+
+        assert_eq!(motiongfx.purge_despawned(&world, &id), 1);
+        assert_eq!(motiongfx.purge_despawned(&world, &id), 0);
+        let timeline = motiongfx.get_timeline(&id).unwrap();
+        assert!(despawned_subjects(timeline, &world).is_empty());
+    }
+}
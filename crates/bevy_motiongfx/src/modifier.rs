@@ -0,0 +1,43 @@
+//! Bevy specific [`Modifier`]s, see [`motiongfx::modifier`] for the
+//! generic ones.
+
+use bevy_math::Vec3;
+use motiongfx::prelude::*;
+
+/// Rounds the x and y of a translation to whole pixels once the clip
+/// progress reaches `from`, so motion comes to rest on the pixel grid
+/// instead of between pixels.
+///
+/// `units_per_pixel` is the world size of a screen pixel, e.g. the
+/// `scale` of an `OrthographicProjection` using the default window
+/// size scaling. The motion before `from` stays smooth:
+///
+/// ```ignore
+/// builder
+///     .act(entity, path!(<Transform>::translation), |t| t + offset)
+///     .with_modifier(pixel_snap(projection.scale, 0.8))
+///     .play(s(1));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `units_per_pixel` is not positive and finite.
+pub fn pixel_snap(
+    units_per_pixel: f32,
+    from: f32,
+) -> impl Modifier<Vec3> {
+    assert!(
+        units_per_pixel > 0.0 && units_per_pixel.is_finite(),
+        "pixel size must be positive and finite, got {units_per_pixel}"
+    );
+
+    move |value: Vec3, t: f32| {
+        if t < from {
+            return value;
+        }
+
+        let snapped = (value.truncate() / units_per_pixel).round()
+            * units_per_pixel;
+        snapped.extend(value.z)
+    }
+}
@@ -0,0 +1,107 @@
+//! Morph target weights, enabled with the `morph` feature.
+//!
+//! The weights of [`MorphWeights`] are private, so they cannot be
+//! reached with `path!(<MorphWeights>::weights[i])`. [`weight`]
+//! provides the same per-index field instead, which works with any
+//! action:
+//!
+//! ```ignore
+//! let blink = builder
+//!     .act_to(face, morph::weight(BLINK), 1.0)
+//!     .with_ease(ease::cubic::ease_in_out)
+//!     .play(ms(120));
+//!
+//! // Or blend into a whole pose at once.
+//! let smile = builder.act_morph_pose(face, &[0.0, 0.8, 0.4], s(1));
+//! ```
+//!
+//! Weights are looked up at bake and sample time. An index past the
+//! weights of the mesh reads as `0.0` and ignores writes.
+
+use core::time::Duration;
+
+use alloc::vec::Vec;
+use bevy_ecs::entity::Entity;
+use bevy_mesh::morph::MorphWeights;
+use bevy_platform::sync::Mutex;
+use motiongfx::field::VirtualField;
+use motiongfx::prelude::*;
+
+use crate::world::BevyTimelineBuilder;
+
+/// Weight fields by index, shared so that every action on a weight
+/// forms a single sequence.
+static WEIGHTS: Mutex<Vec<VirtualField<MorphWeights, f32>>> =
+    Mutex::new(Vec::new());
+
+/// The morph target weight at `index` of a [`MorphWeights`].
+pub fn weight(index: usize) -> VirtualField<MorphWeights, f32> {
+    let mut weights =
+        WEIGHTS.lock().unwrap_or_else(|poison| poison.into_inner());
+
+    while weights.len() <= index {
+        let i = weights.len();
+        weights.push(VirtualField::new(
+            path!(<MorphWeights>),
+            move |morph: &MorphWeights| {
+                morph.weights().get(i).copied().unwrap_or(0.0)
+            },
+            move |morph: &mut MorphWeights, weight: f32| {
+                if let Some(w) = morph.weights_mut().get_mut(i) {
+                    *w = weight;
+                }
+            },
+        ));
+    }
+
+    weights[index].clone()
+}
+
+/// Morph target actions on [`MorphWeights`].
+pub trait MorphExt {
+    /// Blends every weight of `target` to `pose` over `duration`,
+    /// all at once.
+    ///
+    /// Weights past the end of `pose` are left untouched.
+    fn act_morph_pose(
+        &mut self,
+        target: Entity,
+        pose: &[f32],
+        duration: Duration,
+    ) -> TrackFragment;
+}
+
+impl MorphExt for BevyTimelineBuilder<'_> {
+    fn act_morph_pose(
+        &mut self,
+        target: Entity,
+        pose: &[f32],
+        duration: Duration,
+    ) -> TrackFragment {
+        let fragments = pose
+            .iter()
+            .enumerate()
+            .map(|(i, &w)| {
+                self.act_to(target, weight(i), w).play(duration)
+            })
+            .collect::<Vec<_>>();
+
+        fragments.ord_all()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn weights_are_shared_per_index() {
+        let first = weight(2);
+        let second = weight(2);
+        assert_eq!(first.conversion().id(), second.conversion().id());
+        assert_ne!(
+            first.conversion().id(),
+            weight(1).conversion().id()
+        );
+    }
+}
@@ -0,0 +1,142 @@
+//! Gizmo drawn motion paths, enabled with the `gizmos` feature.
+//!
+//! Entities with a [`MotionPath`] get the baked
+//! `Transform::translation` actions of the current track of every
+//! timeline drawn as a polyline, sampled through the interp, ease and
+//! modifiers of each clip. Handy for tuning arcs and staggered
+//! layouts without playing the animation over and over:
+//!
+//! ```ignore
+//! app.add_plugins(MotionPathPlugin);
+//!
+//! commands.spawn((
+//!     Mesh3d(mesh),
+//!     Transform::default(),
+//!     MotionPath::new(palettes::basic::YELLOW),
+//! ));
+//! ```
+
+use core::any::TypeId;
+
+use bevy_app::prelude::*;
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_gizmos::prelude::*;
+use bevy_math::Vec3;
+use bevy_transform::prelude::*;
+use motiongfx::field::FieldTarget;
+use motiongfx::prelude::*;
+
+use crate::MotionGfxSystems;
+use crate::manager::{MotionGfxManager, TimelineId};
+
+/// Draws the [`MotionPath`] of every entity.
+pub struct MotionPathPlugin;
+
+impl Plugin for MotionPathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            draw_motion_paths.after(MotionGfxSystems::Sample),
+        );
+    }
+}
+
+/// Draws the translation path of this entity.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct MotionPath {
+    pub color: Color,
+    /// Number of line segments drawn per clip.
+    pub resolution: u32,
+}
+
+impl MotionPath {
+    pub const DEFAULT_RESOLUTION: u32 = 32;
+
+    pub fn new(color: impl Into<Color>) -> Self {
+        Self {
+            color: color.into(),
+            resolution: Self::DEFAULT_RESOLUTION,
+        }
+    }
+
+    /// Builder method for setting [`Self::resolution`].
+    pub fn with_resolution(mut self, resolution: u32) -> Self {
+        self.resolution = resolution;
+        self
+    }
+}
+
+impl Default for MotionPath {
+    fn default() -> Self {
+        Self::new(Color::WHITE)
+    }
+}
+
+fn draw_motion_paths(
+    mut gizmos: Gizmos,
+    motiongfx: Res<MotionGfxManager>,
+    q_timelines: Query<&TimelineId>,
+    q_paths: Query<(&MotionPath, Option<&ChildOf>)>,
+    q_globals: Query<&GlobalTransform>,
+) {
+    if q_paths.is_empty() {
+        return;
+    }
+
+    let field = path!(<Transform>::translation).field;
+
+    for id in q_timelines.iter() {
+        let Some(timeline) = motiongfx.get_timeline(id) else {
+            continue;
+        };
+        let table = timeline.action_table();
+        let bindings = timeline.subject_bindings();
+        let track = timeline.curr_track();
+        let Some(spans) = track.lookup_field_spans(field) else {
+            continue;
+        };
+
+        for (key, span) in spans {
+            if key.target() != FieldTarget::Whole
+                || key.subject_id().type_id()
+                    != TypeId::of::<Entity>()
+            {
+                continue;
+            }
+            let Some(&entity) = bindings
+                .resolve::<Entity>(table, &key.subject_id().uid())
+            else {
+                continue;
+            };
+            let Ok((motion_path, child_of)) = q_paths.get(entity)
+            else {
+                continue;
+            };
+
+            // Translations are relative to the parent.
+            let parent = child_of.and_then(|child_of| {
+                q_globals.get(child_of.parent()).ok()
+            });
+            let resolution = motion_path.resolution.max(1);
+
+            let points = track
+                .clips(*span)
+                .iter()
+                .flat_map(|clip| {
+                    (0..=resolution).filter_map(move |i| {
+                        table.evaluate::<Vec3>(
+                            &clip.id,
+                            i as f32 / resolution as f32,
+                        )
+                    })
+                })
+                .map(|point| match parent {
+                    Some(parent) => parent.transform_point(point),
+                    None => point,
+                });
+
+            gizmos.linestrip(points, motion_path.color);
+        }
+    }
+}
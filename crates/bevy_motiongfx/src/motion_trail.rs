@@ -0,0 +1,167 @@
+//! Gizmo drawn motion trails, enabled with the `gizmos` feature.
+//!
+//! Entities with a [`MotionTrail`] record their world position every
+//! frame after the timelines are sampled, and get the recent history
+//! drawn as a polyline fading out towards its tail:
+//!
+//! ```ignore
+//! app.add_plugins(MotionTrailPlugin);
+//!
+//! commands.spawn((
+//!     Mesh3d(mesh),
+//!     Transform::default(),
+//!     MotionTrail::new(palettes::basic::AQUA, ms(400)),
+//! ));
+//! ```
+//!
+//! Only entities with a [`MotionTrail`] keep any history. Unlike a
+//! [`MotionPath`](crate::motion_path::MotionPath), the trail shows
+//! where the entity actually went, so blending, modifiers and
+//! parents moving underneath it all show up.
+
+use core::time::Duration;
+
+use alloc::collections::VecDeque;
+use bevy_app::prelude::*;
+use bevy_color::{Alpha, Color};
+use bevy_ecs::prelude::*;
+use bevy_gizmos::prelude::*;
+use bevy_math::Vec3;
+use bevy_time::Time;
+use bevy_transform::TransformSystems;
+use bevy_transform::prelude::*;
+
+use crate::MotionGfxSystems;
+
+/// Records and draws the [`MotionTrail`] of every entity.
+pub struct MotionTrailPlugin;
+
+impl Plugin for MotionTrailPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            (record_motion_trails, draw_motion_trails)
+                .chain()
+                .after(MotionGfxSystems::Sample)
+                .after(TransformSystems::Propagate),
+        );
+    }
+}
+
+/// Draws a fading trail behind this entity.
+#[derive(Component, Debug, Clone)]
+#[require(Transform)]
+pub struct MotionTrail {
+    pub color: Color,
+    /// How long a point stays on the trail.
+    pub lifetime: Duration,
+    /// Points closer than this to the previous one are not recorded.
+    pub min_distance: f32,
+    /// Recorded `(time, position)` pairs, oldest first.
+    points: VecDeque<(Duration, Vec3)>,
+}
+
+impl MotionTrail {
+    pub const DEFAULT_MIN_DISTANCE: f32 = 0.01;
+
+    pub fn new(color: impl Into<Color>, lifetime: Duration) -> Self {
+        Self {
+            color: color.into(),
+            lifetime,
+            min_distance: Self::DEFAULT_MIN_DISTANCE,
+            points: VecDeque::new(),
+        }
+    }
+
+    /// Builder method for setting [`Self::min_distance`].
+    pub fn with_min_distance(mut self, min_distance: f32) -> Self {
+        self.min_distance = min_distance;
+        self
+    }
+
+    /// Records `position` at `now` and drops the points older than
+    /// [`Self::lifetime`].
+    pub fn record(&mut self, now: Duration, position: Vec3) {
+        let far_enough =
+            self.points.back().is_none_or(|(_, last)| {
+                last.distance(position) >= self.min_distance
+            });
+        if far_enough {
+            self.points.push_back((now, position));
+        }
+
+        while self.points.front().is_some_and(|(time, _)| {
+            now.saturating_sub(*time) > self.lifetime
+        }) {
+            self.points.pop_front();
+        }
+    }
+
+    /// Forgets every recorded point, e.g. after a seek.
+    pub fn clear(&mut self) {
+        self.points.clear();
+    }
+
+    /// The recorded `(time, position)` pairs, oldest first.
+    pub fn points(&self) -> impl Iterator<Item = (Duration, Vec3)> {
+        self.points.iter().copied()
+    }
+}
+
+fn record_motion_trails(
+    time: Res<Time>,
+    mut q_trails: Query<(&mut MotionTrail, &GlobalTransform)>,
+) {
+    let now = time.elapsed();
+
+    for (mut trail, global) in q_trails.iter_mut() {
+        trail.record(now, global.translation());
+    }
+}
+
+fn draw_motion_trails(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    q_trails: Query<&MotionTrail>,
+) {
+    let now = time.elapsed();
+
+    for trail in q_trails.iter() {
+        if trail.points.len() < 2 {
+            continue;
+        }
+
+        let lifetime = trail.lifetime.as_secs_f32().max(f32::EPSILON);
+        let alpha = trail.color.alpha();
+        let points = trail.points().map(|(at, point)| {
+            let age = now.saturating_sub(at).as_secs_f32() / lifetime;
+            let fade = (1.0 - age).clamp(0.0, 1.0);
+            (point, trail.color.with_alpha(alpha * fade))
+        });
+
+        gizmos.linestrip_gradient(points);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use motiongfx::time::ms;
+
+    use super::*;
+
+    #[test]
+    fn record_skips_close_points_and_expires_old_ones() {
+        let mut trail = MotionTrail::new(Color::WHITE, ms(100))
+            .with_min_distance(0.5);
+
+        trail.record(ms(0), Vec3::ZERO);
+        trail.record(ms(10), Vec3::X * 0.1);
+        trail.record(ms(20), Vec3::X);
+        assert_eq!(trail.points().count(), 2);
+
+        trail.record(ms(110), Vec3::X * 2.0);
+        let times: Vec<_> = trail.points().map(|(t, _)| t).collect();
+        assert_eq!(times, [ms(20), ms(110)]);
+    }
+}
@@ -0,0 +1,205 @@
+//! Named color palettes and coordinated palette swaps, enabled with
+//! the `color` feature.
+//!
+//! A [`ColorPalette`] maps keys, e.g. [`ColorKey`], to colors. A
+//! [`PaletteSwap`] binds color fields of entities and materials to
+//! those keys, and re-colors all of them from one palette to another
+//! in a single [`TrackFragment`]:
+//!
+//! ```ignore
+//! let swap = PaletteSwap::new()
+//!     .with_target(title, path!(<TextColor>::0), ColorKey::Base8)
+//!     .with_target(
+//!         AssetOf::<MeshMaterial3d<StandardMaterial>>::new(cube),
+//!         path!(<StandardMaterial>::base_color),
+//!         ColorKey::Blue,
+//!     );
+//!
+//! let track = swap.build(&mut builder, &dark, &light, s(1));
+//! ```
+
+use core::hash::Hash;
+use core::time::Duration;
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_platform::collections::HashMap;
+use motiongfx::prelude::*;
+use motiongfx::subject::SubjectId;
+
+use crate::world::{BevyTimelineBuilder, BevyWorld};
+
+/// Colors by key.
+#[derive(Resource, Debug, Clone)]
+pub struct ColorPalette<K> {
+    palette: HashMap<K, Color>,
+}
+
+impl<K: Hash + Eq> ColorPalette<K> {
+    pub fn new(palette: HashMap<K, Color>) -> Self {
+        Self { palette }
+    }
+
+    /// Builder method for setting the color of `key`.
+    pub fn with_color(mut self, key: K, color: Color) -> Self {
+        self.palette.insert(key, color);
+        self
+    }
+
+    /// Get the color of `key`.
+    pub fn get(&self, key: &K) -> Option<Color> {
+        self.palette.get(key).copied()
+    }
+
+    /// Get the color of `key`, or [`Color::WHITE`] if it has none.
+    pub fn get_or_default(&self, key: &K) -> Color {
+        self.get(key).unwrap_or(Color::WHITE)
+    }
+}
+
+impl Default for ColorPalette<ColorKey> {
+    fn default() -> Self {
+        Self::new(HashMap::from_iter([
+            (ColorKey::Red, Color::srgb_u8(255, 100, 97)),
+            (ColorKey::Orange, Color::srgb_u8(255, 160, 80)),
+            (ColorKey::Yellow, Color::srgb_u8(255, 215, 100)),
+            (ColorKey::Green, Color::srgb_u8(130, 215, 110)),
+            (ColorKey::Blue, Color::srgb_u8(90, 170, 255)),
+            (ColorKey::Purple, Color::srgb_u8(190, 130, 255)),
+            (ColorKey::Base0, Color::srgb_u8(20, 20, 24)),
+            (ColorKey::Base1, Color::srgb_u8(32, 32, 38)),
+            (ColorKey::Base2, Color::srgb_u8(48, 48, 56)),
+            (ColorKey::Base3, Color::srgb_u8(70, 70, 80)),
+            (ColorKey::Base4, Color::srgb_u8(100, 100, 112)),
+            (ColorKey::Base5, Color::srgb_u8(140, 140, 152)),
+            (ColorKey::Base6, Color::srgb_u8(180, 180, 190)),
+            (ColorKey::Base7, Color::srgb_u8(215, 215, 222)),
+            (ColorKey::Base8, Color::srgb_u8(245, 245, 250)),
+        ]))
+    }
+}
+
+/// Keys of the default [`ColorPalette`], accents followed by a
+/// ramp of base colors from dark to light.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColorKey {
+    #[default]
+    Red,
+    Orange,
+    Yellow,
+    Green,
+    Blue,
+    Purple,
+    Base0,
+    Base1,
+    Base2,
+    Base3,
+    Base4,
+    Base5,
+    Base6,
+    Base7,
+    Base8,
+}
+
+/// Re-colors a single target, see [`PaletteSwap::build`].
+type RecolorFn = Box<
+    dyn Fn(
+            &mut BevyTimelineBuilder<'_>,
+            Option<Color>,
+            Color,
+            Duration,
+        ) -> TrackFragment
+        + Send
+        + Sync,
+>;
+
+/// Color fields bound to the keys of a [`ColorPalette`], see the
+/// [module docs](self).
+pub struct PaletteSwap<K> {
+    targets: Vec<(K, RecolorFn)>,
+}
+
+impl<K: Hash + Eq> PaletteSwap<K> {
+    pub fn new() -> Self {
+        Self {
+            targets: Vec::new(),
+        }
+    }
+
+    /// Builder method for binding the color `field` of `target` to
+    /// `key`.
+    pub fn with_target<I, S>(
+        mut self,
+        target: I,
+        field: impl ActionField<S, Color> + Clone + ThreadSafe,
+        key: K,
+    ) -> Self
+    where
+        BevyWorld: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+    {
+        self.targets.push((
+            key,
+            Box::new(move |builder, from, to, duration| {
+                // Snap to the old palette, whatever the current color.
+                let snap = from.map(|from| {
+                    builder
+                        .act_to(target, field.clone(), from)
+                        .play(Duration::ZERO)
+                });
+                let fade = builder
+                    .act_to(target, field.clone(), to)
+                    .play(duration);
+
+                match snap {
+                    Some(snap) => [snap, fade].ord_chain(),
+                    None => fade,
+                }
+            }),
+        ));
+        self
+    }
+
+    /// Number of bound targets.
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns `true` if no target is bound.
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+
+    /// Re-colors every target from its color in `from` to its color
+    /// in `to` over `duration`, all at once.
+    ///
+    /// Targets start from their current color if their key is
+    /// missing in `from`, and are skipped if it is missing in `to`.
+    pub fn build(
+        &self,
+        builder: &mut BevyTimelineBuilder<'_>,
+        from: &ColorPalette<K>,
+        to: &ColorPalette<K>,
+        duration: Duration,
+    ) -> TrackFragment {
+        let fragments = self
+            .targets
+            .iter()
+            .filter_map(|(key, recolor)| {
+                let to = to.get(key)?;
+                Some(recolor(builder, from.get(key), to, duration))
+            })
+            .collect::<Vec<_>>();
+
+        fragments.ord_all()
+    }
+}
+
+impl<K: Hash + Eq> Default for PaletteSwap<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
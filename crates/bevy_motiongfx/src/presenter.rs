@@ -0,0 +1,287 @@
+//! Keyboard driven presentations, enabled with the `presenter`
+//! feature.
+//!
+//! Every track of a timeline with a [`Presenter`] is a slide. The
+//! [`PresenterKeys`] move between slides, with the default bindings
+//! also covering presentation clickers:
+//!
+//! ```ignore
+//! app.add_plugins(PresenterPlugin);
+//!
+//! let slides = commands
+//!     .spawn((motiongfx.add_timeline(timeline), Presenter))
+//!     .id();
+//! // An optional "2 / 5" counter, with the `ui` feature.
+//! commands.spawn(SlideCounter::new(slides));
+//! ```
+//!
+//! Moving to another slide pauses on its start, the play key then
+//! plays it through, or backwards while holding shift.
+
+use core::time::Duration;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_input::ButtonInput;
+use bevy_input::keyboard::KeyCode;
+
+use crate::controller::RealtimePlayer;
+use crate::manager::{MotionGfxManager, TimelineId};
+use crate::world::BevyTimeline;
+
+/// Navigates the timelines of every [`Presenter`] with the
+/// [`PresenterKeys`].
+pub struct PresenterPlugin;
+
+impl Plugin for PresenterPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PresenterKeys>()
+            .add_systems(Update, navigate_slides);
+
+        #[cfg(feature = "ui")]
+        app.add_systems(
+            PostUpdate,
+            update_slide_counters
+                .after(crate::MotionGfxSystems::Sample),
+        );
+    }
+}
+
+/// Makes the timeline of this entity a slide deck, see the
+/// [module docs](self).
+#[derive(Component, Debug, Default, Clone, Copy)]
+#[require(RealtimePlayer)]
+pub struct Presenter;
+
+/// Key bindings of the [`Presenter`]s.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct PresenterKeys {
+    pub next: Vec<KeyCode>,
+    pub previous: Vec<KeyCode>,
+    /// Plays the current slide, or backwards with shift held.
+    pub play: Vec<KeyCode>,
+    pub pause: Vec<KeyCode>,
+    pub first: Vec<KeyCode>,
+    pub last: Vec<KeyCode>,
+}
+
+impl PresenterKeys {
+    /// The command of the first binding pressed this frame.
+    pub fn command(
+        &self,
+        keys: &ButtonInput<KeyCode>,
+    ) -> Option<SlideCommand> {
+        let pressed = |bindings: &[KeyCode]| {
+            keys.any_just_pressed(bindings.iter().copied())
+        };
+
+        if pressed(&self.next) {
+            Some(SlideCommand::Next)
+        } else if pressed(&self.previous) {
+            Some(SlideCommand::Previous)
+        } else if pressed(&self.play) {
+            let shift = keys.any_pressed([
+                KeyCode::ShiftLeft,
+                KeyCode::ShiftRight,
+            ]);
+            Some(SlideCommand::Play { backward: shift })
+        } else if pressed(&self.pause) {
+            Some(SlideCommand::Pause)
+        } else if pressed(&self.first) {
+            Some(SlideCommand::First)
+        } else if pressed(&self.last) {
+            Some(SlideCommand::Last)
+        } else {
+            None
+        }
+    }
+}
+
+impl Default for PresenterKeys {
+    fn default() -> Self {
+        Self {
+            // Clickers send page up and down.
+            next: vec![
+                KeyCode::ArrowRight,
+                KeyCode::ArrowDown,
+                KeyCode::PageDown,
+            ],
+            previous: vec![
+                KeyCode::ArrowLeft,
+                KeyCode::ArrowUp,
+                KeyCode::PageUp,
+            ],
+            play: vec![KeyCode::Space],
+            pause: vec![KeyCode::Escape],
+            first: vec![KeyCode::Home],
+            last: vec![KeyCode::End],
+        }
+    }
+}
+
+/// A navigation step of a [`Presenter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SlideCommand {
+    /// Pauses on the start of the next slide.
+    Next,
+    /// Pauses on the start of the previous slide.
+    Previous,
+    /// Plays the current slide, moving on to the next one if it
+    /// already ended, or to the end of the previous one backwards.
+    Play {
+        backward: bool,
+    },
+    Pause,
+    /// Pauses on the start of the first slide.
+    First,
+    /// Pauses on the end of the last slide.
+    Last,
+}
+
+impl SlideCommand {
+    pub fn apply(
+        self,
+        timeline: &mut BevyTimeline,
+        player: &mut RealtimePlayer,
+    ) {
+        let index = timeline.curr_index();
+
+        match self {
+            Self::Next => {
+                timeline.set_target_track(index + 1);
+                timeline.set_target_time(Duration::ZERO);
+                player.set_playing(false);
+            }
+            Self::Previous => {
+                timeline.set_target_track(index.saturating_sub(1));
+                timeline.set_target_time(Duration::ZERO);
+                player.set_playing(false);
+            }
+            Self::Play { backward: false } => {
+                player.set_playing(true).set_time_scale(1.0);
+
+                if timeline.is_track_end() && !timeline.is_complete()
+                {
+                    timeline.set_target_track(index + 1);
+                    timeline.set_target_time(Duration::ZERO);
+                }
+            }
+            Self::Play { backward: true } => {
+                player.set_playing(true).set_time_scale(-1.0);
+
+                if timeline.curr_time() == Duration::ZERO && index > 0
+                {
+                    timeline.set_target_track(index - 1);
+                    // Clamped to the new track's duration.
+                    timeline.set_target_time(Duration::MAX);
+                }
+            }
+            Self::Pause => {
+                player.set_playing(false);
+            }
+            Self::First => {
+                timeline.set_target_track(0);
+                timeline.set_target_time(Duration::ZERO);
+                player.set_playing(false);
+            }
+            Self::Last => {
+                timeline
+                    .set_target_track(timeline.last_track_index());
+                timeline.set_target_time(Duration::MAX);
+                player.set_playing(false);
+            }
+        }
+    }
+}
+
+fn navigate_slides(
+    mut motiongfx: ResMut<MotionGfxManager>,
+    keys: Res<ButtonInput<KeyCode>>,
+    bindings: Res<PresenterKeys>,
+    mut q_presenters: Query<
+        (&TimelineId, &mut RealtimePlayer),
+        With<Presenter>,
+    >,
+) {
+    let Some(command) = bindings.command(&keys) else {
+        return;
+    };
+
+    for (id, mut player) in q_presenters.iter_mut() {
+        if let Some(timeline) = motiongfx.get_timeline_mut(id) {
+            command.apply(timeline, &mut player);
+        }
+    }
+}
+
+/// Shows the current slide of a [`Presenter`] as `"current / total"`
+/// in the [`Text`](bevy_ui::widget::Text) of this entity.
+#[cfg(feature = "ui")]
+#[derive(Component, Debug, Clone, Copy)]
+#[require(bevy_ui::widget::Text)]
+pub struct SlideCounter {
+    /// The entity with the [`Presenter`].
+    pub presenter: Entity,
+}
+
+#[cfg(feature = "ui")]
+impl SlideCounter {
+    pub const fn new(presenter: Entity) -> Self {
+        Self { presenter }
+    }
+}
+
+#[cfg(feature = "ui")]
+fn update_slide_counters(
+    motiongfx: Res<MotionGfxManager>,
+    q_presenters: Query<&TimelineId, With<Presenter>>,
+    mut q_counters: Query<(
+        &SlideCounter,
+        &mut bevy_ui::widget::Text,
+    )>,
+) {
+    for (counter, mut text) in q_counters.iter_mut() {
+        let Some(timeline) = q_presenters
+            .get(counter.presenter)
+            .ok()
+            .and_then(|id| motiongfx.get_timeline(id))
+        else {
+            continue;
+        };
+
+        let slide = alloc::format!(
+            "{} / {}",
+            timeline.curr_index() + 1,
+            timeline.tracks().len()
+        );
+        // Writing every frame would re-layout the text every frame.
+        if text.0 != slide {
+            text.0 = slide;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_map_to_commands() {
+        let bindings = PresenterKeys::default();
+        let mut keys = ButtonInput::<KeyCode>::default();
+        assert_eq!(bindings.command(&keys), None);
+
+        keys.press(KeyCode::PageDown);
+        assert_eq!(bindings.command(&keys), Some(SlideCommand::Next));
+
+        keys.clear();
+        keys.press(KeyCode::ShiftLeft);
+        keys.press(KeyCode::Space);
+        assert_eq!(
+            bindings.command(&keys),
+            Some(SlideCommand::Play { backward: true })
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Skeletal poses, enabled with the `transform` feature.
+//!
+//! Joints of a skinned mesh are addressed by [`Name`] under their
+//! armature root through [`BoneOf`], so a pose can be authored
+//! before the scene holding the joints is spawned:
+//!
+//! ```ignore
+//! let wave = Pose::new()
+//!     .with_bone("UpperArm.R", Quat::from_rotation_z(-FRAC_PI_2))
+//!     .with_bone("LowerArm.R", Quat::from_rotation_x(0.6));
+//!
+//! let track = [
+//!     builder.act_pose(armature, &wave, ms(400)),
+//!     builder.act_pose(armature, &wave.rest(), ms(400)),
+//! ]
+//! .ord_chain();
+//! ```
+//!
+//! Any other action works on a [`BoneOf`] subject as well, e.g. to
+//! translate a single bone or to layer a pose on top of an imported
+//! [glTF animation](crate::gltf) through [blending](crate::blend).
+//!
+//! [`Name`]: bevy_ecs::name::Name
+
+use core::time::Duration;
+
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+use bevy_ecs::entity::Entity;
+use bevy_math::Quat;
+use bevy_transform::components::Transform;
+use motiongfx::prelude::*;
+
+use crate::world::{BevyTimelineBuilder, BoneOf};
+
+/// Local joint rotations by bone name.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Pose {
+    bones: Vec<(Cow<'static, str>, Quat)>,
+}
+
+impl Pose {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The same bones, all at [`Quat::IDENTITY`].
+    pub fn rest(&self) -> Self {
+        Self {
+            bones: self
+                .bones
+                .iter()
+                .map(|(name, _)| (name.clone(), Quat::IDENTITY))
+                .collect(),
+        }
+    }
+
+    /// Builder method for setting the rotation of the bone `name`.
+    pub fn with_bone(
+        mut self,
+        name: impl Into<Cow<'static, str>>,
+        rotation: Quat,
+    ) -> Self {
+        let name = name.into();
+        match self.bones.iter_mut().find(|(n, _)| *n == name) {
+            Some((_, r)) => *r = rotation,
+            None => self.bones.push((name, rotation)),
+        }
+        self
+    }
+
+    /// The `(name, rotation)` pairs, in insertion order.
+    pub fn bones(&self) -> &[(Cow<'static, str>, Quat)] {
+        &self.bones
+    }
+}
+
+/// Pose actions on the joints under an armature root.
+pub trait SkeletonExt {
+    /// Rotates every bone of `pose` under `root` to its rotation
+    /// over `duration`, all at once.
+    ///
+    /// Bones missing under `root` are skipped at bake time.
+    fn act_pose(
+        &mut self,
+        root: Entity,
+        pose: &Pose,
+        duration: Duration,
+    ) -> TrackFragment;
+}
+
+impl SkeletonExt for BevyTimelineBuilder<'_> {
+    fn act_pose(
+        &mut self,
+        root: Entity,
+        pose: &Pose,
+        duration: Duration,
+    ) -> TrackFragment {
+        let fragments = pose
+            .bones
+            .iter()
+            .map(|(name, rotation)| {
+                self.act_to(
+                    BoneOf::new(root, name),
+                    path!(<Transform>::rotation),
+                    *rotation,
+                )
+                .play(duration)
+            })
+            .collect::<Vec<_>>();
+
+        fragments.ord_all()
+    }
+}
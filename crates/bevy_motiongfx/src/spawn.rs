@@ -0,0 +1,349 @@
+//! Structural actions that spawn and despawn entities or run
+//! arbitrary commands as the playhead crosses them.
+//!
+//! [`SpawnCues`] sits next to the [`TimelineId`] of a timeline, like
+//! [`AudioCues`](crate::audio::AudioCues), and keeps entities in
+//! sync with the playhead:
+//!
+//! ```ignore
+//! app.add_plugins(SpawnCuePlugin);
+//!
+//! commands.spawn((
+//!     motiongfx.add_timeline(timeline),
+//!     RealtimePlayer::new().with_playing(true),
+//!     SpawnCues::new()
+//!         .with_cue(SpawnCue::spawn(0, s(3), (mesh, material)))
+//!         .with_cue(SpawnCue::despawn(1, s(1), title))
+//!         .with_cue(
+//!             SpawnCue::command(0, s(3), move |commands| {
+//!                 commands.entity(emitter).insert(Burst);
+//!             })
+//!             .with_undo(move |commands| {
+//!                 commands.entity(emitter).remove::<Burst>();
+//!             }),
+//!         ),
+//! ));
+//! ```
+//!
+//! Every cue is a pure function of the playhead, so scrubbing
+//! backwards undoes it. For that reason, despawned entities are
+//! [`Disabled`] rather than removed, which hides them from every
+//! query until they are brought back. Commands are undone through
+//! their [`SpawnCue::with_undo`] closure, if any.
+
+use core::time::Duration;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use bevy_app::prelude::*;
+use bevy_ecs::entity_disabling::Disabled;
+use bevy_ecs::prelude::*;
+use motiongfx::ThreadSafe;
+
+use crate::MotionGfxSystems;
+use crate::manager::{MotionGfxManager, TimelineId};
+
+/// Syncs the [`SpawnCues`] of every timeline.
+pub struct SpawnCuePlugin;
+
+impl Plugin for SpawnCuePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            sync_spawn_cues.after(MotionGfxSystems::Sample),
+        );
+    }
+}
+
+/// Entities spawned and despawned, and commands run, on the
+/// timeline of this entity.
+#[derive(Component, Clone, Default)]
+pub struct SpawnCues {
+    cues: Vec<SpawnCue>,
+}
+
+impl SpawnCues {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_cue(mut self, cue: SpawnCue) -> Self {
+        self.cues.push(cue);
+        self
+    }
+
+    pub fn push(&mut self, cue: SpawnCue) {
+        self.cues.push(cue);
+    }
+
+    pub fn cues(&self) -> &[SpawnCue] {
+        &self.cues
+    }
+}
+
+/// Spawns an entity, returning its id.
+type SpawnFn = Arc<dyn Fn(&mut Commands) -> Entity + Send + Sync>;
+/// Runs a one-shot command.
+type CommandFn = Arc<dyn Fn(&mut Commands) + Send + Sync>;
+
+#[derive(Clone)]
+enum CueKind {
+    Spawn {
+        spawn: SpawnFn,
+        entity: Option<Entity>,
+    },
+    Despawn {
+        entity: Entity,
+        disabled: bool,
+    },
+    Command {
+        run: CommandFn,
+        undo: Option<CommandFn>,
+        applied: bool,
+    },
+}
+
+/// A structural change applied while the playhead is between
+/// [`Self::start`] and [`Self::end`].
+#[derive(Clone)]
+pub struct SpawnCue {
+    /// The `(track, time)` the cue starts at.
+    pub start: (usize, Duration),
+    /// The `(track, time)` the cue is undone at, if ever.
+    pub end: Option<(usize, Duration)>,
+    kind: CueKind,
+}
+
+impl SpawnCue {
+    /// Spawns a clone of `bundle` at `time` of `track`.
+    pub fn spawn(
+        track: usize,
+        time: Duration,
+        bundle: impl Bundle + Clone,
+    ) -> Self {
+        Self::spawn_with(track, time, move |commands| {
+            commands.spawn(bundle.clone()).id()
+        })
+    }
+
+    /// Similar to [`Self::spawn`], but spawns through `spawn`, e.g.
+    /// to add children.
+    pub fn spawn_with(
+        track: usize,
+        time: Duration,
+        spawn: impl Fn(&mut Commands) -> Entity + ThreadSafe,
+    ) -> Self {
+        Self {
+            start: (track, time),
+            end: None,
+            kind: CueKind::Spawn {
+                spawn: Arc::new(spawn),
+                entity: None,
+            },
+        }
+    }
+
+    /// Despawns `entity` at `time` of `track` by disabling it.
+    pub fn despawn(
+        track: usize,
+        time: Duration,
+        entity: Entity,
+    ) -> Self {
+        Self {
+            start: (track, time),
+            end: None,
+            kind: CueKind::Despawn {
+                entity,
+                disabled: false,
+            },
+        }
+    }
+
+    /// Runs `run` once whenever the playhead moves past `time` of
+    /// `track`, e.g. to trigger a particle burst.
+    pub fn command(
+        track: usize,
+        time: Duration,
+        run: impl Fn(&mut Commands) + ThreadSafe,
+    ) -> Self {
+        Self {
+            start: (track, time),
+            end: None,
+            kind: CueKind::Command {
+                run: Arc::new(run),
+                undo: None,
+                applied: false,
+            },
+        }
+    }
+
+    /// Builder method for the closure undoing a [`Self::command`]
+    /// cue, run when the playhead moves back before [`Self::start`]
+    /// or past [`Self::end`].
+    ///
+    /// Other cues are undone on their own and ignore it.
+    pub fn with_undo(
+        mut self,
+        undo: impl Fn(&mut Commands) + ThreadSafe,
+    ) -> Self {
+        if let CueKind::Command { undo: slot, .. } = &mut self.kind {
+            *slot = Some(Arc::new(undo));
+        }
+        self
+    }
+
+    /// Builder method for setting [`Self::end`].
+    pub fn with_end(mut self, track: usize, time: Duration) -> Self {
+        self.end = Some((track, time));
+        self
+    }
+
+    /// The entity spawned by this cue, if it currently exists.
+    pub fn spawned(&self) -> Option<Entity> {
+        match self.kind {
+            CueKind::Spawn { entity, .. } => entity,
+            _ => None,
+        }
+    }
+
+    /// Applies or undoes the cue for the playhead at `now`.
+    fn sync(
+        &mut self,
+        now: (usize, Duration),
+        commands: &mut Commands,
+    ) {
+        let active =
+            self.start <= now && self.end.is_none_or(|end| now < end);
+
+        match &mut self.kind {
+            CueKind::Spawn { spawn, entity } => {
+                match (active, *entity) {
+                    (true, None) => *entity = Some(spawn(commands)),
+                    (false, Some(spawned)) => {
+                        commands.entity(spawned).try_despawn();
+                        *entity = None;
+                    }
+                    _ => {}
+                }
+            }
+            CueKind::Despawn { entity, disabled } => {
+                if active == *disabled {
+                    return;
+                }
+
+                if active {
+                    commands.entity(*entity).try_insert(Disabled);
+                } else {
+                    commands.entity(*entity).try_remove::<Disabled>();
+                }
+                *disabled = active;
+            }
+            CueKind::Command { run, undo, applied } => {
+                if active == *applied {
+                    return;
+                }
+
+                if active {
+                    run(commands);
+                } else if let Some(undo) = undo {
+                    undo(commands);
+                }
+                *applied = active;
+            }
+        }
+    }
+}
+
+fn sync_spawn_cues(
+    mut commands: Commands,
+    motiongfx: Res<MotionGfxManager>,
+    mut q_cues: Query<(&TimelineId, &mut SpawnCues)>,
+) {
+    for (id, mut cues) in q_cues.iter_mut() {
+        let Some(timeline) = motiongfx.get_timeline(id) else {
+            continue;
+        };
+        let now = (timeline.curr_index(), timeline.curr_time());
+
+        for cue in cues.cues.iter_mut() {
+            cue.sync(now, &mut commands);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::world::CommandQueue;
+    use motiongfx::time::s;
+
+    use super::*;
+
+    #[derive(Component, Clone)]
+    struct Arrow;
+
+    fn sync(
+        world: &mut World,
+        cue: &mut SpawnCue,
+        now: (usize, Duration),
+    ) {
+        let mut queue = CommandQueue::default();
+        cue.sync(now, &mut Commands::new(&mut queue, world));
+        queue.apply(world);
+    }
+
+    #[test]
+    fn spawn_follows_the_playhead() {
+        let mut world = World::new();
+        let mut cue =
+            SpawnCue::spawn(0, s(3), Arrow).with_end(1, s(0));
+
+        sync(&mut world, &mut cue, (0, s(2)));
+        assert_eq!(cue.spawned(), None);
+
+        sync(&mut world, &mut cue, (0, s(3)));
+        let arrow = cue.spawned().unwrap();
+        assert!(world.get::<Arrow>(arrow).is_some());
+
+        sync(&mut world, &mut cue, (1, s(0)));
+        assert_eq!(cue.spawned(), None);
+        assert!(world.get_entity(arrow).is_err());
+    }
+
+    #[test]
+    fn despawn_is_undone_backwards() {
+        let mut world = World::new();
+        let title = world.spawn(Arrow).id();
+        let mut cue = SpawnCue::despawn(0, s(1), title);
+
+        sync(&mut world, &mut cue, (0, s(1)));
+        assert!(world.get::<Disabled>(title).is_some());
+
+        sync(&mut world, &mut cue, (0, s(0)));
+        assert!(world.get::<Disabled>(title).is_none());
+    }
+
+    #[test]
+    fn commands_run_once_and_undo() {
+        let mut world = World::new();
+        let target = world.spawn_empty().id();
+        let mut cue = SpawnCue::command(0, s(1), move |commands| {
+            commands.entity(target).insert(Arrow);
+        })
+        .with_undo(move |commands| {
+            commands.entity(target).remove::<Arrow>();
+        });
+
+        sync(&mut world, &mut cue, (0, s(1)));
+        world.entity_mut(target).remove::<Arrow>();
+        // Already applied, staying past the cue does not rerun it.
+        sync(&mut world, &mut cue, (0, s(2)));
+        assert!(world.get::<Arrow>(target).is_none());
+
+        sync(&mut world, &mut cue, (0, s(0)));
+        sync(&mut world, &mut cue, (0, s(1)));
+        assert!(world.get::<Arrow>(target).is_some());
+
+        sync(&mut world, &mut cue, (0, s(0)));
+        assert!(world.get::<Arrow>(target).is_none());
+    }
+}
@@ -0,0 +1,135 @@
+//! Sprite sheet animation, enabled with the `sprite` feature.
+//!
+//! [`FlipbookExt`] steps the texture atlas index of a [`Sprite`]
+//! through a range of frames at a fixed frame rate, in sync with the
+//! rest of the timeline:
+//!
+//! ```ignore
+//! let idle = builder.act_flipbook(player, 0..4, 8.0);
+//! let run = builder.act_flipbook_loop(player, 4..12, 12.0, 3);
+//! builder.add_tracks([idle, run].ord_chain().compile());
+//! ```
+//!
+//! Sprites without a texture atlas are left untouched.
+
+use core::ops::Range;
+use core::time::Duration;
+
+use bevy_ecs::entity::Entity;
+use bevy_math::ops;
+use bevy_platform::sync::LazyLock;
+use bevy_sprite::Sprite;
+use motiongfx::field::VirtualField;
+use motiongfx::prelude::*;
+
+use crate::world::BevyTimelineBuilder;
+
+/// The texture atlas index of a [`Sprite`] as an [`f32`], shared so
+/// that every flipbook on a sprite forms a single sequence.
+static ATLAS_INDEX: LazyLock<VirtualField<Sprite, f32>> =
+    LazyLock::new(|| {
+        VirtualField::new(
+            path!(<Sprite>),
+            |sprite: &Sprite| {
+                sprite
+                    .texture_atlas
+                    .as_ref()
+                    .map_or(0.0, |atlas| atlas.index as f32)
+            },
+            |sprite: &mut Sprite, index: f32| {
+                if let Some(atlas) = &mut sprite.texture_atlas {
+                    atlas.index = ops::round(index) as usize;
+                }
+            },
+        )
+    });
+
+/// Flipbook actions on the texture atlas of a [`Sprite`].
+pub trait FlipbookExt {
+    /// Plays the atlas `frames` once at `fps` frames per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is not positive and finite.
+    fn act_flipbook(
+        &mut self,
+        target: Entity,
+        frames: Range<usize>,
+        fps: f32,
+    ) -> TrackFragment;
+
+    /// Similar to [`Self::act_flipbook`], but plays the `frames`
+    /// `loops` times in a row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is not positive and finite.
+    fn act_flipbook_loop(
+        &mut self,
+        target: Entity,
+        frames: Range<usize>,
+        fps: f32,
+        loops: u32,
+    ) -> TrackFragment;
+}
+
+impl FlipbookExt for BevyTimelineBuilder<'_> {
+    fn act_flipbook(
+        &mut self,
+        target: Entity,
+        frames: Range<usize>,
+        fps: f32,
+    ) -> TrackFragment {
+        self.act_flipbook_loop(target, frames, fps, 1)
+    }
+
+    fn act_flipbook_loop(
+        &mut self,
+        target: Entity,
+        frames: Range<usize>,
+        fps: f32,
+        loops: u32,
+    ) -> TrackFragment {
+        assert!(
+            fps > 0.0 && fps.is_finite(),
+            "frame rate must be positive and finite, got {fps}"
+        );
+
+        let count = frames.len() * loops as usize;
+        let duration = Duration::from_secs_f32(count as f32 / fps);
+        self.act_fn(target, ATLAS_INDEX.clone(), move |t: f32| {
+            frame_at(&frames, count, t) as f32
+        })
+        .with_sample_rate(fps)
+        .play(duration)
+    }
+}
+
+/// The atlas index shown at progress `t` of a flipbook playing
+/// `count` frames out of `frames`, looping as needed.
+fn frame_at(frames: &Range<usize>, count: usize, t: f32) -> usize {
+    if count == 0 {
+        return frames.start;
+    }
+
+    // `t` sits on a frame start, round off the float error.
+    let frame =
+        (ops::round(t * count as f32) as usize).min(count - 1);
+    frames.start + frame % frames.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frames_loop_and_hold_the_last() {
+        let frames = 4..7;
+
+        assert_eq!(frame_at(&frames, 6, 0.0), 4);
+        assert_eq!(frame_at(&frames, 6, 2.0 / 6.0), 6);
+        assert_eq!(frame_at(&frames, 6, 3.0 / 6.0), 4);
+        assert_eq!(frame_at(&frames, 6, 1.0), 6);
+        assert_eq!(frame_at(&frames, 0, 1.0), 4);
+    }
+}
@@ -0,0 +1,240 @@
+//! Typewriter style text reveal.
+//!
+//! A [`Typewriter`] holds the full string and a normalized
+//! [`Typewriter::progress`]. Timelines animate the progress like any
+//! other `f32` field, and [`TypewriterPlugin<T>`] turns it into the
+//! revealed substring of the `T` text component after sampling.
+//!
+//! ```ignore
+//! app.add_plugins(TypewriterPlugin::<Text2d>::default());
+//!
+//! let entity = commands
+//!     .spawn((
+//!         Text2d::default(),
+//!         Typewriter::new("Hello, world!")
+//!             .with_cursor(TypewriterCursor::default()),
+//!     ))
+//!     .id();
+//!
+//! builder
+//!     .act(entity, path!(<Typewriter>::progress), |_| 1.0)
+//!     .with_interp(|a, b, t| a + (b - a) * t)
+//!     .play(s(2))
+//! ```
+
+use core::marker::PhantomData;
+use core::time::Duration;
+
+use alloc::string::String;
+use bevy_app::prelude::*;
+use bevy_ecs::prelude::*;
+use bevy_text::TextSpanAccess;
+use bevy_time::prelude::*;
+
+use crate::MotionGfxSystems;
+
+#[cfg(feature = "text2d")]
+pub mod glyph;
+
+/// Writes the revealed text of every [`Typewriter`] into its `T` text
+/// component, e.g. `Text` or `Text2d`.
+pub struct TypewriterPlugin<T>(PhantomData<T>);
+
+impl<T> Default for TypewriterPlugin<T> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<T: TextSpanAccess> Plugin for TypewriterPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            reveal_text::<T>.after(MotionGfxSystems::Sample),
+        );
+    }
+}
+
+fn reveal_text<T: TextSpanAccess>(
+    mut q_texts: Query<(&Typewriter, &mut T)>,
+    time: Res<Time>,
+) {
+    for (typewriter, mut text) in q_texts.iter_mut() {
+        let revealed = typewriter.revealed();
+        let cursor = typewriter
+            .cursor
+            .as_ref()
+            .filter(|c| c.is_visible(typewriter, time.elapsed()))
+            .map_or("", |c| c.glyph.as_str());
+
+        // Only touch the span on change, so text layout isn't redone
+        // every frame.
+        let unchanged = text
+            .read_span()
+            .strip_prefix(revealed)
+            .is_some_and(|rest| rest == cursor);
+        if unchanged {
+            continue;
+        }
+
+        let span = text.write_span();
+        span.clear();
+        span.push_str(revealed);
+        span.push_str(cursor);
+    }
+}
+
+/// Progressively reveals [`Self::text`] as [`Self::progress`] goes
+/// from `0.0` to `1.0`.
+#[derive(Component, Debug, Clone, Default)]
+pub struct Typewriter {
+    /// The full string to reveal.
+    pub text: String,
+    /// Normalized reveal progress, clamped to \[0.0..=1.0\].
+    pub progress: f32,
+    /// The smallest piece of text revealed at once.
+    pub unit: RevealUnit,
+    /// Cursor drawn after the revealed text, if any.
+    pub cursor: Option<TypewriterCursor>,
+}
+
+impl Typewriter {
+    /// Creates a hidden typewriter revealing `text` per character.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_progress(mut self, progress: f32) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    pub fn with_unit(mut self, unit: RevealUnit) -> Self {
+        self.unit = unit;
+        self
+    }
+
+    pub fn with_cursor(mut self, cursor: TypewriterCursor) -> Self {
+        self.cursor = Some(cursor);
+        self
+    }
+
+    /// Number of [`RevealUnit`]s in [`Self::text`].
+    pub fn unit_count(&self) -> usize {
+        match self.unit {
+            RevealUnit::Char => self.text.chars().count(),
+            RevealUnit::Word => self.text.split_whitespace().count(),
+        }
+    }
+
+    /// Number of [`RevealUnit`]s revealed at the current progress.
+    pub fn revealed_count(&self) -> usize {
+        let progress = self.progress.clamp(0.0, 1.0);
+        (progress * self.unit_count() as f32) as usize
+    }
+
+    /// The revealed part of [`Self::text`].
+    pub fn revealed(&self) -> &str {
+        let count = self.revealed_count();
+        let end = match self.unit {
+            RevealUnit::Char => self
+                .text
+                .char_indices()
+                .nth(count)
+                .map_or(self.text.len(), |(i, _)| i),
+            // The end of the `count`-th word, trailing whitespace
+            // shows up together with the next word.
+            RevealUnit::Word => match count.checked_sub(1) {
+                None => 0,
+                Some(n) => self
+                    .text
+                    .split_whitespace()
+                    .nth(n)
+                    // Words are subslices of `text`.
+                    .map_or(self.text.len(), |word| {
+                        word.as_ptr() as usize
+                            - self.text.as_ptr() as usize
+                            + word.len()
+                    }),
+            },
+        };
+
+        &self.text[..end]
+    }
+
+    /// Returns `true` once the whole text is revealed.
+    pub fn is_complete(&self) -> bool {
+        self.revealed_count() == self.unit_count()
+    }
+}
+
+/// The smallest piece of text a [`Typewriter`] reveals at once.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RevealUnit {
+    /// One `char` at a time.
+    #[default]
+    Char,
+    /// One whitespace separated word at a time.
+    Word,
+}
+
+/// A cursor drawn right after the revealed text of a [`Typewriter`].
+#[derive(Debug, Clone)]
+pub struct TypewriterCursor {
+    /// The string drawn as the cursor.
+    pub glyph: String,
+    /// Time between the cursor showing and hiding, [`None`] to keep
+    /// it solid.
+    pub blink: Option<Duration>,
+    /// Hides the cursor once the whole text is revealed.
+    pub hide_on_complete: bool,
+}
+
+impl TypewriterCursor {
+    pub fn new(glyph: impl Into<String>) -> Self {
+        Self {
+            glyph: glyph.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_blink(mut self, blink: Option<Duration>) -> Self {
+        self.blink = blink;
+        self
+    }
+
+    pub fn with_hide_on_complete(mut self, hide: bool) -> Self {
+        self.hide_on_complete = hide;
+        self
+    }
+
+    fn is_visible(
+        &self,
+        typewriter: &Typewriter,
+        elapsed: Duration,
+    ) -> bool {
+        if self.hide_on_complete && typewriter.is_complete() {
+            return false;
+        }
+
+        match self.blink {
+            Some(blink) if !blink.is_zero() => (elapsed.as_nanos()
+                / blink.as_nanos())
+            .is_multiple_of(2),
+            _ => true,
+        }
+    }
+}
+
+impl Default for TypewriterCursor {
+    fn default() -> Self {
+        Self {
+            glyph: String::from("|"),
+            blink: Some(Duration::from_millis(500)),
+            hide_on_complete: true,
+        }
+    }
+}
@@ -0,0 +1,403 @@
+//! Per-glyph [`Text2d`] animation.
+//!
+//! [`SplitText::spawn`] spawns a [`Text2d`] entity with one child
+//! entity per visible glyph, and [`stagger_glyphs`] staggers an
+//! animation across them:
+//!
+//! ```ignore
+//! app.add_plugins(SplitTextPlugin);
+//!
+//! let (_, glyphs) = SplitText::spawn(
+//!     &mut commands,
+//!     "Hello, world!",
+//!     TextFont::from_font_size(60.0),
+//! );
+//!
+//! let track = stagger_glyphs(glyphs, ms(50), |glyph| {
+//!     builder
+//!         .act(glyph, path!(<Transform>::scale), |_| Vec3::ONE)
+//!         .with_interp(|a, b, t| a.lerp(*b, t))
+//!         .play(ms(300))
+//! });
+//! ```
+//!
+//! Every glyph entity sits at the origin of a slot entity which
+//! follows the glyph's position in the text layout, so animated
+//! transforms stay relative to the glyph.
+
+use core::time::Duration;
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+use bevy_app::prelude::*;
+use bevy_camera::visibility::Visibility;
+use bevy_color::Color;
+use bevy_ecs::prelude::*;
+use bevy_math::Vec2;
+use bevy_platform::collections::HashMap;
+use bevy_sprite::{Anchor, Text2d};
+use bevy_text::{
+    Text2dUpdateSystems, TextColor, TextFont, TextLayoutInfo,
+};
+use bevy_transform::components::Transform;
+use motiongfx::prelude::*;
+
+/// Positions the glyphs of every [`SplitText`] after the text layout.
+pub struct SplitTextPlugin;
+
+impl Plugin for SplitTextPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            PostUpdate,
+            layout_split_text.after(Text2dUpdateSystems),
+        );
+    }
+}
+
+/// A [`Text2d`] drawn through one child entity per glyph.
+///
+/// The [`Text2d`] of this entity only drives the layout and is made
+/// transparent, its color is handed to the glyphs instead. Setting
+/// [`Self::set_text`] reflows the glyphs: existing glyph entities are
+/// reused in order, so animations on them keep working.
+#[derive(Component, Debug, Clone)]
+#[require(Text2d)]
+pub struct SplitText {
+    text: String,
+    /// `(slot, glyph)` entities, one per non-whitespace char.
+    glyphs: Vec<(Entity, Entity)>,
+    /// The original color of the text, taken on the first layout.
+    color: Option<Color>,
+}
+
+impl SplitText {
+    /// Spawns a split text entity with `bundle`, returning it along
+    /// with its glyph entities in reading order.
+    pub fn spawn(
+        commands: &mut Commands,
+        text: impl Into<String>,
+        bundle: impl Bundle,
+    ) -> (Entity, Vec<Entity>) {
+        let text = text.into();
+        let entity =
+            commands.spawn((bundle, Text2d(text.clone()))).id();
+
+        let glyphs = text
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| spawn_glyph(commands, entity, c))
+            .collect::<Vec<_>>();
+
+        let split = SplitText {
+            text,
+            glyphs: glyphs.clone(),
+            color: None,
+        };
+        commands.entity(entity).insert(split);
+
+        (entity, glyphs.into_iter().map(|(_, glyph)| glyph).collect())
+    }
+
+    /// The text being split.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Replaces the text, reflowing the glyphs on the next layout.
+    pub fn set_text(&mut self, text: impl Into<String>) {
+        self.text = text.into();
+    }
+
+    /// The glyph entities in reading order.
+    ///
+    /// After [`Self::set_text`], glyphs are added or removed at the
+    /// end once the new text is laid out.
+    pub fn glyphs(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.glyphs.iter().map(|&(_, glyph)| glyph)
+    }
+}
+
+/// Marker for the per-glyph entities of a [`SplitText`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Glyph;
+
+/// Plays the fragment created by `f` for every glyph, starting each
+/// one `delay` after the previous.
+pub fn stagger_glyphs(
+    glyphs: impl IntoIterator<Item = Entity>,
+    delay: Duration,
+    mut f: impl FnMut(Entity) -> TrackFragment,
+) -> TrackFragment {
+    stagger(glyphs, delay, |glyph, _| f(glyph))
+}
+
+fn spawn_glyph(
+    commands: &mut Commands,
+    parent: Entity,
+    c: char,
+) -> (Entity, Entity) {
+    let slot = commands
+        .spawn((
+            Transform::default(),
+            Visibility::default(),
+            ChildOf(parent),
+        ))
+        .id();
+    let glyph = commands
+        .spawn((
+            Glyph,
+            Text2d(String::from(c)),
+            TextColor(Color::NONE),
+            ChildOf(slot),
+        ))
+        .id();
+
+    (slot, glyph)
+}
+
+#[expect(clippy::type_complexity)]
+fn layout_split_text(
+    mut commands: Commands,
+    mut q_splits: Query<
+        (
+            Entity,
+            &mut SplitText,
+            &mut Text2d,
+            &mut TextColor,
+            &TextFont,
+            &TextLayoutInfo,
+            &Anchor,
+        ),
+        (
+            Or<(Changed<SplitText>, Changed<TextLayoutInfo>)>,
+            Without<Glyph>,
+        ),
+    >,
+    mut q_slots: Query<&mut Transform, Without<Glyph>>,
+    mut q_glyphs: Query<
+        (&mut Text2d, &mut TextFont, &mut TextColor),
+        (With<Glyph>, Without<SplitText>),
+    >,
+) {
+    for (
+        entity,
+        mut split,
+        mut text,
+        mut color,
+        font,
+        layout,
+        anchor,
+    ) in q_splits.iter_mut()
+    {
+        // Wait for the layout of the new text.
+        if text.0 != split.text {
+            text.0.clone_from(&split.text);
+            continue;
+        }
+
+        let glyph_color = match split.color {
+            Some(glyph_color) => glyph_color,
+            None => {
+                let glyph_color = color.0;
+                split.color = Some(glyph_color);
+                color.0 = Color::NONE;
+                // Glyphs spawned with the text are still transparent.
+                for &(_, glyph) in split.glyphs.iter() {
+                    if let Ok((_, _, mut c)) = q_glyphs.get_mut(glyph)
+                    {
+                        c.0 = glyph_color;
+                    }
+                }
+                glyph_color
+            }
+        };
+
+        let chars = glyph_chars(&split.text);
+
+        // Reflow: reuse glyphs in order, then grow or shrink.
+        while split.glyphs.len() < chars.len() {
+            let c = chars[split.glyphs.len()].2;
+            let (slot, glyph) = spawn_glyph(&mut commands, entity, c);
+            commands
+                .entity(glyph)
+                .insert((font.clone(), TextColor(glyph_color)));
+            split.glyphs.push((slot, glyph));
+        }
+        // Draining goes through `DerefMut`, which would mark the
+        // split changed and lay it out again every frame.
+        if split.glyphs.len() > chars.len() {
+            for (slot, _) in split.glyphs.drain(chars.len()..) {
+                commands.entity(slot).despawn();
+            }
+        }
+
+        let positions = layout
+            .glyphs
+            .iter()
+            .filter(|g| g.span_index == 0)
+            .map(|g| ((g.line_index, g.byte_index), g.position))
+            .collect::<HashMap<_, _>>();
+        let top_left = (Anchor::TOP_LEFT.0 - anchor.0) * layout.size;
+        let scale = layout.scale_factor.recip();
+
+        for (&(line, byte, c), &(slot, glyph)) in
+            chars.iter().zip(split.glyphs.iter())
+        {
+            if let Ok((mut text, mut glyph_font, _)) =
+                q_glyphs.get_mut(glyph)
+            {
+                let c = c.encode_utf8(&mut [0; 4]).to_owned();
+                if text.0 != c {
+                    text.0 = c;
+                }
+                if *glyph_font != *font {
+                    *glyph_font = font.clone();
+                }
+            }
+
+            if let Some(position) = positions.get(&(line, byte))
+                && let Ok(mut transform) = q_slots.get_mut(slot)
+            {
+                let offset = top_left
+                    + Vec2::new(position.x, -position.y) * scale;
+                transform.translation = offset.extend(0.0);
+            }
+        }
+    }
+}
+
+/// The `(line, byte in line, char)` of every non-whitespace char,
+/// matching the keys of the laid out glyphs.
+fn glyph_chars(text: &str) -> Vec<(usize, usize, char)> {
+    text.split('\n')
+        .enumerate()
+        .flat_map(|(line, s)| {
+            s.char_indices()
+                .filter(|(_, c)| !c.is_whitespace())
+                .map(move |(byte, c)| (line, byte, c))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glyph_chars_skip_whitespace_per_line() {
+        assert_eq!(
+            glyph_chars("ab c\n d\u{e9}f"),
+            [
+                (0, 0, 'a'),
+                (0, 1, 'b'),
+                (0, 3, 'c'),
+                (1, 1, 'd'),
+                (1, 2, '\u{e9}'),
+                // `é` takes two bytes.
+                (1, 4, 'f'),
+            ]
+        );
+        assert!(glyph_chars(" \n\t").is_empty());
+    }
+
+    /// Counts the frames in which a split or a glyph font changed.
+    #[derive(Resource, Default)]
+    struct Changes(usize);
+
+    fn count_changes(
+        q_splits: Query<(), Changed<SplitText>>,
+        q_fonts: Query<(), (With<Glyph>, Changed<TextFont>)>,
+        mut changes: ResMut<Changes>,
+    ) {
+        if !q_splits.is_empty() || !q_fonts.is_empty() {
+            changes.0 += 1;
+        }
+    }
+
+    fn setup(text: &str) -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(SplitTextPlugin)
+            .init_resource::<Changes>()
+            .add_systems(Last, count_changes);
+
+        let world = app.world_mut();
+        let (entity, _) = SplitText::spawn(
+            &mut world.commands(),
+            text,
+            TextFont::default(),
+        );
+        world.flush();
+        app.update();
+
+        (app, entity)
+    }
+
+    /// Stands in for the text layout, which reruns on a new text.
+    fn set_text(app: &mut App, entity: Entity, text: &str) {
+        let world = app.world_mut();
+        world.get_mut::<SplitText>(entity).unwrap().set_text(text);
+        app.update();
+        let world = app.world_mut();
+        world
+            .get_mut::<TextLayoutInfo>(entity)
+            .unwrap()
+            .set_changed();
+        app.update();
+    }
+
+    fn glyphs(app: &App, entity: Entity) -> Vec<Entity> {
+        app.world()
+            .get::<SplitText>(entity)
+            .unwrap()
+            .glyphs()
+            .collect()
+    }
+
+    fn glyph_text(app: &App, glyph: Entity) -> &str {
+        &app.world().get::<Text2d>(glyph).unwrap().0
+    }
+
+    #[test]
+    fn set_text_grows_and_shrinks_the_glyphs() {
+        let (mut app, entity) = setup("ab");
+        let initial = glyphs(&app, entity);
+        assert_eq!(initial.len(), 2);
+
+        set_text(&mut app, entity, "ab c\nd");
+        let grown = glyphs(&app, entity);
+        assert_eq!(grown.len(), 4);
+        // Existing glyphs are reused in order.
+        assert_eq!(grown[..2], initial[..]);
+        let texts = grown
+            .iter()
+            .map(|&g| glyph_text(&app, g))
+            .collect::<Vec<_>>();
+        assert_eq!(texts, ["a", "b", "c", "d"]);
+
+        set_text(&mut app, entity, "x");
+        let shrunk = glyphs(&app, entity);
+        assert_eq!(shrunk, [grown[0]]);
+        assert_eq!(glyph_text(&app, shrunk[0]), "x");
+        for &glyph in &grown[1..] {
+            assert!(app.world().get_entity(glyph).is_err());
+        }
+    }
+
+    #[test]
+    fn settled_text_is_left_unchanged() {
+        let (mut app, entity) = setup("ab");
+        set_text(&mut app, entity, "a");
+
+        app.world_mut().resource_mut::<Changes>().0 = 0;
+        let world = app.world_mut();
+        world
+            .get_mut::<TextLayoutInfo>(entity)
+            .unwrap()
+            .set_changed();
+        app.update();
+        app.update();
+
+        assert_eq!(app.world().resource::<Changes>().0, 0);
+    }
+}
@@ -0,0 +1,370 @@
+//! Prebuilt transitions between slides, enabled with the
+//! `transform` feature.
+//!
+//! Every transition returns a [`TrackFragment`] to place at the
+//! start of the incoming slide, or at the end of the outgoing one:
+//!
+//! ```ignore
+//! let offset = Vec3::X * 20.0;
+//! let details = [
+//!     transition::push(&mut b, intro, details, offset, ms(600)),
+//!     details_slide,
+//! ]
+//! .ord_chain();
+//!
+//! b.add_tracks([intro_slide.compile(), details.compile()]);
+//! ```
+//!
+//! - [`push`] slides the root of the outgoing slide out while the
+//!   incoming one slides in after it.
+//! - [`crossfade`], with the `color` feature, fades the alpha of a
+//!   color field of each slide, e.g. a material base color.
+//! - [`wipe_in`] and [`wipe_out`], with the `ui` feature, grow and
+//!   shrink a full screen UI node masking the slides.
+//!
+//! Transitions only use relative actions, so they pick up from
+//! wherever the slides were left.
+
+use core::time::Duration;
+
+#[cfg(feature = "color")]
+use bevy_color::{Alpha, Color};
+use bevy_ecs::entity::Entity;
+use bevy_math::Vec3;
+use bevy_transform::components::Transform;
+use motiongfx::prelude::*;
+#[cfg(feature = "color")]
+use motiongfx::subject::SubjectId;
+
+use crate::world::BevyTimelineBuilder;
+#[cfg(feature = "color")]
+use crate::world::BevyWorld;
+
+/// Slides `outgoing` away by `offset` while `incoming`, starting
+/// `offset` behind its resting place, slides into it.
+pub fn push(
+    builder: &mut BevyTimelineBuilder<'_>,
+    outgoing: Entity,
+    incoming: Entity,
+    offset: Vec3,
+    duration: Duration,
+) -> TrackFragment {
+    let translation = path!(<Transform>::translation);
+
+    [
+        builder
+            .act(outgoing, translation, move |t| t - offset)
+            .with_ease(ease::cubic::ease_in_out)
+            .play(duration),
+        [
+            builder
+                .act(incoming, translation, move |t| t + offset)
+                .play(Duration::ZERO),
+            builder
+                .act(incoming, translation, move |t| t - offset)
+                .with_ease(ease::cubic::ease_in_out)
+                .play(duration),
+        ]
+        .ord_chain(),
+    ]
+    .ord_all()
+}
+
+/// Fades the alpha of the color `field` of `target` to `alpha`.
+#[cfg(feature = "color")]
+pub fn fade<I, S>(
+    builder: &mut BevyTimelineBuilder<'_>,
+    target: I,
+    field: impl ActionField<S, Color>,
+    alpha: f32,
+    duration: Duration,
+) -> TrackFragment
+where
+    BevyWorld: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+{
+    builder
+        .act(target, field, move |color: &Color| {
+            color.with_alpha(alpha)
+        })
+        .play(duration)
+}
+
+/// Fades the color `field` of `outgoing` out while the one of
+/// `incoming` fades in.
+///
+/// The faded colors need an alpha blended material to show the
+/// fade.
+#[cfg(feature = "color")]
+pub fn crossfade<I, S>(
+    builder: &mut BevyTimelineBuilder<'_>,
+    outgoing: I,
+    incoming: I,
+    field: impl ActionField<S, Color> + Clone,
+    duration: Duration,
+) -> TrackFragment
+where
+    BevyWorld: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+{
+    [
+        fade(builder, outgoing, field.clone(), 0.0, duration),
+        fade(builder, incoming, field, 1.0, duration),
+    ]
+    .ord_all()
+}
+
+#[cfg(feature = "ui")]
+pub use wipe::{Edge, wipe_in, wipe_out};
+
+#[cfg(feature = "ui")]
+mod wipe {
+    use bevy_ui::{Node, Val};
+
+    use super::*;
+
+    /// A side of the screen.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub enum Edge {
+        Left,
+        Right,
+        Top,
+        Bottom,
+    }
+
+    /// Grows the `mask` node from `from` until it covers the screen.
+    ///
+    /// The node should be absolutely positioned on top of the
+    /// slides, its anchoring and size are set by the wipe.
+    pub fn wipe_in(
+        builder: &mut BevyTimelineBuilder<'_>,
+        mask: Entity,
+        from: Edge,
+        duration: Duration,
+    ) -> TrackFragment {
+        wipe(builder, mask, from, [0.0, 100.0], duration)
+    }
+
+    /// Shrinks the `mask` node towards `to` until it is gone,
+    /// revealing the slides under it.
+    pub fn wipe_out(
+        builder: &mut BevyTimelineBuilder<'_>,
+        mask: Entity,
+        to: Edge,
+        duration: Duration,
+    ) -> TrackFragment {
+        wipe(builder, mask, to, [100.0, 0.0], duration)
+    }
+
+    /// Anchors `mask` to `edge` and animates its size across the
+    /// screen between the `[from, to]` percentages.
+    ///
+    /// The size snaps to `from` first, as a default node is
+    /// [`Val::Auto`] sized, which can't be interpolated.
+    fn wipe(
+        builder: &mut BevyTimelineBuilder<'_>,
+        mask: Entity,
+        edge: Edge,
+        [from, to]: [f32; 2],
+        duration: Duration,
+    ) -> TrackFragment {
+        let (zero, auto) = (Val::Px(0.0), Val::Auto);
+        let (left, right, top, bottom) = match edge {
+            Edge::Left => (zero, auto, zero, zero),
+            Edge::Right => (auto, zero, zero, zero),
+            Edge::Top => (zero, zero, zero, auto),
+            Edge::Bottom => (zero, zero, auto, zero),
+        };
+        let (along, across) = match edge {
+            Edge::Left | Edge::Right => {
+                (path!(<Node>::width), path!(<Node>::height))
+            }
+            Edge::Top | Edge::Bottom => {
+                (path!(<Node>::height), path!(<Node>::width))
+            }
+        };
+
+        let mut snap = |field, value: Val| {
+            builder
+                .act_step(mask, field, move |_: &Val| value)
+                .play(Duration::ZERO)
+        };
+        let anchor = [
+            snap(path!(<Node>::left), left),
+            snap(path!(<Node>::right), right),
+            snap(path!(<Node>::top), top),
+            snap(path!(<Node>::bottom), bottom),
+            snap(across, Val::Percent(100.0)),
+            snap(along, Val::Percent(from)),
+        ]
+        .ord_all();
+
+        let grow = builder
+            .act(mask, along, move |_: &Val| Val::Percent(to))
+            .with_ease(ease::cubic::ease_in_out)
+            .play(duration);
+
+        [anchor, grow].ord_chain()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+    use bevy_ecs::prelude::*;
+    use motiongfx::time::{ms, s};
+
+    use crate::manager::MotionGfxManager;
+
+    use super::*;
+
+    /// Plays the fragment of `f` over one second, reading the world
+    /// at its start, midpoint and end.
+    fn play<R>(
+        world: &mut World,
+        f: impl FnOnce(&mut BevyTimelineBuilder) -> TrackFragment,
+        read: impl Fn(&World) -> R,
+    ) -> Vec<R> {
+        let mut motiongfx = MotionGfxManager::default();
+        let mut b = motiongfx.create_builder();
+        let track = f(&mut b);
+        b.add_tracks(track.compile());
+        let timeline = b.compile();
+        let id = motiongfx.add_timeline(timeline);
+        motiongfx.load_pending_timelines(world);
+
+        [Duration::ZERO, ms(500), s(1)]
+            .into_iter()
+            .map(|time| {
+                motiongfx
+                    .get_timeline_mut(&id)
+                    .unwrap()
+                    .set_target_time(time);
+                motiongfx.sample_timelines(world);
+                read(world)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn push_slides_the_incoming_slide_in_behind() {
+        let mut world = World::new();
+        let outgoing = world.spawn(Transform::default()).id();
+        let incoming = world.spawn(Transform::default()).id();
+
+        let offset = Vec3::X * 10.0;
+        let translations = play(
+            &mut world,
+            |b| push(b, outgoing, incoming, offset, s(1)),
+            |world| {
+                [outgoing, incoming].map(|entity| {
+                    world
+                        .get::<Transform>(entity)
+                        .unwrap()
+                        .translation
+                })
+            },
+        );
+
+        assert_eq!(
+            translations,
+            [
+                [Vec3::ZERO, offset],
+                [-offset * 0.5, offset * 0.5],
+                [-offset, Vec3::ZERO],
+            ]
+        );
+    }
+
+    #[cfg(feature = "color")]
+    #[test]
+    fn crossfade_swaps_the_alphas() {
+        #[derive(Component)]
+        struct Tint {
+            color: Color,
+        }
+
+        let mut world = World::new();
+        let outgoing = world
+            .spawn(Tint {
+                color: Color::WHITE,
+            })
+            .id();
+        let incoming = world
+            .spawn(Tint {
+                color: Color::WHITE.with_alpha(0.0),
+            })
+            .id();
+
+        let alphas = play(
+            &mut world,
+            |b| {
+                crossfade(
+                    b,
+                    outgoing,
+                    incoming,
+                    path!(<Tint>::color),
+                    s(1),
+                )
+            },
+            |world| {
+                [outgoing, incoming].map(|entity| {
+                    world.get::<Tint>(entity).unwrap().color.alpha()
+                })
+            },
+        );
+
+        assert_eq!(alphas, [[1.0, 0.0], [0.5, 0.5], [0.0, 1.0]]);
+    }
+
+    #[cfg(feature = "ui")]
+    #[test]
+    fn wipes_grow_and_shrink_the_mask_from_its_edge() {
+        use bevy_ui::{Node, Val};
+
+        let mut world = World::new();
+        let mask = world.spawn(Node::default()).id();
+
+        let node =
+            |world: &World| world.get::<Node>(mask).unwrap().clone();
+        let nodes = play(
+            &mut world,
+            |b| wipe_in(b, mask, Edge::Right, s(1)),
+            node,
+        );
+
+        let widths =
+            nodes.iter().map(|n| n.width).collect::<Vec<_>>();
+        assert_eq!(
+            widths,
+            [
+                Val::Percent(0.0),
+                Val::Percent(50.0),
+                Val::Percent(100.0)
+            ]
+        );
+        for node in &nodes {
+            assert_eq!(
+                (node.left, node.right, node.top, node.bottom),
+                (Val::Auto, Val::Px(0.0), Val::Px(0.0), Val::Px(0.0))
+            );
+            assert_eq!(node.height, Val::Percent(100.0));
+        }
+
+        let heights = play(
+            &mut world,
+            |b| wipe_out(b, mask, Edge::Top, s(1)),
+            |world| world.get::<Node>(mask).unwrap().height,
+        );
+        assert_eq!(
+            heights,
+            [
+                Val::Percent(100.0),
+                Val::Percent(50.0),
+                Val::Percent(0.0)
+            ]
+        );
+    }
+}
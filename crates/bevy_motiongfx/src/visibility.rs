@@ -0,0 +1,59 @@
+//! Visibility toggles, enabled with the `visibility` feature.
+//!
+//! [`Visibility`] is an enum with nothing to interpolate, so
+//! [`VisibilityExt`] flips it at a single point in time instead:
+//!
+//! ```ignore
+//! let track = [
+//!     builder.act_show(arrow),
+//!     builder.act(arrow, path!(<Transform>::scale), |_| Vec3::ONE)
+//!         .play(ms(300)),
+//!     track::delay(s(2), builder.act_hide(arrow)),
+//! ]
+//! .ord_chain();
+//! ```
+//!
+//! Scrubbing back before a toggle restores the visibility the entity
+//! had before it.
+
+use core::time::Duration;
+
+use bevy_camera::visibility::Visibility;
+use bevy_ecs::entity::Entity;
+use motiongfx::prelude::*;
+
+use crate::world::BevyTimelineBuilder;
+
+/// Instant [`Visibility`] changes.
+pub trait VisibilityExt {
+    /// Sets the [`Visibility`] of `target` to `visibility`, taking no
+    /// time on the track.
+    fn act_visibility(
+        &mut self,
+        target: Entity,
+        visibility: Visibility,
+    ) -> TrackFragment;
+
+    /// Makes `target` [`Visibility::Visible`].
+    fn act_show(&mut self, target: Entity) -> TrackFragment {
+        self.act_visibility(target, Visibility::Visible)
+    }
+
+    /// Makes `target` [`Visibility::Hidden`].
+    fn act_hide(&mut self, target: Entity) -> TrackFragment {
+        self.act_visibility(target, Visibility::Hidden)
+    }
+}
+
+impl VisibilityExt for BevyTimelineBuilder<'_> {
+    fn act_visibility(
+        &mut self,
+        target: Entity,
+        visibility: Visibility,
+    ) -> TrackFragment {
+        self.act_step(target, path!(<Visibility>), move |_: &_| {
+            visibility
+        })
+        .play(Duration::ZERO)
+    }
+}
@@ -1,5 +1,13 @@
+use core::cmp::Ordering;
+#[cfg(feature = "asset")]
+use core::marker::PhantomData;
+
+use alloc::vec;
+use alloc::vec::Vec;
 use bevy_ecs::component::Mutable;
+use bevy_ecs::intern::{Interned, Interner};
 use bevy_ecs::prelude::*;
+use bevy_platform::collections::HashMap;
 use motiongfx::prelude::*;
 
 /// Newtype wrapper around [`World`] that is local to this crate,
@@ -17,6 +25,32 @@ impl BevyWorld {
         // SAFETY: `BevyWorld` is repr(transparent) over `World`.
         unsafe { &mut *(world as *mut World as *mut Self) }
     }
+
+    /// The first entity named `name` in the hierarchy under `root`,
+    /// `root` included, searched depth first.
+    pub fn find_named(
+        &self,
+        root: Entity,
+        name: &str,
+    ) -> Option<Entity> {
+        let mut stack = vec![root];
+
+        while let Some(entity) = stack.pop() {
+            if self
+                .0
+                .get::<Name>(entity)
+                .is_some_and(|n| n.as_str() == name)
+            {
+                return Some(entity);
+            }
+
+            if let Some(children) = self.0.get::<Children>(entity) {
+                stack.extend(children.iter().rev());
+            }
+        }
+
+        None
+    }
 }
 
 impl<S: Component<Mutability = Mutable>> SubjectSource<Entity, S>
@@ -35,6 +69,199 @@ impl<S: Component<Mutability = Mutable>> SubjectSource<Entity, S>
     }
 }
 
+/// Subject id of a [`Resource`].
+///
+/// Resources are unique by type, so a single unit id addresses all of
+/// them, the field path decides which resource is animated.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct ResourceId;
+
+impl<S: Resource> SubjectSource<ResourceId, S> for BevyWorld {
+    fn get_source(&self, _id: ResourceId) -> Option<&S> {
+        self.0.get_resource::<S>()
+    }
+
+    fn apply_source<R>(
+        &mut self,
+        _id: ResourceId,
+        f: impl FnOnce(&mut S) -> R,
+    ) -> Option<R> {
+        self.0.get_resource_mut::<S>().map(|mut r| f(r.as_mut()))
+    }
+}
+
+/// Subject id of the entity [`Name`]d [`Self::name`] in the hierarchy
+/// under [`Self::root`], e.g. a joint of a skinned mesh under its
+/// armature.
+///
+/// The entity is looked up by name when baked, and once sampled it
+/// is cached in the [`BoneCache`] until a hierarchy or a name
+/// changes, so bones can be targeted before the scene holding them
+/// is spawned.
+///
+/// Names are interned, so that the id stays [`Copy`] while taking
+/// names only known at runtime, e.g. from a loaded scene.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoneOf {
+    pub root: Entity,
+    pub name: Interned<str>,
+}
+
+static BONE_NAMES: Interner<str> = Interner::new();
+
+impl BoneOf {
+    pub fn new(root: Entity, name: &str) -> Self {
+        Self {
+            root,
+            name: BONE_NAMES.intern(name),
+        }
+    }
+}
+
+impl PartialOrd for BoneOf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for BoneOf {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.root, self.name.0).cmp(&(other.root, other.name.0))
+    }
+}
+
+/// The entities of the [`BoneOf`] subjects sampled so far.
+///
+/// Cleared whenever a [`ChildOf`] or a [`Name`] is inserted or
+/// replaced, as the bone under a root may have changed.
+#[derive(Resource, Debug, Default)]
+pub struct BoneCache(HashMap<BoneOf, Entity>);
+
+/// Clears the [`BoneCache`] on hierarchy and name changes.
+pub(crate) fn clear_bone_cache<E: Event>(
+    _: On<E, (ChildOf, Name)>,
+    mut cache: ResMut<BoneCache>,
+) {
+    if !cache.0.is_empty() {
+        cache.0.clear();
+    }
+}
+
+impl BevyWorld {
+    /// The cached entity of `bone`, if it is still named alike.
+    fn cached_bone(&self, bone: BoneOf) -> Option<Entity> {
+        let entity =
+            *self.0.get_resource::<BoneCache>()?.0.get(&bone)?;
+        self.0
+            .get::<Name>(entity)
+            .is_some_and(|n| n.as_str() == bone.name.0)
+            .then_some(entity)
+    }
+
+    /// Similar to [`Self::cached_bone`], but finds and caches the
+    /// entity on a miss.
+    fn resolve_bone(&mut self, bone: BoneOf) -> Option<Entity> {
+        if let Some(entity) = self.cached_bone(bone) {
+            return Some(entity);
+        }
+
+        let entity = self.find_named(bone.root, bone.name.0)?;
+        if let Some(mut cache) =
+            self.0.get_resource_mut::<BoneCache>()
+        {
+            cache.0.insert(bone, entity);
+        }
+        Some(entity)
+    }
+}
+
+impl<S: Component<Mutability = Mutable>> SubjectSource<BoneOf, S>
+    for BevyWorld
+{
+    fn get_source(&self, id: BoneOf) -> Option<&S> {
+        let entity = self
+            .cached_bone(id)
+            .or_else(|| self.find_named(id.root, id.name.0))?;
+        self.0.get::<S>(entity)
+    }
+
+    fn apply_source<R>(
+        &mut self,
+        id: BoneOf,
+        f: impl FnOnce(&mut S) -> R,
+    ) -> Option<R> {
+        let entity = self.resolve_bone(id)?;
+        self.0.get_mut::<S>(entity).map(|mut m| f(m.as_mut()))
+    }
+}
+
+/// The members of a group of entities animated together, e.g. a
+/// crowd of sprites. Addressed by [`CrowdOf`].
+#[derive(Component, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Crowd(pub Vec<Entity>);
+
+/// Subject id of every member of the [`Crowd`] on this entity.
+///
+/// Actions added with [`TimelineBuilder::act_group`] interpolate
+/// once per sample and write the value into every member. Other
+/// actions, and baking, only see the first member.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct CrowdOf(pub Entity);
+
+impl<S: Component<Mutability = Mutable>> SubjectSource<CrowdOf, S>
+    for BevyWorld
+{
+    fn get_source(&self, id: CrowdOf) -> Option<&S> {
+        let first = *self.0.get::<Crowd>(id.0)?.0.first()?;
+        self.0.get::<S>(first)
+    }
+
+    fn apply_source<R>(
+        &mut self,
+        id: CrowdOf,
+        f: impl FnOnce(&mut S) -> R,
+    ) -> Option<R> {
+        let first = *self.0.get::<Crowd>(id.0)?.0.first()?;
+        self.0.get_mut::<S>(first).map(|mut m| f(m.as_mut()))
+    }
+}
+
+impl<S: Component<Mutability = Mutable>> GroupSource<CrowdOf, S>
+    for BevyWorld
+{
+    fn apply_group(
+        &mut self,
+        id: CrowdOf,
+        mut f: impl FnMut(&mut S),
+    ) {
+        // Taken out for the duration of the loop, so the members can
+        // be borrowed mutably without collecting them.
+        let Some(members) =
+            self.0.get_mut::<Crowd>(id.0).map(|mut crowd| {
+                core::mem::take(
+                    &mut crowd.bypass_change_detection().0,
+                )
+            })
+        else {
+            return;
+        };
+
+        for &member in members.iter() {
+            if let Some(mut source) = self.0.get_mut::<S>(member) {
+                f(source.as_mut());
+            }
+        }
+
+        if let Some(mut crowd) = self.0.get_mut::<Crowd>(id.0) {
+            crowd.bypass_change_detection().0 = members;
+        }
+    }
+}
+
 #[cfg(feature = "asset")]
 impl<S: bevy_asset::Asset>
     SubjectSource<bevy_asset::UntypedAssetId, S> for BevyWorld
@@ -61,5 +288,202 @@ impl<S: bevy_asset::Asset>
     }
 }
 
+/// Subject id of the asset referenced by the `C` handle component of
+/// an entity, e.g. `MeshMaterial3d<M>` for a custom material `M`.
+///
+/// Unlike [`UntypedAssetId`](bevy_asset::UntypedAssetId) subjects,
+/// the handle is looked up on every bake and sample, so swapping the
+/// handle on the entity retargets the animation.
+#[cfg(feature = "asset")]
+pub struct AssetOf<C> {
+    pub entity: Entity,
+    _marker: PhantomData<fn() -> C>,
+}
+
+#[cfg(feature = "asset")]
+impl<C> AssetOf<C> {
+    pub const fn new(entity: Entity) -> Self {
+        Self {
+            entity,
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "asset")]
+impl<C> Clone for AssetOf<C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(feature = "asset")]
+impl<C> Copy for AssetOf<C> {}
+
+#[cfg(feature = "asset")]
+impl<C> core::fmt::Debug for AssetOf<C> {
+    fn fmt(
+        &self,
+        f: &mut core::fmt::Formatter<'_>,
+    ) -> core::fmt::Result {
+        f.debug_tuple("AssetOf").field(&self.entity).finish()
+    }
+}
+
+#[cfg(feature = "asset")]
+impl<C> PartialEq for AssetOf<C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.entity == other.entity
+    }
+}
+
+#[cfg(feature = "asset")]
+impl<C> Eq for AssetOf<C> {}
+
+#[cfg(feature = "asset")]
+impl<C> PartialOrd for AssetOf<C> {
+    fn partial_cmp(
+        &self,
+        other: &Self,
+    ) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "asset")]
+impl<C> Ord for AssetOf<C> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.entity.cmp(&other.entity)
+    }
+}
+
+#[cfg(feature = "asset")]
+impl<C> core::hash::Hash for AssetOf<C> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.entity.hash(state);
+    }
+}
+
+#[cfg(feature = "asset")]
+impl<C> SubjectSource<AssetOf<C>, C::Asset> for BevyWorld
+where
+    C: bevy_asset::AsAssetId,
+{
+    fn get_source(&self, id: AssetOf<C>) -> Option<&C::Asset> {
+        let asset_id = self.0.get::<C>(id.entity)?.as_asset_id();
+
+        self.0
+            .get_resource::<bevy_asset::Assets<C::Asset>>()?
+            .get(asset_id)
+    }
+
+    fn apply_source<R>(
+        &mut self,
+        id: AssetOf<C>,
+        f: impl FnOnce(&mut C::Asset) -> R,
+    ) -> Option<R> {
+        let asset_id = self.0.get::<C>(id.entity)?.as_asset_id();
+
+        self.0
+            .get_resource_mut::<bevy_asset::Assets<C::Asset>>()?
+            .into_inner()
+            .get_mut(asset_id)
+            .map(|asset| f(asset.into_inner()))
+    }
+}
+
 pub type BevyTimeline = Timeline<BevyWorld>;
 pub type BevyTimelineBuilder<'a> = TimelineBuilder<'a, BevyWorld>;
+/// A timeline authored against named placeholder entities, bound
+/// to actual entities by [`MotionGfxManager::instantiate`].
+///
+/// [`MotionGfxManager::instantiate`]:
+///     crate::manager::MotionGfxManager::instantiate
+pub type TimelineTemplate = TimelineGraph<BevyWorld, Entity>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Component, Debug, PartialEq)]
+    struct Angle(f32);
+
+    #[test]
+    fn bones_resolve_by_name_under_the_root() {
+        let mut world = World::new();
+        let root = world.spawn(Name::new("Armature")).id();
+        let spine = world
+            .spawn((Name::new("Spine"), Angle(0.0), ChildOf(root)))
+            .id();
+        world.spawn((Name::new("Head"), Angle(1.0), ChildOf(spine)));
+        // Outside of the armature.
+        world.spawn((Name::new("Head"), Angle(2.0)));
+
+        let world = BevyWorld::from_mut(&mut world);
+        let head = BoneOf::new(root, "Head");
+        assert_eq!(world.get_source(head), Some(&Angle(1.0)));
+
+        world.apply_source(head, |angle: &mut Angle| angle.0 = 3.0);
+        assert_eq!(world.get_source(head), Some(&Angle(3.0)));
+        let tail: Option<&Angle> =
+            world.get_source(BoneOf::new(root, "Tail"));
+        assert_eq!(tail, None);
+    }
+
+    #[test]
+    fn bones_are_cached_until_the_hierarchy_changes() {
+        let mut world = World::new();
+        world.init_resource::<BoneCache>();
+        world.add_observer(clear_bone_cache::<Insert>);
+        world.add_observer(clear_bone_cache::<Replace>);
+        let root = world.spawn(Name::new("Armature")).id();
+        let head = world
+            .spawn((Name::new("Head"), Angle(1.0), ChildOf(root)))
+            .id();
+
+        // Runtime names intern to the same id.
+        let bone = BoneOf::new(root, &alloc::format!("He{}", "ad"));
+        assert_eq!(bone, BoneOf::new(root, "Head"));
+
+        BevyWorld::from_mut(&mut world)
+            .apply_source(bone, |angle: &mut Angle| angle.0 = 2.0);
+        assert_eq!(world.resource::<BoneCache>().0[&bone], head);
+
+        // Renaming through `Mut<Name>` is caught on the next lookup.
+        world.get_mut::<Name>(head).unwrap().set("Neck");
+        let world_ref = BevyWorld::from_ref(&world);
+        let angle: Option<&Angle> = world_ref.get_source(bone);
+        assert_eq!(angle, None);
+
+        world.entity_mut(head).insert(Name::new("Head"));
+        BevyWorld::from_mut(&mut world)
+            .apply_source(bone, |angle: &mut Angle| angle.0 = 3.0);
+        // Moving the bone out of the armature clears the cache.
+        world.entity_mut(head).remove::<ChildOf>();
+        assert!(world.resource::<BoneCache>().0.is_empty());
+        let angle: Option<&Angle> =
+            BevyWorld::from_ref(&world).get_source(bone);
+        assert_eq!(angle, None);
+    }
+
+    #[test]
+    fn crowds_write_every_member() {
+        let mut world = World::new();
+        let members = (0..3)
+            .map(|i| world.spawn(Angle(i as f32)).id())
+            .collect::<Vec<_>>();
+        let crowd = CrowdOf(world.spawn(Crowd(members.clone())).id());
+
+        let world = BevyWorld::from_mut(&mut world);
+        assert_eq!(world.get_source(crowd), Some(&Angle(0.0)));
+
+        world.apply_group(crowd, |angle: &mut Angle| angle.0 += 1.0);
+        for (i, member) in members.into_iter().enumerate() {
+            assert_eq!(
+                world.0.get(member),
+                Some(&Angle(i as f32 + 1.0))
+            );
+        }
+        assert_eq!(world.0.get::<Crowd>(crowd.0).unwrap().0.len(), 3);
+    }
+}
@@ -1,3 +1,5 @@
+//! Drives a timeline on a hand-written world without any engine.
+
 use std::collections::HashMap;
 use std::time::Duration;
 
@@ -1,16 +1,22 @@
 use core::any::TypeId;
+use core::ops::Add;
 use core::time::Duration;
 
 use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use bevy_math::ops;
 use field_path::field::UntypedField;
 
 use crate::ThreadSafe;
+use crate::ease::EaseLut;
+use crate::field::FieldTarget;
 use crate::subject::SubjectId;
 
 mod id_registry;
 mod table;
 
-pub use id_registry::{IdRegistry, UId};
+pub use id_registry::{IdRegistry, SubjectBindings, UId};
 pub use table::{
     ActionBuilder, ActionId, ActionMarker, ActionTable,
     InterpActionBuilder,
@@ -68,6 +74,8 @@ pub struct ActionKey {
     subject_id: UntypedSubjectId,
     /// The source and target field related to the subject.
     field: UntypedField,
+    /// The part of the field being animated.
+    target: FieldTarget,
 }
 
 impl ActionKey {
@@ -75,7 +83,23 @@ impl ActionKey {
         subject_id: UntypedSubjectId,
         field: UntypedField,
     ) -> Self {
-        Self { subject_id, field }
+        Self {
+            subject_id,
+            field,
+            target: FieldTarget::Whole,
+        }
+    }
+
+    pub fn with_target(
+        subject_id: UntypedSubjectId,
+        field: UntypedField,
+        target: FieldTarget,
+    ) -> Self {
+        Self {
+            subject_id,
+            field,
+            target,
+        }
     }
 
     pub fn subject_id(&self) -> &UntypedSubjectId {
@@ -85,6 +109,15 @@ impl ActionKey {
     pub fn field(&self) -> &UntypedField {
         &self.field
     }
+
+    pub fn target(&self) -> FieldTarget {
+        self.target
+    }
+
+    /// Shorthand for [`FieldTarget::index`].
+    pub fn index(&self) -> Option<usize> {
+        self.target.index()
+    }
 }
 
 /// An action trait which consists of a function for getting
@@ -106,6 +139,67 @@ impl<T> ActionStorage<T> {
     }
 }
 
+/// A function computing the value of an action straight from the
+/// eased progress of its clip, e.g. a sine wobble or noise.
+pub trait Procedural<T>: ThreadSafe + Fn(f32) -> T {}
+
+impl<T, U> Procedural<T> for U where U: ThreadSafe + Fn(f32) -> T {}
+
+/// A storage value for a [`Procedural`] action, sampled in place of
+/// the baked [`Segment`].
+///
+/// Inserted by [`TimelineBuilder::act_fn`].
+///
+/// [`TimelineBuilder::act_fn`]: crate::timeline::TimelineBuilder::act_fn
+#[derive(Clone)]
+pub struct ProceduralStorage<T>(pub Arc<dyn Procedural<T>>);
+
+/// Whether an action ends at a fixed value or offsets whatever it
+/// starts from, which a plain [`Action`] closure can't tell.
+///
+/// Recorded by [`TimelineBuilder::act_to`] and
+/// [`TimelineBuilder::act_by`].
+///
+/// [`TimelineBuilder::act_to`]: crate::timeline::TimelineBuilder::act_to
+/// [`TimelineBuilder::act_by`]: crate::timeline::TimelineBuilder::act_by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionKind {
+    /// Ends at the same value, whatever it starts from.
+    Absolute,
+    /// Adds the same delta to whatever it starts from.
+    Relative,
+}
+
+/// A storage value for the target value or delta of an action with
+/// an [`ActionKind`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActionValue<T> {
+    /// The value an [`ActionKind::Absolute`] action ends at.
+    To(T),
+    /// The delta of an [`ActionKind::Relative`] action.
+    By(T),
+}
+
+impl<T> ActionValue<T> {
+    pub fn kind(&self) -> ActionKind {
+        match self {
+            Self::To(_) => ActionKind::Absolute,
+            Self::By(_) => ActionKind::Relative,
+        }
+    }
+
+    /// Where the action ends when it starts from `start`.
+    pub fn apply(&self, start: &T) -> T
+    where
+        T: Add<Output = T> + Clone,
+    {
+        match self {
+            Self::To(value) => value.clone(),
+            Self::By(delta) => start.clone() + delta.clone(),
+        }
+    }
+}
+
 /// Function for interpolating a type based on a [`f32`] time.
 pub type InterpFn<T> = fn(start: &T, end: &T, t: f32) -> T;
 
@@ -116,6 +210,38 @@ pub type InterpFn<T> = fn(start: &T, end: &T, t: f32) -> T;
 #[derive(Debug, Clone, Copy)]
 pub struct InterpStorage<T>(pub InterpFn<T>);
 
+/// Marks an action whose [`InterpStorage`] is a linear
+/// [`Interpolation`], so it can be sampled in bulk. See
+/// [`crate::lanes`].
+///
+/// [`Interpolation`]: crate::interpolation::Interpolation
+#[derive(Debug, Clone, Copy)]
+pub struct LinearInterp;
+
+/// A post-processing step applied to the interpolated value of an
+/// action, given the progress of its clip in \[0.0..=1.0\].
+///
+/// See [`crate::modifier`] for the stock ones.
+pub trait Modifier<T>: ThreadSafe + Fn(T, f32) -> T {}
+
+impl<T, U> Modifier<T> for U where U: ThreadSafe + Fn(T, f32) -> T {}
+
+/// A storage value for the [`Modifier`]s of an action, applied in
+/// order after interpolation.
+///
+/// Inserted by [`InterpActionBuilder::with_modifier`].
+pub struct ModifierStorage<T>(pub Vec<Box<dyn Modifier<T>>>);
+
+impl<T> ModifierStorage<T> {
+    /// Runs `value` through every modifier in order.
+    #[inline]
+    pub fn apply(&self, value: T, t: f32) -> T {
+        self.0
+            .iter()
+            .fold(value, |value, modifier| modifier(value, t))
+    }
+}
+
 /// Easing function on a [`f32`] time.
 pub type EaseFn = fn(t: f32) -> f32;
 
@@ -123,14 +249,56 @@ pub type EaseFn = fn(t: f32) -> f32;
 ///
 /// This can be optionally inserted alongside [`ActionStorage`]
 /// to customize the action.
+#[derive(Debug, Clone)]
+pub enum EaseStorage {
+    /// Evaluates the [`EaseFn`] on every sample.
+    Fn(EaseFn),
+    /// Samples a pre-baked [`EaseLut`], shared between actions.
+    Lut(Arc<EaseLut>),
+}
+
+impl EaseStorage {
+    #[inline]
+    pub fn ease(&self, t: f32) -> f32 {
+        match self {
+            Self::Fn(ease) => ease(t),
+            Self::Lut(lut) => lut.sample(t),
+        }
+    }
+}
+
+/// A storage value quantizing the sampled progress of an action into
+/// a number of evenly spaced steps.
+///
+/// Inserted by [`InterpActionBuilder::with_sample_rate`].
 #[derive(Debug, Clone, Copy)]
-pub struct EaseStorage(pub EaseFn);
+pub struct StepStorage(pub f32);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+impl StepStorage {
+    /// Snaps `t` down to the start of its step.
+    #[inline]
+    pub fn quantize(&self, t: f32) -> f32 {
+        ops::floor(t * self.0) / self.0
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct ActionClip {
     pub id: ActionId,
     pub start: Duration,
     pub duration: Duration,
+    /// Plays the baked segment of the action from end to start, see
+    /// [`TrackFragment::reversed`].
+    ///
+    /// [`TrackFragment::reversed`]: crate::track::TrackFragment::reversed
+    pub reversed: bool,
+    /// Progress through the baked segment at the start of the clip,
+    /// only above `0.0` once trimmed, see [`TrackFragment::split`].
+    ///
+    /// [`TrackFragment::split`]: crate::track::TrackFragment::split
+    pub from: f32,
+    /// Progress through the baked segment at the end of the clip.
+    pub to: f32,
 }
 
 impl ActionClip {
@@ -139,9 +307,67 @@ impl ActionClip {
             id,
             start: Duration::ZERO,
             duration,
+            reversed: false,
+            from: 0.0,
+            to: 1.0,
         }
     }
 
+    /// Does the clip only cover part of the baked segment?
+    #[inline]
+    pub fn is_trimmed(&self) -> bool {
+        self.from != 0.0 || self.to != 1.0
+    }
+
+    /// Maps `mode` through [`Self::from`], [`Self::to`] and
+    /// [`Self::reversed`], so it samples the part of the baked
+    /// segment the clip covers in the direction it plays.
+    #[inline]
+    pub fn orient(&self, mode: SampleMode) -> SampleMode {
+        if !self.reversed && !self.is_trimmed() {
+            return mode;
+        }
+
+        let t = match mode {
+            SampleMode::Start => self.from,
+            SampleMode::End => self.to,
+            SampleMode::Interp(t) => {
+                self.from + (self.to - self.from) * t
+            }
+        };
+        let t = if self.reversed { 1.0 - t } else { t };
+
+        if t <= 0.0 {
+            SampleMode::Start
+        } else if t >= 1.0 {
+            SampleMode::End
+        } else {
+            SampleMode::Interp(t)
+        }
+    }
+
+    /// Cuts the clip at `time`, which should lie within it. Each
+    /// half keeps its share of the baked segment.
+    pub fn split_at(&self, time: Duration) -> (Self, Self) {
+        let mid =
+            self.from + (self.to - self.from) * self.progress(time);
+        let time = time.clamp(self.start, self.end());
+
+        (
+            Self {
+                duration: time - self.start,
+                to: mid,
+                ..*self
+            },
+            Self {
+                start: time,
+                duration: self.end() - time,
+                from: mid,
+                ..*self
+            },
+        )
+    }
+
     /// Saturating, so an absurd authored duration degrades to a clamped
     /// timeline rather than a panic deep in playback.
     #[inline]
@@ -179,6 +405,12 @@ impl<T> Segment<T> {
     }
 }
 
+/// The value of a field read from the world when the sequence
+/// starting with an action was baked.
+///
+/// See [`Timeline::reset_subjects`](crate::timeline::Timeline::reset_subjects).
+pub struct Snapshot<T>(pub T);
+
 /// Determines how a [`Segment`] should be sampled.
 #[derive(Debug, Clone, Copy)]
 pub enum SampleMode {
@@ -198,6 +430,9 @@ mod tests {
             id: ActionId::PLACEHOLDER,
             start,
             duration,
+            reversed: false,
+            from: 0.0,
+            to: 1.0,
         }
     }
 
@@ -218,6 +453,9 @@ mod tests {
             id: ActionId::PLACEHOLDER,
             start: Duration::MAX,
             duration: Duration::MAX,
+            reversed: false,
+            from: 0.0,
+            to: 1.0,
         };
 
         assert_eq!(clip.end(), Duration::MAX);
@@ -1,7 +1,9 @@
 use core::any::TypeId;
 
+use alloc::vec::Vec;
 use hashbrown::HashMap;
 
+use crate::action::ActionTable;
 use crate::resources::Resources;
 use crate::subject::SubjectId;
 
@@ -35,6 +37,13 @@ impl<I: SubjectId> IdRegistry<I> {
         }
     }
 
+    /// Reserves room for `additional` more [`SubjectId`]s.
+    pub fn reserve(&mut self, additional: usize) {
+        self.uid_map.reserve(additional);
+        self.id_map.reserve(additional);
+        self.instance_counts.reserve(additional);
+    }
+
     /// Registers the [`SubjectId`] with an intial instance count of 1
     /// if it doesn't exist yet, otherwise, increase the existing
     /// instance count.
@@ -81,6 +90,39 @@ impl<I: SubjectId> IdRegistry<I> {
         true
     }
 
+    /// Moves the [`UId`] of every `old` [`SubjectId`] over to its
+    /// `new` one, all at once so subjects can also swap [`UId`]s.
+    ///
+    /// Pairs whose `old` id is not registered, or whose `new` id
+    /// stays registered to another subject, are left as they are.
+    ///
+    /// Returns the moved [`UId`]s.
+    pub fn retarget(
+        &mut self,
+        pairs: impl IntoIterator<Item = (I, I)>,
+    ) -> Vec<UId> {
+        let moved = pairs
+            .into_iter()
+            .filter_map(|(old, new)| {
+                Some((old, new, self.uid_map.remove(&old)?))
+            })
+            .collect::<Vec<_>>();
+
+        let mut uids = Vec::with_capacity(moved.len());
+        for (old, new, uid) in moved {
+            if self.uid_map.contains_key(&new) {
+                self.uid_map.insert(old, uid);
+                continue;
+            }
+
+            self.uid_map.insert(new, uid);
+            self.id_map.insert(uid, new);
+            uids.push(uid);
+        }
+
+        uids
+    }
+
     /// Checks if the registry is empty.
     pub fn is_empty(&self) -> bool {
         self.uid_map.is_empty()
@@ -101,6 +143,41 @@ impl<I: SubjectId> Default for IdRegistry<I> {
     }
 }
 
+/// Subjects standing in for those of an [`ActionTable`], by [`UId`].
+///
+/// Lets timeline instances sharing one table each play on their own
+/// subjects, see [`Timeline::bind_subject`].
+///
+/// [`Timeline::bind_subject`]: crate::timeline::Timeline::bind_subject
+#[derive(Default)]
+pub struct SubjectBindings {
+    /// A `HashMap<UId, I>` per [`SubjectId`] type `I`.
+    resources: Resources,
+}
+
+impl SubjectBindings {
+    pub(crate) fn insert<I: SubjectId>(&mut self, uid: UId, id: I) {
+        self.resources
+            .get_or_insert_with(HashMap::<UId, I>::new)
+            .insert(uid, id);
+    }
+
+    /// Returns the subject bound to `uid`, if any.
+    pub fn get<I: SubjectId>(&self, uid: &UId) -> Option<&I> {
+        self.resources.get::<HashMap<UId, I>>()?.get(uid)
+    }
+
+    /// Returns the subject bound to `uid`, falling back to the one
+    /// registered in `action_table`.
+    pub fn resolve<'a, I: SubjectId>(
+        &'a self,
+        action_table: &'a ActionTable,
+        uid: &UId,
+    ) -> Option<&'a I> {
+        self.get(uid).or_else(|| action_table.get_id(uid))
+    }
+}
+
 /// A type-erased cleanup function, run when an action is removed to
 /// decrement the spawning [`SubjectId`]'s [`IdRegistry`] entry.
 ///
@@ -208,6 +285,25 @@ mod tests {
         assert!(!registry.remove_instance(&uid));
     }
 
+    #[test]
+    fn retarget_moves_and_swaps_uids() {
+        let mut registry = IdRegistry::new();
+        let uid1 = registry.register_instance(1u32);
+        let uid2 = registry.register_instance(2u32);
+        let uid3 = registry.register_instance(3u32);
+
+        let moved = registry.retarget([(1, 2), (2, 1), (3, 4)]);
+        assert_eq!(moved, [uid1, uid2, uid3]);
+        assert_eq!(registry.get_id(&uid1), Some(&2));
+        assert_eq!(registry.get_uid(&1), Some(&uid2));
+        assert_eq!(registry.get_uid(&3), None);
+        assert_eq!(registry.get_id(&uid3), Some(&4));
+
+        // 1 is taken and 5 was never registered.
+        assert!(registry.retarget([(4, 1), (5, 6)]).is_empty());
+        assert_eq!(registry.get_uid(&4), Some(&uid3));
+    }
+
     #[test]
     fn registrations_of_different_subjects_are_independent() {
         let mut registry = IdRegistry::new();
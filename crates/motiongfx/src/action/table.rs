@@ -2,7 +2,11 @@ use core::any::TypeId;
 use core::marker::PhantomData;
 use core::time::Duration;
 
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
 use field_path::field::UntypedField;
+use hashbrown::HashMap;
 use typarena::ColumnId;
 use typarena::id::{GenId, IdGenerator};
 use typarena::type_table::TypeTable;
@@ -11,14 +15,26 @@ use super::id_registry::{
     CleanupRegistry, IdRegistry, UId, cleanup_fn,
 };
 use super::{
-    Action, ActionClip, ActionKey, ActionStorage, EaseFn,
-    EaseStorage, InterpFn, InterpStorage, Segment, UntypedSubjectId,
+    Action, ActionClip, ActionKey, ActionKind, ActionStorage,
+    ActionValue, EaseFn, EaseStorage, InterpFn, InterpStorage,
+    LinearInterp, Modifier, ModifierStorage, Procedural,
+    ProceduralStorage, Segment, Snapshot, StepStorage,
+    UntypedSubjectId,
 };
 use crate::ThreadSafe;
+use crate::ease::EaseLut;
+use crate::field::{FieldTarget, NonePolicy};
+use crate::interpolation::Interpolation;
+use crate::lanes::{LaneSegments, Lanes, remove_lanes};
 use crate::resources::Resources;
 use crate::subject::SubjectId;
 use crate::track::TrackFragment;
 
+/// [`EaseLut`]s baked by [`InterpActionBuilder::with_ease_lut`], keyed
+/// by the address of their [`EaseFn`].
+#[derive(Default)]
+struct EaseLutCache(HashMap<usize, Arc<EaseLut>>);
+
 /// Phantom marker distinguishing [`ActionId`]'s [`GenId`] domain from
 /// any other id domain.
 pub struct ActionMarker;
@@ -62,8 +78,48 @@ impl ActionTable {
         I: SubjectId,
         T: ThreadSafe,
     {
-        let field = field.into();
+        self.add_at(target, field.into(), FieldTarget::Whole, action)
+    }
+
+    /// Similar to [`Self::add`], but targets the element at `index`
+    /// of a collection field.
+    pub fn add_indexed<I, T>(
+        &mut self,
+        target: I,
+        field: impl Into<UntypedField>,
+        index: usize,
+        action: impl Action<T>,
+    ) -> ActionBuilder<'_, T>
+    where
+        I: SubjectId,
+        T: ThreadSafe,
+    {
+        self.add_at(
+            target,
+            field.into(),
+            FieldTarget::Element(index),
+            action,
+        )
+    }
+
+    /// Reserves room for `additional` more actions on `I` subjects.
+    pub fn reserve<I: SubjectId>(&mut self, additional: usize) {
+        self.resources
+            .get_or_insert_with(IdRegistry::<I>::new)
+            .reserve(additional);
+    }
 
+    pub(crate) fn add_at<I, T>(
+        &mut self,
+        target: I,
+        field: UntypedField,
+        field_target: FieldTarget,
+        action: impl Action<T>,
+    ) -> ActionBuilder<'_, T>
+    where
+        I: SubjectId,
+        T: ThreadSafe,
+    {
         let uid = self
             .resources
             .get_or_insert_with(IdRegistry::new)
@@ -72,14 +128,16 @@ impl ActionTable {
             .get_or_insert_with(CleanupRegistry::new)
             .insert(TypeId::of::<I>(), cleanup_fn::<I>);
 
+        let subject_id = UntypedSubjectId::new::<I>(uid);
         let key =
-            ActionKey::new(UntypedSubjectId::new::<I>(uid), field);
+            ActionKey::with_target(subject_id, field, field_target);
         let id = self.id_gen.new_id();
         self.table.insert_by_column(id, key, self.key_col);
         self.table.insert(id, ActionStorage::new(action));
 
         ActionBuilder {
             table: &mut self.table,
+            resources: &mut self.resources,
             id,
             key,
             ease_col: self.ease_col,
@@ -100,6 +158,7 @@ impl ActionTable {
         if let Some(cleanup) = cleanup {
             cleanup(&mut self.resources, key.subject_id().uid());
         }
+        remove_lanes(self, &id);
         self.table.remove_row(&id);
         self.id_gen.recycle(id);
 
@@ -116,6 +175,90 @@ impl ActionTable {
     pub fn get_id<I: SubjectId>(&self, uid: &UId) -> Option<&I> {
         self.resources.get::<IdRegistry<I>>()?.get_id(uid)
     }
+
+    pub fn get_uid<I: SubjectId>(&self, id: &I) -> Option<&UId> {
+        self.resources.get::<IdRegistry<I>>()?.get_uid(id)
+    }
+
+    /// Moves the actions of every `old` subject over to its `new`
+    /// one. See [`IdRegistry::retarget`].
+    ///
+    /// Returns the moved [`UId`]s.
+    pub fn retarget<I: SubjectId>(
+        &mut self,
+        pairs: impl IntoIterator<Item = (I, I)>,
+    ) -> Vec<UId> {
+        match self.resources.get_mut::<IdRegistry<I>>() {
+            Some(registry) => registry.retarget(pairs),
+            None => Vec::new(),
+        }
+    }
+
+    /// The [`ActionKind`] of an action, `None` for plain [`Action`]
+    /// closures.
+    pub fn kind(&self, id: &ActionId) -> Option<ActionKind> {
+        self.table.get::<ActionKind>(id).copied()
+    }
+
+    /// What baking an action starts from when its field is `None`,
+    /// see [`NonePolicy`].
+    pub fn none_policy(&self, id: &ActionId) -> NonePolicy {
+        self.table
+            .get::<NonePolicy>(id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// The target value or delta of an action with an
+    /// [`ActionKind`].
+    pub fn value<T: ThreadSafe>(
+        &self,
+        id: &ActionId,
+    ) -> Option<&ActionValue<T>> {
+        self.table.get::<ActionValue<T>>(id)
+    }
+
+    /// Evaluates the baked [`Segment`] of an action at progress `t`,
+    /// applying its steps, ease and modifiers the same way sampling
+    /// does, without touching the world.
+    ///
+    /// Returns `None` if the action is not a baked `T` interpolation.
+    pub fn evaluate<T: Clone + ThreadSafe>(
+        &self,
+        id: &ActionId,
+        t: f32,
+    ) -> Option<T> {
+        let segment = self.table.get::<Segment<T>>(id)?;
+        let interp = self.table.get::<InterpStorage<T>>(id)?;
+        let procedural = self.table.get::<ProceduralStorage<T>>(id);
+
+        let t = if t <= 0.0 {
+            0.0
+        } else if t >= 1.0 {
+            1.0
+        } else {
+            match self.table.get::<StepStorage>(id) {
+                Some(steps) => steps.quantize(t),
+                None => t,
+            }
+        };
+        let eased = || match self.ease(id) {
+            Some(ease) => ease.ease(t),
+            None => t,
+        };
+
+        let value = match procedural {
+            Some(procedural) => (procedural.0)(eased()),
+            None if t <= 0.0 => segment.start.clone(),
+            None if t >= 1.0 => segment.end.clone(),
+            None => interp.0(&segment.start, &segment.end, eased()),
+        };
+
+        Some(match self.table.get::<ModifierStorage<T>>(id) {
+            Some(modifiers) => modifiers.apply(value, t),
+            None => value,
+        })
+    }
 }
 
 impl ActionTable {
@@ -169,6 +312,50 @@ impl ActionTable {
     ) {
         self.table.insert_by_column(id, segment, col);
     }
+
+    /// The [`LaneSegments`] mirroring the `T` segments, if any were
+    /// mirrored.
+    pub(crate) fn lane_segments<T: Lanes>(
+        &self,
+    ) -> Option<&LaneSegments<T>> {
+        self.resources.get::<LaneSegments<T>>()
+    }
+
+    /// Mirrors the baked `T` segments of `ids` into the
+    /// [`LaneSegments`] of `T`.
+    pub(crate) fn mirror_lane_segments<T: Lanes>(
+        &mut self,
+        ids: impl IntoIterator<Item = ActionId>,
+    ) {
+        let lanes =
+            self.resources.get_or_insert_with(LaneSegments::<T>::new);
+        for id in ids {
+            if let Some(segment) = self.table.get::<Segment<T>>(&id) {
+                lanes.insert(id, segment);
+            }
+        }
+    }
+
+    pub(crate) fn remove_lane_segment<T: Lanes>(
+        &mut self,
+        id: &ActionId,
+    ) {
+        if let Some(lanes) =
+            self.resources.get_mut::<LaneSegments<T>>()
+        {
+            lanes.remove(id);
+        }
+    }
+
+    /// Record the value a sequence was baked from on its first
+    /// action.
+    pub(crate) fn set_snapshot<T: ThreadSafe>(
+        &mut self,
+        id: ActionId,
+        value: T,
+    ) {
+        self.table.insert(id, Snapshot(value));
+    }
 }
 
 impl Default for ActionTable {
@@ -179,6 +366,7 @@ impl Default for ActionTable {
 
 pub struct ActionBuilder<'w, T> {
     table: &'w mut TypeTable<ActionId>,
+    resources: &'w mut Resources,
     id: ActionId,
     key: ActionKey,
     ease_col: ColumnId,
@@ -192,19 +380,65 @@ impl<T> ActionBuilder<'_, T> {
     pub fn id(&self) -> ActionId {
         self.id
     }
+
+    /// Records the [`NonePolicy`] of the action's field.
+    pub(crate) fn with_none_policy(self, policy: NonePolicy) -> Self {
+        if policy != NonePolicy::Default {
+            self.table.insert(self.id, policy);
+        }
+        self
+    }
+}
+
+impl<T> ActionBuilder<'_, T>
+where
+    T: ThreadSafe,
+{
+    /// Records the intent of the action, see [`ActionKind`].
+    pub(crate) fn with_value(self, value: ActionValue<T>) -> Self {
+        self.table.insert(self.id, value.kind());
+        self.table.insert(self.id, value);
+        self
+    }
+
+    /// Samples `procedural` instead of the baked segment, see
+    /// [`ProceduralStorage`].
+    pub(crate) fn with_procedural(
+        self,
+        procedural: Arc<dyn Procedural<T>>,
+    ) -> Self {
+        self.table.insert(self.id, ProceduralStorage(procedural));
+        self
+    }
 }
 
 impl<'w, T> ActionBuilder<'w, T>
 where
     T: 'static,
 {
+    /// Set the interpolation method of the action to
+    /// [`Interpolation::interp`].
+    pub fn with_interpolation<M>(self) -> InterpActionBuilder<'w, T>
+    where
+        T: Interpolation<M>,
+    {
+        if T::LINEAR {
+            self.table.insert(self.id, LinearInterp);
+        }
+        self.with_interp(T::interp)
+    }
+
     /// Set the interpolation method of the action.
     pub fn with_interp(
         self,
         interp: InterpFn<T>,
     ) -> InterpActionBuilder<'w, T> {
         self.table.insert(self.id, InterpStorage(interp));
-        InterpActionBuilder { inner: self }
+        InterpActionBuilder {
+            inner: self,
+            sample_rate: None,
+            modifiers: Vec::new(),
+        }
     }
 }
 
@@ -213,19 +447,82 @@ where
 /// compiled into a [`TrackFragment`].
 pub struct InterpActionBuilder<'w, T> {
     inner: ActionBuilder<'w, T>,
+    sample_rate: Option<f32>,
+    modifiers: Vec<Box<dyn Modifier<T>>>,
 }
 
-impl<T> InterpActionBuilder<'_, T> {
+impl<T> InterpActionBuilder<'_, T>
+where
+    T: 'static,
+{
     /// Set the easing method of the action.
     pub fn with_ease(self, ease: EaseFn) -> Self {
         self.inner.table.insert_by_column(
             self.inner.id,
-            EaseStorage(ease),
+            EaseStorage::Fn(ease),
             self.inner.ease_col,
         );
         self
     }
 
+    /// Similar to [`Self::with_ease`], but samples the ease from an
+    /// [`EaseLut`] baked once and shared by every action of the
+    /// timeline using the same `ease`.
+    ///
+    /// Worth it for expensive curves on timelines with a large
+    /// number of concurrently active actions.
+    pub fn with_ease_lut(self, ease: EaseFn) -> Self {
+        let lut = self
+            .inner
+            .resources
+            .get_or_insert_with(EaseLutCache::default)
+            .0
+            .entry(ease as usize)
+            .or_insert_with(|| {
+                Arc::new(EaseLut::new(
+                    ease,
+                    EaseLut::DEFAULT_RESOLUTION,
+                ))
+            })
+            .clone();
+
+        self.inner.table.insert_by_column(
+            self.inner.id,
+            EaseStorage::Lut(lut),
+            self.inner.ease_col,
+        );
+        self
+    }
+
+    /// Samples the action at `fps` frames per second of its clip,
+    /// holding each value in between, e.g. `12.0` for a stop-motion
+    /// look while the rest of the timeline stays smooth.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is not positive and finite.
+    pub fn with_sample_rate(mut self, fps: f32) -> Self {
+        assert!(
+            fps > 0.0 && fps.is_finite(),
+            "sample rate must be positive and finite, got {fps}"
+        );
+        self.sample_rate = Some(fps);
+        self
+    }
+
+    /// Post-processes every sampled value with `modifier`, after the
+    /// interpolation and any previously added modifiers.
+    ///
+    /// Modifiers also apply to the start and end values, so e.g.
+    /// snapping to a grid holds across the whole clip.
+    pub fn with_modifier(
+        mut self,
+        modifier: impl Modifier<T>,
+    ) -> Self {
+        self.modifiers.push(Box::new(modifier));
+        self
+    }
+
     /// Get the [`ActionId`] of the containing action.
     pub fn id(&self) -> ActionId {
         self.inner.id()
@@ -234,9 +531,23 @@ impl<T> InterpActionBuilder<'_, T> {
     /// Confirms the configuration of the action and creates a
     /// [`TrackFragment`].
     pub fn play(self, duration: Duration) -> TrackFragment {
+        let id = self.id();
+        if let Some(fps) = self.sample_rate {
+            let steps = fps * duration.as_secs_f32();
+            // Zero-length clips only ever sample their end.
+            if steps > 0.0 {
+                self.inner.table.insert(id, StepStorage(steps));
+            }
+        }
+        if !self.modifiers.is_empty() {
+            self.inner
+                .table
+                .insert(id, ModifierStorage(self.modifiers));
+        }
+
         TrackFragment::single(
             self.inner.key,
-            ActionClip::new(self.id(), duration),
+            ActionClip::new(id, duration),
         )
     }
 }
@@ -300,4 +611,52 @@ mod tests {
         // dropped the (now-empty) `IdRegistry<u32>` entry.
         assert_eq!(world.get_id::<u32>(&uid), None);
     }
+
+    #[test]
+    fn ease_luts_are_shared_per_ease() {
+        let mut world = ActionTable::new();
+
+        let mut add = |ease: EaseFn| {
+            world
+                .add(1u32, field(), |x: &f32| *x)
+                .with_interp(|a, b, t| a + (b - a) * t)
+                .with_ease_lut(ease)
+                .id()
+        };
+        let id1 = add(crate::ease::back::ease_in);
+        let id2 = add(crate::ease::back::ease_in);
+        let id3 = add(crate::ease::back::ease_out);
+
+        let lut = |id| match world.ease(&id) {
+            Some(EaseStorage::Lut(lut)) => lut.clone(),
+            _ => panic!("ease should be baked"),
+        };
+        assert!(Arc::ptr_eq(&lut(id1), &lut(id2)));
+        assert!(!Arc::ptr_eq(&lut(id1), &lut(id3)));
+    }
+
+    #[test]
+    fn evaluate_matches_sampling() {
+        let mut world = ActionTable::new();
+
+        let builder = world
+            .add(1u32, field(), |x: &f32| x + 10.0)
+            .with_interp(|a, b, t| a + (b - a) * t)
+            .with_ease(|t| t * t)
+            .with_modifier(|x: f32, _| x + 1.0);
+        let id = builder.id();
+        let _ = builder.play(Duration::from_secs(1));
+        assert_eq!(world.evaluate::<f32>(&id, 0.5), None);
+
+        let col = world.ensure_segment_column::<f32>();
+        world.set_segment_by_column(
+            id,
+            Segment::new(0.0_f32, 10.0),
+            col,
+        );
+
+        assert_eq!(world.evaluate::<f32>(&id, -1.0), Some(1.0));
+        assert_eq!(world.evaluate::<f32>(&id, 0.5), Some(3.5));
+        assert_eq!(world.evaluate::<f32>(&id, 2.0), Some(11.0));
+    }
 }
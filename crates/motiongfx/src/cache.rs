@@ -0,0 +1,603 @@
+//! Binary caches of baked timelines, to skip baking at startup.
+//!
+//! Baking reads the start of every animated field from the world
+//! and runs every action once. For authored content that never
+//! changes, e.g. cutscenes, the baked segments can be written out
+//! ahead of time and read back instead:
+//!
+//! ```ignore
+//! // Offline, e.g. in a build step.
+//! timeline.bake_actions(&registry, &world);
+//! let cache = timeline.write_bake_cache(&registry)?;
+//! std::fs::write("intro.bake", cache)?;
+//!
+//! // At startup, from the same builder code.
+//! let mut timeline = build_intro(&mut registry);
+//! let cache = std::fs::read("intro.bake")?;
+//! timeline.read_bake_cache(&registry, &cache)?;
+//! ```
+//!
+//! Actions are code, so the timeline itself is still built as
+//! usual, only baking is skipped. The cache only holds values,
+//! matched to the actions by their order in the tracks, and is read
+//! in place from a byte slice, so it can just as well be memory
+//! mapped. Its header records the clip count of every track and the
+//! value types of the actions, so a cache written before the
+//! timeline changed is rejected instead of misread.
+//!
+//! Values are written with their [`BakeCodec`], registered in the
+//! [`CodecRegistry`]. `f32`, [`Vec2`], [`Vec3`], [`Vec4`] and
+//! [`Quat`] are registered by default.
+
+use core::any::{TypeId, type_name};
+use core::fmt;
+
+use alloc::vec::Vec;
+use bevy_math::{Quat, Vec2, Vec3, Vec4};
+use hashbrown::{HashMap, HashSet};
+
+use crate::ThreadSafe;
+use crate::action::{
+    ActionId, ActionStorage, ActionTable, Segment, Snapshot,
+};
+use crate::lanes::Lanes;
+use crate::track::Track;
+
+/// Marks the start of a bake cache, followed by its version.
+const MAGIC: &[u8; 8] = b"MGFXBAKE";
+const VERSION: u8 = 2;
+
+/// The clip has a baked segment.
+const SEGMENT: u8 = 1 << 0;
+/// The clip starts a sequence and holds its [`Snapshot`].
+const SNAPSHOT: u8 = 1 << 1;
+
+/// A value that can be written into a bake cache.
+pub trait BakeCodec: Sized {
+    /// Appends the value to `bytes`.
+    fn encode(&self, bytes: &mut Vec<u8>);
+
+    /// Reads a value from the front of `bytes`, advancing past it.
+    fn decode(bytes: &mut &[u8]) -> Option<Self>;
+}
+
+impl BakeCodec for f32 {
+    fn encode(&self, bytes: &mut Vec<u8>) {
+        bytes.extend_from_slice(&self.to_le_bytes());
+    }
+
+    fn decode(bytes: &mut &[u8]) -> Option<Self> {
+        let (value, rest) = bytes.split_first_chunk()?;
+        *bytes = rest;
+        Some(f32::from_le_bytes(*value))
+    }
+}
+
+macro_rules! impl_array_codec {
+    ($($ty:ty),*) => {$(
+        impl BakeCodec for $ty {
+            fn encode(&self, bytes: &mut Vec<u8>) {
+                for lane in self.to_array() {
+                    lane.encode(bytes);
+                }
+            }
+
+            fn decode(bytes: &mut &[u8]) -> Option<Self> {
+                let mut lanes = <$ty>::default().to_array();
+                for lane in lanes.iter_mut() {
+                    *lane = f32::decode(bytes)?;
+                }
+                Some(<$ty>::from_array(lanes))
+            }
+        }
+    )*};
+}
+
+impl_array_codec!(Vec2, Vec3, Vec4, Quat);
+
+/// The [`BakeCodec`]s of every value type a bake cache can hold.
+pub struct CodecRegistry {
+    codecs: HashMap<TypeId, ErasedCodec>,
+}
+
+impl CodecRegistry {
+    /// A registry with the codecs of the `bevy_math` types.
+    pub fn new() -> Self {
+        let mut registry = Self {
+            codecs: HashMap::new(),
+        };
+        registry.register_lanes::<f32>();
+        registry.register_lanes::<Vec2>();
+        registry.register_lanes::<Vec3>();
+        registry.register::<Vec4>();
+        registry.register::<Quat>();
+        registry
+    }
+
+    /// Registers the codec of `T`, so that `T` actions can be
+    /// cached.
+    pub fn register<T>(&mut self)
+    where
+        T: BakeCodec + Clone + ThreadSafe,
+    {
+        self.codecs.insert(
+            TypeId::of::<T>(),
+            ErasedCodec {
+                tag: type_tag::<T>(),
+                is_action: is_action::<T>,
+                write: write_clip::<T>,
+                read: read_clip::<T>,
+            },
+        );
+    }
+
+    /// Similar to [`Self::register`], but also fills the bulk
+    /// sampled [lanes](crate::lanes) of `T`.
+    fn register_lanes<T: BakeCodec + Lanes>(&mut self) {
+        self.codecs.insert(
+            TypeId::of::<T>(),
+            ErasedCodec {
+                tag: type_tag::<T>(),
+                is_action: is_action::<T>,
+                write: write_clip::<T>,
+                read: read_lanes_clip::<T>,
+            },
+        );
+    }
+
+    /// The codec of the action `id`, if its type is registered.
+    fn get(
+        &self,
+        action_table: &ActionTable,
+        id: &ActionId,
+    ) -> Option<&ErasedCodec> {
+        self.codecs
+            .values()
+            .find(|codec| (codec.is_action)(action_table, id))
+    }
+}
+
+impl Default for CodecRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A [`BakeCodec`] with its value type erased.
+struct ErasedCodec {
+    /// The [`type_tag`] of the value type.
+    tag: u64,
+    /// Returns `true` if the action is of this codec's type.
+    is_action: fn(&ActionTable, &ActionId) -> bool,
+    write: fn(&ActionTable, &ActionId, &mut Vec<u8>),
+    read: fn(&mut ActionTable, ActionId, &mut &[u8]) -> Option<()>,
+}
+
+fn is_action<T: ThreadSafe>(
+    action_table: &ActionTable,
+    id: &ActionId,
+) -> bool {
+    action_table.table().get::<ActionStorage<T>>(id).is_some()
+}
+
+fn write_clip<T: BakeCodec + ThreadSafe>(
+    action_table: &ActionTable,
+    id: &ActionId,
+    bytes: &mut Vec<u8>,
+) {
+    let table = action_table.table();
+    let segment = table.get::<Segment<T>>(id);
+    let snapshot = table.get::<Snapshot<T>>(id);
+
+    let mut flags = 0;
+    if segment.is_some() {
+        flags |= SEGMENT;
+    }
+    if snapshot.is_some() {
+        flags |= SNAPSHOT;
+    }
+    bytes.push(flags);
+
+    if let Some(segment) = segment {
+        segment.start.encode(bytes);
+        segment.end.encode(bytes);
+    }
+    if let Some(Snapshot(value)) = snapshot {
+        value.encode(bytes);
+    }
+}
+
+fn read_clip<T: BakeCodec + Clone + ThreadSafe>(
+    action_table: &mut ActionTable,
+    id: ActionId,
+    bytes: &mut &[u8],
+) -> Option<()> {
+    let (&flags, rest) = bytes.split_first()?;
+    *bytes = rest;
+
+    if flags & SEGMENT != 0 {
+        let segment =
+            Segment::new(T::decode(bytes)?, T::decode(bytes)?);
+        let col = action_table.ensure_segment_column::<T>();
+        action_table.set_segment_by_column(id, segment, col);
+    }
+    if flags & SNAPSHOT != 0 {
+        action_table.set_snapshot(id, T::decode(bytes)?);
+    }
+
+    Some(())
+}
+
+fn read_lanes_clip<T: BakeCodec + Lanes>(
+    action_table: &mut ActionTable,
+    id: ActionId,
+    bytes: &mut &[u8],
+) -> Option<()> {
+    read_clip::<T>(action_table, id, bytes)?;
+    action_table.mirror_lane_segments::<T>([id]);
+    Some(())
+}
+
+/// Writes the segments of every `baked` track.
+pub(crate) fn write(
+    action_table: &ActionTable,
+    tracks: &[Track],
+    baked: &[bool],
+    codecs: &CodecRegistry,
+) -> Result<Vec<u8>, CacheError> {
+    let clips = baked_clips(action_table, tracks, baked, codecs)?;
+
+    let mut bytes = Vec::new();
+    bytes.extend_from_slice(MAGIC);
+    bytes.push(VERSION);
+    bytes.extend_from_slice(&(tracks.len() as u32).to_le_bytes());
+    for (track_index, track) in tracks.iter().enumerate() {
+        bytes.push(baked[track_index] as u8);
+        let count = clip_ids(track).count() as u32;
+        bytes.extend_from_slice(&count.to_le_bytes());
+    }
+    bytes.extend_from_slice(&fingerprint(&clips).to_le_bytes());
+
+    for (id, codec) in clips.iter().flatten() {
+        (codec.write)(action_table, id, &mut bytes);
+    }
+
+    Ok(bytes)
+}
+
+/// Reads the segments written by [`write`] for the same tracks,
+/// marking the tracks read as `baked`.
+///
+/// The header is checked against `tracks` before any value is read.
+/// Tracks are read in order, so on error every track before the
+/// failing one is fully read.
+pub(crate) fn read(
+    action_table: &mut ActionTable,
+    tracks: &[Track],
+    baked: &mut [bool],
+    codecs: &CodecRegistry,
+    bytes: &[u8],
+) -> Result<(), CacheError> {
+    let mut bytes = bytes
+        .strip_prefix(&MAGIC[..])
+        .and_then(|rest| rest.strip_prefix(&[VERSION]))
+        .ok_or(CacheError::InvalidHeader)?;
+
+    if read_u32(&mut bytes)? as usize != tracks.len() {
+        return Err(CacheError::Mismatch);
+    }
+
+    let mut cached = Vec::with_capacity(tracks.len());
+    for track in tracks {
+        let (&flag, rest) =
+            bytes.split_first().ok_or(CacheError::UnexpectedEnd)?;
+        bytes = rest;
+        cached.push(flag != 0);
+
+        let count = read_u32(&mut bytes)? as usize;
+        if count != clip_ids(track).count() {
+            return Err(CacheError::Mismatch);
+        }
+    }
+
+    let clips = baked_clips(action_table, tracks, &cached, codecs)?;
+    let (hash, rest) =
+        bytes.split_first_chunk().ok_or(CacheError::UnexpectedEnd)?;
+    bytes = rest;
+    if u64::from_le_bytes(*hash) != fingerprint(&clips) {
+        return Err(CacheError::Mismatch);
+    }
+
+    for (track_index, track_clips) in clips.iter().enumerate() {
+        for (id, codec) in track_clips {
+            (codec.read)(action_table, *id, &mut bytes)
+                .ok_or(CacheError::UnexpectedEnd)?;
+        }
+
+        if cached[track_index] {
+            baked[track_index] = true;
+        }
+    }
+
+    if bytes.is_empty() {
+        Ok(())
+    } else {
+        Err(CacheError::Mismatch)
+    }
+}
+
+fn read_u32(bytes: &mut &[u8]) -> Result<u32, CacheError> {
+    let (value, rest) =
+        bytes.split_first_chunk().ok_or(CacheError::UnexpectedEnd)?;
+    *bytes = rest;
+    Ok(u32::from_le_bytes(*value))
+}
+
+/// The actions and codecs of the clips of every `baked` track, in
+/// order, empty for the other tracks.
+///
+/// Repeated clips share the segment of their action, so only the
+/// first clip of every action is listed.
+fn baked_clips<'a>(
+    action_table: &ActionTable,
+    tracks: &[Track],
+    baked: &[bool],
+    codecs: &'a CodecRegistry,
+) -> Result<Vec<Vec<(ActionId, &'a ErasedCodec)>>, CacheError> {
+    let mut seen = HashSet::new();
+    let mut clips = Vec::with_capacity(tracks.len());
+    for (track_index, track) in tracks.iter().enumerate() {
+        let mut track_clips = Vec::new();
+        if baked[track_index] {
+            for (clip_index, id) in clip_ids(track).enumerate() {
+                if !seen.insert(id) {
+                    continue;
+                }
+
+                let codec = codecs.get(action_table, &id).ok_or(
+                    CacheError::MissingCodec {
+                        track: track_index,
+                        clip: clip_index,
+                    },
+                )?;
+                track_clips.push((id, codec));
+            }
+        }
+        clips.push(track_clips);
+    }
+
+    Ok(clips)
+}
+
+/// Hashes the value type of every listed clip, so that a cache is
+/// not read into actions of other types.
+fn fingerprint(clips: &[Vec<(ActionId, &ErasedCodec)>]) -> u64 {
+    clips.iter().flatten().fold(FNV_OFFSET, |hash, (_, codec)| {
+        fnv(hash, &codec.tag.to_le_bytes())
+    })
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a, which unlike `TypeId` is stable across builds.
+fn fnv(hash: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(hash, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100_0000_01b3)
+    })
+}
+
+/// A tag of `T` that is stable across builds.
+fn type_tag<T>() -> u64 {
+    fnv(FNV_OFFSET, type_name::<T>().as_bytes())
+}
+
+/// The actions of every clip of `track`, in order.
+fn clip_ids(track: &Track) -> impl Iterator<Item = ActionId> + '_ {
+    track
+        .sequences_spans()
+        .iter()
+        .flat_map(|(_, span)| track.clips(*span))
+        .map(|clip| clip.id)
+}
+
+/// Reasons a bake cache can fail to be written or read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheError {
+    /// The bytes are not a bake cache of this version.
+    InvalidHeader,
+    /// The cache was written for different tracks: their count,
+    /// their clip counts or the value types of their actions differ.
+    Mismatch,
+    /// The cache ends before its last value.
+    UnexpectedEnd,
+    /// The action of a clip has no [`BakeCodec`] in the
+    /// [`CodecRegistry`].
+    MissingCodec { track: usize, clip: usize },
+}
+
+impl fmt::Display for CacheError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidHeader => write!(f, "not a bake cache"),
+            Self::Mismatch => {
+                write!(f, "the bake cache is for different tracks")
+            }
+            Self::UnexpectedEnd => {
+                write!(f, "the bake cache ends unexpectedly")
+            }
+            Self::MissingCodec { track, clip } => write!(
+                f,
+                "no codec for the action of clip {clip} in track \
+                {track}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for CacheError {}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+
+    #[derive(Clone, Copy, Default)]
+    struct Node {
+        x: f32,
+        frame: u32,
+        offset: Vec2,
+    }
+
+    struct World(Node);
+
+    impl SubjectSource<u32, Node> for World {
+        fn get_source(&self, _id: u32) -> Option<&Node> {
+            Some(&self.0)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            _id: u32,
+            f: impl FnOnce(&mut Node) -> R,
+        ) -> Option<R> {
+            Some(f(&mut self.0))
+        }
+    }
+
+    fn build(
+        registry: &mut Registry,
+        frames: bool,
+    ) -> Timeline<World> {
+        build_with(registry, |b| {
+            let x =
+                b.act(0u32, path!(<Node>::x), |x| x + 1.0).play(s(1));
+            match frames {
+                true => [
+                    x,
+                    b.act_step(0u32, path!(<Node>::frame), |f| f + 1)
+                        .play(s(1)),
+                ]
+                .ord_chain(),
+                false => x,
+            }
+        })
+    }
+
+    /// Builds the first track with `f`, followed by a scale track.
+    fn build_with(
+        registry: &mut Registry,
+        f: impl FnOnce(&mut TimelineBuilder<World>) -> TrackFragment,
+    ) -> Timeline<World> {
+        let mut b = TimelineBuilder::<World>::new(registry);
+        let track = f(&mut b);
+        let scale =
+            b.act(0u32, path!(<Node>::x), |x| x * 2.0).play(s(1));
+        b.add_tracks([track.compile(), scale.compile()]);
+        b.compile()
+    }
+
+    #[test]
+    fn cache_replaces_baking() {
+        let mut registry = Registry::new();
+        let mut world = World(Node {
+            x: 2.0,
+            ..Node::default()
+        });
+
+        let mut timeline = build(&mut registry, false);
+        timeline.bake_track(0, &registry, &world);
+        let cache = timeline.write_bake_cache(&registry).unwrap();
+
+        // Read without the world it was baked from.
+        let mut timeline = build(&mut registry, false);
+        timeline.read_bake_cache(&registry, &cache).unwrap();
+        assert!(timeline.is_track_baked(0));
+        assert!(!timeline.is_track_baked(1));
+
+        world.0.x = 0.0;
+        timeline.set_target_time(ms(500));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        assert_eq!(world.0.x, 2.5);
+
+        timeline.reset_subjects(&registry, &mut world);
+        assert_eq!(world.0.x, 2.0);
+    }
+
+    #[test]
+    fn invalid_caches_are_errors() {
+        let mut registry = Registry::new();
+        let world = World(Node::default());
+
+        let mut timeline = build(&mut registry, true);
+        timeline.bake_actions(&registry, &world);
+        // The `u32` frames have no codec.
+        assert!(matches!(
+            timeline.write_bake_cache(&registry),
+            Err(CacheError::MissingCodec { track: 0, .. })
+        ));
+
+        let mut timeline = build(&mut registry, false);
+        timeline.bake_actions(&registry, &world);
+        let cache = timeline.write_bake_cache(&registry).unwrap();
+
+        assert_eq!(
+            timeline.read_bake_cache(&registry, b"MGFX"),
+            Err(CacheError::InvalidHeader)
+        );
+        assert_eq!(
+            timeline.read_bake_cache(
+                &registry,
+                &cache[..cache.len() - 1]
+            ),
+            Err(CacheError::UnexpectedEnd)
+        );
+
+        let mut longer = cache.clone();
+        longer.push(0);
+        assert_eq!(
+            timeline.read_bake_cache(&registry, &longer),
+            Err(CacheError::Mismatch)
+        );
+    }
+
+    #[test]
+    fn stale_caches_are_mismatches() {
+        let mut registry = Registry::new();
+        let world = World(Node::default());
+
+        let mut timeline = build(&mut registry, false);
+        timeline.bake_actions(&registry, &world);
+        let cache = timeline.write_bake_cache(&registry).unwrap();
+
+        // Same tracks, but the first one gained a clip.
+        let mut timeline = build_with(&mut registry, |b| {
+            [
+                b.act(0u32, path!(<Node>::x), |x| x + 1.0).play(s(1)),
+                b.act(0u32, path!(<Node>::x), |x| x - 1.0).play(s(1)),
+            ]
+            .ord_chain()
+        });
+        assert_eq!(
+            timeline.read_bake_cache(&registry, &cache),
+            Err(CacheError::Mismatch)
+        );
+
+        // Same clip counts, but a `Vec2` action instead of `f32`.
+        let mut timeline = build_with(&mut registry, |b| {
+            b.act_builder(0u32, path!(<Node>::offset), |o| {
+                o + Vec2::ONE
+            })
+            .with_interp(|a, b, t| a.lerp(*b, t))
+            .play(s(1))
+        });
+        assert_eq!(
+            timeline.read_bake_cache(&registry, &cache),
+            Err(CacheError::Mismatch)
+        );
+        // Nothing is read from a stale cache.
+        assert!(!timeline.is_track_baked(0));
+        assert!(!timeline.is_track_baked(1));
+    }
+}
@@ -0,0 +1,398 @@
+//! A declarative timeline format, for timelines loaded from files
+//! rather than built in code.
+//!
+//! A [`TimelineDef`] names its targets, fields and easings with
+//! strings and writes end values as [`Expr`](crate::expr::Expr)s.
+//! With the `ron` feature, it can be read from [RON]:
+//!
+//! ```ron
+//! (
+//!     tracks: [
+//!         Chain([
+//!             Act(
+//!                 target: "cube",
+//!                 field: "Transform::translation::x",
+//!                 to: "start + 2.0",
+//!                 ease: Some("cubic_in_out"),
+//!                 duration: 1.0,
+//!             ),
+//!             Delay(
+//!                 delay: 0.5,
+//!                 fragment: Act(
+//!                     target: "cube",
+//!                     field: "Transform::scale::x",
+//!                     to: "start * 2.0",
+//!                     duration: 0.5,
+//!                 ),
+//!             ),
+//!         ]),
+//!     ],
+//! )
+//! ```
+//!
+//! Fields and easings are looked up through a [`ScriptRegistry`]
+//! and targets through a resolver when the definition is compiled.
+//!
+//! [RON]: https://github.com/ron-rs/ron
+
+use core::fmt;
+use core::time::Duration;
+
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::graph::{GraphFragment, ResolveError, TimelineGraph};
+use crate::registry::Registry;
+use crate::script::{ScriptError, ScriptRegistry};
+use crate::subject::SubjectId;
+use crate::timeline::Timeline;
+use crate::track::Alignment;
+
+/// A timeline described as data, see the [module docs](self).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct TimelineDef {
+    /// Every track of the timeline, in order.
+    pub tracks: Vec<FragmentDef>,
+}
+
+/// A node of a [`TimelineDef`] track, mirroring [`GraphFragment`].
+///
+/// Durations and delays are in seconds.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum FragmentDef {
+    /// Animates `field` of `target` to `to` over `duration`.
+    Act {
+        /// Name of the subject.
+        target: String,
+        /// Name of a field in the [`ScriptRegistry`].
+        field: String,
+        /// The end value, as an [`Expr`](crate::expr::Expr).
+        to: String,
+        /// Name of an ease in the [`ScriptRegistry`].
+        #[cfg_attr(feature = "serde", serde(default))]
+        ease: Option<String>,
+        duration: f32,
+    },
+    /// See [`GraphFragment::Chain`].
+    Chain(Vec<FragmentDef>),
+    /// See [`GraphFragment::All`].
+    All(Vec<FragmentDef>),
+    /// See [`GraphFragment::Any`].
+    Any(Vec<FragmentDef>),
+    /// See [`GraphFragment::Flow`].
+    Flow {
+        delay: f32,
+        fragments: Vec<FragmentDef>,
+    },
+    /// See [`GraphFragment::Overlap`].
+    Overlap {
+        overlap: f32,
+        fragments: Vec<FragmentDef>,
+    },
+    /// See [`GraphFragment::Align`] with [`Alignment::End`].
+    AlignEnd(Vec<FragmentDef>),
+    /// See [`GraphFragment::Align`] with [`Alignment::Center`].
+    AlignCenter(Vec<FragmentDef>),
+    /// See [`GraphFragment::Align`] with [`Alignment::To`].
+    AlignTo {
+        time: f32,
+        fragments: Vec<FragmentDef>,
+    },
+    /// See [`GraphFragment::Delay`].
+    Delay {
+        delay: f32,
+        fragment: Box<FragmentDef>,
+    },
+}
+
+impl TimelineDef {
+    /// Parses a definition from [RON](https://github.com/ron-rs/ron).
+    #[cfg(feature = "ron")]
+    pub fn from_ron(
+        source: &str,
+    ) -> Result<Self, ron::de::SpannedError> {
+        ron::from_str(source)
+    }
+
+    /// Describes the definition as a [`TimelineGraph`], looking up
+    /// fields and easings in `scripts`.
+    pub fn to_graph<W, I>(
+        &self,
+        scripts: &ScriptRegistry<W, I>,
+    ) -> Result<TimelineGraph<W, I>, DefinitionError>
+    where
+        W: 'static,
+        I: SubjectId,
+    {
+        let mut graph = TimelineGraph::new();
+        for track in self.tracks.iter() {
+            let fragment = track.to_graph(scripts, &mut graph)?;
+            graph.add_track(fragment);
+        }
+
+        Ok(graph)
+    }
+
+    /// Compiles the definition into a [`Timeline`], binding every
+    /// target name through `resolver`.
+    pub fn compile<W, I>(
+        &self,
+        scripts: &ScriptRegistry<W, I>,
+        registry: &mut Registry,
+        resolver: impl Fn(&str) -> Option<I>,
+    ) -> Result<Timeline<W>, DefinitionError>
+    where
+        W: 'static,
+        I: SubjectId,
+    {
+        let graph = self.to_graph(scripts)?;
+        Ok(graph.resolve(registry, resolver)?)
+    }
+}
+
+impl FragmentDef {
+    fn to_graph<W, I>(
+        &self,
+        scripts: &ScriptRegistry<W, I>,
+        graph: &mut TimelineGraph<W, I>,
+    ) -> Result<GraphFragment, DefinitionError>
+    where
+        W: 'static,
+        I: SubjectId,
+    {
+        let mut to_graph_all = |fragments: &[FragmentDef]| {
+            fragments
+                .iter()
+                .map(|f| f.to_graph(scripts, graph))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(match self {
+            Self::Act {
+                target,
+                field,
+                to,
+                ease,
+                duration,
+            } => scripts.act(
+                graph,
+                target.clone(),
+                field,
+                to,
+                ease.as_deref(),
+                seconds(*duration)?,
+            )?,
+            Self::Chain(fragments) => {
+                GraphFragment::chain(to_graph_all(fragments)?)
+            }
+            Self::All(fragments) => {
+                GraphFragment::all(to_graph_all(fragments)?)
+            }
+            Self::Any(fragments) => {
+                GraphFragment::any(to_graph_all(fragments)?)
+            }
+            Self::Flow { delay, fragments } => GraphFragment::flow(
+                seconds(*delay)?,
+                to_graph_all(fragments)?,
+            ),
+            Self::Overlap { overlap, fragments } => {
+                GraphFragment::overlap(
+                    seconds(*overlap)?,
+                    to_graph_all(fragments)?,
+                )
+            }
+            Self::AlignEnd(fragments) => GraphFragment::align(
+                Alignment::End,
+                to_graph_all(fragments)?,
+            ),
+            Self::AlignCenter(fragments) => GraphFragment::align(
+                Alignment::Center,
+                to_graph_all(fragments)?,
+            ),
+            Self::AlignTo { time, fragments } => {
+                GraphFragment::align(
+                    Alignment::To(seconds(*time)?),
+                    to_graph_all(fragments)?,
+                )
+            }
+            Self::Delay { delay, fragment } => fragment
+                .to_graph(scripts, graph)?
+                .delayed(seconds(*delay)?),
+        })
+    }
+}
+
+fn seconds(secs: f32) -> Result<Duration, DefinitionError> {
+    Duration::try_from_secs_f32(secs)
+        .map_err(|_| DefinitionError::InvalidDuration(secs))
+}
+
+/// Reasons a [`TimelineDef`] can fail to compile.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefinitionError {
+    /// A field, ease or value could not be looked up or parsed.
+    Script(ScriptError),
+    /// A target could not be bound, see [`TimelineGraph::resolve`].
+    Resolve(ResolveError),
+    /// A duration or delay is negative or not finite.
+    InvalidDuration(f32),
+}
+
+impl From<ScriptError> for DefinitionError {
+    fn from(err: ScriptError) -> Self {
+        Self::Script(err)
+    }
+}
+
+impl From<ResolveError> for DefinitionError {
+    fn from(err: ResolveError) -> Self {
+        Self::Resolve(err)
+    }
+}
+
+impl fmt::Display for DefinitionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Script(err) => err.fmt(f),
+            Self::Resolve(err) => err.fmt(f),
+            Self::InvalidDuration(secs) => {
+                write!(f, "invalid duration of {secs} seconds")
+            }
+        }
+    }
+}
+
+impl core::error::Error for DefinitionError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::time::s;
+    use crate::world::SubjectSource;
+
+    use super::*;
+
+    struct World(f32);
+
+    impl SubjectSource<usize, f32> for World {
+        fn get_source(&self, _: usize) -> Option<&f32> {
+            Some(&self.0)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            _: usize,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            Some(f(&mut self.0))
+        }
+    }
+
+    fn act(to: &str, duration: f32) -> FragmentDef {
+        FragmentDef::Act {
+            target: "a".into(),
+            field: "x".into(),
+            to: to.into(),
+            ease: None,
+            duration,
+        }
+    }
+
+    #[test]
+    fn compiles_to_a_timeline() {
+        let mut scripts = ScriptRegistry::<World, usize>::new();
+        scripts.register_field("x", crate::path!(<f32>));
+        let def = TimelineDef {
+            tracks: vec![FragmentDef::Chain(vec![
+                act("start + 1", 1.0),
+                act("start * 2", 1.0),
+            ])],
+        };
+
+        let mut registry = Registry::new();
+        let mut timeline = def
+            .compile(&scripts, &mut registry, |name| {
+                (name == "a").then_some(0)
+            })
+            .unwrap();
+        assert_eq!(timeline.curr_track().duration(), s(2));
+
+        let mut world = World(1.0);
+        timeline.bake_actions(&registry, &world);
+        timeline.set_target_time(s(2));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+
+        assert_eq!(world.0, 4.0);
+    }
+
+    #[test]
+    fn invalid_definitions_are_errors() {
+        let mut scripts = ScriptRegistry::<World, usize>::new();
+        scripts.register_field("x", crate::path!(<f32>));
+        let mut registry = Registry::new();
+
+        let def = TimelineDef {
+            tracks: vec![FragmentDef::Delay {
+                delay: -1.0,
+                fragment: Box::new(act("1", 1.0)),
+            }],
+        };
+        assert_eq!(
+            def.to_graph(&scripts).err(),
+            Some(DefinitionError::InvalidDuration(-1.0)),
+        );
+
+        let def = TimelineDef {
+            tracks: vec![act("1", 1.0)],
+        };
+        assert!(matches!(
+            def.compile(&scripts, &mut registry, |_| None),
+            Err(DefinitionError::Resolve(_)),
+        ));
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn parses_ron() {
+        use crate::time::cs;
+
+        let def = TimelineDef::from_ron(
+            r#"(
+                tracks: [
+                    Flow(delay: 0.5, fragments: [
+                        Act(
+                            target: "a",
+                            field: "x",
+                            to: "1",
+                            duration: 1.0,
+                        ),
+                        Act(
+                            target: "a",
+                            field: "x",
+                            to: "2",
+                            ease: Some("quad_in"),
+                            duration: 1.0,
+                        ),
+                    ]),
+                ],
+            )"#,
+        )
+        .unwrap();
+
+        let mut scripts = ScriptRegistry::<World, usize>::new();
+        scripts.register_field("x", crate::path!(<f32>));
+        let graph = def.to_graph(&scripts).unwrap();
+        assert_eq!(graph.tracks()[0].duration(), s(1) + cs(50));
+    }
+}
@@ -1,11 +1,59 @@
 use core::f32::consts::PI;
 
+use alloc::boxed::Box;
 use bevy_math::ops::*;
 
+use crate::action::EaseFn;
+
 pub fn linear(t: f32) -> f32 {
     t
 }
 
+/// An [`EaseFn`] pre-baked into evenly spaced samples.
+///
+/// Sampling is a lookup and a linear interpolation, which is cheaper
+/// than evaluating [`elastic`], [`back`] or other expensive curves.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EaseLut {
+    samples: Box<[f32]>,
+}
+
+impl EaseLut {
+    /// Resolution used by
+    /// [`InterpActionBuilder::with_ease_lut`](crate::action::InterpActionBuilder::with_ease_lut).
+    pub const DEFAULT_RESOLUTION: usize = 256;
+
+    /// Bakes `ease` into `resolution` segments, clamped to at least
+    /// 1.
+    pub fn new(ease: EaseFn, resolution: usize) -> Self {
+        let resolution = resolution.max(1);
+        let step = 1.0 / resolution as f32;
+
+        Self {
+            samples: (0..=resolution)
+                .map(|i| ease(i as f32 * step))
+                .collect(),
+        }
+    }
+
+    /// The number of segments between samples.
+    #[inline]
+    pub fn resolution(&self) -> usize {
+        self.samples.len() - 1
+    }
+
+    /// Linearly interpolates the baked samples at `t`, clamped within
+    /// \[0.0..=1.0\].
+    #[inline]
+    pub fn sample(&self, t: f32) -> f32 {
+        let x = t.clamp(0.0, 1.0) * self.resolution() as f32;
+        let i = (x as usize).min(self.resolution() - 1);
+        let (a, b) = (self.samples[i], self.samples[i + 1]);
+
+        a + (b - a) * (x - i as f32)
+    }
+}
+
 // Easing taken from https://easings.net/
 
 const C1: f32 = 1.70158;
@@ -260,3 +308,46 @@ pub mod elastic {
         }
     }
 }
+
+/// Every built-in [`EaseFn`] by name, looked up by [`by_name`].
+///
+/// Families are named `"<family>_in"`, `"<family>_out"` and
+/// `"<family>_in_out"`, e.g. `"cubic_in_out"`.
+pub const NAMED: &[(&str, EaseFn)] = &[
+    ("linear", linear),
+    ("sine_in", sine::ease_in),
+    ("sine_out", sine::ease_out),
+    ("sine_in_out", sine::ease_in_out),
+    ("quad_in", quad::ease_in),
+    ("quad_out", quad::ease_out),
+    ("quad_in_out", quad::ease_in_out),
+    ("cubic_in", cubic::ease_in),
+    ("cubic_out", cubic::ease_out),
+    ("cubic_in_out", cubic::ease_in_out),
+    ("quart_in", quart::ease_in),
+    ("quart_out", quart::ease_out),
+    ("quart_in_out", quart::ease_in_out),
+    ("quint_in", quint::ease_in),
+    ("quint_out", quint::ease_out),
+    ("quint_in_out", quint::ease_in_out),
+    ("expo_in", expo::ease_in),
+    ("expo_out", expo::ease_out),
+    ("expo_in_out", expo::ease_in_out),
+    ("circ_in", circ::ease_in),
+    ("circ_out", circ::ease_out),
+    ("circ_in_out", circ::ease_in_out),
+    ("back_in", back::ease_in),
+    ("back_out", back::ease_out),
+    ("back_in_out", back::ease_in_out),
+    ("elastic_in", elastic::ease_in),
+    ("elastic_out", elastic::ease_out),
+    ("elastic_in_out", elastic::ease_in_out),
+];
+
+/// Looks up a built-in [`EaseFn`] by name, see [`NAMED`].
+pub fn by_name(name: &str) -> Option<EaseFn> {
+    NAMED
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, ease)| *ease)
+}
@@ -0,0 +1,464 @@
+//! A tiny expression language for authoring [`f32`] action values
+//! as strings, e.g. in data files:
+//!
+//! ```ignore
+//! let wobble = Expr::parse("start + sin(t * 3.0) * 2.0")?;
+//!
+//! b.act(id, path!(<f32>), wobble.action()).play(s(1));
+//! b.act_fn(id, path!(<f32>), wobble.procedural()).play(s(1));
+//! ```
+//!
+//! Expressions support `+`, `-`, `*`, `/`, `%`, `^` for powers,
+//! unary minus and parentheses over:
+//!
+//! - the variables `start`, the value the action starts from, and
+//!   `t`, the eased progress of its clip,
+//! - the constants `pi`, `tau` and `e`,
+//! - the functions `sin`, `cos`, `tan`, `abs`, `sqrt`, `exp`, `ln`,
+//!   `floor`, `ceil`, `round`, `min`, `max`, `clamp` and `lerp`.
+
+use core::f32::consts::{E, PI, TAU};
+use core::fmt;
+
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use bevy_math::ops;
+
+use crate::action::{Action, Procedural};
+
+/// A parsed expression, see the [module docs](self).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expr {
+    node: Node,
+}
+
+impl Expr {
+    pub fn parse(source: &str) -> Result<Self, ExprError> {
+        let mut parser = Parser { source, pos: 0 };
+        let node = parser.expr()?;
+
+        parser.skip_whitespace();
+        match parser.peek() {
+            Some(_) => Err(ExprError::Unexpected { at: parser.pos }),
+            None => Ok(Self { node }),
+        }
+    }
+
+    /// Evaluates the expression with the given variables.
+    pub fn eval(&self, start: f32, t: f32) -> f32 {
+        self.node.eval(start, t)
+    }
+
+    /// An [`Action`] ending at the expression evaluated with
+    /// `t = 1.0`.
+    pub fn action(&self) -> impl Action<f32> {
+        let expr = self.clone();
+        move |start: &f32| expr.eval(*start, 1.0)
+    }
+
+    /// A [`Procedural`] evaluating the expression at every `t`, with
+    /// `start = 0.0` as procedural actions have no start.
+    pub fn procedural(&self) -> impl Procedural<f32> {
+        let expr = self.clone();
+        move |t: f32| expr.eval(0.0, t)
+    }
+}
+
+/// Reasons [`Expr::parse`] can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprError {
+    /// Unexpected character or token at this byte offset.
+    Unexpected { at: usize },
+    /// The expression ends in the middle.
+    UnexpectedEnd,
+    /// Unknown variable, constant or function.
+    UnknownName(String),
+    /// A function called with the wrong number of arguments.
+    Arity {
+        name: String,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unexpected { at } => {
+                write!(f, "unexpected input at offset {at}")
+            }
+            Self::UnexpectedEnd => {
+                write!(f, "unexpected end of expression")
+            }
+            Self::UnknownName(name) => {
+                write!(f, "unknown name \"{name}\"")
+            }
+            Self::Arity {
+                name,
+                expected,
+                found,
+            } => write!(
+                f,
+                "\"{name}\" takes {expected} arguments, got {found}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for ExprError {}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Node {
+    Num(f32),
+    Start,
+    T,
+    Neg(Box<Node>),
+    Binary(BinaryOp, Box<Node>, Box<Node>),
+    Call(Func, Box<[Node]>),
+}
+
+impl Node {
+    fn eval(&self, start: f32, t: f32) -> f32 {
+        match self {
+            Self::Num(value) => *value,
+            Self::Start => start,
+            Self::T => t,
+            Self::Neg(node) => -node.eval(start, t),
+            Self::Binary(op, a, b) => {
+                let (a, b) = (a.eval(start, t), b.eval(start, t));
+                match op {
+                    BinaryOp::Add => a + b,
+                    BinaryOp::Sub => a - b,
+                    BinaryOp::Mul => a * b,
+                    BinaryOp::Div => a / b,
+                    BinaryOp::Rem => a % b,
+                    BinaryOp::Pow => ops::powf(a, b),
+                }
+            }
+            Self::Call(func, args) => {
+                let arg = |i: usize| args[i].eval(start, t);
+                func.call(arg)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Pow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Func {
+    Sin,
+    Cos,
+    Tan,
+    Abs,
+    Sqrt,
+    Exp,
+    Ln,
+    Floor,
+    Ceil,
+    Round,
+    Min,
+    Max,
+    Clamp,
+    Lerp,
+}
+
+impl Func {
+    fn from_name(name: &str) -> Option<Self> {
+        Some(match name {
+            "sin" => Self::Sin,
+            "cos" => Self::Cos,
+            "tan" => Self::Tan,
+            "abs" => Self::Abs,
+            "sqrt" => Self::Sqrt,
+            "exp" => Self::Exp,
+            "ln" => Self::Ln,
+            "floor" => Self::Floor,
+            "ceil" => Self::Ceil,
+            "round" => Self::Round,
+            "min" => Self::Min,
+            "max" => Self::Max,
+            "clamp" => Self::Clamp,
+            "lerp" => Self::Lerp,
+            _ => return None,
+        })
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Min | Self::Max => 2,
+            Self::Clamp | Self::Lerp => 3,
+            _ => 1,
+        }
+    }
+
+    fn call(self, arg: impl Fn(usize) -> f32) -> f32 {
+        match self {
+            Self::Sin => ops::sin(arg(0)),
+            Self::Cos => ops::cos(arg(0)),
+            Self::Tan => ops::tan(arg(0)),
+            Self::Abs => ops::abs(arg(0)),
+            Self::Sqrt => ops::sqrt(arg(0)),
+            Self::Exp => ops::exp(arg(0)),
+            Self::Ln => ops::ln(arg(0)),
+            Self::Floor => ops::floor(arg(0)),
+            Self::Ceil => ops::ceil(arg(0)),
+            Self::Round => ops::round(arg(0)),
+            Self::Min => arg(0).min(arg(1)),
+            Self::Max => arg(0).max(arg(1)),
+            Self::Clamp => arg(0).max(arg(1)).min(arg(2)),
+            Self::Lerp => {
+                let (a, b) = (arg(0), arg(1));
+                a + (b - a) * arg(2)
+            }
+        }
+    }
+}
+
+/// Recursive descent over:
+///
+/// ```text
+/// expr  = term (("+" | "-") term)*
+/// term  = unary (("*" | "/" | "%") unary)*
+/// unary = "-" unary | power
+/// power = atom ("^" unary)?
+/// atom  = number | name | name "(" args ")" | "(" expr ")"
+/// ```
+struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn expr(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.term()?;
+        loop {
+            let op = match self.peek_token() {
+                Some(b'+') => BinaryOp::Add,
+                Some(b'-') => BinaryOp::Sub,
+                _ => return Ok(node),
+            };
+            self.pos += 1;
+            node = Node::Binary(
+                op,
+                Box::new(node),
+                Box::new(self.term()?),
+            );
+        }
+    }
+
+    fn term(&mut self) -> Result<Node, ExprError> {
+        let mut node = self.unary()?;
+        loop {
+            let op = match self.peek_token() {
+                Some(b'*') => BinaryOp::Mul,
+                Some(b'/') => BinaryOp::Div,
+                Some(b'%') => BinaryOp::Rem,
+                _ => return Ok(node),
+            };
+            self.pos += 1;
+            node = Node::Binary(
+                op,
+                Box::new(node),
+                Box::new(self.unary()?),
+            );
+        }
+    }
+
+    fn unary(&mut self) -> Result<Node, ExprError> {
+        if self.peek_token() == Some(b'-') {
+            self.pos += 1;
+            return Ok(Node::Neg(Box::new(self.unary()?)));
+        }
+
+        let base = self.atom()?;
+        if self.peek_token() == Some(b'^') {
+            self.pos += 1;
+            let exponent = self.unary()?;
+            return Ok(Node::Binary(
+                BinaryOp::Pow,
+                Box::new(base),
+                Box::new(exponent),
+            ));
+        }
+        Ok(base)
+    }
+
+    fn atom(&mut self) -> Result<Node, ExprError> {
+        let start = self.pos;
+        match self.peek_token() {
+            None => Err(ExprError::UnexpectedEnd),
+            Some(b'(') => {
+                self.pos += 1;
+                let node = self.expr()?;
+                self.expect(b')')?;
+                Ok(node)
+            }
+            Some(c) if c.is_ascii_digit() || c == b'.' => {
+                let len = self
+                    .take_while(|c| c.is_ascii_digit() || c == b'.');
+                self.source[start..start + len]
+                    .parse()
+                    .map(Node::Num)
+                    .map_err(|_| ExprError::Unexpected { at: start })
+            }
+            Some(c) if c.is_ascii_alphabetic() || c == b'_' => {
+                let len = self.take_while(|c| {
+                    c.is_ascii_alphanumeric() || c == b'_'
+                });
+                let name = &self.source[start..start + len];
+                self.name(name)
+            }
+            Some(_) => Err(ExprError::Unexpected { at: start }),
+        }
+    }
+
+    fn name(&mut self, name: &str) -> Result<Node, ExprError> {
+        if self.peek_token() != Some(b'(') {
+            return match name {
+                "start" => Ok(Node::Start),
+                "t" => Ok(Node::T),
+                "pi" => Ok(Node::Num(PI)),
+                "tau" => Ok(Node::Num(TAU)),
+                "e" => Ok(Node::Num(E)),
+                _ => Err(ExprError::UnknownName(name.to_string())),
+            };
+        }
+
+        let func = Func::from_name(name).ok_or_else(|| {
+            ExprError::UnknownName(name.to_string())
+        })?;
+        self.pos += 1;
+
+        let mut args = Vec::new();
+        if self.peek_token() == Some(b')') {
+            self.pos += 1;
+        } else {
+            loop {
+                args.push(self.expr()?);
+                match self.peek_token() {
+                    Some(b',') => self.pos += 1,
+                    Some(b')') => {
+                        self.pos += 1;
+                        break;
+                    }
+                    Some(_) => {
+                        return Err(ExprError::Unexpected {
+                            at: self.pos,
+                        });
+                    }
+                    None => return Err(ExprError::UnexpectedEnd),
+                }
+            }
+        }
+
+        if args.len() != func.arity() {
+            return Err(ExprError::Arity {
+                name: name.to_string(),
+                expected: func.arity(),
+                found: args.len(),
+            });
+        }
+        Ok(Node::Call(func, args.into_boxed_slice()))
+    }
+
+    fn expect(&mut self, token: u8) -> Result<(), ExprError> {
+        match self.peek_token() {
+            Some(c) if c == token => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(_) => Err(ExprError::Unexpected { at: self.pos }),
+            None => Err(ExprError::UnexpectedEnd),
+        }
+    }
+
+    /// Advances past the bytes matching `f`, returning their count.
+    fn take_while(&mut self, f: impl Fn(u8) -> bool) -> usize {
+        let len = self.source.as_bytes()[self.pos..]
+            .iter()
+            .take_while(|c| f(**c))
+            .count();
+        self.pos += len;
+        len
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.take_while(|c| c.is_ascii_whitespace());
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.source.as_bytes().get(self.pos).copied()
+    }
+
+    /// Peeks the next byte after any whitespace.
+    fn peek_token(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.peek()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(source: &str) -> f32 {
+        Expr::parse(source).unwrap().eval(2.0, 0.5)
+    }
+
+    #[test]
+    fn parse_respects_precedence() {
+        assert_eq!(eval("1 + 2 * 3"), 7.0);
+        assert_eq!(eval("(1 + 2) * 3"), 9.0);
+        assert_eq!(eval("-2 ^ 2"), -4.0);
+        assert_eq!(eval("2 ^ 3 ^ 2"), 512.0);
+        assert_eq!(eval("7 % 4 - 1"), 2.0);
+        assert_eq!(eval("start + t * 4"), 4.0);
+    }
+
+    #[test]
+    fn eval_calls_functions() {
+        assert_eq!(eval("sin(0) + max(start, 3)"), 3.0);
+        assert_eq!(eval("clamp(5, 0, start)"), 2.0);
+        assert_eq!(eval("lerp(0, 10, t)"), 5.0);
+        assert_eq!(eval("floor(pi)"), 3.0);
+    }
+
+    #[test]
+    fn parse_reports_errors() {
+        assert_eq!(Expr::parse("1 +"), Err(ExprError::UnexpectedEnd));
+        assert_eq!(
+            Expr::parse("1 $ 2"),
+            Err(ExprError::Unexpected { at: 2 })
+        );
+        assert_eq!(
+            Expr::parse("speed * 2"),
+            Err(ExprError::UnknownName("speed".into()))
+        );
+        assert_eq!(
+            Expr::parse("min(1)"),
+            Err(ExprError::Arity {
+                name: "min".into(),
+                expected: 2,
+                found: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn action_ends_at_t_one() {
+        let expr = Expr::parse("start + t * 3").unwrap();
+
+        assert_eq!(expr.action()(&1.0), 4.0);
+        assert_eq!(expr.procedural()(0.5), 1.5);
+    }
+}
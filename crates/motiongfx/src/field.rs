@@ -0,0 +1,1193 @@
+//! Targets an action can write to.
+//!
+//! Most actions animate a field reached through a plain
+//! [`FieldAccessor`]. [`IndexedField`] goes one step further and
+//! addresses a single element of a collection field, such as one bar
+//! height in a `Vec<f32>`:
+//!
+//! ```
+//! use motiongfx::prelude::*;
+//!
+//! struct Chart {
+//!     values: Vec<f32>,
+//! }
+//!
+//! let third = path!(<Chart>::values[3]);
+//! assert_eq!(third.index(), 3);
+//! ```
+//!
+//! Elements are looked up at bake and sample time. An index that is
+//! out of bounds at that point is skipped rather than panicking.
+//! Maps keyed by `usize` work the same way, with a missing key
+//! treated like an out of bounds index. See [`ElementAccess`] for
+//! every supported collection.
+//!
+//! [`OptionField`] similarly targets the value inside an `Option`
+//! field, created with a trailing `?`:
+//!
+//! ```
+//! use motiongfx::prelude::*;
+//!
+//! struct Sprite {
+//!     custom_size: Option<f32>,
+//! }
+//!
+//! let size = path!(<Sprite>::custom_size?)
+//!     .with_policy(NonePolicy::Skip);
+//! assert_eq!(size.policy(), NonePolicy::Skip);
+//! ```
+//!
+//! [`CompositeField`] animates several fields of a source at once
+//! through a [`Composite`] view, so a single action can keep them
+//! coherent:
+//!
+//! ```
+//! use motiongfx::prelude::*;
+//!
+//! struct Rect {
+//!     x: f32,
+//!     width: f32,
+//! }
+//!
+//! /// Grows the rect while its left edge stays put.
+//! #[derive(Clone)]
+//! struct LeftAnchored {
+//!     left: f32,
+//!     width: f32,
+//! }
+//!
+//! impl Composite<Rect> for LeftAnchored {
+//!     fn read(rect: &Rect) -> Self {
+//!         Self {
+//!             left: rect.x - rect.width * 0.5,
+//!             width: rect.width,
+//!         }
+//!     }
+//!
+//!     fn write(self, rect: &mut Rect) {
+//!         rect.x = self.left + self.width * 0.5;
+//!         rect.width = self.width;
+//!     }
+//! }
+//!
+//! let grow = CompositeField::<_, LeftAnchored>::new(path!(<Rect>));
+//! ```
+//!
+//! [`MaskedField`] instead animates the whole source as is, but only
+//! writes back the fields of a [`FieldMask`], so it can run alongside
+//! actions on the other fields:
+//!
+//! ```
+//! use motiongfx::prelude::*;
+//!
+//! #[derive(Clone)]
+//! struct Rect {
+//!     x: f32,
+//!     width: f32,
+//!     color: [f32; 4],
+//! }
+//!
+//! let mask = FieldMask::new()
+//!     .with(path!(<Rect>::x))
+//!     .with(path!(<Rect>::width));
+//! let rect = MaskedField::new(path!(<Rect>), mask);
+//! ```
+//!
+//! [`VirtualField`] animates a value that is not stored on the
+//! source at all, converting to and from it on every read and write:
+//!
+//! ```
+//! use motiongfx::prelude::*;
+//!
+//! struct Dial {
+//!     angle: f32,
+//! }
+//!
+//! // The angle is stored in radians but animated in degrees.
+//! let degrees = VirtualField::new(
+//!     path!(<Dial>),
+//!     |dial: &Dial| dial.angle.to_degrees(),
+//!     |dial: &mut Dial, degrees: f32| {
+//!         dial.angle = degrees.to_radians();
+//!     },
+//! );
+//! ```
+
+use core::any::TypeId;
+use core::hash::BuildHasher;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use alloc::boxed::Box;
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use field_path::field::UntypedField;
+use field_path::field_accessor::FieldAccessor;
+
+use crate::ThreadSafe;
+use crate::pipeline::PipelineKey;
+use crate::registry::Registry;
+use crate::subject::SubjectId;
+use crate::world::SubjectSource;
+
+/// A target field of type `T` on a source `S` that an action can be
+/// bound to.
+pub trait ActionField<S, T> {
+    /// The underlying field and the part of it being animated.
+    fn target(&self) -> (UntypedField, FieldTarget);
+
+    /// What baking starts from when the field is `None`, stored with
+    /// every action on this field. Only [`OptionField`]s can be
+    /// `None`.
+    #[inline]
+    fn none_policy(&self) -> NonePolicy {
+        NonePolicy::Default
+    }
+
+    /// Registers the accessor and pipeline needed to animate this
+    /// target, returning the key of that pipeline.
+    fn register<W, I>(self, registry: &mut Registry) -> PipelineKey
+    where
+        S: 'static,
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId;
+}
+
+impl<S, T> ActionField<S, T> for FieldAccessor<S, T>
+where
+    S: 'static,
+    T: Clone + ThreadSafe,
+{
+    #[inline]
+    fn target(&self) -> (UntypedField, FieldTarget) {
+        (self.field.untyped(), FieldTarget::Whole)
+    }
+
+    fn register<W, I>(self, registry: &mut Registry) -> PipelineKey
+    where
+        S: 'static,
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+    {
+        registry.register::<W, I, S, T>(self);
+        PipelineKey::new::<W, I, S, T>()
+    }
+}
+
+/// The part of a field an action animates, stored in its
+/// [`ActionKey`](crate::action::ActionKey).
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum FieldTarget {
+    /// The whole field.
+    #[default]
+    Whole,
+    /// The element at an index of a collection field. See
+    /// [`IndexedField`].
+    Element(usize),
+    /// The value inside an `Option` field. See [`OptionField`].
+    Inner,
+    /// A [`Composite`] view of the whole source, identified by the
+    /// type of the view. See [`CompositeField`].
+    Composite(TypeId),
+    /// The whole source, writing back only the fields of a
+    /// [`FieldMask`]. See [`MaskedField`].
+    Masked(MaskId),
+    /// A value derived from the whole source through a
+    /// [`Conversion`]. See [`VirtualField`].
+    Virtual(ConversionId),
+}
+
+impl FieldTarget {
+    /// The element index, for [`FieldTarget::Element`] targets.
+    #[inline]
+    pub fn index(&self) -> Option<usize> {
+        match self {
+            Self::Element(index) => Some(*index),
+            _ => None,
+        }
+    }
+}
+
+/// A single element of a collection field `C` on a source `S`.
+///
+/// Usually created through the [`path!`](crate::path) macro with a
+/// trailing `[index]`, but the index can just as well be computed at
+/// runtime with [`IndexedField::new`].
+pub struct IndexedField<S, C> {
+    field_acc: FieldAccessor<S, C>,
+    index: usize,
+}
+
+impl<S, C> IndexedField<S, C> {
+    pub const fn new(
+        field_acc: FieldAccessor<S, C>,
+        index: usize,
+    ) -> Self {
+        Self { field_acc, index }
+    }
+
+    /// The accessor of the whole collection.
+    #[inline]
+    pub fn collection(&self) -> &FieldAccessor<S, C> {
+        &self.field_acc
+    }
+
+    /// The index of the element within the collection.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<S, C> Clone for IndexedField<S, C> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, C> Copy for IndexedField<S, C> {}
+
+impl<S, C> ActionField<S, C::Element> for IndexedField<S, C>
+where
+    S: 'static,
+    C: ElementAccess + ThreadSafe,
+    C::Element: Clone + ThreadSafe,
+{
+    #[inline]
+    fn target(&self) -> (UntypedField, FieldTarget) {
+        (
+            self.field_acc.field.untyped(),
+            FieldTarget::Element(self.index),
+        )
+    }
+
+    fn register<W, I>(self, registry: &mut Registry) -> PipelineKey
+    where
+        S: 'static,
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+    {
+        registry.register_indexed::<W, I, S, C>(self.field_acc);
+        PipelineKey::new_indexed::<W, I, S, C>()
+    }
+}
+
+/// What an [`OptionField`] starts from when the field is `None` at
+/// bake time.
+///
+/// Kept per action rather than in its [`ActionKey`], so actions on
+/// the same field with different policies still share a sequence.
+/// The first action of the sequence decides.
+///
+/// [`ActionKey`]: crate::action::ActionKey
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub enum NonePolicy {
+    /// Start from `T::default()`.
+    #[default]
+    Default,
+    /// Leave the field untouched, none of its actions are baked.
+    Skip,
+}
+
+/// The value inside an `Option<T>` field on a source `S`.
+///
+/// Baking reads the inner value, falling back to the [`NonePolicy`]
+/// when the field is `None`. Sampling always writes `Some(value)`.
+///
+/// Usually created through the [`path!`](crate::path) macro with a
+/// trailing `?`.
+pub struct OptionField<S, T> {
+    field_acc: FieldAccessor<S, Option<T>>,
+    policy: NonePolicy,
+}
+
+impl<S, T> OptionField<S, T> {
+    pub const fn new(field_acc: FieldAccessor<S, Option<T>>) -> Self {
+        Self {
+            field_acc,
+            policy: NonePolicy::Default,
+        }
+    }
+
+    /// Sets the policy used when the field is `None` at bake time.
+    pub const fn with_policy(mut self, policy: NonePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// The accessor of the whole `Option` field.
+    #[inline]
+    pub fn option(&self) -> &FieldAccessor<S, Option<T>> {
+        &self.field_acc
+    }
+
+    #[inline]
+    pub fn policy(&self) -> NonePolicy {
+        self.policy
+    }
+}
+
+impl<S, T> Clone for OptionField<S, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, T> Copy for OptionField<S, T> {}
+
+impl<S, T> ActionField<S, T> for OptionField<S, T>
+where
+    S: 'static,
+    T: Clone + Default + ThreadSafe,
+{
+    #[inline]
+    fn target(&self) -> (UntypedField, FieldTarget) {
+        (self.field_acc.field.untyped(), FieldTarget::Inner)
+    }
+
+    #[inline]
+    fn none_policy(&self) -> NonePolicy {
+        self.policy
+    }
+
+    fn register<W, I>(self, registry: &mut Registry) -> PipelineKey
+    where
+        S: 'static,
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+    {
+        registry.register_optional::<W, I, S, T>(self.field_acc);
+        PipelineKey::new_optional::<W, I, S, T>()
+    }
+}
+
+/// Several fields of a source `S` read and written as one value.
+///
+/// Only the fields touched by [`Composite::write`] are animated, so
+/// other actions on the rest of `S` keep working.
+pub trait Composite<S>: Clone + ThreadSafe {
+    /// Reads the view from `source`, used as the start of baking.
+    fn read(source: &S) -> Self;
+
+    /// Writes a sampled view back into `source`.
+    fn write(self, source: &mut S);
+}
+
+/// A [`Composite`] view `T` of the whole source `S`, bound through
+/// the root field accessor of `S`, e.g. `path!(<S>)`.
+///
+/// Actions and interpolation operate on `T`, so a single closure
+/// updates every field of the view together.
+pub struct CompositeField<S, T> {
+    root: FieldAccessor<S, S>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<S, T> CompositeField<S, T> {
+    pub const fn new(root: FieldAccessor<S, S>) -> Self {
+        Self {
+            root,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The accessor of the whole source.
+    #[inline]
+    pub fn root(&self) -> &FieldAccessor<S, S> {
+        &self.root
+    }
+}
+
+impl<S, T> Clone for CompositeField<S, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<S, T> Copy for CompositeField<S, T> {}
+
+impl<S, T> ActionField<S, T> for CompositeField<S, T>
+where
+    S: ThreadSafe,
+    T: Composite<S>,
+{
+    #[inline]
+    fn target(&self) -> (UntypedField, FieldTarget) {
+        (
+            self.root.field.untyped(),
+            FieldTarget::Composite(TypeId::of::<T>()),
+        )
+    }
+
+    fn register<W, I>(self, registry: &mut Registry) -> PipelineKey
+    where
+        S: 'static,
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+    {
+        registry.register_composite::<W, I, S, T>(self.root);
+        PipelineKey::new_composite::<W, I, S, T>()
+    }
+}
+
+/// Unique id of a [`FieldMask`].
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct MaskId(usize);
+
+/// Copies one field from a sampled source into the animated one.
+type MaskWriter<S> = Box<dyn Fn(&S, &mut S) + Send + Sync>;
+
+/// The fields of a source `S` that a [`MaskedField`] action is
+/// allowed to write.
+///
+/// Every mask gets its own [`MaskId`]. Actions sharing a mask form a
+/// single sequence, actions with different masks never overwrite
+/// each other in the queue.
+pub struct FieldMask<S> {
+    id: MaskId,
+    writers: Vec<MaskWriter<S>>,
+}
+
+impl<S: 'static> FieldMask<S> {
+    /// Creates a mask with a fresh id and no fields.
+    pub fn new() -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        Self {
+            id: MaskId(NEXT_ID.fetch_add(1, Ordering::Relaxed)),
+            writers: Vec::new(),
+        }
+    }
+
+    /// Allows `field` to be written.
+    pub fn with<T>(mut self, field: FieldAccessor<S, T>) -> Self
+    where
+        T: Clone + ThreadSafe,
+    {
+        self.writers.push(Box::new(move |from, to| {
+            *field.accessor.get_mut(to) =
+                field.accessor.get_ref(from).clone();
+        }));
+        self
+    }
+
+    #[inline]
+    pub fn id(&self) -> MaskId {
+        self.id
+    }
+
+    /// Copies the masked fields of `from` into `to`.
+    pub fn apply(&self, from: &S, to: &mut S) {
+        for writer in self.writers.iter() {
+            writer(from, to);
+        }
+    }
+}
+
+impl<S: 'static> Default for FieldMask<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The whole source `S`, bound through its root field accessor, of
+/// which sampling only writes the fields in a [`FieldMask`].
+///
+/// Clones share the mask, so actions made from the same
+/// [`MaskedField`] chain like actions on a regular field.
+pub struct MaskedField<S> {
+    root: FieldAccessor<S, S>,
+    mask: Arc<FieldMask<S>>,
+}
+
+impl<S> MaskedField<S> {
+    pub fn new(
+        root: FieldAccessor<S, S>,
+        mask: FieldMask<S>,
+    ) -> Self {
+        Self {
+            root,
+            mask: Arc::new(mask),
+        }
+    }
+
+    /// The accessor of the whole source.
+    #[inline]
+    pub fn root(&self) -> &FieldAccessor<S, S> {
+        &self.root
+    }
+
+    #[inline]
+    pub fn mask(&self) -> &FieldMask<S> {
+        &self.mask
+    }
+}
+
+impl<S> Clone for MaskedField<S> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            mask: self.mask.clone(),
+        }
+    }
+}
+
+impl<S> ActionField<S, S> for MaskedField<S>
+where
+    S: Clone + ThreadSafe,
+{
+    #[inline]
+    fn target(&self) -> (UntypedField, FieldTarget) {
+        (self.root.field.untyped(), FieldTarget::Masked(self.mask.id))
+    }
+
+    fn register<W, I>(self, registry: &mut Registry) -> PipelineKey
+    where
+        S: 'static,
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+    {
+        registry.register_masked::<W, I, S>(self.root, self.mask);
+        PipelineKey::new_masked::<W, I, S>()
+    }
+}
+
+/// Unique id of a [`Conversion`].
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+pub struct ConversionId {
+    id: usize,
+    target_id: TypeId,
+}
+
+impl ConversionId {
+    /// The [`TypeId`] of the value the conversion produces.
+    #[inline]
+    pub fn target_id(&self) -> TypeId {
+        self.target_id
+    }
+}
+
+type Getter<S, T> = Box<dyn Fn(&S) -> T + Send + Sync>;
+type Setter<S, T> = Box<dyn Fn(&mut S, T) + Send + Sync>;
+
+/// A getter and setter deriving a `T` value from a source `S`, for
+/// values that are not stored on `S` as is.
+///
+/// Every conversion gets its own [`ConversionId`], like a
+/// [`FieldMask`].
+pub struct Conversion<S, T> {
+    id: ConversionId,
+    get: Getter<S, T>,
+    set: Setter<S, T>,
+}
+
+impl<S, T: 'static> Conversion<S, T> {
+    /// Creates a conversion with a fresh id.
+    pub fn new(
+        get: impl Fn(&S) -> T + ThreadSafe,
+        set: impl Fn(&mut S, T) + ThreadSafe,
+    ) -> Self {
+        static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+
+        Self {
+            id: ConversionId {
+                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                target_id: TypeId::of::<T>(),
+            },
+            get: Box::new(get),
+            set: Box::new(set),
+        }
+    }
+}
+
+impl<S, T> Conversion<S, T> {
+    #[inline]
+    pub fn id(&self) -> ConversionId {
+        self.id
+    }
+
+    /// Derives the value from `source`.
+    #[inline]
+    pub fn get(&self, source: &S) -> T {
+        (self.get)(source)
+    }
+
+    /// Writes `value` back into `source`.
+    #[inline]
+    pub fn set(&self, source: &mut S, value: T) {
+        (self.set)(source, value)
+    }
+}
+
+/// A virtual `T` field of a source `S`, bound through its root field
+/// accessor and read and written through a [`Conversion`].
+///
+/// Clones share the conversion, so actions made from the same
+/// [`VirtualField`] chain like actions on a regular field.
+pub struct VirtualField<S, T> {
+    root: FieldAccessor<S, S>,
+    conversion: Arc<Conversion<S, T>>,
+}
+
+impl<S, T: 'static> VirtualField<S, T> {
+    pub fn new(
+        root: FieldAccessor<S, S>,
+        get: impl Fn(&S) -> T + ThreadSafe,
+        set: impl Fn(&mut S, T) + ThreadSafe,
+    ) -> Self {
+        Self {
+            root,
+            conversion: Arc::new(Conversion::new(get, set)),
+        }
+    }
+}
+
+impl<S, T> VirtualField<S, T> {
+    /// The accessor of the whole source.
+    #[inline]
+    pub fn root(&self) -> &FieldAccessor<S, S> {
+        &self.root
+    }
+
+    #[inline]
+    pub fn conversion(&self) -> &Conversion<S, T> {
+        &self.conversion
+    }
+}
+
+impl<S, T> Clone for VirtualField<S, T> {
+    fn clone(&self) -> Self {
+        Self {
+            root: self.root,
+            conversion: self.conversion.clone(),
+        }
+    }
+}
+
+impl<S, T> ActionField<S, T> for VirtualField<S, T>
+where
+    S: ThreadSafe,
+    T: Clone + ThreadSafe,
+{
+    #[inline]
+    fn target(&self) -> (UntypedField, FieldTarget) {
+        (
+            self.root.field.untyped(),
+            FieldTarget::Virtual(self.conversion.id),
+        )
+    }
+
+    fn register<W, I>(self, registry: &mut Registry) -> PipelineKey
+    where
+        S: 'static,
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+    {
+        registry.register_virtual::<W, I, S, T>(
+            self.root,
+            self.conversion,
+        );
+        PipelineKey::new_virtual::<W, I, S, T>()
+    }
+}
+
+/// Bounds-checked element access for collection fields.
+///
+/// Implemented for sequences indexed by position and for maps keyed
+/// by `usize`.
+pub trait ElementAccess {
+    type Element;
+
+    fn element(&self, index: usize) -> Option<&Self::Element>;
+
+    fn element_mut(
+        &mut self,
+        index: usize,
+    ) -> Option<&mut Self::Element>;
+}
+
+impl<T> ElementAccess for Vec<T> {
+    type Element = T;
+
+    #[inline]
+    fn element(&self, index: usize) -> Option<&T> {
+        self.get(index)
+    }
+
+    #[inline]
+    fn element_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(index)
+    }
+}
+
+impl<T> ElementAccess for VecDeque<T> {
+    type Element = T;
+
+    #[inline]
+    fn element(&self, index: usize) -> Option<&T> {
+        self.get(index)
+    }
+
+    #[inline]
+    fn element_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(index)
+    }
+}
+
+impl<T, const N: usize> ElementAccess for [T; N] {
+    type Element = T;
+
+    #[inline]
+    fn element(&self, index: usize) -> Option<&T> {
+        self.get(index)
+    }
+
+    #[inline]
+    fn element_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(index)
+    }
+}
+
+impl<T> ElementAccess for Box<[T]> {
+    type Element = T;
+
+    #[inline]
+    fn element(&self, index: usize) -> Option<&T> {
+        self.get(index)
+    }
+
+    #[inline]
+    fn element_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(index)
+    }
+}
+
+impl<T> ElementAccess for BTreeMap<usize, T> {
+    type Element = T;
+
+    #[inline]
+    fn element(&self, index: usize) -> Option<&T> {
+        self.get(&index)
+    }
+
+    #[inline]
+    fn element_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(&index)
+    }
+}
+
+impl<T, H: BuildHasher> ElementAccess
+    for hashbrown::HashMap<usize, T, H>
+{
+    type Element = T;
+
+    #[inline]
+    fn element(&self, index: usize) -> Option<&T> {
+        self.get(&index)
+    }
+
+    #[inline]
+    fn element_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(&index)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, H: BuildHasher> ElementAccess
+    for std::collections::HashMap<usize, T, H>
+{
+    type Element = T;
+
+    #[inline]
+    fn element(&self, index: usize) -> Option<&T> {
+        self.get(&index)
+    }
+
+    #[inline]
+    fn element_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.get_mut(&index)
+    }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> ElementAccess for smallvec::SmallVec<A> {
+    type Element = A::Item;
+
+    #[inline]
+    fn element(&self, index: usize) -> Option<&A::Item> {
+        self.get(index)
+    }
+
+    #[inline]
+    fn element_mut(&mut self, index: usize) -> Option<&mut A::Item> {
+        self.get_mut(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use crate::prelude::*;
+
+    #[derive(Default, Clone)]
+    struct Chart {
+        values: Vec<f32>,
+        size: Option<f32>,
+        labels: hashbrown::HashMap<usize, f32>,
+        x: f32,
+        width: f32,
+    }
+
+    #[derive(Clone)]
+    struct LeftAnchored {
+        left: f32,
+        width: f32,
+    }
+
+    impl Composite<Chart> for LeftAnchored {
+        fn read(chart: &Chart) -> Self {
+            Self {
+                left: chart.x - chart.width * 0.5,
+                width: chart.width,
+            }
+        }
+
+        fn write(self, chart: &mut Chart) {
+            chart.x = self.left + self.width * 0.5;
+            chart.width = self.width;
+        }
+    }
+
+    struct World(Chart);
+
+    impl SubjectSource<u32, Chart> for World {
+        fn get_source(&self, _id: u32) -> Option<&Chart> {
+            Some(&self.0)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            _id: u32,
+            f: impl FnOnce(&mut Chart) -> R,
+        ) -> Option<R> {
+            Some(f(&mut self.0))
+        }
+    }
+
+    fn sample_at(
+        registry: &Registry,
+        timeline: &mut Timeline<World>,
+        world: &mut World,
+        time: core::time::Duration,
+    ) {
+        timeline.set_target_time(time);
+        timeline.queue_actions();
+        timeline.sample_queued_actions(registry, world);
+    }
+
+    #[test]
+    fn indexed_action_only_touches_its_element() {
+        let mut registry = Registry::new();
+        let mut world = World(Chart {
+            values: vec![0.0, 0.0, 0.0],
+            ..Default::default()
+        });
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act(0, path!(<Chart>::values[1]), |x| x + 10.0)
+                .play(s(1)),
+            b.act(0, path!(<Chart>::values[2]), |x| x - 10.0)
+                .play(s(1)),
+        ]
+        .ord_all()
+        .compile();
+        b.add_tracks(track);
+        let mut timeline = b.compile();
+
+        timeline.bake_actions(&registry, &world);
+        sample_at(&registry, &mut timeline, &mut world, cs(50));
+
+        assert_eq!(world.0.values, [0.0, 5.0, -5.0]);
+    }
+
+    #[test]
+    fn chained_element_actions_bake_in_sequence() {
+        let mut registry = Registry::new();
+        let mut world = World(Chart {
+            values: vec![1.0, 2.0],
+            ..Default::default()
+        });
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act(0, path!(<Chart>::values[0]), |x| x * 2.0)
+                .play(s(1)),
+            b.act(0, path!(<Chart>::values[0]), |x| x + 1.0)
+                .play(s(1)),
+        ]
+        .ord_chain()
+        .compile();
+        b.add_tracks(track);
+        let mut timeline = b.compile();
+
+        timeline.bake_actions(&registry, &world);
+        sample_at(&registry, &mut timeline, &mut world, s(2));
+
+        assert_eq!(world.0.values, [3.0, 2.0]);
+    }
+
+    #[test]
+    fn out_of_bounds_index_is_skipped() {
+        let mut registry = Registry::new();
+        let mut world = World(Chart {
+            values: vec![1.0],
+            ..Default::default()
+        });
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b
+            .act(0, path!(<Chart>::values[4]), |x| x + 1.0)
+            .play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+
+        timeline.bake_actions(&registry, &world);
+        sample_at(&registry, &mut timeline, &mut world, s(1));
+
+        assert_eq!(world.0.values, [1.0]);
+    }
+
+    #[test]
+    fn map_element_is_addressed_by_key() {
+        let mut registry = Registry::new();
+        let mut world = World(Chart {
+            labels: [(7, 1.0), (9, 1.0)].into_iter().collect(),
+            ..Default::default()
+        });
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act(0, path!(<Chart>::labels[9]), |x| x + 1.0)
+                .play(s(1)),
+            b.act(0, path!(<Chart>::labels[3]), |x| x + 1.0)
+                .play(s(1)),
+        ]
+        .ord_all()
+        .compile();
+        b.add_tracks(track);
+        let mut timeline = b.compile();
+
+        timeline.bake_actions(&registry, &world);
+        sample_at(&registry, &mut timeline, &mut world, s(1));
+
+        assert_eq!(world.0.labels[&7], 1.0);
+        assert_eq!(world.0.labels[&9], 2.0);
+        assert!(!world.0.labels.contains_key(&3));
+    }
+
+    #[test]
+    fn composite_action_writes_its_fields_together() {
+        let mut registry = Registry::new();
+        let mut world = World(Chart {
+            values: vec![0.0],
+            x: 1.0,
+            width: 2.0,
+            ..Default::default()
+        });
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let grow =
+            CompositeField::<_, LeftAnchored>::new(path!(<Chart>));
+        let track = [
+            b.act_builder(0, grow, |v: &LeftAnchored| LeftAnchored {
+                width: v.width * 2.0,
+                ..v.clone()
+            })
+            .with_interp(|a, b, t| LeftAnchored {
+                left: a.left + (b.left - a.left) * t,
+                width: a.width + (b.width - a.width) * t,
+            })
+            .play(s(1)),
+            b.act(0, path!(<Chart>::values[0]), |x| x + 1.0)
+                .play(s(1)),
+        ]
+        .ord_all()
+        .compile();
+        b.add_tracks(track);
+        let mut timeline = b.compile();
+
+        timeline.bake_actions(&registry, &world);
+        sample_at(&registry, &mut timeline, &mut world, cs(50));
+
+        // The left edge stays at 0.0 while the width grows.
+        assert_eq!(world.0.width, 3.0);
+        assert_eq!(world.0.x, 1.5);
+        assert_eq!(world.0.values, [0.5]);
+    }
+
+    #[test]
+    fn masked_action_leaves_other_fields_alone() {
+        let mut registry = Registry::new();
+        let mut world = World(Chart {
+            values: vec![0.0],
+            x: 1.0,
+            width: 2.0,
+            ..Default::default()
+        });
+
+        let mask = FieldMask::new()
+            .with(path!(<Chart>::x))
+            .with(path!(<Chart>::width));
+        let chart = MaskedField::new(path!(<Chart>), mask);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act_builder(0, chart, |c: &Chart| Chart {
+                x: c.x + 1.0,
+                width: c.width * 2.0,
+                // Ignored by the mask.
+                values: vec![100.0],
+                ..c.clone()
+            })
+            .with_interp(|a, b, t| Chart {
+                x: a.x + (b.x - a.x) * t,
+                width: a.width + (b.width - a.width) * t,
+                ..b.clone()
+            })
+            .play(s(1)),
+            b.act(0, path!(<Chart>::values[0]), |x| x + 1.0)
+                .play(s(1)),
+        ]
+        .ord_all()
+        .compile();
+        b.add_tracks(track);
+        let mut timeline = b.compile();
+
+        timeline.bake_actions(&registry, &world);
+        sample_at(&registry, &mut timeline, &mut world, cs(50));
+
+        assert_eq!(world.0.x, 1.5);
+        assert_eq!(world.0.width, 3.0);
+        assert_eq!(world.0.values, [0.5]);
+    }
+
+    #[test]
+    fn virtual_field_converts_on_read_and_write() {
+        let mut registry = Registry::new();
+        let mut world = World(Chart {
+            x: 1.0,
+            width: 2.0,
+            ..Default::default()
+        });
+
+        // The right edge, moved by shifting the chart.
+        let right = VirtualField::new(
+            path!(<Chart>),
+            |c: &Chart| c.x + c.width * 0.5,
+            |c: &mut Chart, right: f32| c.x = right - c.width * 0.5,
+        );
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act(0, right.clone(), |r| r + 2.0).play(s(1)),
+            b.act(0, right, |r| r * 2.0).play(s(1)),
+        ]
+        .ord_chain()
+        .compile();
+        b.add_tracks(track);
+        let mut timeline = b.compile();
+
+        timeline.bake_actions(&registry, &world);
+        sample_at(&registry, &mut timeline, &mut world, s(1));
+        assert_eq!(world.0.x, 3.0);
+
+        sample_at(&registry, &mut timeline, &mut world, s(2));
+        assert_eq!(world.0.x, 7.0);
+        assert_eq!(world.0.width, 2.0);
+    }
+
+    fn play_size(
+        world: &mut World,
+        field: OptionField<Chart, f32>,
+    ) -> Option<f32> {
+        let mut registry = Registry::new();
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b.act(0, field, |x| x + 4.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+
+        timeline.bake_actions(&registry, world);
+        sample_at(&registry, &mut timeline, world, cs(50));
+        world.0.size
+    }
+
+    #[test]
+    fn option_field_animates_inner_value() {
+        let mut world = World(Chart {
+            size: Some(2.0),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            play_size(&mut world, path!(<Chart>::size?)),
+            Some(4.0)
+        );
+    }
+
+    #[test]
+    fn option_field_none_policies() {
+        let mut world = World(Chart::default());
+        assert_eq!(
+            play_size(&mut world, path!(<Chart>::size?)),
+            Some(2.0)
+        );
+
+        let mut world = World(Chart::default());
+        let skip =
+            path!(<Chart>::size?).with_policy(NonePolicy::Skip);
+        assert_eq!(play_size(&mut world, skip), None);
+    }
+
+    #[test]
+    fn none_policies_share_the_sequence_of_their_field() {
+        let size = path!(<Chart>::size?);
+        let skip = size.with_policy(NonePolicy::Skip);
+
+        let play = |first, second| {
+            let mut registry = Registry::new();
+            let mut world = World(Chart::default());
+
+            let mut b = TimelineBuilder::<World>::new(&mut registry);
+            let track = [
+                b.act(0, first, |x| x + 4.0).play(s(1)),
+                b.act(0, second, |x| x * 2.0).play(s(1)),
+            ]
+            .ord_chain()
+            .compile();
+            assert_eq!(track.sequences_spans().len(), 1);
+
+            b.add_tracks(track);
+            let mut timeline = b.compile();
+            timeline.bake_actions(&registry, &world);
+            sample_at(&registry, &mut timeline, &mut world, s(2));
+            world.0.size
+        };
+
+        // The first action decides for the whole sequence.
+        assert_eq!(play(size, skip), Some(8.0));
+        assert_eq!(play(skip, size), None);
+    }
+}
@@ -0,0 +1,1198 @@
+//! A world-free description of a timeline.
+//!
+//! [`TimelineGraph`] records *what* should be animated: subjects by
+//! name, fields, eases, durations, and how fragments are ordered. No
+//! subject id is looked up and no [`Registry`] is touched while
+//! authoring, which keeps the graph cheap to build, inspect, and test
+//! in isolation.
+//!
+//! [`TimelineGraph::resolve`] is the second phase: it binds every
+//! subject name to a concrete [`SubjectId`] and replays the graph
+//! through a regular [`TimelineBuilder`] to produce a [`Timeline`].
+//! The graph sits on top of the builder rather than under it, so
+//! builder-only features such as curves and modifiers can't be
+//! described in a graph.
+//! Apart from the action closures and eases, everything in the graph
+//! is plain data. With the `serde` feature, the [`GraphFragment`]
+//! ordering tree can be serialized on its own, while whole timelines
+//! are stored as a [`TimelineDef`] instead.
+//!
+//! [`TimelineDef`]: crate::definition::TimelineDef
+//! [`TimelineBuilder`]: crate::timeline::TimelineBuilder
+
+use core::fmt;
+use core::time::Duration;
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use field_path::field::UntypedField;
+
+use crate::ThreadSafe;
+use crate::action::{Action, EaseFn};
+use crate::field::{ActionField, FieldTarget};
+use crate::interpolation::Interpolation;
+use crate::registry::Registry;
+use crate::subject::SubjectId;
+use crate::timeline::{Timeline, TimelineBuilder};
+use crate::track::{self, Alignment, TrackFragment};
+use crate::world::SubjectSource;
+
+/// Replays a single graph action into a [`TimelineBuilder`].
+type BuildFn<W, I> = Arc<
+    dyn Fn(
+            &mut TimelineBuilder<'_, W>,
+            I,
+            Option<EaseFn>,
+            Duration,
+        ) -> TrackFragment
+        + Send
+        + Sync,
+>;
+
+/// Index of an action inside a [`TimelineGraph`].
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub struct GraphActionId(usize);
+
+impl GraphActionId {
+    /// The position of the action in [`TimelineGraph::actions`].
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.0
+    }
+}
+
+/// The inspectable part of a graph action.
+#[derive(Debug, Clone)]
+pub struct ActionDesc {
+    /// Name of the subject, bound during [`TimelineGraph::resolve`].
+    pub subject: String,
+    /// The field being animated.
+    pub field: UntypedField,
+    /// The part of `field` being animated.
+    pub target: FieldTarget,
+    /// Optional easing applied when the action is resolved.
+    pub ease: Option<EaseFn>,
+}
+
+struct ActionNode<W, I> {
+    desc: ActionDesc,
+    build: BuildFn<W, I>,
+}
+
+impl<W, I> Clone for ActionNode<W, I> {
+    fn clone(&self) -> Self {
+        Self {
+            desc: self.desc.clone(),
+            build: self.build.clone(),
+        }
+    }
+}
+
+/// A node in the ordering tree of a [`TimelineGraph`].
+///
+/// Mirrors the combinators in [`track`] one-to-one, so resolving a
+/// fragment produces exactly the [`TrackFragment`] the direct builder
+/// would have.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum GraphFragment {
+    /// A single action played for a duration.
+    Clip {
+        action: GraphActionId,
+        duration: Duration,
+    },
+    /// See [`track::chain`].
+    Chain(Vec<GraphFragment>),
+    /// See [`track::all`].
+    All(Vec<GraphFragment>),
+    /// See [`track::any`].
+    Any(Vec<GraphFragment>),
+    /// See [`track::flow`].
+    Flow {
+        delay: Duration,
+        fragments: Vec<GraphFragment>,
+    },
+    /// See [`track::overlap`].
+    Overlap {
+        overlap: Duration,
+        fragments: Vec<GraphFragment>,
+    },
+    /// See [`track::align`].
+    Align {
+        alignment: Alignment,
+        fragments: Vec<GraphFragment>,
+    },
+    /// See [`track::delay`].
+    Delay {
+        delay: Duration,
+        fragment: Box<GraphFragment>,
+    },
+}
+
+impl GraphFragment {
+    /// Run all fragments one after another.
+    pub fn chain(
+        fragments: impl IntoIterator<Item = GraphFragment>,
+    ) -> Self {
+        Self::Chain(fragments.into_iter().collect())
+    }
+
+    /// Run all fragments concurrently and wait for all of them.
+    pub fn all(
+        fragments: impl IntoIterator<Item = GraphFragment>,
+    ) -> Self {
+        Self::All(fragments.into_iter().collect())
+    }
+
+    /// Run all fragments concurrently and wait for any of them.
+    pub fn any(
+        fragments: impl IntoIterator<Item = GraphFragment>,
+    ) -> Self {
+        Self::Any(fragments.into_iter().collect())
+    }
+
+    /// Run one fragment after another with a fixed delay.
+    pub fn flow(
+        delay: Duration,
+        fragments: impl IntoIterator<Item = GraphFragment>,
+    ) -> Self {
+        Self::Flow {
+            delay,
+            fragments: fragments.into_iter().collect(),
+        }
+    }
+
+    /// Run one fragment after another, each starting `overlap`
+    /// before the previous one ends.
+    pub fn overlap(
+        overlap: Duration,
+        fragments: impl IntoIterator<Item = GraphFragment>,
+    ) -> Self {
+        Self::Overlap {
+            overlap,
+            fragments: fragments.into_iter().collect(),
+        }
+    }
+
+    /// Run all fragments concurrently, placed by `alignment`.
+    pub fn align(
+        alignment: Alignment,
+        fragments: impl IntoIterator<Item = GraphFragment>,
+    ) -> Self {
+        Self::Align {
+            alignment,
+            fragments: fragments.into_iter().collect(),
+        }
+    }
+
+    /// Run this fragment after a fixed delay.
+    pub fn delayed(self, delay: Duration) -> Self {
+        Self::Delay {
+            delay,
+            fragment: Box::new(self),
+        }
+    }
+
+    /// The duration this fragment resolves to, computed with the
+    /// same saturating rules as the [`track`] combinators.
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Clip { duration, .. } => *duration,
+            Self::Chain(fragments) => {
+                fragments.iter().fold(Duration::ZERO, |acc, f| {
+                    acc.saturating_add(f.duration())
+                })
+            }
+            Self::All(fragments) => fragments
+                .iter()
+                .map(Self::duration)
+                .max()
+                .unwrap_or_default(),
+            Self::Any(fragments) => fragments
+                .iter()
+                .map(Self::duration)
+                .min()
+                .unwrap_or_default(),
+            Self::Flow { delay, fragments } => {
+                let mut flow_delay = Duration::ZERO;
+                let mut duration = Duration::ZERO;
+
+                for (i, fragment) in fragments.iter().enumerate() {
+                    if i > 0 {
+                        flow_delay =
+                            flow_delay.saturating_add(*delay);
+                    }
+                    duration = flow_delay
+                        .saturating_add(fragment.duration())
+                        .max(duration);
+                }
+
+                duration
+            }
+            Self::Overlap { overlap, fragments } => {
+                let mut start = Duration::ZERO;
+                let mut prev = Duration::ZERO;
+                let mut duration = Duration::ZERO;
+
+                for (i, fragment) in fragments.iter().enumerate() {
+                    if i > 0 {
+                        start = start.saturating_add(
+                            prev.saturating_sub(*overlap),
+                        );
+                    }
+                    prev = fragment.duration();
+                    duration =
+                        start.saturating_add(prev).max(duration);
+                }
+
+                duration
+            }
+            Self::Align {
+                alignment,
+                fragments,
+            } => {
+                let longest = fragments
+                    .iter()
+                    .map(Self::duration)
+                    .max()
+                    .unwrap_or_default();
+
+                fragments
+                    .iter()
+                    .map(|f| {
+                        let duration = f.duration();
+                        alignment
+                            .offset(duration, longest)
+                            .saturating_add(duration)
+                    })
+                    .max()
+                    .unwrap_or_default()
+            }
+            Self::Delay { delay, fragment } => {
+                fragment.duration().saturating_add(*delay)
+            }
+        }
+    }
+
+    /// Returns `true` if any clip of this fragment plays `action`.
+    fn plays(&self, action: GraphActionId) -> bool {
+        match self {
+            Self::Clip { action: a, .. } => *a == action,
+            Self::Chain(fragments)
+            | Self::All(fragments)
+            | Self::Any(fragments)
+            | Self::Flow { fragments, .. }
+            | Self::Overlap { fragments, .. }
+            | Self::Align { fragments, .. } => {
+                fragments.iter().any(|f| f.plays(action))
+            }
+            Self::Delay { fragment, .. } => fragment.plays(action),
+        }
+    }
+
+    fn retime(&mut self, action: GraphActionId, duration: Duration) {
+        match self {
+            Self::Clip {
+                action: a,
+                duration: d,
+            } => {
+                if *a == action {
+                    *d = duration;
+                }
+            }
+            Self::Chain(fragments)
+            | Self::All(fragments)
+            | Self::Any(fragments)
+            | Self::Flow { fragments, .. }
+            | Self::Overlap { fragments, .. }
+            | Self::Align { fragments, .. } => {
+                for f in fragments.iter_mut() {
+                    f.retime(action, duration);
+                }
+            }
+            Self::Delay { fragment, .. } => {
+                fragment.retime(action, duration)
+            }
+        }
+    }
+}
+
+/// The state of a [`TimelineGraph`] to return to on rollback, undo
+/// or redo.
+///
+/// Action closures are shared, so cloning the nodes only copies their
+/// descriptions.
+struct Checkpoint<W, I> {
+    actions: Vec<ActionNode<W, I>>,
+    tracks: Vec<GraphFragment>,
+}
+
+/// A runtime edit that can be merged with the previous one of the
+/// same kind, e.g. every step of dragging a clip edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EditKey {
+    Retime(GraphActionId),
+    Retarget(GraphActionId),
+    Ease(GraphActionId),
+}
+
+/// A world-free, two-phase alternative to [`TimelineBuilder`].
+///
+/// Subjects are referred to by name and only bound to `I` when
+/// [`Self::resolve`] runs.
+///
+/// Edits can be grouped with [`Self::begin`], [`Self::commit`] and
+/// [`Self::rollback`], so that multi-step edits (pasting many clips,
+/// retiming a selection) apply and [undo](Self::undo) atomically.
+///
+/// Runtime edits made through [`Self::retime`], [`Self::retarget`]
+/// and [`Self::set_ease`] outside of a transaction record their own
+/// undo step. Repeated edits of the same kind on the same action are
+/// coalesced into one step until [`Self::end_coalescing`], so a drag
+/// undoes in one go. At most [`Self::history_limit`] steps are kept.
+pub struct TimelineGraph<W, I> {
+    actions: Vec<ActionNode<W, I>>,
+    tracks: Vec<GraphFragment>,
+    /// Checkpoints of the open transactions, innermost last.
+    transactions: Vec<Checkpoint<W, I>>,
+    /// Checkpoints to undo to, oldest first.
+    undo_stack: VecDeque<Checkpoint<W, I>>,
+    /// Checkpoints to redo to, most recently undone last.
+    redo_stack: Vec<Checkpoint<W, I>>,
+    /// The last runtime edit, while it can still be coalesced.
+    last_edit: Option<EditKey>,
+    history_limit: usize,
+}
+
+impl<W, I> TimelineGraph<W, I>
+where
+    W: 'static,
+    I: SubjectId,
+{
+    /// Creates an empty graph.
+    pub fn new() -> Self {
+        Self {
+            actions: Vec::new(),
+            tracks: Vec::new(),
+            transactions: Vec::new(),
+            undo_stack: VecDeque::new(),
+            redo_stack: Vec::new(),
+            last_edit: None,
+            history_limit: Self::DEFAULT_HISTORY_LIMIT,
+        }
+    }
+
+    /// The number of undo steps kept by default.
+    pub const DEFAULT_HISTORY_LIMIT: usize = 128;
+
+    /// Describe an [`Action`] with interpolation using
+    /// [`Interpolation::interp`].
+    pub fn act<S, T, M>(
+        &mut self,
+        subject: impl Into<String>,
+        field: impl ActionField<S, T> + Clone + ThreadSafe,
+        action: impl Action<T>,
+    ) -> GraphActionBuilder<'_, W, I>
+    where
+        W: SubjectSource<I, S>,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+        M: 'static,
+    {
+        let target = field.target();
+        let action = Arc::new(action);
+
+        self.push_action(
+            subject.into(),
+            target,
+            move |b, id, ease, d| {
+                let action = action.clone();
+                let builder = b.act::<I, S, T, M>(
+                    id,
+                    field.clone(),
+                    move |x| action(x),
+                );
+
+                match ease {
+                    Some(ease) => builder.with_ease(ease),
+                    None => builder,
+                }
+                .play(d)
+            },
+        )
+    }
+
+    /// Describe an [`Action`] using step interpolation.
+    pub fn act_step<S, T>(
+        &mut self,
+        subject: impl Into<String>,
+        field: impl ActionField<S, T> + Clone + ThreadSafe,
+        action: impl Action<T>,
+    ) -> GraphActionBuilder<'_, W, I>
+    where
+        W: SubjectSource<I, S>,
+        S: 'static,
+        T: Clone + ThreadSafe,
+    {
+        let target = field.target();
+        let action = Arc::new(action);
+
+        self.push_action(
+            subject.into(),
+            target,
+            move |b, id, ease, d| {
+                let action = action.clone();
+                let builder =
+                    b.act_step(id, field.clone(), move |x| action(x));
+
+                match ease {
+                    Some(ease) => builder.with_ease(ease),
+                    None => builder,
+                }
+                .play(d)
+            },
+        )
+    }
+
+    /// Add a track described by `fragment` to the graph.
+    pub fn add_track(
+        &mut self,
+        fragment: GraphFragment,
+    ) -> &mut Self {
+        self.touch();
+        self.tracks.push(fragment);
+        self
+    }
+
+    /// Starts a transaction. Transactions nest, only committing the
+    /// outermost one records an undo step.
+    pub fn begin(&mut self) -> &mut Self {
+        let checkpoint = self.checkpoint();
+        self.transactions.push(checkpoint);
+        self
+    }
+
+    /// Keeps the edits of the innermost transaction.
+    ///
+    /// Returns `false` if no transaction is open.
+    pub fn commit(&mut self) -> bool {
+        let Some(checkpoint) = self.transactions.pop() else {
+            return false;
+        };
+
+        if self.transactions.is_empty() {
+            self.push_undo(checkpoint);
+            self.redo_stack.clear();
+        }
+        true
+    }
+
+    /// Discards the edits of the innermost transaction.
+    ///
+    /// Returns `false` if no transaction is open.
+    pub fn rollback(&mut self) -> bool {
+        let Some(checkpoint) = self.transactions.pop() else {
+            return false;
+        };
+
+        self.restore(checkpoint);
+        true
+    }
+
+    /// Reverts the last committed transaction, along with any edit
+    /// made outside of a transaction since.
+    ///
+    /// Returns `false` if there is nothing to undo or a transaction
+    /// is still open.
+    pub fn undo(&mut self) -> bool {
+        if self.in_transaction() {
+            return false;
+        }
+        let Some(checkpoint) = self.undo_stack.pop_back() else {
+            return false;
+        };
+
+        let current = self.checkpoint();
+        self.redo_stack.push(current);
+        self.restore(checkpoint);
+        true
+    }
+
+    /// Reapplies the last undone step.
+    ///
+    /// Returns `false` if there is nothing to redo or a transaction
+    /// is still open. Any new edit clears the redo history.
+    pub fn redo(&mut self) -> bool {
+        if self.in_transaction() {
+            return false;
+        }
+        let Some(checkpoint) = self.redo_stack.pop() else {
+            return false;
+        };
+
+        let current = self.checkpoint();
+        self.push_undo(current);
+        self.restore(checkpoint);
+        true
+    }
+
+    /// Sets the duration of every clip playing `action`.
+    ///
+    /// Returns `false` if no clip plays `action`.
+    pub fn retime(
+        &mut self,
+        action: GraphActionId,
+        duration: Duration,
+    ) -> bool {
+        let plays = |f: &GraphFragment| f.plays(action);
+        if !self.tracks.iter().any(plays) {
+            return false;
+        }
+
+        self.record(EditKey::Retime(action));
+        for track in self.tracks.iter_mut() {
+            track.retime(action, duration);
+        }
+        true
+    }
+
+    /// Binds `action` to another subject name.
+    ///
+    /// Returns `false` if `action` does not exist.
+    pub fn retarget(
+        &mut self,
+        action: GraphActionId,
+        subject: impl Into<String>,
+    ) -> bool {
+        if action.0 >= self.actions.len() {
+            return false;
+        }
+
+        self.record(EditKey::Retarget(action));
+        self.actions[action.0].desc.subject = subject.into();
+        true
+    }
+
+    /// Replaces the easing method of `action`.
+    ///
+    /// Returns `false` if `action` does not exist.
+    pub fn set_ease(
+        &mut self,
+        action: GraphActionId,
+        ease: Option<EaseFn>,
+    ) -> bool {
+        if action.0 >= self.actions.len() {
+            return false;
+        }
+
+        self.record(EditKey::Ease(action));
+        self.actions[action.0].desc.ease = ease;
+        true
+    }
+
+    /// Stops the next runtime edit from being coalesced into the
+    /// previous undo step, e.g. when a drag is released.
+    pub fn end_coalescing(&mut self) {
+        self.last_edit = None;
+    }
+
+    /// The maximum number of undo steps kept.
+    pub fn history_limit(&self) -> usize {
+        self.history_limit
+    }
+
+    /// Sets the maximum number of undo steps kept, dropping the
+    /// oldest ones beyond it.
+    pub fn set_history_limit(&mut self, limit: usize) -> &mut Self {
+        self.history_limit = limit;
+        while self.undo_stack.len() > limit {
+            self.undo_stack.pop_front();
+        }
+        self
+    }
+
+    /// Number of steps [`Self::undo`] can revert.
+    pub fn undo_len(&self) -> usize {
+        self.undo_stack.len()
+    }
+
+    /// Number of steps [`Self::redo`] can reapply.
+    pub fn redo_len(&self) -> usize {
+        self.redo_stack.len()
+    }
+
+    /// Returns `true` while a transaction is open.
+    pub fn in_transaction(&self) -> bool {
+        !self.transactions.is_empty()
+    }
+
+    /// Descriptions of every action, indexable by
+    /// [`GraphActionId::index`].
+    pub fn actions(&self) -> impl Iterator<Item = &ActionDesc> {
+        self.actions.iter().map(|node| &node.desc)
+    }
+
+    /// Get the description of a single action.
+    pub fn action(&self, id: GraphActionId) -> Option<&ActionDesc> {
+        self.actions.get(id.0).map(|node| &node.desc)
+    }
+
+    /// The ordering tree of every track, in insertion order.
+    pub fn tracks(&self) -> &[GraphFragment] {
+        &self.tracks
+    }
+
+    /// Mutable access to the tracks, e.g. to retime clips.
+    pub fn tracks_mut(&mut self) -> &mut [GraphFragment] {
+        self.touch();
+        &mut self.tracks
+    }
+
+    /// Bind every subject name through `resolver` and compile the
+    /// graph into a [`Timeline`].
+    ///
+    /// The graph is left untouched, so it can be resolved again
+    /// against different subjects.
+    pub fn resolve(
+        &self,
+        registry: &mut Registry,
+        resolver: impl Fn(&str) -> Option<I>,
+    ) -> Result<Timeline<W>, ResolveError> {
+        let ids = self
+            .actions
+            .iter()
+            .map(|node| {
+                resolver(&node.desc.subject).ok_or_else(|| {
+                    ResolveError::UnboundSubject(
+                        node.desc.subject.clone(),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut builder = registry.create_builder::<W>();
+        for fragment in self.tracks.iter() {
+            let track = self
+                .resolve_fragment(&mut builder, &ids, fragment)?
+                .compile();
+            builder.add_tracks(track);
+        }
+
+        builder.try_compile().ok_or(ResolveError::Empty)
+    }
+
+    fn resolve_fragment(
+        &self,
+        builder: &mut TimelineBuilder<'_, W>,
+        ids: &[I],
+        fragment: &GraphFragment,
+    ) -> Result<TrackFragment, ResolveError> {
+        let mut resolve_all = |fragments: &[GraphFragment]| {
+            fragments
+                .iter()
+                .map(|f| self.resolve_fragment(builder, ids, f))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(match fragment {
+            GraphFragment::Clip { action, duration } => {
+                // Clips can name any action once edited through
+                // `tracks_mut` or deserialized.
+                let (Some(node), Some(&id)) =
+                    (self.actions.get(action.0), ids.get(action.0))
+                else {
+                    return Err(ResolveError::UnknownAction(*action));
+                };
+                (node.build)(builder, id, node.desc.ease, *duration)
+            }
+            GraphFragment::Chain(fragments) => {
+                track::chain(resolve_all(fragments)?)
+            }
+            GraphFragment::All(fragments) => {
+                track::all(resolve_all(fragments)?)
+            }
+            GraphFragment::Any(fragments) => {
+                track::any(resolve_all(fragments)?)
+            }
+            GraphFragment::Flow { delay, fragments } => {
+                track::flow(*delay, resolve_all(fragments)?)
+            }
+            GraphFragment::Overlap { overlap, fragments } => {
+                track::overlap(*overlap, resolve_all(fragments)?)
+            }
+            GraphFragment::Align {
+                alignment,
+                fragments,
+            } => track::align(*alignment, resolve_all(fragments)?),
+            GraphFragment::Delay { delay, fragment } => track::delay(
+                *delay,
+                self.resolve_fragment(builder, ids, fragment)?,
+            ),
+        })
+    }
+
+    fn checkpoint(&self) -> Checkpoint<W, I> {
+        Checkpoint {
+            actions: self.actions.clone(),
+            tracks: self.tracks.clone(),
+        }
+    }
+
+    fn restore(&mut self, checkpoint: Checkpoint<W, I>) {
+        self.actions = checkpoint.actions;
+        self.tracks = checkpoint.tracks;
+        self.last_edit = None;
+    }
+
+    fn push_undo(&mut self, checkpoint: Checkpoint<W, I>) {
+        self.last_edit = None;
+        if self.history_limit == 0 {
+            return;
+        }
+        if self.undo_stack.len() == self.history_limit {
+            self.undo_stack.pop_front();
+        }
+        self.undo_stack.push_back(checkpoint);
+    }
+
+    /// Records an undo step before a runtime edit, unless it is part
+    /// of a transaction or coalesced into the previous step.
+    fn record(&mut self, key: EditKey) {
+        if self.in_transaction() {
+            return;
+        }
+        self.redo_stack.clear();
+        if self.last_edit == Some(key) {
+            return;
+        }
+
+        let checkpoint = self.checkpoint();
+        self.push_undo(checkpoint);
+        self.last_edit = Some(key);
+    }
+
+    /// Marks an authoring edit, which is not recorded on its own but
+    /// still invalidates redo and coalescing.
+    fn touch(&mut self) {
+        if !self.in_transaction() {
+            self.redo_stack.clear();
+        }
+        self.last_edit = None;
+    }
+
+    fn push_action(
+        &mut self,
+        subject: String,
+        (field, target): (UntypedField, FieldTarget),
+        build: impl Fn(
+            &mut TimelineBuilder<'_, W>,
+            I,
+            Option<EaseFn>,
+            Duration,
+        ) -> TrackFragment
+        + Send
+        + Sync
+        + 'static,
+    ) -> GraphActionBuilder<'_, W, I> {
+        self.touch();
+        let id = GraphActionId(self.actions.len());
+        self.actions.push(ActionNode {
+            desc: ActionDesc {
+                subject,
+                field,
+                target,
+                ease: None,
+            },
+            build: Arc::new(build),
+        });
+
+        GraphActionBuilder {
+            node: &mut self.actions[id.0],
+            id,
+        }
+    }
+}
+
+impl<W, I> Default for TimelineGraph<W, I>
+where
+    W: 'static,
+    I: SubjectId,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Configures a freshly described graph action.
+pub struct GraphActionBuilder<'g, W, I> {
+    node: &'g mut ActionNode<W, I>,
+    id: GraphActionId,
+}
+
+impl<W, I> GraphActionBuilder<'_, W, I> {
+    /// Set the easing method of the action.
+    pub fn with_ease(self, ease: EaseFn) -> Self {
+        self.node.desc.ease = Some(ease);
+        self
+    }
+
+    /// Get the [`GraphActionId`] of the containing action.
+    pub fn id(&self) -> GraphActionId {
+        self.id
+    }
+
+    /// Confirms the configuration of the action and creates a
+    /// [`GraphFragment::Clip`].
+    pub fn play(self, duration: Duration) -> GraphFragment {
+        GraphFragment::Clip {
+            action: self.id,
+            duration,
+        }
+    }
+}
+
+/// Reasons [`TimelineGraph::resolve`] can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolveError {
+    /// The resolver returned `None` for this subject name.
+    UnboundSubject(String),
+    /// A clip plays an action that is not in the graph.
+    UnknownAction(GraphActionId),
+    /// The graph has no tracks to compile.
+    Empty,
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnboundSubject(name) => {
+                write!(f, "no subject bound to \"{name}\"")
+            }
+            Self::UnknownAction(action) => write!(
+                f,
+                "no action {} in the timeline graph",
+                action.index()
+            ),
+            Self::Empty => write!(f, "timeline graph has no tracks"),
+        }
+    }
+}
+
+impl core::error::Error for ResolveError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::time::{cs, s};
+
+    use super::*;
+
+    struct World(Vec<f32>);
+
+    impl SubjectSource<usize, f32> for World {
+        fn get_source(&self, id: usize) -> Option<&f32> {
+            self.0.get(id)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            id: usize,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            self.0.get_mut(id).map(f)
+        }
+    }
+
+    fn bind(name: &str) -> Option<usize> {
+        match name {
+            "a" => Some(0),
+            "b" => Some(1),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn authoring_does_not_need_a_world() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+        let a = graph
+            .act("a", crate::path!(<f32>), |x| x + 1.0)
+            .with_ease(crate::ease::quad::ease_in)
+            .play(s(1));
+        let b = graph
+            .act("b", crate::path!(<f32>), |x| x * 2.0)
+            .play(s(2));
+        graph.add_track(GraphFragment::chain([a, b]));
+
+        let descs = graph.actions().collect::<Vec<_>>();
+        assert_eq!(descs.len(), 2);
+        assert_eq!(descs[0].subject, "a");
+        assert!(descs[0].ease.is_some());
+        assert!(descs[1].ease.is_none());
+        assert_eq!(graph.tracks()[0].duration(), s(3));
+    }
+
+    #[test]
+    fn fragment_durations_match_the_track_combinators() {
+        let clip = |centis| GraphFragment::Clip {
+            action: GraphActionId(0),
+            duration: cs(centis),
+        };
+
+        assert_eq!(
+            GraphFragment::all([clip(100), clip(300)]).duration(),
+            cs(300)
+        );
+        assert_eq!(
+            GraphFragment::any([clip(100), clip(300)]).duration(),
+            cs(100)
+        );
+        assert_eq!(
+            GraphFragment::flow(cs(50), [clip(100), clip(100)])
+                .duration(),
+            cs(150)
+        );
+        assert_eq!(
+            GraphFragment::overlap(cs(30), [clip(100), clip(100)])
+                .duration(),
+            cs(170)
+        );
+        assert_eq!(
+            GraphFragment::align(
+                Alignment::To(cs(50)),
+                [clip(100), clip(20)]
+            )
+            .duration(),
+            cs(100)
+        );
+        assert_eq!(clip(200).delayed(cs(150)).duration(), cs(350));
+    }
+
+    #[cfg(feature = "ron")]
+    #[test]
+    fn fragments_round_trip_through_ron() {
+        let clip = |index| GraphFragment::Clip {
+            action: GraphActionId(index),
+            duration: cs(50),
+        };
+        let fragment = GraphFragment::chain([
+            GraphFragment::align(Alignment::To(s(1)), [clip(0)]),
+            GraphFragment::overlap(cs(10), [clip(1), clip(2)])
+                .delayed(s(2)),
+        ]);
+
+        let ron = ron::to_string(&fragment).unwrap();
+        assert_eq!(
+            ron::from_str::<GraphFragment>(&ron).unwrap(),
+            fragment
+        );
+    }
+
+    #[test]
+    fn resolve_binds_subjects_and_samples() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+        let a = graph
+            .act("a", crate::path!(<f32>), |x| x + 10.0)
+            .play(s(1));
+        let b = graph
+            .act("b", crate::path!(<f32>), |x| x - 10.0)
+            .play(s(1));
+        graph.add_track(GraphFragment::all([a, b]));
+
+        let mut registry = Registry::new();
+        let mut world = World(vec![0.0, 0.0]);
+        let mut timeline =
+            graph.resolve(&mut registry, bind).unwrap();
+
+        timeline.bake_actions(&registry, &world);
+        timeline.set_target_time(cs(50));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+
+        assert!((world.0[0] - 5.0).abs() < f32::EPSILON);
+        assert!((world.0[1] + 5.0).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn rollback_discards_actions_and_tracks() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+        let a =
+            graph.act("a", crate::path!(<f32>), |x| *x).play(s(1));
+        graph.add_track(a);
+
+        graph.begin();
+        for _ in 0..3 {
+            let b = graph
+                .act("b", crate::path!(<f32>), |x| *x)
+                .play(s(1));
+            graph.add_track(b);
+        }
+        if let GraphFragment::Clip { duration, .. } =
+            &mut graph.tracks_mut()[0]
+        {
+            *duration = s(5);
+        }
+        assert!(graph.rollback());
+
+        assert_eq!(graph.actions().count(), 1);
+        assert_eq!(graph.tracks().len(), 1);
+        assert_eq!(graph.tracks()[0].duration(), s(1));
+        assert!(!graph.rollback());
+    }
+
+    #[test]
+    fn undo_reverts_a_committed_transaction_at_once() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+
+        graph.begin();
+        let a =
+            graph.act("a", crate::path!(<f32>), |x| *x).play(s(1));
+        graph.add_track(a);
+
+        // A nested commit is still part of the outer transaction.
+        graph.begin();
+        let b =
+            graph.act("b", crate::path!(<f32>), |x| *x).play(s(1));
+        graph.add_track(b);
+        assert!(graph.commit());
+
+        assert!(!graph.undo());
+        assert!(graph.commit());
+        assert_eq!(graph.tracks().len(), 2);
+
+        assert!(graph.undo());
+        assert_eq!(graph.actions().count(), 0);
+        assert!(graph.tracks().is_empty());
+        assert!(!graph.undo());
+    }
+
+    #[test]
+    fn redo_reapplies_undone_edits() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+        let a =
+            graph.act("a", crate::path!(<f32>), |x| *x).play(s(1));
+        let id = match a {
+            GraphFragment::Clip { action, .. } => action,
+            _ => unreachable!(),
+        };
+        graph.add_track(a);
+
+        assert!(graph.retarget(id, "b"));
+        assert!(graph.undo());
+        assert_eq!(graph.action(id).unwrap().subject, "a");
+
+        assert!(graph.redo());
+        assert_eq!(graph.action(id).unwrap().subject, "b");
+        assert!(!graph.redo());
+
+        // A new edit drops the redo history.
+        assert!(graph.undo());
+        assert!(graph.set_ease(id, Some(crate::ease::quad::ease_in)));
+        assert!(!graph.redo());
+    }
+
+    #[test]
+    fn drags_coalesce_into_one_undo_step() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+        let a =
+            graph.act("a", crate::path!(<f32>), |x| *x).play(s(1));
+        let id = match a {
+            GraphFragment::Clip { action, .. } => action,
+            _ => unreachable!(),
+        };
+        graph.add_track(a);
+
+        for centis in 110..=200 {
+            assert!(graph.retime(id, cs(centis)));
+        }
+        graph.end_coalescing();
+        assert!(graph.retime(id, s(3)));
+        assert_eq!(graph.undo_len(), 2);
+
+        assert!(graph.undo());
+        assert_eq!(graph.tracks()[0].duration(), s(2));
+        assert!(graph.undo());
+        assert_eq!(graph.tracks()[0].duration(), s(1));
+        assert!(!graph.retime(GraphActionId(1), s(1)));
+    }
+
+    #[test]
+    fn history_limit_drops_the_oldest_steps() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+        let a =
+            graph.act("a", crate::path!(<f32>), |x| *x).play(s(1));
+        let id = match a {
+            GraphFragment::Clip { action, .. } => action,
+            _ => unreachable!(),
+        };
+        graph.add_track(a);
+        graph.set_history_limit(2);
+
+        for secs in 2..=4 {
+            graph.retime(id, s(secs));
+            graph.end_coalescing();
+        }
+        assert_eq!(graph.undo_len(), 2);
+
+        while graph.undo() {}
+        assert_eq!(graph.tracks()[0].duration(), s(2));
+    }
+
+    #[test]
+    fn resolve_reports_unbound_subjects() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+        let c =
+            graph.act("c", crate::path!(<f32>), |x| *x).play(s(1));
+        graph.add_track(c);
+
+        let mut registry = Registry::new();
+        assert_eq!(
+            graph.resolve(&mut registry, bind).err(),
+            Some(ResolveError::UnboundSubject("c".into()))
+        );
+    }
+
+    #[test]
+    fn resolve_reports_unknown_actions() {
+        let mut graph = TimelineGraph::<World, usize>::new();
+        let a =
+            graph.act("a", crate::path!(<f32>), |x| *x).play(s(1));
+        let unknown = GraphFragment::Clip {
+            action: GraphActionId(1),
+            duration: s(1),
+        };
+        graph.add_track(GraphFragment::chain([
+            a,
+            unknown.delayed(s(1)),
+        ]));
+
+        let mut registry = Registry::new();
+        assert_eq!(
+            graph.resolve(&mut registry, bind).err(),
+            Some(ResolveError::UnknownAction(GraphActionId(1)))
+        );
+    }
+
+    #[test]
+    fn resolve_without_tracks_is_an_error() {
+        let graph = TimelineGraph::<World, usize>::new();
+
+        let mut registry = Registry::new();
+        assert_eq!(
+            graph.resolve(&mut registry, bind).err(),
+            Some(ResolveError::Empty)
+        );
+    }
+}
@@ -0,0 +1,306 @@
+//! A snapshot of what a [`Timeline`] is doing, for diagnosing
+//! animations that silently do nothing.
+//!
+//! ```ignore
+//! timeline.queue_actions();
+//! timeline.sample_queued_actions(&registry, &mut world);
+//!
+//! let report = timeline.inspect(&registry);
+//! for issue in report.issues() {
+//!     log::warn!("{issue}");
+//! }
+//! println!("{report}");
+//! ```
+
+use core::fmt::{self, Display};
+use core::time::Duration;
+
+use alloc::vec::Vec;
+
+use crate::action::{ActionClip, ActionId, ActionKey, SampleMode};
+use crate::pipeline::PipelineKey;
+use crate::registry::Registry;
+use crate::timeline::Timeline;
+
+/// Everything a [`Timeline`] holds and has marked for sampling, see
+/// [`Timeline::inspect`].
+#[derive(Debug, Clone)]
+pub struct TimelineReport {
+    pub curr_index: usize,
+    pub curr_time: Duration,
+    pub target_index: usize,
+    pub target_time: Duration,
+    pub tracks: Vec<TrackReport>,
+    /// Actions marked for sampling by the last
+    /// [`Timeline::queue_actions`], sorted by key.
+    pub queued: Vec<QueuedAction>,
+    /// Pipelines used by the timeline, sorted by key.
+    pub pipelines: Vec<PipelineReport>,
+}
+
+impl TimelineReport {
+    /// Sequences and pipelines that can never be sampled.
+    pub fn issues(&self) -> impl Iterator<Item = Issue> + '_ {
+        let accessors = self.tracks.iter().flat_map(|track| {
+            track
+                .sequences
+                .iter()
+                .filter(|sequence| !sequence.has_accessor)
+                .map(|sequence| Issue::MissingAccessor(sequence.key))
+        });
+        let pipelines = self
+            .pipelines
+            .iter()
+            .filter(|pipeline| !pipeline.registered)
+            .map(|pipeline| Issue::MissingPipeline(pipeline.key));
+
+        accessors.chain(pipelines)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TrackReport {
+    pub duration: Duration,
+    pub sequences: Vec<SequenceReport>,
+}
+
+/// The clips of a single [`ActionKey`] within a track.
+#[derive(Debug, Clone)]
+pub struct SequenceReport {
+    pub key: ActionKey,
+    pub clips: Vec<ActionClip>,
+    /// Is the field of [`Self::key`] registered in the
+    /// [`AccessorRegistry`](crate::registry::AccessorRegistry)?
+    pub has_accessor: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct QueuedAction {
+    pub key: ActionKey,
+    pub id: ActionId,
+    pub mode: SampleMode,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PipelineReport {
+    pub key: PipelineKey,
+    /// Number of actions going through the pipeline.
+    pub actions: u32,
+    /// Number of actions the pipeline samples this frame.
+    pub queued: usize,
+    /// Is the pipeline registered in the
+    /// [`PipelineRegistry`](crate::registry::PipelineRegistry)?
+    pub registered: bool,
+}
+
+/// Why part of a timeline never gets sampled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Issue {
+    MissingAccessor(ActionKey),
+    MissingPipeline(PipelineKey),
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingAccessor(key) => {
+                write!(f, "no accessor registered for {key:?}")
+            }
+            Self::MissingPipeline(key) => {
+                write!(f, "no pipeline registered for {key:?}")
+            }
+        }
+    }
+}
+
+impl<W: 'static> Timeline<W> {
+    /// Reports the tracks, the actions queued by the last
+    /// [`Self::queue_actions`] and the pipelines of this timeline,
+    /// checked against `registry`.
+    pub fn inspect(&self, registry: &Registry) -> TimelineReport {
+        let tracks = self
+            .tracks()
+            .iter()
+            .map(|track| TrackReport {
+                duration: track.duration(),
+                sequences: track
+                    .sequences_spans()
+                    .iter()
+                    .map(|(key, span)| SequenceReport {
+                        key: *key,
+                        clips: track.clips(*span).to_vec(),
+                        has_accessor: registry
+                            .accessor
+                            .contains(key.field()),
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        let mut queued = self
+            .queue_cache()
+            .iter()
+            .map(|(key, (id, mode))| QueuedAction {
+                key: *key,
+                id: *id,
+                mode: *mode,
+            })
+            .collect::<Vec<_>>();
+        queued.sort_by_key(|action| action.key);
+
+        let mut pipelines = self
+            .pipeline_counts()
+            .iter()
+            .map(|(key, actions)| PipelineReport {
+                key: *key,
+                actions: *actions,
+                queued: self
+                    .sample_queue()
+                    .find(|(queued, _)| *queued == key)
+                    .map_or(0, |(_, count)| count),
+                registered: registry.pipeline.contains(key),
+            })
+            .collect::<Vec<_>>();
+        pipelines.sort_by_key(|pipeline| pipeline.key);
+
+        TimelineReport {
+            curr_index: self.curr_index(),
+            curr_time: self.curr_time(),
+            target_index: self.target_index(),
+            target_time: self.target_time(),
+            tracks,
+            queued,
+            pipelines,
+        }
+    }
+}
+
+impl Display for TimelineReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "track {} at {:.3}s (target: track {} at {:.3}s)",
+            self.curr_index,
+            self.curr_time.as_secs_f32(),
+            self.target_index,
+            self.target_time.as_secs_f32(),
+        )?;
+
+        for (index, track) in self.tracks.iter().enumerate() {
+            writeln!(
+                f,
+                "track {index} ({:.3}s)",
+                track.duration.as_secs_f32()
+            )?;
+
+            for sequence in track.sequences.iter() {
+                write!(f, "  {:?}", sequence.key)?;
+                if !sequence.has_accessor {
+                    write!(f, " [no accessor]")?;
+                }
+                writeln!(f)?;
+
+                for clip in sequence.clips.iter() {
+                    writeln!(
+                        f,
+                        "    {:.3}s..{:.3}s",
+                        clip.start.as_secs_f32(),
+                        clip.end().as_secs_f32(),
+                    )?;
+                }
+            }
+        }
+
+        writeln!(f, "queued ({})", self.queued.len())?;
+        for action in self.queued.iter() {
+            writeln!(f, "  {:?} {:?}", action.key, action.mode)?;
+        }
+
+        writeln!(f, "pipelines ({})", self.pipelines.len())?;
+        for pipeline in self.pipelines.iter() {
+            write!(
+                f,
+                "  {:?}: {}/{} queued",
+                pipeline.key, pipeline.queued, pipeline.actions,
+            )?;
+            if !pipeline.registered {
+                write!(f, " [not registered]")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use crate::prelude::*;
+
+    use super::*;
+
+    struct World(f32);
+
+    impl SubjectSource<u32, f32> for World {
+        fn get_source(&self, _id: u32) -> Option<&f32> {
+            Some(&self.0)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            _id: u32,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            Some(f(&mut self.0))
+        }
+    }
+
+    #[test]
+    fn inspect_reports_queued_actions() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act(0, path!(<f32>), |x| x + 1.0).play(s(1)),
+            b.act(0, path!(<f32>), |x| x + 1.0).play(s(1)),
+        ]
+        .ord_chain();
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        timeline.set_target_time(ms(500));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+
+        let report = timeline.inspect(&registry);
+        assert_eq!(report.tracks.len(), 1);
+        assert_eq!(report.tracks[0].sequences.len(), 1);
+        assert_eq!(report.tracks[0].sequences[0].clips.len(), 2);
+        assert_eq!(report.queued.len(), 1);
+        assert_eq!(report.pipelines.len(), 1);
+        assert_eq!(report.pipelines[0].actions, 2);
+        assert_eq!(report.pipelines[0].queued, 1);
+        assert_eq!(report.issues().count(), 0);
+        assert!(report.to_string().contains("1/2 queued"));
+    }
+
+    #[test]
+    fn inspect_flags_missing_registrations() {
+        let mut registry = Registry::new();
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b.act(0, path!(<f32>), |x| x + 1.0).play(s(1));
+        b.add_tracks(track.compile());
+        let timeline = b.compile();
+
+        let report = timeline.inspect(&Registry::new());
+        let issues = report.issues().collect::<Vec<_>>();
+        assert_eq!(issues.len(), 2);
+        assert!(matches!(issues[0], Issue::MissingAccessor(_)));
+        assert!(matches!(issues[1], Issue::MissingPipeline(_)));
+    }
+}
@@ -4,6 +4,10 @@
 /// downstream crates can provide a local marker type to implement
 /// this trait for foreign `Self` types.
 pub trait Interpolation<M> {
+    /// Is [`Self::interp`] a plain linear interpolation? Lets numeric
+    /// fields be sampled in bulk, see [`crate::lanes`].
+    const LINEAR: bool = false;
+
     fn interp(a: &Self, b: &Self, t: f32) -> Self;
 }
 
@@ -15,6 +19,8 @@ macro_rules! impl_float_interpolation {
 
     ($ty:ty, $base:ty, $marker:ty) => {
         impl $crate::interpolation::Interpolation<$marker> for $ty {
+            const LINEAR: bool = true;
+
             #[inline]
             fn interp(a: &Self, b: &Self, t: f32) -> Self {
                 let t = <$base>::from(t);
@@ -0,0 +1,522 @@
+//! Structure-of-arrays storage of numeric segments, sampled in bulk.
+//!
+//! The baked [`Segment`]s of `f32`, [`Vec2`] and [`Vec3`] actions are
+//! mirrored into one contiguous array per [`Lanes`] lane. Sampling
+//! then interpolates every queued action of a pipeline in tight
+//! loops over those arrays, which the compiler vectorizes, instead
+//! of going through the interpolation of each action one by one.
+//!
+//! Only actions with a linear [`Interpolation`] and no procedural or
+//! modifiers take the bulk path, the rest are sampled as usual.
+//!
+//! [`Interpolation`]: crate::interpolation::Interpolation
+
+use core::marker::PhantomData;
+
+use alloc::vec::Vec;
+use bevy_math::{Vec2, Vec3};
+use hashbrown::HashMap;
+
+use crate::ThreadSafe;
+use crate::action::{
+    ActionId, ActionTable, LinearInterp, ModifierStorage,
+    ProceduralStorage, SampleMode, Segment, StepStorage,
+};
+use crate::pipeline::{
+    BakeCtx, PipelineKey, SampleCtx, bake, sample,
+};
+use crate::subject::SubjectId;
+use crate::world::SubjectSource;
+
+/// The most lanes a [`Lanes`] type can have.
+pub const MAX_LANES: usize = 3;
+
+/// A value made of up to [`MAX_LANES`] `f32` lanes, interpolated
+/// lane by lane.
+pub trait Lanes: Clone + ThreadSafe {
+    /// The number of lanes actually used.
+    const LANES: usize;
+
+    fn to_lanes(&self) -> [f32; MAX_LANES];
+
+    fn from_lanes(lanes: [f32; MAX_LANES]) -> Self;
+}
+
+impl Lanes for f32 {
+    const LANES: usize = 1;
+
+    #[inline]
+    fn to_lanes(&self) -> [f32; MAX_LANES] {
+        [*self, 0.0, 0.0]
+    }
+
+    #[inline]
+    fn from_lanes([x, ..]: [f32; MAX_LANES]) -> Self {
+        x
+    }
+}
+
+impl Lanes for Vec2 {
+    const LANES: usize = 2;
+
+    #[inline]
+    fn to_lanes(&self) -> [f32; MAX_LANES] {
+        [self.x, self.y, 0.0]
+    }
+
+    #[inline]
+    fn from_lanes([x, y, _]: [f32; MAX_LANES]) -> Self {
+        Vec2::new(x, y)
+    }
+}
+
+impl Lanes for Vec3 {
+    const LANES: usize = 3;
+
+    #[inline]
+    fn to_lanes(&self) -> [f32; MAX_LANES] {
+        self.to_array()
+    }
+
+    #[inline]
+    fn from_lanes(lanes: [f32; MAX_LANES]) -> Self {
+        Vec3::from_array(lanes)
+    }
+}
+
+/// The baked segments of every `T` action, one array per lane.
+pub(crate) struct LaneSegments<T> {
+    rows: HashMap<ActionId, usize>,
+    ids: Vec<ActionId>,
+    start: [Vec<f32>; MAX_LANES],
+    /// `end - start`, so that sampling is a single multiply-add.
+    delta: [Vec<f32>; MAX_LANES],
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Lanes> LaneSegments<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            rows: HashMap::new(),
+            ids: Vec::new(),
+            start: Default::default(),
+            delta: Default::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Inserts or overwrites the segment of `id`.
+    pub(crate) fn insert(
+        &mut self,
+        id: ActionId,
+        segment: &Segment<T>,
+    ) {
+        let start = segment.start.to_lanes();
+        let end = segment.end.to_lanes();
+
+        let row = *self.rows.entry(id).or_insert_with(|| {
+            self.ids.push(id);
+            for lane in 0..T::LANES {
+                self.start[lane].push(0.0);
+                self.delta[lane].push(0.0);
+            }
+            self.ids.len() - 1
+        });
+
+        for lane in 0..T::LANES {
+            self.start[lane][row] = start[lane];
+            self.delta[lane][row] = end[lane] - start[lane];
+        }
+    }
+
+    pub(crate) fn remove(&mut self, id: &ActionId) {
+        let Some(row) = self.rows.remove(id) else {
+            return;
+        };
+
+        self.ids.swap_remove(row);
+        for lane in 0..T::LANES {
+            self.start[lane].swap_remove(row);
+            self.delta[lane].swap_remove(row);
+        }
+        if let Some(moved) = self.ids.get(row) {
+            self.rows.insert(*moved, row);
+        }
+    }
+
+    #[inline]
+    fn row(&self, id: &ActionId) -> Option<usize> {
+        self.rows.get(id).copied()
+    }
+}
+
+/// Forgets the lane segments of a removed action, whatever its type.
+pub(crate) fn remove_lanes(table: &mut ActionTable, id: &ActionId) {
+    table.remove_lane_segment::<f32>(id);
+    table.remove_lane_segment::<Vec2>(id);
+    table.remove_lane_segment::<Vec3>(id);
+}
+
+/// [`bake`], mirroring the baked segments into [`LaneSegments`].
+pub(crate) fn bake_lanes<W, I, S, T>(ctx: BakeCtx<W>)
+where
+    W: SubjectSource<I, S> + 'static,
+    I: SubjectId,
+    S: 'static,
+    T: Lanes,
+{
+    let BakeCtx {
+        world,
+        track,
+        action_table,
+        accessor_registry,
+        filter,
+        keep_start,
+        snapshot_only,
+    } = ctx;
+
+    bake::<W, I, S, T>(BakeCtx {
+        world,
+        track,
+        action_table: &mut *action_table,
+        accessor_registry,
+        filter,
+        keep_start,
+        snapshot_only,
+    });
+
+    let pipeline = PipelineKey::new::<W, I, S, T>();
+    let ids = track
+        .sequences_spans()
+        .iter()
+        .filter(|(key, _)| {
+            PipelineKey::from_action_key::<W>(*key) == pipeline
+                && filter.is_none_or(|filter| filter(key))
+        })
+        .flat_map(|(_, span)| track.clips(*span))
+        .map(|clip| clip.id)
+        .collect::<Vec<_>>();
+
+    action_table.mirror_lane_segments::<T>(ids);
+}
+
+/// The number of actions interpolated per batch, sized to keep the
+/// batch on the stack.
+const BATCH: usize = 64;
+
+/// [`sample`], interpolating every action with a linear
+/// interpolation in bulk over its [`LaneSegments`].
+pub(crate) fn sample_lanes<W, I, S, T>(mut ctx: SampleCtx<W>)
+where
+    W: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+    T: Lanes,
+{
+    let action_table = ctx.action_table;
+    let segments = action_table.lane_segments::<T>();
+    let Some(segments) = segments.filter(|_| !ctx.restore) else {
+        sample::<W, I, S, T>(ctx);
+        return;
+    };
+
+    let table = action_table.table();
+    let linear_col = table.type_column::<LinearInterp>();
+    let step_col = table.type_column::<StepStorage>();
+    let procedural_col = table.type_column::<ProceduralStorage<T>>();
+    let modifier_col = table.type_column::<ModifierStorage<T>>();
+
+    let is_bulk = |id: &ActionId| {
+        linear_col.is_some_and(|col| {
+            table.get_by_column::<LinearInterp>(col, id).is_some()
+        }) && procedural_col.is_none_or(|col| {
+            table
+                .get_by_column::<ProceduralStorage<T>>(col, id)
+                .is_none()
+        }) && modifier_col.is_none_or(|col| {
+            table
+                .get_by_column::<ModifierStorage<T>>(col, id)
+                .is_none()
+        })
+    };
+
+    let samples = ctx.samples;
+    let mut batch = Batch::new();
+    // The start of the current run of samples the bulk path can't
+    // take, e.g. custom interpolations. Runs are sampled as usual
+    // straight from `samples`, without collecting them.
+    let mut run = None;
+
+    for (i, &(id, sample_mode)) in samples.iter().enumerate() {
+        let row = segments.row(&id).filter(|_| is_bulk(&id));
+        let t = match (row, sample_mode) {
+            (Some(_), SampleMode::Start) => 0.0,
+            (Some(_), SampleMode::Interp(t)) => {
+                let t = match step_col.and_then(|col| {
+                    table.get_by_column::<StepStorage>(col, &id)
+                }) {
+                    Some(steps) => steps.quantize(t),
+                    None => t,
+                };
+                match action_table.ease(&id) {
+                    Some(ease) => ease.ease(t),
+                    None => t,
+                }
+            }
+            // The end is read as is rather than interpolated to, so
+            // it is not off by a rounding error.
+            (_, SampleMode::End) | (None, _) => {
+                run.get_or_insert(i);
+                continue;
+            }
+        };
+        if let Some(start) = run.take() {
+            sample_run::<W, I, S, T>(&mut ctx, &samples[start..i]);
+        }
+
+        batch.push(row.unwrap_or_default(), t);
+        if batch.len == BATCH {
+            batch.lerp(segments);
+            write_batch::<W, I, S, T>(&mut ctx, segments, &batch);
+            batch.len = 0;
+        }
+    }
+
+    if batch.len > 0 {
+        batch.lerp(segments);
+        write_batch::<W, I, S, T>(&mut ctx, segments, &batch);
+    }
+
+    if let Some(start) = run {
+        sample_run::<W, I, S, T>(&mut ctx, &samples[start..]);
+    }
+}
+
+/// Samples `samples` without the bulk path.
+fn sample_run<W, I, S, T>(
+    ctx: &mut SampleCtx<W>,
+    samples: &[(ActionId, SampleMode)],
+) where
+    W: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+    T: Lanes,
+{
+    sample::<W, I, S, T>(SampleCtx {
+        world: &mut *ctx.world,
+        action_table: ctx.action_table,
+        accessor_registry: ctx.accessor_registry,
+        samples,
+        weight: ctx.weight,
+        track: ctx.track,
+        restore: false,
+        bindings: ctx.bindings,
+    });
+}
+
+/// A batch of actions to interpolate together.
+struct Batch {
+    len: usize,
+    /// The rows of the actions in their [`LaneSegments`].
+    rows: [usize; BATCH],
+    t: [f32; BATCH],
+    start: [f32; BATCH],
+    delta: [f32; BATCH],
+    /// The interpolated lanes of each action.
+    out: [[f32; BATCH]; MAX_LANES],
+}
+
+impl Batch {
+    fn new() -> Self {
+        Self {
+            len: 0,
+            rows: [0; BATCH],
+            t: [0.0; BATCH],
+            start: [0.0; BATCH],
+            delta: [0.0; BATCH],
+            out: [[0.0; BATCH]; MAX_LANES],
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, row: usize, t: f32) {
+        self.rows[self.len] = row;
+        self.t[self.len] = t;
+        self.len += 1;
+    }
+
+    fn lerp<T: Lanes>(&mut self, segments: &LaneSegments<T>) {
+        let len = self.len;
+
+        for lane in 0..T::LANES {
+            // Gather first, so the interpolation itself runs over
+            // contiguous arrays.
+            for (i, &row) in self.rows[..len].iter().enumerate() {
+                self.start[i] = segments.start[lane][row];
+                self.delta[i] = segments.delta[lane][row];
+            }
+
+            for (((out, start), delta), t) in self.out[lane][..len]
+                .iter_mut()
+                .zip(&self.start[..len])
+                .zip(&self.delta[..len])
+                .zip(&self.t[..len])
+            {
+                *out = start + delta * t;
+            }
+        }
+    }
+
+    #[inline]
+    fn value<T: Lanes>(&self, i: usize) -> T {
+        let mut lanes = [0.0; MAX_LANES];
+        for (lane, value) in lanes.iter_mut().enumerate() {
+            *value = self.out[lane][i];
+        }
+        T::from_lanes(lanes)
+    }
+}
+
+/// Writes the interpolated values of `batch` into the world.
+fn write_batch<W, I, S, T>(
+    ctx: &mut SampleCtx<W>,
+    segments: &LaneSegments<T>,
+    batch: &Batch,
+) where
+    W: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+    T: Lanes,
+{
+    for (i, &row) in batch.rows[..batch.len].iter().enumerate() {
+        let id = &segments.ids[row];
+        let Some(key) = ctx.action_table.key(id) else {
+            continue;
+        };
+        let Some(accessor) =
+            ctx.accessor_registry.get::<S, T>(key.field())
+        else {
+            continue;
+        };
+        let Some(&sid) = ctx
+            .bindings
+            .resolve::<I>(ctx.action_table, &key.subject_id().uid())
+        else {
+            continue;
+        };
+        let weight = ctx.weight
+            * ctx
+                .track
+                .map_or(1.0, |track| track.field_weight(key.field()));
+        if weight <= 0.0 {
+            continue;
+        }
+
+        let target = batch.value::<T>(i);
+        ctx.world.apply_source(sid, |source| {
+            let value = accessor.get_mut(source);
+            *value = if weight < 1.0 {
+                blend(value, &target, weight)
+            } else {
+                target
+            };
+        });
+    }
+}
+
+/// Linear interpolation from `current` to `target`, lane by lane.
+#[inline]
+fn blend<T: Lanes>(current: &T, target: &T, weight: f32) -> T {
+    let (current, target) = (current.to_lanes(), target.to_lanes());
+    T::from_lanes(core::array::from_fn(|lane| {
+        current[lane] + (target[lane] - current[lane]) * weight
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+
+    struct Linear;
+    crate::impl_float_interpolation!(Vec3, f32, Linear);
+
+    struct Points([Vec3; 2]);
+
+    impl SubjectSource<u32, Vec3> for Points {
+        fn get_source(&self, id: u32) -> Option<&Vec3> {
+            self.0.get(id as usize)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            id: u32,
+            f: impl FnOnce(&mut Vec3) -> R,
+        ) -> Option<R> {
+            self.0.get_mut(id as usize).map(f)
+        }
+    }
+
+    fn squared(a: &Vec3, b: &Vec3, t: f32) -> Vec3 {
+        a.lerp(*b, t * t)
+    }
+
+    #[test]
+    fn bulk_and_custom_interpolations_mix() {
+        let mut registry = Registry::new();
+        let mut world = Points([Vec3::ZERO; 2]);
+
+        let mut b = TimelineBuilder::<Points>::new(&mut registry);
+        let field = path!(<Vec3>);
+        let track = [
+            b.act(0u32, field, |_| Vec3::splat(4.0)).play(s(1)),
+            b.act_builder(1u32, field, |_| Vec3::splat(4.0))
+                .with_interp(squared)
+                .play(s(1)),
+        ]
+        .ord_all();
+        b.add_tracks(track.compile().with_weight(0.5));
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let lanes =
+            timeline.action_table().lane_segments::<Vec3>().unwrap();
+        assert_eq!(lanes.ids.len(), 2);
+
+        timeline.set_target_time(ms(500));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        // Weights blend through the interpolation of each action.
+        assert_eq!(world.0, [Vec3::splat(1.0), Vec3::splat(0.25)]);
+    }
+
+    #[test]
+    fn removed_rows_are_filled_by_the_last() {
+        let mut registry = Registry::new();
+        let world = Points([Vec3::ZERO; 2]);
+
+        let mut b = TimelineBuilder::<Points>::new(&mut registry);
+        let first = b.act(0u32, path!(<Vec3>), |_| Vec3::X);
+        let first_id = first.id();
+        let track = [
+            first.play(s(1)),
+            b.act(1u32, path!(<Vec3>), |_| Vec3::Y).play(s(1)),
+        ]
+        .ord_all();
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let mut editor = timeline.edit(&mut registry);
+        assert!(editor.remove(first_id));
+        editor.finish(&world);
+
+        let lanes =
+            timeline.action_table().lane_segments::<Vec3>().unwrap();
+        let row = lanes.row(&lanes.ids[0]).unwrap();
+        assert_eq!(lanes.ids.len(), 1);
+        assert_eq!(row, 0);
+        assert_eq!(lanes.delta[1][row], 1.0);
+    }
+}
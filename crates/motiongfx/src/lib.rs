@@ -2,13 +2,25 @@
 #![no_std]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 pub mod action;
+pub mod cache;
+pub mod definition;
 pub mod ease;
+pub mod expr;
+pub mod field;
+pub mod graph;
+pub mod inspect;
 pub mod interpolation;
+pub mod lanes;
+pub mod modifier;
 pub mod pipeline;
+pub mod plot;
 pub mod registry;
 mod resources;
+pub mod script;
 pub mod sequence;
 pub mod subject;
 pub mod time;
@@ -24,25 +36,48 @@ pub mod prelude {
 
     pub use crate::ThreadSafe;
     pub use crate::action::{
-        Action, ActionBuilder, ActionId, EaseFn, InterpActionBuilder,
-        InterpFn,
+        Action, ActionBuilder, ActionId, ActionKind, ActionValue,
+        EaseFn, InterpActionBuilder, InterpFn, Modifier, Procedural,
     };
+    pub use crate::cache::BakeCodec;
+    pub use crate::definition::TimelineDef;
     pub use crate::ease;
+    pub use crate::expr::Expr;
+    pub use crate::field::{
+        ActionField, Composite, CompositeField, FieldMask,
+        IndexedField, MaskedField, NonePolicy, OptionField,
+        VirtualField,
+    };
+    pub use crate::graph::{GraphFragment, TimelineGraph};
     pub use crate::interpolation::Interpolation;
+    pub use crate::modifier::{self, Wiggle};
     pub use crate::path;
     pub use crate::pipeline::PipelineKey;
     pub use crate::registry::{
         AccessorRegistry, PipelineRegistry, Registry,
     };
-    pub use crate::time::{cs, ms, ns, s};
-    pub use crate::timeline::{Timeline, TimelineBuilder};
-    pub use crate::track::{Track, TrackFragment, TrackOrdering};
-    pub use crate::world::SubjectSource;
+    pub use crate::time::{cs, ms, ns, s, us};
+    pub use crate::timeline::{
+        SampledValue, Timeline, TimelineBuilder,
+    };
+    pub use crate::track::{
+        Alignment, Stagger, Track, TrackFragment, TrackOrdering,
+        stagger,
+    };
+    pub use crate::world::{GroupSource, SubjectSource};
 }
 
 /// See [`field_path::field_accessor!`].
 ///
-/// This macro just forwards the tokens to the mentioned macro.
+/// This macro just forwards the tokens to the mentioned macro, except
+/// for:
+///
+/// - A trailing `[index]`, which produces an
+///   [`IndexedField`](crate::field::IndexedField) targeting a single
+///   element of the collection field.
+/// - A trailing `?`, which produces an
+///   [`OptionField`](crate::field::OptionField) targeting the value
+///   inside the `Option` field.
 ///
 /// ## Example
 ///
@@ -50,11 +85,28 @@ pub mod prelude {
 /// use motiongfx::path;
 ///
 /// struct Foo(u32);
+/// struct Bar([f32; 4]);
 ///
 /// let path = path!(<Foo>::0);
+/// let element = path!(<Bar>::0[2]);
+///
+/// struct Baz(Option<f32>);
+///
+/// let inner = path!(<Baz>::0?);
 /// ```
 #[macro_export]
 macro_rules! path {
+    (<$s:ty> $(::$f:tt)* [$index:expr]) => {
+        $crate::field::IndexedField::new(
+            $crate::field_path::field_accessor!(<$s> $(::$f)*),
+            $index,
+        )
+    };
+    (<$s:ty> $(::$f:tt)* ?) => {
+        $crate::field::OptionField::new(
+            $crate::field_path::field_accessor!(<$s> $(::$f)*),
+        )
+    };
     ($($t:tt)*) => {
         $crate::field_path::field_accessor!($($t)*)
     };
@@ -0,0 +1,244 @@
+//! Stock [`Modifier`]s for
+//! [`InterpActionBuilder::with_modifier`].
+//!
+//! Modifiers run in the order they are added, so effects compose
+//! without a custom interpolation:
+//!
+//! ```ignore
+//! builder
+//!     .act(entity, path!(<Transform>::translation::x), |x| x + 100.0)
+//!     .with_interp(|a, b, t| a + (b - a) * t)
+//!     .with_modifier(modifier::jitter(2.0, 7))
+//!     .with_modifier(modifier::quantize(1.0))
+//!     .play(s(1));
+//! ```
+//!
+//! [`InterpActionBuilder::with_modifier`]:
+//! crate::action::InterpActionBuilder::with_modifier
+
+use core::ops::{Add, Mul};
+use core::time::Duration;
+
+use bevy_math::ops;
+
+use crate::action::Modifier;
+
+/// Snaps the value to the nearest multiple of `step`, e.g. `1.0` to
+/// stay on the pixel grid.
+///
+/// # Panics
+///
+/// Panics if `step` is not positive and finite.
+pub fn quantize(step: f32) -> impl Modifier<f32> {
+    assert!(
+        step > 0.0 && step.is_finite(),
+        "quantize step must be positive and finite, got {step}"
+    );
+
+    move |value: f32, _| ops::round(value / step) * step
+}
+
+/// Snaps the value to a grid of `step` sized cells starting at
+/// `origin`, e.g. to line up with a tile map that isn't at zero.
+///
+/// # Panics
+///
+/// Panics if `step` is not positive and finite.
+pub fn grid(origin: f32, step: f32) -> impl Modifier<f32> {
+    let snap = quantize(step);
+    move |value: f32, t| snap(value - origin, t) + origin
+}
+
+/// Offsets the value by `offset`.
+pub fn offset<T>(offset: T) -> impl Modifier<T>
+where
+    T: Add<Output = T> + Clone + Send + Sync + 'static,
+{
+    move |value: T, _| value + offset.clone()
+}
+
+/// Keeps the value within `min..=max`.
+pub fn clamp<T>(min: T, max: T) -> impl Modifier<T>
+where
+    T: PartialOrd + Clone + Send + Sync + 'static,
+{
+    move |value: T, _| {
+        if value < min {
+            min.clone()
+        } else if value > max {
+            max.clone()
+        } else {
+            value
+        }
+    }
+}
+
+/// Scales the value by `factor`.
+pub fn multiply<T>(factor: f32) -> impl Modifier<T>
+where
+    T: Mul<f32, Output = T>,
+{
+    move |value: T, _| value * factor
+}
+
+/// Offsets the value by noise in `-amplitude..=amplitude`.
+///
+/// The noise is a hash of the clip progress and `seed`, so scrubbing
+/// back to a time reproduces the same value. Use different seeds to
+/// decorrelate actions.
+pub fn jitter(amplitude: f32, seed: u32) -> impl Modifier<f32> {
+    move |value: f32, t: f32| value + amplitude * noise(t, seed)
+}
+
+/// Smooth noise for hand-held camera feel or organic idle motion,
+/// added on top of whatever an action animates:
+///
+/// ```ignore
+/// let wiggle = Wiggle::new(4.0, 2.0).with_seed(7);
+/// builder
+///     .act(camera, path!(<Transform>::translation::x), |x| x + 100.0)
+///     .with_modifier(wiggle.modifier(s(3)))
+///     .play(s(3));
+/// ```
+///
+/// See [`TimelineBuilder::wiggle`] to wiggle a field that is
+/// otherwise held still.
+///
+/// [`TimelineBuilder::wiggle`]: crate::timeline::TimelineBuilder::wiggle
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Wiggle {
+    /// The largest offset from the animated value.
+    pub amplitude: f32,
+    /// Roughly how many bumps per second.
+    pub frequency: f32,
+    /// Use different seeds to decorrelate fields.
+    pub seed: u32,
+}
+
+impl Wiggle {
+    pub const fn new(amplitude: f32, frequency: f32) -> Self {
+        Self {
+            amplitude,
+            frequency,
+            seed: 0,
+        }
+    }
+
+    /// Builder method for setting [`Self::seed`].
+    pub const fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The offset at `x` bumps in, within
+    /// \[-amplitude..=amplitude\] and zero on whole bumps.
+    #[inline]
+    pub fn sample(&self, x: f32) -> f32 {
+        self.amplitude * gradient_noise(x, self.seed)
+    }
+
+    /// Adds the noise to the values of a clip lasting `duration`.
+    ///
+    /// The frequency is rounded to whole bumps over the clip, so the
+    /// clip still starts and ends at its authored values.
+    pub fn modifier(self, duration: Duration) -> impl Modifier<f32> {
+        let bumps =
+            ops::round(duration.as_secs_f32() * self.frequency)
+                .max(1.0);
+
+        move |value: f32, t: f32| value + self.sample(t * bumps)
+    }
+}
+
+/// 1D gradient noise in \[-1.0..=1.0\], zero on whole numbers.
+pub(crate) fn gradient_noise(x: f32, seed: u32) -> f32 {
+    let i = ops::floor(x);
+    let f = x - i;
+    // Perlin's smootherstep fade between the two gradients.
+    let fade = f * f * f * (f * (f * 6.0 - 15.0) + 10.0);
+
+    let a = noise(i, seed) * f;
+    let b = noise(i + 1.0, seed) * (f - 1.0);
+
+    // 1D gradient noise peaks at half the gradient.
+    (a + (b - a) * fade) * 2.0
+}
+
+/// Hashes `t` and `seed` into \[-1.0..=1.0\].
+pub(crate) fn noise(t: f32, seed: u32) -> f32 {
+    // PCG output permutation over the combined bits.
+    let state = t
+        .to_bits()
+        .wrapping_mul(747796405)
+        .wrapping_add(seed.wrapping_mul(2891336453) | 1);
+    let word = ((state >> ((state >> 28) + 4)) ^ state)
+        .wrapping_mul(277803737);
+    let hash = (word >> 22) ^ word;
+
+    (hash as f32 / u32::MAX as f32) * 2.0 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quantize_snaps_to_nearest_step() {
+        let snap = quantize(0.5);
+
+        assert_eq!(snap(1.2, 0.0), 1.0);
+        assert_eq!(snap(1.3, 0.0), 1.5);
+        assert_eq!(snap(-0.2, 0.0), 0.0);
+    }
+
+    #[test]
+    fn grid_snaps_around_origin() {
+        let snap = grid(0.25, 1.0);
+
+        assert_eq!(snap(1.0, 0.0), 1.25);
+        assert_eq!(snap(-0.5, 0.0), -0.75);
+        assert_eq!(offset(2.0)(1.0, 0.0), 3.0);
+    }
+
+    #[test]
+    fn clamp_keeps_value_in_range() {
+        let clamp = clamp(0.0, 1.0);
+
+        assert_eq!(clamp(-1.0, 0.0), 0.0);
+        assert_eq!(clamp(0.5, 0.0), 0.5);
+        assert_eq!(clamp(2.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn jitter_is_bounded_and_reproducible() {
+        let jitter = jitter(2.0, 3);
+
+        for i in 0..=100 {
+            let t = i as f32 / 100.0;
+            let value = jitter(10.0, t);
+
+            assert!((8.0..=12.0).contains(&value));
+            assert_eq!(value, jitter(10.0, t));
+        }
+        assert_ne!(jitter(10.0, 0.25), jitter(10.0, 0.5));
+    }
+
+    #[test]
+    fn wiggle_is_smooth_and_keeps_the_ends() {
+        let wiggle = Wiggle::new(2.0, 3.0).with_seed(5);
+        let modifier = wiggle.modifier(Duration::from_secs(2));
+
+        assert_eq!(modifier(10.0, 0.0), 10.0);
+        assert!((modifier(10.0, 1.0) - 10.0).abs() < 1e-4);
+
+        let mut prev = modifier(10.0, 0.0);
+        for i in 1..=200 {
+            let value = modifier(10.0, i as f32 / 200.0);
+
+            assert!((8.0..=12.0).contains(&value));
+            assert!((value - prev).abs() < 0.5);
+            prev = value;
+        }
+        assert_ne!(modifier(10.0, 0.1), 10.0);
+    }
+}
@@ -1,6 +1,6 @@
 pub mod func_pointers;
 
-use core::any::TypeId;
+use core::any::{TypeId, type_name};
 use core::marker::PhantomData;
 use core::time::Duration;
 
@@ -9,13 +9,18 @@ use func_pointers::{BakeFnPtr, SampleFnPtr};
 use crate::ThreadSafe;
 use crate::action::{
     ActionClip, ActionId, ActionKey, ActionTable, InterpStorage,
-    SampleMode, Segment,
+    ModifierStorage, ProceduralStorage, SampleMode, Segment,
+    Snapshot, StepStorage, SubjectBindings,
 };
+use crate::field::{
+    Composite, ElementAccess, FieldTarget, NonePolicy,
+};
+use crate::lanes::{Lanes, bake_lanes, sample_lanes};
 use crate::pipeline::func_pointers::{BakeFn, SampleFn};
 use crate::registry::AccessorRegistry;
 use crate::subject::SubjectId;
 use crate::track::Track;
-use crate::world::SubjectSource;
+use crate::world::{GroupSource, SubjectSource};
 
 pub struct PipelineHandle<W, I, S, T> {
     #[expect(clippy::complexity)]
@@ -70,6 +75,8 @@ pub struct PipelineKey {
     subject_id: TypeId,
     source_id: TypeId,
     target_id: TypeId,
+    /// The kind of [`FieldTarget`] the pipeline writes to.
+    kind: TargetKind,
 }
 
 impl PipelineKey {
@@ -85,6 +92,83 @@ impl PipelineKey {
             subject_id: TypeId::of::<I>(),
             source_id: TypeId::of::<S>(),
             target_id: TypeId::of::<T>(),
+            kind: TargetKind::Whole,
+        }
+    }
+
+    /// Key of a pipeline that animates single elements of a `C`
+    /// collection field.
+    pub fn new_indexed<W, I, S, C>() -> Self
+    where
+        W: 'static,
+        I: SubjectId,
+        S: 'static,
+        C: 'static,
+    {
+        Self {
+            kind: TargetKind::Element,
+            ..Self::new::<W, I, S, C>()
+        }
+    }
+
+    /// Key of a pipeline that animates the value inside an
+    /// `Option<T>` field.
+    pub fn new_optional<W, I, S, T>() -> Self
+    where
+        W: 'static,
+        I: SubjectId,
+        S: 'static,
+        T: 'static,
+    {
+        Self {
+            kind: TargetKind::Inner,
+            ..Self::new::<W, I, S, Option<T>>()
+        }
+    }
+
+    /// Key of a pipeline that animates the `T` [`Composite`] view of
+    /// the whole `S` source.
+    pub fn new_composite<W, I, S, T>() -> Self
+    where
+        W: 'static,
+        I: SubjectId,
+        S: 'static,
+        T: 'static,
+    {
+        Self {
+            kind: TargetKind::Composite(TypeId::of::<T>()),
+            ..Self::new::<W, I, S, S>()
+        }
+    }
+
+    /// Key of a pipeline that animates `T` values converted from the
+    /// whole `S` source, see [`VirtualField`].
+    ///
+    /// [`VirtualField`]: crate::field::VirtualField
+    pub fn new_virtual<W, I, S, T>() -> Self
+    where
+        W: 'static,
+        I: SubjectId,
+        S: 'static,
+        T: 'static,
+    {
+        Self {
+            kind: TargetKind::Virtual(TypeId::of::<T>()),
+            ..Self::new::<W, I, S, S>()
+        }
+    }
+
+    /// Key of a pipeline that animates the whole `S` source through
+    /// a [`FieldMask`](crate::field::FieldMask).
+    pub fn new_masked<W, I, S>() -> Self
+    where
+        W: 'static,
+        I: SubjectId,
+        S: 'static,
+    {
+        Self {
+            kind: TargetKind::Masked,
+            ..Self::new::<W, I, S, S>()
         }
     }
 
@@ -94,6 +178,20 @@ impl PipelineKey {
             subject_id: key.subject_id().type_id(),
             source_id: key.field().source_id(),
             target_id: key.field().target_id(),
+            kind: TargetKind::of(key.target()),
+        }
+    }
+
+    /// Name of the kind of [`FieldTarget`] the pipeline writes to,
+    /// e.g. `"whole"` or `"element"`.
+    pub fn kind_name(&self) -> &'static str {
+        match self.kind {
+            TargetKind::Whole => "whole",
+            TargetKind::Element => "element",
+            TargetKind::Inner => "inner",
+            TargetKind::Composite(_) => "composite",
+            TargetKind::Masked => "masked",
+            TargetKind::Virtual(_) => "virtual",
         }
     }
 
@@ -102,6 +200,32 @@ impl PipelineKey {
     }
 }
 
+/// [`FieldTarget`] without its per-action data.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord,
+)]
+enum TargetKind {
+    Whole,
+    Element,
+    Inner,
+    Composite(TypeId),
+    Masked,
+    Virtual(TypeId),
+}
+
+impl TargetKind {
+    fn of(target: FieldTarget) -> Self {
+        match target {
+            FieldTarget::Whole => Self::Whole,
+            FieldTarget::Element(_) => Self::Element,
+            FieldTarget::Inner => Self::Inner,
+            FieldTarget::Composite(id) => Self::Composite(id),
+            FieldTarget::Masked(_) => Self::Masked,
+            FieldTarget::Virtual(id) => Self::Virtual(id.target_id()),
+        }
+    }
+}
+
 /// A pipeline for baking and sampling actions of type `(I, S, T)`.
 /// The world type `W` is erased at storage; it must match at call sites.
 #[derive(Debug, Clone, Copy)]
@@ -127,10 +251,155 @@ impl<W, I, S, T> Pipeline<W, I, S, T> {
         }
     }
 
-    pub fn untyped(&self) -> PipelineUntyped {
+    /// A pipeline that animates single elements of the `T`
+    /// collection field, keyed by [`PipelineKey::new_indexed`].
+    pub fn indexed() -> Self
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: ElementAccess + ThreadSafe,
+        T::Element: Clone + ThreadSafe,
+    {
+        Self {
+            bake: bake_projected::<W, I, S, T, Element>,
+            sample: sample_projected::<W, I, S, T, Element, Single>,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn untyped(&self) -> PipelineUntyped
+    where
+        I: 'static,
+        S: 'static,
+        T: 'static,
+    {
         PipelineUntyped {
             bake: BakeFnPtr::new(self.bake),
             sample: SampleFnPtr::new(self.sample),
+            name: type_name::<(I, S, T)>(),
+        }
+    }
+}
+
+impl<W, I, S, T> Pipeline<W, I, S, T> {
+    /// A pipeline that samples the actions of numeric fields in
+    /// bulk, keyed by [`PipelineKey::new`]. See [`crate::lanes`].
+    pub fn lanes() -> Self
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Lanes,
+    {
+        Self {
+            bake: bake_lanes::<W, I, S, T>,
+            sample: sample_lanes::<W, I, S, T>,
+            _marker: PhantomData,
+        }
+    }
+
+    /// A pipeline that writes each sampled value to every member of
+    /// a group subject, keyed by [`PipelineKey::new`].
+    ///
+    /// The value is interpolated once per action, then copied into
+    /// every member. Baking reads the start value through
+    /// [`SubjectSource`], so members are expected to start alike.
+    pub fn grouped() -> Self
+    where
+        W: GroupSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + ThreadSafe,
+    {
+        Self {
+            bake: bake::<W, I, S, T>,
+            sample: sample_grouped::<W, I, S, T>,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<W, I, S, T> Pipeline<W, I, S, Option<T>> {
+    /// A pipeline that animates the value inside the `Option<T>`
+    /// field, keyed by [`PipelineKey::new_optional`].
+    pub fn optional() -> Self
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + Default + ThreadSafe,
+    {
+        Self {
+            bake: bake_projected::<W, I, S, Option<T>, Inner>,
+            sample: sample_projected::<
+                W,
+                I,
+                S,
+                Option<T>,
+                Inner,
+                Single,
+            >,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<W, I, S> Pipeline<W, I, S, S> {
+    /// A pipeline that animates the `T` [`Composite`] view of the
+    /// whole source, keyed by [`PipelineKey::new_composite`].
+    pub fn composite<T>() -> Self
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Composite<S>,
+    {
+        Self {
+            bake: bake_projected::<W, I, S, S, Composed<T>>,
+            sample: sample_projected::<W, I, S, S, Composed<T>, Single>,
+            _marker: PhantomData,
+        }
+    }
+
+    /// A pipeline that animates `T` values converted from the whole
+    /// source, keyed by [`PipelineKey::new_virtual`].
+    pub fn converted<T>() -> Self
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + ThreadSafe,
+    {
+        Self {
+            bake: bake_projected::<W, I, S, S, Converted<T>>,
+            sample: sample_projected::<
+                W,
+                I,
+                S,
+                S,
+                Converted<T>,
+                Single,
+            >,
+            _marker: PhantomData,
+        }
+    }
+
+    /// A pipeline that animates the whole source but only writes the
+    /// fields of each action's [`FieldMask`], keyed by
+    /// [`PipelineKey::new_masked`].
+    ///
+    /// [`FieldMask`]: crate::field::FieldMask
+    pub fn masked() -> Self
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: Clone + ThreadSafe,
+    {
+        Self {
+            bake: bake_projected::<W, I, S, S, Masked>,
+            sample: sample_projected::<W, I, S, S, Masked, Single>,
+            _marker: PhantomData,
         }
     }
 }
@@ -151,9 +420,23 @@ where
 pub struct PipelineUntyped {
     bake: BakeFnPtr,
     sample: SampleFnPtr,
+    /// Type name of the subject, source and target types.
+    name: &'static str,
 }
 
 impl PipelineUntyped {
+    #[inline]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Overrides [`Self::name`], for pipelines whose target type is
+    /// not part of their [`Pipeline`] type.
+    pub(crate) fn with_name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
     /// # Safety
     ///
     /// `W` must match the type used when registering this pipeline.
@@ -176,6 +459,16 @@ pub struct BakeCtx<'a, W> {
     pub track: &'a Track,
     pub action_table: &'a mut ActionTable,
     pub accessor_registry: &'a AccessorRegistry,
+    /// Only bakes the sequences whose [`ActionKey`] passes the
+    /// filter, all of them if `None`.
+    pub filter: Option<&'a dyn Fn(&ActionKey) -> bool>,
+    /// Start each sequence from the start of its earliest already
+    /// baked segment, or else its [`Snapshot`], instead of the world
+    /// if there is one.
+    pub keep_start: bool,
+    /// Only record the [`Snapshot`] of each sequence from the world,
+    /// leaving its segments unbaked.
+    pub snapshot_only: bool,
 }
 
 pub fn bake<W, I, S, T>(ctx: BakeCtx<W>)
@@ -184,18 +477,35 @@ where
     I: SubjectId,
     S: 'static,
     T: Clone + ThreadSafe,
+{
+    bake_projected::<W, I, S, T, Whole>(ctx);
+}
+
+fn bake_projected<W, I, S, C, P>(ctx: BakeCtx<W>)
+where
+    W: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+    C: 'static,
+    P: Projection<C>,
 {
     // Resolve the per-`T` columns once so the clip loop doesn't
     // re-hash the `TypeId` on every access. No `T` action, no bake.
-    let Some(action_col) = ctx.action_table.action_column::<T>()
+    let Some(action_col) =
+        ctx.action_table.action_column::<P::Target>()
     else {
         return;
     };
-    let segment_col = ctx.action_table.ensure_segment_column::<T>();
+    let segment_col =
+        ctx.action_table.ensure_segment_column::<P::Target>();
 
     for (key, span) in ctx.track.sequences_spans() {
+        if ctx.filter.is_some_and(|filter| !filter(key)) {
+            continue;
+        }
+
         let Some(accessor) =
-            ctx.accessor_registry.get::<S, T>(key.field())
+            ctx.accessor_registry.get::<S, C>(key.field())
         else {
             continue;
         };
@@ -210,12 +520,97 @@ where
             continue;
         };
 
-        let mut start = accessor.get_ref(source).clone();
+        let clips = ctx.track.clips(*span);
+        let baked_start = ctx
+            .keep_start
+            .then(|| {
+                clips
+                    .iter()
+                    .find_map(|clip| {
+                        let segment = ctx
+                            .action_table
+                            .table()
+                            .get_by_column::<Segment<P::Target>>(
+                            segment_col,
+                            &clip.id,
+                        )?;
+
+                        Some(if clip.reversed {
+                            segment.end.clone()
+                        } else {
+                            segment.start.clone()
+                        })
+                    })
+                    .or_else(|| {
+                        let first = clips.first()?;
+                        ctx.action_table
+                            .table()
+                            .get::<Snapshot<P::Target>>(&first.id)
+                            .map(|Snapshot(value)| value.clone())
+                    })
+            })
+            .flatten();
+
+        let from_world = baked_start.is_none();
+        let Some(mut start) = baked_start.or_else(|| {
+            P::project(
+                accessor.get_ref(source),
+                key,
+                ctx.accessor_registry,
+            )
+            .or_else(|| {
+                let policy = clips
+                    .first()
+                    .map_or_else(NonePolicy::default, |clip| {
+                        ctx.action_table.none_policy(&clip.id)
+                    });
+                P::fallback(policy)
+            })
+        }) else {
+            continue;
+        };
+
+        // Keep what was read from the world so the subject can be
+        // reset to it.
+        if let Some(first) = clips.first()
+            && from_world
+        {
+            ctx.action_table.set_snapshot(first.id, start.clone());
+        }
+        if ctx.snapshot_only {
+            continue;
+        }
+
+        for (index, ActionClip { id, reversed, .. }) in
+            clips.iter().enumerate()
+        {
+            // Reversed clips, the later halves of split clips and
+            // repeated clips replay the segment already baked for
+            // their action, so they only move the start along.
+            let replay = *reversed
+                || clips[..index].iter().any(|clip| clip.id == *id);
+
+            if replay {
+                if let Some(segment) = ctx
+                    .action_table
+                    .table()
+                    .get_by_column::<Segment<P::Target>>(
+                        segment_col,
+                        id,
+                    )
+                {
+                    start = if *reversed {
+                        segment.start.clone()
+                    } else {
+                        segment.end.clone()
+                    };
+                }
+                continue;
+            }
 
-        for ActionClip { id, .. } in ctx.track.clips(*span) {
             let Some(action) = ctx
                 .action_table
-                .get_action_by_column::<T>(action_col, id)
+                .get_action_by_column::<P::Target>(action_col, id)
             else {
                 continue;
             };
@@ -241,6 +636,18 @@ pub struct SampleCtx<'a, W> {
     /// The queued actions for this pipeline, each with its
     /// [`SampleMode`] resolved at queue time.
     pub samples: &'a [(ActionId, SampleMode)],
+    /// How much of each sampled value to blend over the current value
+    /// of its field, through the interpolation of its action. `1.0`
+    /// overwrites the field.
+    pub weight: f32,
+    /// The track being sampled, whose [`Track::field_weight`] scales
+    /// [`Self::weight`].
+    pub track: Option<&'a Track>,
+    /// Writes the [`Snapshot`] of each queued action instead of
+    /// sampling it, ignoring [`Self::weight`].
+    pub restore: bool,
+    /// Subjects sampled in place of those of [`Self::action_table`].
+    pub bindings: &'a SubjectBindings,
 }
 
 pub fn sample<W, I, S, T>(ctx: SampleCtx<W>)
@@ -250,23 +657,59 @@ where
     S: 'static,
     T: Clone + ThreadSafe,
 {
+    sample_projected::<W, I, S, T, Whole, Single>(ctx);
+}
+
+fn sample_grouped<W, I, S, T>(ctx: SampleCtx<W>)
+where
+    W: GroupSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+    T: Clone + ThreadSafe,
+{
+    sample_projected::<W, I, S, T, Whole, Grouped>(ctx);
+}
+
+fn sample_projected<W, I, S, C, P, F>(ctx: SampleCtx<W>)
+where
+    W: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+    C: 'static,
+    P: Projection<C>,
+    F: Fanout<W, I, S>,
+{
+    if ctx.restore {
+        restore_projected::<W, I, S, C, P, F>(ctx);
+        return;
+    }
+
     let table = ctx.action_table.table();
-    let Some(segment_col) = table.type_column::<Segment<T>>() else {
+    let Some(segment_col) = table.type_column::<Segment<P::Target>>()
+    else {
         return;
     };
-    let Some(interp_col) = table.type_column::<InterpStorage<T>>()
+    let Some(interp_col) =
+        table.type_column::<InterpStorage<P::Target>>()
     else {
         return;
     };
+    let step_col = table.type_column::<StepStorage>();
+    let modifier_col =
+        table.type_column::<ModifierStorage<P::Target>>();
+    let procedural_col =
+        table.type_column::<ProceduralStorage<P::Target>>();
 
     for &(id, sample_mode) in ctx.samples {
-        let Some(segment) =
-            table.get_by_column::<Segment<T>>(segment_col, &id)
+        let Some(segment) = table
+            .get_by_column::<Segment<P::Target>>(segment_col, &id)
         else {
             continue;
         };
-        let Some(interp) =
-            table.get_by_column::<InterpStorage<T>>(interp_col, &id)
+        let Some(interp) = table
+            .get_by_column::<InterpStorage<P::Target>>(
+                interp_col, &id,
+            )
         else {
             continue;
         };
@@ -276,36 +719,431 @@ where
         };
         let ease = ctx.action_table.ease(&id);
         let Some(accessor) =
-            ctx.accessor_registry.get::<S, T>(key.field())
+            ctx.accessor_registry.get::<S, C>(key.field())
         else {
             continue;
         };
 
-        let Some(&sid) =
-            ctx.action_table.get_id(&key.subject_id().uid())
+        let Some(&sid) = ctx
+            .bindings
+            .resolve(ctx.action_table, &key.subject_id().uid())
         else {
             continue;
         };
 
-        let target = match sample_mode {
-            SampleMode::Start => segment.start.clone(),
-            SampleMode::End => segment.end.clone(),
-            SampleMode::Interp(t) => {
-                let t = match ease {
-                    Some(ease) => ease.0(t),
-                    None => t,
-                };
+        let t = match sample_mode {
+            SampleMode::Start => 0.0,
+            SampleMode::End => 1.0,
+            SampleMode::Interp(t) => match step_col.and_then(|col| {
+                table.get_by_column::<StepStorage>(col, &id)
+            }) {
+                Some(steps) => steps.quantize(t),
+                None => t,
+            },
+        };
+        let eased = || match ease {
+            Some(ease) => ease.ease(t),
+            None => t,
+        };
+        let procedural = procedural_col.and_then(|col| {
+            table.get_by_column::<ProceduralStorage<P::Target>>(
+                col, &id,
+            )
+        });
 
-                interp.0(&segment.start, &segment.end, t)
+        let target = match (procedural, sample_mode) {
+            (Some(procedural), _) => (procedural.0)(eased()),
+            (None, SampleMode::Start) => segment.start.clone(),
+            (None, SampleMode::End) => segment.end.clone(),
+            (None, SampleMode::Interp(_)) => {
+                interp.0(&segment.start, &segment.end, eased())
             }
         };
+        let target = match modifier_col.and_then(|col| {
+            table
+                .get_by_column::<ModifierStorage<P::Target>>(col, &id)
+        }) {
+            Some(modifiers) => modifiers.apply(target, t),
+            None => target,
+        };
+        let weight = ctx.weight
+            * ctx
+                .track
+                .map_or(1.0, |track| track.field_weight(key.field()));
+        if weight <= 0.0 {
+            continue;
+        }
+
+        F::apply(ctx.world, sid, target, |source, target| {
+            let value = accessor.get_mut(source);
+            let target = if weight < 1.0 {
+                let Some(current) =
+                    P::project(value, key, ctx.accessor_registry)
+                        .or_else(|| {
+                            P::fallback(
+                                ctx.action_table.none_policy(&id),
+                            )
+                        })
+                else {
+                    return;
+                };
+                interp.0(&current, &target, weight)
+            } else {
+                target
+            };
+
+            P::write(value, key, target, ctx.accessor_registry);
+        });
+    }
+}
+
+/// Writes the [`Snapshot`] of every queued action back into its
+/// field.
+fn restore_projected<W, I, S, C, P, F>(ctx: SampleCtx<W>)
+where
+    W: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+    C: 'static,
+    P: Projection<C>,
+    F: Fanout<W, I, S>,
+{
+    let table = ctx.action_table.table();
+    let Some(snapshot_col) =
+        table.type_column::<Snapshot<P::Target>>()
+    else {
+        return;
+    };
+
+    for &(id, _) in ctx.samples {
+        let Some(Snapshot(value)) = table
+            .get_by_column::<Snapshot<P::Target>>(snapshot_col, &id)
+        else {
+            continue;
+        };
+        let Some(key) = ctx.action_table.key(&id) else {
+            continue;
+        };
+        let Some(accessor) =
+            ctx.accessor_registry.get::<S, C>(key.field())
+        else {
+            continue;
+        };
+        let Some(&sid) = ctx
+            .bindings
+            .resolve(ctx.action_table, &key.subject_id().uid())
+        else {
+            continue;
+        };
 
-        ctx.world.apply_source(sid, |source| {
-            *accessor.get_mut(source) = target;
+        F::apply(ctx.world, sid, value.clone(), |source, value| {
+            P::write(
+                accessor.get_mut(source),
+                key,
+                value,
+                ctx.accessor_registry,
+            );
         });
     }
 }
 
+/// Routes a sampled value to the source(s) behind a subject id.
+trait Fanout<W, I, S> {
+    /// Calls `write` with `value` on every source behind `id`.
+    fn apply<T: Clone>(
+        world: &mut W,
+        id: I,
+        value: T,
+        write: impl Fn(&mut S, T),
+    );
+}
+
+/// The subject id addresses a single source.
+struct Single;
+
+impl<W, I, S> Fanout<W, I, S> for Single
+where
+    W: SubjectSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+{
+    #[inline]
+    fn apply<T: Clone>(
+        world: &mut W,
+        id: I,
+        value: T,
+        write: impl Fn(&mut S, T),
+    ) {
+        world.apply_source(id, |source| write(source, value));
+    }
+}
+
+/// The subject id addresses a group of sources, see
+/// [`GroupSource`].
+struct Grouped;
+
+impl<W, I, S> Fanout<W, I, S> for Grouped
+where
+    W: GroupSource<I, S>,
+    I: SubjectId,
+    S: 'static,
+{
+    #[inline]
+    fn apply<T: Clone>(
+        world: &mut W,
+        id: I,
+        value: T,
+        write: impl Fn(&mut S, T),
+    ) {
+        world.apply_group(id, |source| write(source, value.clone()));
+    }
+}
+
+/// Narrows the value reached by a field accessor down to the value an
+/// action actually animates.
+trait Projection<C> {
+    type Target: Clone + ThreadSafe;
+
+    /// Reads the start value for baking, `None` to skip the sequence.
+    fn project(
+        value: &C,
+        key: &ActionKey,
+        accessors: &AccessorRegistry,
+    ) -> Option<Self::Target>;
+
+    /// The start value when [`Self::project`] has nothing to read,
+    /// following the [`NonePolicy`] of the action.
+    #[inline]
+    fn fallback(_policy: NonePolicy) -> Option<Self::Target> {
+        None
+    }
+
+    /// Writes a sampled value back into the field.
+    fn write(
+        value: &mut C,
+        key: &ActionKey,
+        target: Self::Target,
+        accessors: &AccessorRegistry,
+    );
+}
+
+/// The accessor's value is the target itself.
+struct Whole;
+
+impl<T: Clone + ThreadSafe> Projection<T> for Whole {
+    type Target = T;
+
+    #[inline]
+    fn project(
+        value: &T,
+        key: &ActionKey,
+        _: &AccessorRegistry,
+    ) -> Option<T> {
+        (key.target() == FieldTarget::Whole).then(|| value.clone())
+    }
+
+    #[inline]
+    fn write(
+        value: &mut T,
+        key: &ActionKey,
+        target: T,
+        _: &AccessorRegistry,
+    ) {
+        if key.target() == FieldTarget::Whole {
+            *value = target;
+        }
+    }
+}
+
+/// The target is the element at [`ActionKey::index`].
+struct Element;
+
+impl<C> Projection<C> for Element
+where
+    C: ElementAccess,
+    C::Element: Clone + ThreadSafe,
+{
+    type Target = C::Element;
+
+    #[inline]
+    fn project(
+        value: &C,
+        key: &ActionKey,
+        _: &AccessorRegistry,
+    ) -> Option<C::Element> {
+        value.element(key.index()?).cloned()
+    }
+
+    #[inline]
+    fn write(
+        value: &mut C,
+        key: &ActionKey,
+        target: C::Element,
+        _: &AccessorRegistry,
+    ) {
+        if let Some(element) =
+            key.index().and_then(|index| value.element_mut(index))
+        {
+            *element = target;
+        }
+    }
+}
+
+/// The target is the value inside an `Option`, see
+/// [`FieldTarget::Inner`].
+struct Inner;
+
+impl<T> Projection<Option<T>> for Inner
+where
+    T: Clone + Default + ThreadSafe,
+{
+    type Target = T;
+
+    #[inline]
+    fn project(
+        value: &Option<T>,
+        key: &ActionKey,
+        _: &AccessorRegistry,
+    ) -> Option<T> {
+        if key.target() != FieldTarget::Inner {
+            return None;
+        }
+
+        value.clone()
+    }
+
+    #[inline]
+    fn fallback(policy: NonePolicy) -> Option<T> {
+        match policy {
+            NonePolicy::Default => Some(T::default()),
+            NonePolicy::Skip => None,
+        }
+    }
+
+    #[inline]
+    fn write(
+        value: &mut Option<T>,
+        key: &ActionKey,
+        target: T,
+        _: &AccessorRegistry,
+    ) {
+        if key.target() == FieldTarget::Inner {
+            *value = Some(target);
+        }
+    }
+}
+
+/// The target is a [`Composite`] view of the whole source, see
+/// [`FieldTarget::Composite`].
+struct Composed<T>(PhantomData<T>);
+
+impl<S, T> Projection<S> for Composed<T>
+where
+    T: Composite<S>,
+{
+    type Target = T;
+
+    #[inline]
+    fn project(
+        value: &S,
+        key: &ActionKey,
+        _: &AccessorRegistry,
+    ) -> Option<T> {
+        let id = TypeId::of::<T>();
+        (key.target() == FieldTarget::Composite(id))
+            .then(|| T::read(value))
+    }
+
+    #[inline]
+    fn write(
+        value: &mut S,
+        key: &ActionKey,
+        target: T,
+        _: &AccessorRegistry,
+    ) {
+        let id = TypeId::of::<T>();
+        if key.target() == FieldTarget::Composite(id) {
+            target.write(value);
+        }
+    }
+}
+
+/// The target is the whole source, of which only the fields of a
+/// [`FieldMask`] are written back, see [`FieldTarget::Masked`].
+///
+/// [`FieldMask`]: crate::field::FieldMask
+struct Masked;
+
+impl<S: Clone + ThreadSafe> Projection<S> for Masked {
+    type Target = S;
+
+    #[inline]
+    fn project(
+        value: &S,
+        key: &ActionKey,
+        _: &AccessorRegistry,
+    ) -> Option<S> {
+        matches!(key.target(), FieldTarget::Masked(_))
+            .then(|| value.clone())
+    }
+
+    #[inline]
+    fn write(
+        value: &mut S,
+        key: &ActionKey,
+        target: S,
+        accessors: &AccessorRegistry,
+    ) {
+        if let FieldTarget::Masked(id) = key.target()
+            && let Some(mask) = accessors.get_mask::<S>(id)
+        {
+            mask.apply(&target, value);
+        }
+    }
+}
+
+/// The target is a value converted from the whole source, see
+/// [`FieldTarget::Virtual`].
+struct Converted<T>(PhantomData<T>);
+
+impl<S, T> Projection<S> for Converted<T>
+where
+    S: 'static,
+    T: Clone + ThreadSafe,
+{
+    type Target = T;
+
+    #[inline]
+    fn project(
+        value: &S,
+        key: &ActionKey,
+        accessors: &AccessorRegistry,
+    ) -> Option<T> {
+        let FieldTarget::Virtual(id) = key.target() else {
+            return None;
+        };
+
+        accessors
+            .get_conversion::<S, T>(id)
+            .map(|conversion| conversion.get(value))
+    }
+
+    #[inline]
+    fn write(
+        value: &mut S,
+        key: &ActionKey,
+        target: T,
+        accessors: &AccessorRegistry,
+    ) {
+        if let FieldTarget::Virtual(id) = key.target()
+            && let Some(conversion) =
+                accessors.get_conversion::<S, T>(id)
+        {
+            conversion.set(value, target);
+        }
+    }
+}
+
 #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Range {
     pub start: Duration,
@@ -353,6 +1191,10 @@ mod tests {
             action_table,
             accessor_registry,
             samples,
+            weight: 1.0,
+            track: None,
+            restore: false,
+            bindings: &SubjectBindings::default(),
         });
     }
 
@@ -441,6 +1283,126 @@ mod tests {
         assert!((world.0 - 2.5).abs() < f32::EPSILON);
     }
 
+    /// A sample rate holds the value of the current frame instead
+    /// of following the timeline's own sampling.
+    #[test]
+    fn sample_join_applies_sample_rate() {
+        let field_acc = crate::path!(<f32>);
+        let field = field_acc.field.untyped();
+
+        let mut accessor_registry = AccessorRegistry::new();
+        accessor_registry.register(field_acc);
+
+        let mut action_table = ActionTable::new();
+        let builder = action_table
+            .add(0u32, field, |x: &f32| *x + 10.0)
+            .with_interp(<f32 as Interpolation<()>>::interp)
+            .with_sample_rate(4.0);
+        let id = builder.id();
+        builder.play(crate::time::s(1));
+
+        let seg_col = action_table.ensure_segment_column::<f32>();
+        action_table.set_segment_by_column(
+            id,
+            Segment::new(0.0f32, 10.0f32),
+            seg_col,
+        );
+        let mut world = MockWorld(0.0);
+        let mut sample_at = |t| {
+            sample_mock(
+                &action_table,
+                &accessor_registry,
+                &mut world,
+                &[(id, SampleMode::Interp(t))],
+            );
+            world.0
+        };
+
+        // 4 fps over a 1s clip: every quarter holds its first value.
+        assert_eq!(sample_at(0.2), 0.0);
+        assert_eq!(sample_at(0.3), 2.5);
+        assert_eq!(sample_at(0.74), 5.0);
+        assert_eq!(sample_at(0.75), 7.5);
+    }
+
+    /// Modifiers post-process the interpolated value in the order
+    /// they were added, including at the ends of the clip.
+    #[test]
+    fn sample_join_applies_modifiers_in_order() {
+        let field_acc = crate::path!(<f32>);
+        let field = field_acc.field.untyped();
+
+        let mut accessor_registry = AccessorRegistry::new();
+        accessor_registry.register(field_acc);
+
+        let mut action_table = ActionTable::new();
+        let builder = action_table
+            .add(0u32, field, |x: &f32| *x + 10.0)
+            .with_interp(<f32 as Interpolation<()>>::interp)
+            .with_modifier(crate::modifier::multiply(0.5))
+            .with_modifier(crate::modifier::quantize(2.0));
+        let id = builder.id();
+        builder.play(crate::time::s(1));
+
+        let seg_col = action_table.ensure_segment_column::<f32>();
+        action_table.set_segment_by_column(
+            id,
+            Segment::new(0.0f32, 10.0f32),
+            seg_col,
+        );
+        let mut world = MockWorld(0.0);
+        let mut sample = |mode| {
+            sample_mock(
+                &action_table,
+                &accessor_registry,
+                &mut world,
+                &[(id, mode)],
+            );
+            world.0
+        };
+
+        // Halved first, then snapped: 7.0 -> 3.5 -> 4.0.
+        assert_eq!(sample(SampleMode::Interp(0.7)), 4.0);
+        assert_eq!(sample(SampleMode::Interp(0.3)), 2.0);
+        assert_eq!(sample(SampleMode::End), 6.0);
+    }
+
+    /// A baked ease must track the curve it was baked from closely
+    /// enough to be a drop-in replacement.
+    #[test]
+    fn sample_join_applies_ease_lut() {
+        let field_acc = crate::path!(<f32>);
+        let field = field_acc.field.untyped();
+
+        let mut accessor_registry = AccessorRegistry::new();
+        accessor_registry.register(field_acc);
+
+        let ease = crate::ease::elastic::ease_out;
+        let mut action_table = ActionTable::new();
+        let id = action_table
+            .add(0u32, field, |x: &f32| *x + 1.0)
+            .with_interp(<f32 as Interpolation<()>>::interp)
+            .with_ease_lut(ease)
+            .id();
+        let seg_col = action_table.ensure_segment_column::<f32>();
+        action_table.set_segment_by_column(
+            id,
+            Segment::new(0.0f32, 1.0f32),
+            seg_col,
+        );
+
+        let mut world = MockWorld(0.0);
+        for t in [0.0, 0.13, 0.5, 0.77, 1.0] {
+            sample_mock(
+                &action_table,
+                &accessor_registry,
+                &mut world,
+                &[(id, SampleMode::Interp(t))],
+            );
+            assert!((world.0 - ease(t)).abs() < 1e-3);
+        }
+    }
+
     #[test]
     fn range_overlap_behavior() {
         let a = Range {
@@ -0,0 +1,233 @@
+//! Samples a single field across a [`Timeline`] for debugging eases
+//! and spacing, or sharing motion specs outside of the code.
+//!
+//! ```ignore
+//! let plot = FieldPlot::sample(
+//!     &mut timeline,
+//!     &registry,
+//!     &mut world,
+//!     entity,
+//!     path!(<Transform>::translation::x),
+//!     60.0,
+//! );
+//!
+//! std::fs::write("x.csv", plot.to_csv())?;
+//! std::fs::write("x.svg", plot.to_svg(320.0, 160.0))?;
+//! ```
+
+use core::fmt::{Display, Write};
+use core::time::Duration;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use field_path::field_accessor::FieldAccessor;
+
+use crate::registry::Registry;
+use crate::subject::SubjectId;
+use crate::timeline::Timeline;
+use crate::world::SubjectSource;
+
+/// Values of a field sampled over time, see [`FieldPlot::sample`].
+#[derive(Debug, Clone)]
+pub struct FieldPlot<T> {
+    samples: Vec<(Duration, T)>,
+}
+
+impl<T> FieldPlot<T> {
+    /// Scrubs the target track of `timeline` from start to end at
+    /// `fps` samples per second, reading `field` of `subject` after
+    /// every sample.
+    ///
+    /// The timeline is scrubbed back to its original target time
+    /// afterwards. Samples where the subject is missing are skipped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `fps` is not positive and finite.
+    pub fn sample<W, I, S>(
+        timeline: &mut Timeline<W>,
+        registry: &Registry,
+        world: &mut W,
+        subject: I,
+        field: FieldAccessor<S, T>,
+        fps: f32,
+    ) -> Self
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + 'static,
+    {
+        assert!(
+            fps > 0.0 && fps.is_finite(),
+            "sample rate must be positive and finite, got {fps}"
+        );
+
+        let accessor =
+            registry.accessor.get::<S, T>(&field.field.untyped());
+        let duration =
+            timeline.tracks()[timeline.target_index()].duration();
+        let original_time = timeline.target_time();
+
+        let step = Duration::from_secs_f32(fps.recip());
+        let mut samples = Vec::new();
+        let mut time = Duration::ZERO;
+        loop {
+            scrub(timeline, registry, world, time);
+
+            if let Some(accessor) = &accessor
+                && let Some(source) = world.get_source(subject)
+            {
+                let value = accessor.get_ref(source).clone();
+                samples.push((time, value));
+            }
+
+            if time >= duration {
+                break;
+            }
+            time = time.saturating_add(step).min(duration);
+        }
+
+        scrub(timeline, registry, world, original_time);
+
+        Self { samples }
+    }
+
+    /// The sampled `(time, value)` pairs, in order.
+    pub fn samples(&self) -> &[(Duration, T)] {
+        &self.samples
+    }
+
+    /// Maps every sampled value, e.g. to plot a single component of a
+    /// vector.
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> FieldPlot<U> {
+        FieldPlot {
+            samples: self
+                .samples
+                .into_iter()
+                .map(|(time, value)| (time, f(value)))
+                .collect(),
+        }
+    }
+}
+
+impl<T: Display> FieldPlot<T> {
+    /// Renders the samples as CSV with a `time,value` header, time in
+    /// seconds.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("time,value\n");
+        for (time, value) in self.samples.iter() {
+            // Writing into a `String` cannot fail.
+            let _ = writeln!(csv, "{},{value}", time.as_secs_f64());
+        }
+        csv
+    }
+}
+
+impl<T: Copy + Into<f64>> FieldPlot<T> {
+    /// Renders the samples as a `width` by `height` SVG line plot,
+    /// with time on the x axis and the value range on the y axis.
+    pub fn to_svg(&self, width: f64, height: f64) -> String {
+        let end = self
+            .samples
+            .last()
+            .map_or(0.0, |(time, _)| time.as_secs_f64());
+        let (min, max) = self.samples.iter().fold(
+            (f64::INFINITY, f64::NEG_INFINITY),
+            |(min, max), (_, value)| {
+                let value = (*value).into();
+                (min.min(value), max.max(value))
+            },
+        );
+        // Flat curves are drawn through the middle.
+        let range = max - min;
+
+        let mut svg = String::new();
+        let _ = write!(
+            svg,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" \
+             width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\">\
+             <polyline fill=\"none\" stroke=\"black\" points=\""
+        );
+        for (time, value) in self.samples.iter() {
+            let x = if end > 0.0 {
+                time.as_secs_f64() / end * width
+            } else {
+                0.0
+            };
+            let y = if range > 0.0 {
+                (1.0 - ((*value).into() - min) / range) * height
+            } else {
+                height * 0.5
+            };
+            let _ = write!(svg, "{x:.2},{y:.2} ");
+        }
+        svg.push_str("\"/></svg>\n");
+        svg
+    }
+}
+
+fn scrub<W: 'static>(
+    timeline: &mut Timeline<W>,
+    registry: &Registry,
+    world: &mut W,
+    time: Duration,
+) {
+    timeline.set_target_time(time);
+    timeline.queue_actions();
+    timeline.sample_queued_actions(registry, world);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    use super::*;
+
+    struct World(f32);
+
+    impl SubjectSource<u32, f32> for World {
+        fn get_source(&self, _id: u32) -> Option<&f32> {
+            Some(&self.0)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            _id: u32,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            Some(f(&mut self.0))
+        }
+    }
+
+    #[test]
+    fn sample_covers_the_track_and_restores_time() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b.act(0, path!(<f32>), |x| x + 10.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let plot = FieldPlot::sample(
+            &mut timeline,
+            &registry,
+            &mut world,
+            0,
+            path!(<f32>),
+            4.0,
+        );
+
+        let values: Vec<_> =
+            plot.samples().iter().map(|(_, v)| *v).collect();
+        assert_eq!(values, [0.0, 2.5, 5.0, 7.5, 10.0]);
+        assert_eq!(timeline.target_time(), Duration::ZERO);
+        assert_eq!(world.0, 0.0);
+
+        let csv = plot.to_csv();
+        assert!(csv.starts_with("time,value\n0,0\n0.25,2.5\n"));
+    }
+}
@@ -1,21 +1,30 @@
-use core::any::TypeId;
+use core::any::{Any, TypeId, type_name};
 
+use alloc::sync::Arc;
+use bevy_math::{Vec2, Vec3};
 use field_path::accessor::{Accessor, UntypedAccessor};
 use field_path::field::UntypedField;
 use field_path::field_accessor::FieldAccessor;
 use hashbrown::HashMap;
 
 use crate::ThreadSafe;
+use crate::cache::CodecRegistry;
+use crate::field::{
+    Composite, Conversion, ConversionId, ElementAccess, FieldMask,
+    MaskId,
+};
 use crate::pipeline::{
     BakeCtx, Pipeline, PipelineHandle, PipelineKey, PipelineUntyped,
     SampleCtx,
 };
-use crate::prelude::{SubjectSource, TimelineBuilder};
+use crate::prelude::{GroupSource, SubjectSource, TimelineBuilder};
 use crate::subject::SubjectId;
 
 pub struct Registry {
     pub accessor: AccessorRegistry,
     pub pipeline: PipelineRegistry,
+    /// Value codecs of [bake caches](crate::cache).
+    pub codec: CodecRegistry,
 }
 
 impl Registry {
@@ -23,6 +32,7 @@ impl Registry {
         Self {
             accessor: AccessorRegistry::new(),
             pipeline: PipelineRegistry::new(),
+            codec: CodecRegistry::new(),
         }
     }
 
@@ -39,6 +49,101 @@ impl Registry {
         self.pipeline.register::<W, I, S, T>();
     }
 
+    /// Similar to [`Self::register`], but for animating single
+    /// elements of the `C` collection field.
+    pub fn register_indexed<W, I, S, C>(
+        &mut self,
+        field_acc: FieldAccessor<S, C>,
+    ) where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        C: ElementAccess + ThreadSafe,
+        C::Element: Clone + ThreadSafe,
+    {
+        self.accessor.register(field_acc);
+        self.pipeline.register_indexed::<W, I, S, C>();
+    }
+
+    /// Similar to [`Self::register`], but for animating the value
+    /// inside an `Option<T>` field.
+    pub fn register_optional<W, I, S, T>(
+        &mut self,
+        field_acc: FieldAccessor<S, Option<T>>,
+    ) where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + Default + ThreadSafe,
+    {
+        self.accessor.register(field_acc);
+        self.pipeline.register_optional::<W, I, S, T>();
+    }
+
+    /// Similar to [`Self::register`], but for animating the `T`
+    /// [`Composite`] view of the whole source through its root
+    /// accessor.
+    pub fn register_composite<W, I, S, T>(
+        &mut self,
+        root: FieldAccessor<S, S>,
+    ) where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: ThreadSafe,
+        T: Composite<S>,
+    {
+        self.accessor.register(root);
+        self.pipeline.register_composite::<W, I, S, T>();
+    }
+
+    /// Similar to [`Self::register`], but for animating the whole
+    /// source while only writing the fields of `mask`.
+    pub fn register_masked<W, I, S>(
+        &mut self,
+        root: FieldAccessor<S, S>,
+        mask: Arc<FieldMask<S>>,
+    ) where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: Clone + ThreadSafe,
+    {
+        self.accessor.register(root);
+        self.accessor.register_mask(mask);
+        self.pipeline.register_masked::<W, I, S>();
+    }
+
+    /// Similar to [`Self::register`], but for animating a virtual
+    /// `T` field of the whole source through `conversion`.
+    pub fn register_virtual<W, I, S, T>(
+        &mut self,
+        root: FieldAccessor<S, S>,
+        conversion: Arc<Conversion<S, T>>,
+    ) where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: ThreadSafe,
+        T: Clone + ThreadSafe,
+    {
+        self.accessor.register(root);
+        self.accessor.register_conversion(conversion);
+        self.pipeline.register_virtual::<W, I, S, T>();
+    }
+
+    /// Similar to [`Self::register`], but samples into every member
+    /// of a group subject. See [`Pipeline::grouped`].
+    pub fn register_grouped<W, I, S, T>(
+        &mut self,
+        field_acc: FieldAccessor<S, T>,
+    ) where
+        W: GroupSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + ThreadSafe,
+    {
+        self.accessor.register(field_acc);
+        self.pipeline.register_grouped::<W, I, S, T>();
+    }
+
     /// Create a [`TimelineBuilder`] for a specific `W` world.
     pub fn create_builder<W: 'static>(
         &mut self,
@@ -55,12 +160,18 @@ impl Default for Registry {
 
 pub struct AccessorRegistry {
     accessors: HashMap<UntypedField, UntypedAccessor>,
+    /// Type erased [`FieldMask`]s by id.
+    masks: HashMap<MaskId, Arc<dyn Any + Send + Sync>>,
+    /// Type erased [`Conversion`]s by id.
+    conversions: HashMap<ConversionId, Arc<dyn Any + Send + Sync>>,
 }
 
 impl AccessorRegistry {
     pub fn new() -> Self {
         Self {
             accessors: HashMap::new(),
+            masks: HashMap::new(),
+            conversions: HashMap::new(),
         }
     }
 
@@ -87,6 +198,51 @@ impl AccessorRegistry {
     ) -> Option<Accessor<S, T>> {
         self.accessors.get(field)?.typed()
     }
+
+    /// Returns `true` if an accessor is registered for `field`.
+    pub fn contains(&self, field: &UntypedField) -> bool {
+        self.accessors.contains_key(field)
+    }
+
+    /// Registers a [`FieldMask`].
+    /// Skips masks already registered.
+    pub fn register_mask<S: 'static>(
+        &mut self,
+        mask: Arc<FieldMask<S>>,
+    ) {
+        self.masks.entry(mask.id()).or_insert(mask);
+    }
+
+    /// Retrieve a registered [`FieldMask`] of the `S` source.
+    pub fn get_mask<S: 'static>(
+        &self,
+        id: MaskId,
+    ) -> Option<&FieldMask<S>> {
+        self.masks.get(&id)?.downcast_ref()
+    }
+
+    /// Registers a [`Conversion`].
+    /// Skips conversions already registered.
+    pub fn register_conversion<S, T>(
+        &mut self,
+        conversion: Arc<Conversion<S, T>>,
+    ) where
+        S: 'static,
+        T: 'static,
+    {
+        self.conversions
+            .entry(conversion.id())
+            .or_insert(conversion);
+    }
+
+    /// Retrieve a registered [`Conversion`] between the `S` source
+    /// and `T`.
+    pub fn get_conversion<S: 'static, T: 'static>(
+        &self,
+        id: ConversionId,
+    ) -> Option<&Conversion<S, T>> {
+        self.conversions.get(&id)?.downcast_ref()
+    }
 }
 
 impl Default for AccessorRegistry {
@@ -142,6 +298,17 @@ impl PipelineRegistry {
         false
     }
 
+    /// Returns `true` if a pipeline is registered for `key`.
+    pub fn contains(&self, key: &PipelineKey) -> bool {
+        self.pipelines.contains_key(key)
+    }
+
+    /// Returns the type name of the subject, source and target types
+    /// of the pipeline registered for `key`.
+    pub fn name(&self, key: &PipelineKey) -> Option<&'static str> {
+        self.pipelines.get(key).map(PipelineUntyped::name)
+    }
+
     /// Register a [`Pipeline`].
     /// Skips pipelines already registered.
     pub fn register<W, I, S, T>(&mut self) -> &mut Self
@@ -156,8 +323,141 @@ impl PipelineRegistry {
             return self;
         }
 
+        // Numeric fields are sampled in bulk, see `crate::lanes`.
+        let target = TypeId::of::<T>();
+        let pipeline = if target == TypeId::of::<f32>() {
+            Pipeline::<W, I, S, f32>::lanes().untyped()
+        } else if target == TypeId::of::<Vec2>() {
+            Pipeline::<W, I, S, Vec2>::lanes().untyped()
+        } else if target == TypeId::of::<Vec3>() {
+            Pipeline::<W, I, S, Vec3>::lanes().untyped()
+        } else {
+            Pipeline::<W, I, S, T>::new().untyped()
+        };
+
+        self.pipelines.insert(key, pipeline);
+        self
+    }
+
+    /// Register a grouped [`Pipeline`], replacing the one registered
+    /// for the same key by [`Self::register`].
+    pub fn register_grouped<W, I, S, T>(&mut self) -> &mut Self
+    where
+        W: GroupSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + ThreadSafe,
+    {
+        self.pipelines.insert(
+            PipelineKey::new::<W, I, S, T>(),
+            Pipeline::<W, I, S, T>::grouped().untyped(),
+        );
+        self
+    }
+
+    /// Register an indexed [`Pipeline`] for the `C` collection field.
+    /// Skips pipelines already registered.
+    pub fn register_indexed<W, I, S, C>(&mut self) -> &mut Self
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        C: ElementAccess + ThreadSafe,
+        C::Element: Clone + ThreadSafe,
+    {
+        let key = PipelineKey::new_indexed::<W, I, S, C>();
+        if self.pipelines.contains_key(&key) {
+            return self;
+        }
+
         self.pipelines
-            .insert(key, Pipeline::<W, I, S, T>::new().untyped());
+            .insert(key, Pipeline::<W, I, S, C>::indexed().untyped());
+        self
+    }
+
+    /// Register an optional [`Pipeline`] for the `Option<T>` field.
+    /// Skips pipelines already registered.
+    pub fn register_optional<W, I, S, T>(&mut self) -> &mut Self
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + Default + ThreadSafe,
+    {
+        let key = PipelineKey::new_optional::<W, I, S, T>();
+        if self.pipelines.contains_key(&key) {
+            return self;
+        }
+
+        self.pipelines.insert(
+            key,
+            Pipeline::<W, I, S, Option<T>>::optional().untyped(),
+        );
+        self
+    }
+
+    /// Register a masked [`Pipeline`] for the whole `S` source.
+    /// Skips pipelines already registered.
+    pub fn register_masked<W, I, S>(&mut self) -> &mut Self
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: Clone + ThreadSafe,
+    {
+        let key = PipelineKey::new_masked::<W, I, S>();
+        if self.pipelines.contains_key(&key) {
+            return self;
+        }
+
+        self.pipelines
+            .insert(key, Pipeline::<W, I, S, S>::masked().untyped());
+        self
+    }
+
+    /// Register a virtual [`Pipeline`] for `T` fields converted from
+    /// the whole `S` source.
+    /// Skips pipelines already registered.
+    pub fn register_virtual<W, I, S, T>(&mut self) -> &mut Self
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: ThreadSafe,
+        T: Clone + ThreadSafe,
+    {
+        let key = PipelineKey::new_virtual::<W, I, S, T>();
+        if self.pipelines.contains_key(&key) {
+            return self;
+        }
+
+        self.pipelines.insert(
+            key,
+            Pipeline::<W, I, S, S>::converted::<T>()
+                .untyped()
+                .with_name(type_name::<(I, S, T)>()),
+        );
+        self
+    }
+
+    /// Register a composite [`Pipeline`] for the `T` view of `S`.
+    /// Skips pipelines already registered.
+    pub fn register_composite<W, I, S, T>(&mut self) -> &mut Self
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: ThreadSafe,
+        T: Composite<S>,
+    {
+        let key = PipelineKey::new_composite::<W, I, S, T>();
+        if self.pipelines.contains_key(&key) {
+            return self;
+        }
+
+        self.pipelines.insert(
+            key,
+            Pipeline::<W, I, S, S>::composite::<T>()
+                .untyped()
+                .with_name(type_name::<(I, S, T)>()),
+        );
         self
     }
 }
@@ -0,0 +1,290 @@
+//! String-based bridge for authoring timelines from scripts and other
+//! untyped sources.
+//!
+//! Scripts cannot name Rust types, so a [`ScriptRegistry`] maps
+//! field names to typed [`ActionField`]s and ease names to
+//! [`EaseFn`]s ahead of time. Actions are then described entirely
+//! with strings into a [`TimelineGraph`], their values written as
+//! [`Expr`]s:
+//!
+//! ```ignore
+//! let mut registry = ScriptRegistry::<World, Entity>::new();
+//! registry.register_field("x", path!(<Transform>::translation::x));
+//!
+//! let mut graph = TimelineGraph::new();
+//! let clip = registry.act(
+//!     &mut graph,
+//!     "cube",
+//!     "x",
+//!     "start + 2.0",
+//!     Some("cubic_in_out"),
+//!     s(1),
+//! )?;
+//! graph.add_track(clip);
+//! ```
+//!
+//! With the `rhai` feature, [`rhai::build_graph`] exposes the same
+//! registry and the ordering combinators to [rhai] scripts.
+//!
+//! [rhai]: https://rhai.rs
+
+use core::fmt;
+use core::time::Duration;
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use hashbrown::HashMap;
+
+use crate::ThreadSafe;
+use crate::action::EaseFn;
+use crate::ease;
+use crate::expr::{Expr, ExprError};
+use crate::field::ActionField;
+use crate::graph::{GraphFragment, TimelineGraph};
+use crate::subject::SubjectId;
+use crate::world::SubjectSource;
+
+#[cfg(feature = "rhai")]
+pub mod rhai;
+
+/// Describes an [`Expr`] action on a named subject.
+type ActFn<W, I> = Arc<
+    dyn Fn(
+            &mut TimelineGraph<W, I>,
+            String,
+            Expr,
+            Option<EaseFn>,
+            Duration,
+        ) -> GraphFragment
+        + Send
+        + Sync,
+>;
+
+/// Fields and eases by name, see the [module docs](self).
+pub struct ScriptRegistry<W, I> {
+    fields: HashMap<String, ActFn<W, I>>,
+    eases: HashMap<String, EaseFn>,
+}
+
+impl<W, I> ScriptRegistry<W, I>
+where
+    W: 'static,
+    I: SubjectId,
+{
+    /// Creates a registry without fields, knowing every built-in
+    /// ease of [`ease::NAMED`].
+    pub fn new() -> Self {
+        Self {
+            fields: HashMap::new(),
+            eases: ease::NAMED
+                .iter()
+                .map(|(name, ease)| (String::from(*name), *ease))
+                .collect(),
+        }
+    }
+
+    /// Registers an [`f32`] field under `name`, replacing any field
+    /// previously registered under it.
+    pub fn register_field<S>(
+        &mut self,
+        name: impl Into<String>,
+        field: impl ActionField<S, f32> + Clone + ThreadSafe,
+    ) -> &mut Self
+    where
+        W: SubjectSource<I, S>,
+        S: 'static,
+    {
+        self.fields.insert(
+            name.into(),
+            Arc::new(move |graph, subject, expr, ease, duration| {
+                let builder =
+                    graph.act(subject, field.clone(), expr.action());
+
+                match ease {
+                    Some(ease) => builder.with_ease(ease),
+                    None => builder,
+                }
+                .play(duration)
+            }),
+        );
+        self
+    }
+
+    /// Registers `ease` under `name`, replacing any ease previously
+    /// registered under it.
+    pub fn register_ease(
+        &mut self,
+        name: impl Into<String>,
+        ease: EaseFn,
+    ) -> &mut Self {
+        self.eases.insert(name.into(), ease);
+        self
+    }
+
+    /// Returns `true` if a field is registered under `name`.
+    pub fn contains_field(&self, name: &str) -> bool {
+        self.fields.contains_key(name)
+    }
+
+    /// Names of every registered field, in no particular order.
+    pub fn field_names(&self) -> impl Iterator<Item = &str> {
+        self.fields.keys().map(String::as_str)
+    }
+
+    /// Get the ease registered under `name`.
+    pub fn ease(&self, name: &str) -> Option<EaseFn> {
+        self.eases.get(name).copied()
+    }
+
+    /// Describe an action on the field registered under `field`,
+    /// ending at `expr`, and play it for `duration`.
+    ///
+    /// See [`TimelineGraph::act`].
+    pub fn act(
+        &self,
+        graph: &mut TimelineGraph<W, I>,
+        subject: impl Into<String>,
+        field: &str,
+        expr: &str,
+        ease: Option<&str>,
+        duration: Duration,
+    ) -> Result<GraphFragment, ScriptError> {
+        let act = self.fields.get(field).ok_or_else(|| {
+            ScriptError::UnknownField(String::from(field))
+        })?;
+        let ease = ease
+            .map(|name| {
+                self.ease(name).ok_or_else(|| {
+                    ScriptError::UnknownEase(String::from(name))
+                })
+            })
+            .transpose()?;
+        let expr = Expr::parse(expr)?;
+
+        Ok(act(graph, subject.into(), expr, ease, duration))
+    }
+}
+
+impl<W, I> Default for ScriptRegistry<W, I>
+where
+    W: 'static,
+    I: SubjectId,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<W, I> Clone for ScriptRegistry<W, I> {
+    fn clone(&self) -> Self {
+        Self {
+            fields: self.fields.clone(),
+            eases: self.eases.clone(),
+        }
+    }
+}
+
+/// Reasons [`ScriptRegistry::act`] can fail.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptError {
+    /// No field is registered under the name.
+    UnknownField(String),
+    /// No ease is registered under the name.
+    UnknownEase(String),
+    /// The value expression failed to parse.
+    Expr(ExprError),
+}
+
+impl From<ExprError> for ScriptError {
+    fn from(err: ExprError) -> Self {
+        Self::Expr(err)
+    }
+}
+
+impl fmt::Display for ScriptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownField(name) => {
+                write!(f, "unknown field `{name}`")
+            }
+            Self::UnknownEase(name) => {
+                write!(f, "unknown ease `{name}`")
+            }
+            Self::Expr(err) => write!(f, "invalid expression: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for ScriptError {}
+
+#[cfg(test)]
+mod tests {
+    use alloc::vec::Vec;
+
+    use crate::registry::Registry;
+    use crate::time::s;
+
+    use super::*;
+
+    struct World(Vec<f32>);
+
+    impl SubjectSource<usize, f32> for World {
+        fn get_source(&self, id: usize) -> Option<&f32> {
+            self.0.get(id)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            id: usize,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            self.0.get_mut(id).map(f)
+        }
+    }
+
+    #[test]
+    fn acts_by_name() {
+        let mut scripts = ScriptRegistry::<World, usize>::new();
+        scripts.register_field("value", crate::path!(<f32>));
+
+        let mut graph = TimelineGraph::new();
+        let clip = scripts
+            .act(
+                &mut graph,
+                "a",
+                "value",
+                "start * 2.0",
+                Some("quad_in"),
+                s(1),
+            )
+            .unwrap();
+        graph.add_track(clip);
+
+        let desc = graph.actions().next().unwrap();
+        assert_eq!(desc.subject, "a");
+        assert!(desc.ease.is_some());
+
+        let mut registry = Registry::new();
+        let mut timeline =
+            graph.resolve(&mut registry, |_| Some(0)).unwrap();
+        let mut world = World(alloc::vec![3.0]);
+        timeline.bake_actions(&registry, &world);
+        timeline.set_target_time(s(1));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+
+        assert_eq!(world.0[0], 6.0);
+    }
+
+    #[test]
+    fn unknown_names_are_errors() {
+        let scripts = ScriptRegistry::<World, usize>::new();
+        let mut graph = TimelineGraph::new();
+
+        assert_eq!(
+            scripts.act(&mut graph, "a", "value", "1", None, s(1)),
+            Err(ScriptError::UnknownField("value".into())),
+        );
+        assert_eq!(graph.actions().count(), 0);
+    }
+}
@@ -0,0 +1,281 @@
+//! [rhai](https://rhai.rs) bindings for [`TimelineGraph`] authoring.
+//!
+//! Scripts describe clips with `act` and order them with the same
+//! combinators as [`GraphFragment`], durations given in seconds:
+//!
+//! ```rhai
+//! let rise = act("cube", "y", "start + 2.0", 1.0, "cubic_out");
+//! let fade = act("cube", "alpha", "0.0", 0.5);
+//!
+//! track(chain([rise, delay(0.25, fade)]));
+//! track(flow(0.1, [
+//!     act("a", "x", "start + 1.0", 1.0),
+//!     act("b", "x", "start + 1.0", 1.0),
+//! ]));
+//! ```
+//!
+//! Fields, eases and values are looked up through a
+//! [`ScriptRegistry`], so every field a script animates must be
+//! registered beforehand.
+
+use core::cell::RefCell;
+use core::time::Duration;
+
+use ::rhai::{Array, Engine, EvalAltResult, Position};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::ToString;
+use alloc::vec::Vec;
+
+use super::ScriptRegistry;
+use crate::graph::{GraphFragment, TimelineGraph};
+use crate::subject::SubjectId;
+use crate::track::Alignment;
+
+type ScriptResult<T> = Result<T, Box<EvalAltResult>>;
+
+/// Runs `script` and collects the tracks it adds into a new
+/// [`TimelineGraph`], see the [module docs](self).
+pub fn build_graph<W, I>(
+    registry: &ScriptRegistry<W, I>,
+    script: &str,
+) -> ScriptResult<TimelineGraph<W, I>>
+where
+    W: 'static,
+    I: SubjectId,
+{
+    let mut engine = Engine::new();
+    let graph = Rc::new(RefCell::new(TimelineGraph::new()));
+    register_graph(&mut engine, registry.clone(), graph.clone());
+
+    engine.run(script)?;
+    drop(engine);
+
+    Ok(graph.take())
+}
+
+/// Registers the `act`, `track` and ordering functions on `engine`,
+/// writing into `graph`.
+///
+/// Use this over [`build_graph`] to extend the engine with functions
+/// of your own.
+pub fn register_graph<W, I>(
+    engine: &mut Engine,
+    registry: ScriptRegistry<W, I>,
+    graph: Rc<RefCell<TimelineGraph<W, I>>>,
+) where
+    W: 'static,
+    I: SubjectId,
+{
+    engine.register_type_with_name::<GraphFragment>("Fragment");
+
+    let registry = Rc::new(registry);
+    {
+        let (registry, graph) = (registry.clone(), graph.clone());
+        engine.register_fn(
+            "act",
+            move |subject: &str,
+                  field: &str,
+                  expr: &str,
+                  secs: f64|
+                  -> ScriptResult<GraphFragment> {
+                registry
+                    .act(
+                        &mut graph.borrow_mut(),
+                        subject,
+                        field,
+                        expr,
+                        None,
+                        duration(secs)?,
+                    )
+                    .map_err(runtime_error)
+            },
+        );
+    }
+    {
+        let (registry, graph) = (registry, graph.clone());
+        engine.register_fn(
+            "act",
+            move |subject: &str,
+                  field: &str,
+                  expr: &str,
+                  secs: f64,
+                  ease: &str|
+                  -> ScriptResult<GraphFragment> {
+                registry
+                    .act(
+                        &mut graph.borrow_mut(),
+                        subject,
+                        field,
+                        expr,
+                        Some(ease),
+                        duration(secs)?,
+                    )
+                    .map_err(runtime_error)
+            },
+        );
+    }
+
+    engine.register_fn("track", move |fragment: GraphFragment| {
+        graph.borrow_mut().add_track(fragment);
+    });
+    engine.register_fn(
+        "chain",
+        |fragments: Array| -> ScriptResult<GraphFragment> {
+            Ok(GraphFragment::chain(fragments_of(fragments)?))
+        },
+    );
+    engine.register_fn(
+        "all",
+        |fragments: Array| -> ScriptResult<GraphFragment> {
+            Ok(GraphFragment::all(fragments_of(fragments)?))
+        },
+    );
+    engine.register_fn(
+        "any",
+        |fragments: Array| -> ScriptResult<GraphFragment> {
+            Ok(GraphFragment::any(fragments_of(fragments)?))
+        },
+    );
+    engine.register_fn(
+        "flow",
+        |secs: f64,
+         fragments: Array|
+         -> ScriptResult<GraphFragment> {
+            Ok(GraphFragment::flow(
+                duration(secs)?,
+                fragments_of(fragments)?,
+            ))
+        },
+    );
+    engine.register_fn(
+        "overlap",
+        |secs: f64,
+         fragments: Array|
+         -> ScriptResult<GraphFragment> {
+            Ok(GraphFragment::overlap(
+                duration(secs)?,
+                fragments_of(fragments)?,
+            ))
+        },
+    );
+    engine.register_fn(
+        "align_end",
+        |fragments: Array| -> ScriptResult<GraphFragment> {
+            Ok(GraphFragment::align(
+                Alignment::End,
+                fragments_of(fragments)?,
+            ))
+        },
+    );
+    engine.register_fn(
+        "align_center",
+        |fragments: Array| -> ScriptResult<GraphFragment> {
+            Ok(GraphFragment::align(
+                Alignment::Center,
+                fragments_of(fragments)?,
+            ))
+        },
+    );
+    engine.register_fn(
+        "align_to",
+        |secs: f64,
+         fragments: Array|
+         -> ScriptResult<GraphFragment> {
+            Ok(GraphFragment::align(
+                Alignment::To(duration(secs)?),
+                fragments_of(fragments)?,
+            ))
+        },
+    );
+    engine.register_fn(
+        "delay",
+        |secs: f64,
+         fragment: GraphFragment|
+         -> ScriptResult<GraphFragment> {
+            Ok(fragment.delayed(duration(secs)?))
+        },
+    );
+}
+
+fn fragments_of(array: Array) -> ScriptResult<Vec<GraphFragment>> {
+    array
+        .into_iter()
+        .map(|value| {
+            let type_name = value.type_name();
+            value.try_cast::<GraphFragment>().ok_or_else(|| {
+                runtime_error(format!(
+                    "expected a Fragment, found {type_name}"
+                ))
+            })
+        })
+        .collect()
+}
+
+fn duration(secs: f64) -> ScriptResult<Duration> {
+    Duration::try_from_secs_f64(secs).map_err(|_| {
+        runtime_error(format!("invalid duration of {secs} seconds"))
+    })
+}
+
+fn runtime_error(err: impl ToString) -> Box<EvalAltResult> {
+    EvalAltResult::ErrorRuntime(
+        err.to_string().into(),
+        Position::NONE,
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::time::{cs, s};
+    use crate::world::SubjectSource;
+
+    use super::*;
+
+    struct World(f32);
+
+    impl SubjectSource<usize, f32> for World {
+        fn get_source(&self, _: usize) -> Option<&f32> {
+            Some(&self.0)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            _: usize,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            Some(f(&mut self.0))
+        }
+    }
+
+    #[test]
+    fn scripts_build_tracks() {
+        let mut registry = ScriptRegistry::<World, usize>::new();
+        registry.register_field("x", crate::path!(<f32>));
+
+        let graph = build_graph(
+            &registry,
+            r#"
+                let a = act("a", "x", "start + 1.0", 1.0);
+                let b = act("a", "x", "start * 2", 0.5, "quad_in");
+                track(chain([a, delay(0.25, b)]));
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(graph.tracks().len(), 1);
+        assert_eq!(graph.tracks()[0].duration(), s(1) + cs(75));
+    }
+
+    #[test]
+    fn unknown_fields_fail_the_script() {
+        let registry = ScriptRegistry::<World, usize>::new();
+
+        assert!(
+            build_graph(&registry, r#"act("a", "y", "1.0", 1.0);"#)
+                .is_err()
+        );
+    }
+}
@@ -1,8 +1,9 @@
 use core::time::Duration;
 
+use alloc::vec::Vec;
 use nonempty::NonEmpty;
 
-use crate::action::ActionClip;
+use crate::action::{ActionClip, ActionId};
 
 /// A non-overlapping sequence of [`ActionClip`]s.
 #[derive(Debug, Clone)]
@@ -46,6 +47,56 @@ impl Sequence {
             clip.start = clip.start.saturating_add(duration);
         }
     }
+
+    /// Mirrors the clips within `[0, duration]`, flipping the
+    /// direction each one plays in.
+    pub(crate) fn reverse(self, duration: Duration) -> Self {
+        let mut clips = Vec::from(self.clips);
+        clips.reverse();
+        for clip in clips.iter_mut() {
+            clip.start = duration.saturating_sub(clip.end());
+            clip.reversed = !clip.reversed;
+            (clip.from, clip.to) = (1.0 - clip.to, 1.0 - clip.from);
+        }
+
+        Self {
+            clips: NonEmpty::from_vec(clips)
+                .expect("a sequence is never empty"),
+        }
+    }
+
+    /// Splits the clips at `at`, cutting the clip straddling it, and
+    /// moves the later ones back by `at`.
+    pub(crate) fn split(
+        self,
+        at: Duration,
+    ) -> (Option<Self>, Option<Self>) {
+        let mut before = Vec::new();
+        let mut after = Vec::new();
+
+        for clip in self.clips {
+            if clip.end() <= at {
+                before.push(clip);
+            } else if clip.start >= at {
+                after.push(ActionClip {
+                    start: clip.start - at,
+                    ..clip
+                });
+            } else {
+                let (head, tail) = clip.split_at(at);
+                before.push(head);
+                after.push(ActionClip {
+                    start: Duration::ZERO,
+                    ..tail
+                });
+            }
+        }
+
+        (
+            NonEmpty::from_vec(before).map(|clips| Self { clips }),
+            NonEmpty::from_vec(after).map(|clips| Self { clips }),
+        )
+    }
 }
 
 impl Sequence {
@@ -62,6 +113,53 @@ impl Sequence {
     }
 }
 
+impl Sequence {
+    /// Inserts a clip in time order.
+    pub fn insert(&mut self, clip: ActionClip) {
+        let index = self
+            .clips
+            .iter()
+            .position(|other| other.start > clip.start)
+            .unwrap_or(self.clips.len());
+
+        debug_assert!(
+            index == 0 || self.clips[index - 1].end() <= clip.start,
+            "`ActionClip`s shouldn't overlap!"
+        );
+        debug_assert!(
+            self.clips
+                .get(index)
+                .is_none_or(|next| clip.end() <= next.start),
+            "`ActionClip`s shouldn't overlap!"
+        );
+
+        self.clips.insert(index, clip);
+    }
+
+    /// Removes the clip of action `id`, returning it along with the
+    /// rest of the sequence, which is `None` if it is now empty.
+    ///
+    /// Returns `Err(self)` if the clip is not in the sequence.
+    pub(crate) fn remove(
+        self,
+        id: ActionId,
+    ) -> Result<(ActionClip, Option<Self>), Self> {
+        let Some(index) =
+            self.clips.iter().position(|clip| clip.id == id)
+        else {
+            return Err(self);
+        };
+
+        let mut clips = Vec::from(self.clips);
+        let clip = clips.remove(index);
+
+        Ok((
+            clip,
+            NonEmpty::from_vec(clips).map(|clips| Self { clips }),
+        ))
+    }
+}
+
 impl Extend<ActionClip> for Sequence {
     #[inline]
     fn extend<T: IntoIterator<Item = ActionClip>>(
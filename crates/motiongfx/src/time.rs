@@ -31,6 +31,14 @@ pub const fn ms(millis: u64) -> Duration {
     Duration::from_millis(millis)
 }
 
+/// Whole microseconds as a [`Duration`], e.g. for frame times of
+/// exports that don't land on a whole millisecond.
+#[inline]
+#[must_use]
+pub const fn us(micros: u64) -> Duration {
+    Duration::from_micros(micros)
+}
+
 /// Whole nanoseconds as a [`Duration`].
 #[inline]
 #[must_use]
@@ -47,6 +55,8 @@ mod tests {
         assert_eq!(s(2), ms(2_000));
         assert_eq!(cs(150), ms(1_500));
         assert_eq!(ms(1), ns(1_000_000));
+        assert_eq!(ms(1), us(1_000));
+        assert_eq!(us(1), ns(1_000));
         assert_eq!(cs(u64::MAX), Duration::from_millis(u64::MAX));
     }
 }
@@ -1,30 +1,55 @@
 use core::cmp::Ordering;
 use core::marker::PhantomData;
+use core::ops::Add;
 use core::time::Duration;
 
 use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec;
 use alloc::vec::Vec;
+#[cfg(feature = "curve")]
+use bevy_math::curve::Curve;
+use field_path::field::UntypedField;
 use field_path::field_accessor::FieldAccessor;
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 
 use crate::ThreadSafe;
 use crate::action::{
-    Action, ActionBuilder, ActionId, ActionKey, ActionTable,
-    InterpActionBuilder, SampleMode,
+    Action, ActionBuilder, ActionClip, ActionId, ActionKey,
+    ActionTable, ActionValue, InterpActionBuilder, Procedural,
+    SampleMode, SubjectBindings, UntypedSubjectId,
 };
+use crate::cache::{self, CacheError};
+use crate::field::ActionField;
 use crate::interpolation::Interpolation;
+use crate::modifier::Wiggle;
 use crate::pipeline::{BakeCtx, PipelineKey, Range, SampleCtx};
 use crate::registry::Registry;
 use crate::subject::SubjectId;
-use crate::track::Track;
-use crate::world::SubjectSource;
+use crate::track::{Track, TrackFragment};
+use crate::world::{GroupSource, SubjectSource};
+
+mod edit;
+
+pub use edit::TimelineEditor;
 
 pub struct Timeline<W> {
-    action_table: ActionTable,
-    pipeline_counts: Box<[(PipelineKey, u32)]>,
+    /// Shared with every instance, see [`Timeline::instance`].
+    action_table: Arc<ActionTable>,
+    pipeline_counts: Arc<[(PipelineKey, u32)]>,
     /// Track length is guaranteed to be at least 1 by construction.
     /// See [`TimelineBuilder::compile()`].
-    tracks: Box<[Track]>,
+    ///
+    /// Copied on write, so instances can tweak their own tracks.
+    tracks: Arc<[Track]>,
+    /// Whether each track in `tracks` has been baked.
+    baked: Box<[bool]>,
+    /// Named checkpoints, each with the index of its track.
+    labels: Arc<[(String, usize)]>,
+    /// Subjects this timeline plays in place of those of the shared
+    /// actions, see [`Timeline::bind_subject`].
+    bindings: SubjectBindings,
     /// Cached actions that are queued to be sampled.
     ///
     /// This cache will be cleared everytime [`Timeline::queue_actions`]
@@ -47,30 +72,365 @@ pub struct Timeline<W> {
 }
 
 impl<W: 'static> Timeline<W> {
+    /// Bakes every track up front.
+    ///
+    /// See [`Self::bake_active_tracks`] for baking tracks only as
+    /// they are reached.
     pub fn bake_actions(
         &mut self,
         registry: &Registry,
         subject_world: &W,
     ) {
-        for key in self.pipeline_counts.iter().map(|(key, _)| key) {
-            for track in self.tracks.iter() {
-                let ok = registry.pipeline.bake(
-                    key,
-                    BakeCtx {
-                        world: subject_world,
-                        track,
-                        action_table: &mut self.action_table,
-                        accessor_registry: &registry.accessor,
-                    },
-                );
-                debug_assert!(
-                    ok,
-                    "pipeline not found for key {key:?}"
+        for index in 0..self.tracks.len() {
+            self.bake_track(index, registry, subject_world);
+        }
+    }
+
+    /// Bakes the track at `index` from the current state of
+    /// `subject_world`, re-baking it if it is already baked.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn bake_track(
+        &mut self,
+        index: usize,
+        registry: &Registry,
+        subject_world: &W,
+    ) {
+        self.bake_filtered(
+            index,
+            registry,
+            subject_world,
+            None,
+            false,
+            false,
+        );
+        self.baked[index] = true;
+    }
+
+    /// Records the starting value of every sequence in the tracks
+    /// that are not baked yet from the current state of
+    /// `subject_world`, without baking them.
+    ///
+    /// [`Self::bake_active_tracks`] then bakes from these values
+    /// instead of the world as it is when the tracks are reached,
+    /// which by then holds whatever the earlier tracks left in it.
+    /// Call this when the timeline is loaded so lazily baked tracks
+    /// match [`Self::bake_actions`].
+    pub fn snapshot_tracks(
+        &mut self,
+        registry: &Registry,
+        subject_world: &W,
+    ) {
+        for index in 0..self.tracks.len() {
+            if !self.baked[index] {
+                self.bake_filtered(
+                    index,
+                    registry,
+                    subject_world,
+                    None,
+                    false,
+                    true,
                 );
             }
         }
     }
 
+    /// Bakes the tracks that are not baked yet among those the next
+    /// [`Self::queue_actions`] passes through, plus the one after
+    /// the target track.
+    ///
+    /// Calling this before every queue bakes tracks lazily as the
+    /// timeline reaches them, instead of all at once with
+    /// [`Self::bake_actions`]. Sequences start from the values taken
+    /// by [`Self::snapshot_tracks`], or the world if there are none.
+    pub fn bake_active_tracks(
+        &mut self,
+        registry: &Registry,
+        subject_world: &W,
+    ) {
+        let start = self.curr_index.min(self.target_index);
+        let end = self.curr_index.max(self.target_index) + 1;
+
+        for index in start..=end.min(self.last_track_index()) {
+            self.bake_pending_track(index, registry, subject_world);
+        }
+    }
+
+    /// Bakes every track that is not baked yet like
+    /// [`Self::bake_active_tracks`], e.g. during a loading screen.
+    pub fn bake_remaining_tracks(
+        &mut self,
+        registry: &Registry,
+        subject_world: &W,
+    ) {
+        for index in 0..self.tracks.len() {
+            self.bake_pending_track(index, registry, subject_world);
+        }
+    }
+
+    fn bake_pending_track(
+        &mut self,
+        index: usize,
+        registry: &Registry,
+        subject_world: &W,
+    ) {
+        if self.baked[index] {
+            return;
+        }
+
+        self.bake_filtered(
+            index,
+            registry,
+            subject_world,
+            None,
+            true,
+            false,
+        );
+        self.baked[index] = true;
+    }
+
+    /// Writes the segments of every baked track into a
+    /// [bake cache](crate::cache), to be read back with
+    /// [`Self::read_bake_cache`] instead of baking.
+    pub fn write_bake_cache(
+        &self,
+        registry: &Registry,
+    ) -> Result<Vec<u8>, CacheError> {
+        cache::write(
+            &self.action_table,
+            &self.tracks,
+            &self.baked,
+            &registry.codec,
+        )
+    }
+
+    /// Reads the segments of a [bake cache](crate::cache) written by
+    /// [`Self::write_bake_cache`] from a timeline built the same
+    /// way, marking the tracks it holds as baked.
+    ///
+    /// On error, the tracks read before the failing one stay baked.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the actions are shared by [`Self::instance`].
+    pub fn read_bake_cache(
+        &mut self,
+        registry: &Registry,
+        bytes: &[u8],
+    ) -> Result<(), CacheError> {
+        let action_table = Arc::get_mut(&mut self.action_table).expect(
+            "Timeline actions are shared by `Timeline::instance`.",
+        );
+
+        cache::read(
+            action_table,
+            &self.tracks,
+            &mut self.baked,
+            &registry.codec,
+            bytes,
+        )
+    }
+
+    /// Re-derives the segments of the sequences whose [`ActionKey`]
+    /// passes `filter` from the current state of `subject_world`, in
+    /// every baked track, and their snapshots in the others.
+    ///
+    /// Baking reads the starting value of every track from the world,
+    /// so this is only meaningful before the timeline starts playing,
+    /// e.g. after moving a subject that was already baked.
+    pub fn rebake_actions(
+        &mut self,
+        registry: &Registry,
+        subject_world: &W,
+        filter: impl Fn(&ActionKey) -> bool,
+    ) {
+        for index in 0..self.tracks.len() {
+            // Unbaked tracks only get their snapshots refreshed, see
+            // `Self::snapshot_tracks`.
+            self.bake_filtered(
+                index,
+                registry,
+                subject_world,
+                Some(&filter),
+                false,
+                !self.baked[index],
+            );
+        }
+    }
+
+    /// Similar to [`Self::rebake_actions`], but only for the actions
+    /// animating `field` of `subject`.
+    ///
+    /// Returns `false` if `subject` is not animated by this timeline.
+    pub fn rebake_subject<I: SubjectId>(
+        &mut self,
+        registry: &Registry,
+        subject_world: &W,
+        subject: &I,
+        field: impl Into<UntypedField>,
+    ) -> bool {
+        let Some(&uid) = self.action_table.get_uid(subject) else {
+            return false;
+        };
+        let subject_id = UntypedSubjectId::new::<I>(uid);
+        let field = field.into();
+
+        self.rebake_actions(registry, subject_world, |key| {
+            *key.subject_id() == subject_id && *key.field() == field
+        });
+        true
+    }
+
+    /// Moves every action of `old` over to `new` and re-bakes them
+    /// from `new`, e.g. to replay a timeline authored against one
+    /// prefab instance on a freshly spawned one.
+    ///
+    /// Returns `false` if `old` is not animated by this timeline or
+    /// `new` already is.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the actions are shared, see [`Self::instance`].
+    pub fn retarget<I: SubjectId>(
+        &mut self,
+        registry: &Registry,
+        subject_world: &W,
+        old: &I,
+        new: I,
+    ) -> bool {
+        self.retarget_all(registry, subject_world, [(*old, new)]) == 1
+    }
+
+    /// Similar to [`Self::retarget`], for every `(old, new)` pair at
+    /// once, so subjects can also swap places.
+    ///
+    /// Returns the number of retargeted subjects.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the actions are shared, see [`Self::instance`].
+    pub fn retarget_all<I: SubjectId>(
+        &mut self,
+        registry: &Registry,
+        subject_world: &W,
+        pairs: impl IntoIterator<Item = (I, I)>,
+    ) -> usize {
+        let subject_ids = self
+            .action_table_mut()
+            .retarget(pairs)
+            .into_iter()
+            .map(UntypedSubjectId::new::<I>)
+            .collect::<Vec<_>>();
+
+        if !subject_ids.is_empty() {
+            self.rebake_actions(registry, subject_world, |key| {
+                subject_ids.contains(key.subject_id())
+            });
+        }
+        subject_ids.len()
+    }
+
+    /// Creates another timeline playing the same tracks from the
+    /// start, sharing the actions and baked segments of this one.
+    ///
+    /// Each instance only owns its time and queue state, so playing
+    /// hundreds of copies of an animation costs little more than one.
+    /// Track weights, speeds and masks can still differ per instance,
+    /// and [`Self::bind_subject`] moves an instance to other
+    /// subjects.
+    ///
+    /// Instances sample the segments baked so far, so bake the
+    /// tracks first. While the actions are shared, re-baking keeps
+    /// the baked segments, and baking a new track, editing or
+    /// retargeting panics.
+    pub fn instance(&self) -> Self {
+        Self {
+            action_table: self.action_table.clone(),
+            pipeline_counts: self.pipeline_counts.clone(),
+            tracks: self.tracks.clone(),
+            baked: self.baked.clone(),
+            labels: self.labels.clone(),
+            bindings: SubjectBindings::default(),
+            queue_cache: QueueCache::with_capacity(
+                self.queue_cache.capacity(),
+            ),
+            sample_queue: HashMap::with_capacity(
+                self.pipeline_counts.len(),
+            ),
+            curr_time: Duration::ZERO,
+            target_time: Duration::ZERO,
+            curr_index: 0,
+            target_index: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Plays the actions of `old` on `new` in this timeline only,
+    /// leaving the actions shared with its instances untouched.
+    ///
+    /// The segments stay baked from `old`, so `new` starts from where
+    /// `old` did. Use [`Self::retarget`] to re-bake them from `new`
+    /// when the actions are not shared.
+    ///
+    /// Returns `false` if `old` is not animated by this timeline.
+    pub fn bind_subject<I: SubjectId>(
+        &mut self,
+        old: &I,
+        new: I,
+    ) -> bool {
+        let Some(&uid) = self.action_table.get_uid(old) else {
+            return false;
+        };
+
+        self.bindings.insert(uid, new);
+        true
+    }
+
+    /// The subjects bound by [`Self::bind_subject`].
+    #[inline]
+    pub fn subject_bindings(&self) -> &SubjectBindings {
+        &self.bindings
+    }
+
+    fn bake_filtered(
+        &mut self,
+        index: usize,
+        registry: &Registry,
+        subject_world: &W,
+        filter: Option<&dyn Fn(&ActionKey) -> bool>,
+        keep_start: bool,
+        snapshot_only: bool,
+    ) {
+        let Some(action_table) = Arc::get_mut(&mut self.action_table)
+        else {
+            assert!(
+                self.baked[index] || snapshot_only,
+                "Cannot bake track {index}, its actions are shared \
+                by `Timeline::instance`."
+            );
+            return;
+        };
+        let track = &self.tracks[index];
+
+        for key in self.pipeline_counts.iter().map(|(key, _)| key) {
+            let ok = registry.pipeline.bake(
+                key,
+                BakeCtx {
+                    world: subject_world,
+                    track,
+                    action_table,
+                    accessor_registry: &registry.accessor,
+                    filter,
+                    keep_start,
+                    snapshot_only,
+                },
+            );
+            debug_assert!(ok, "pipeline not found for key {key:?}");
+        }
+    }
+
     /// Determines which actions are active at the current target time
     /// and marks them for sampling.
     ///
@@ -127,7 +487,7 @@ impl<W: 'static> Timeline<W> {
                     self.queue_cache.cache(
                         *key,
                         clip.id,
-                        sample_mode,
+                        clip.orient(sample_mode),
                     );
                 }
             }
@@ -135,10 +495,11 @@ impl<W: 'static> Timeline<W> {
             self.curr_index = self.target_index;
         }
 
-        let time_range = Range {
-            start: curr_time.min(self.target_time()),
-            end: curr_time.max(self.target_time()),
-        };
+        // Clips are laid out in the track's local time, which only
+        // differs from playback time inside dilated windows.
+        let track = &self.tracks[self.curr_index];
+        let local_curr_time = track.local_time(curr_time);
+        let local_target_time = track.local_time(self.target_time());
 
         for (key, span) in
             self.tracks[self.curr_index].sequences_spans()
@@ -147,7 +508,17 @@ impl<W: 'static> Timeline<W> {
                 continue;
             }
 
-            let clips = self.tracks[self.curr_index].clips(*span);
+            // Remapped sequences are sampled at their warped time.
+            let track = &self.tracks[self.curr_index];
+            let clips = track.clips(*span);
+            let curr_time = track.sequence_time(key, local_curr_time);
+            let target_time =
+                track.sequence_time(key, local_target_time);
+
+            let time_range = Range {
+                start: curr_time.min(target_time),
+                end: curr_time.max(target_time),
+            };
 
             // SAFETY: `clips` is not empty.
             let clips_range = Range {
@@ -166,9 +537,9 @@ impl<W: 'static> Timeline<W> {
             // before the sequence if `index == 0`, otherwise,
             // after `span[index - 1]`
             let index = clips.binary_search_by(|clip| {
-                if self.target_time() < clip.start {
+                if target_time < clip.start {
                     Ordering::Greater
-                } else if self.target_time() > clip.end() {
+                } else if target_time > clip.end() {
                     Ordering::Less
                 } else {
                     Ordering::Equal
@@ -183,9 +554,9 @@ impl<W: 'static> Timeline<W> {
                     self.queue_cache.cache(
                         *key,
                         clip.id,
-                        SampleMode::Interp(
-                            clip.progress(self.target_time),
-                        ),
+                        clip.orient(SampleMode::Interp(
+                            clip.progress(target_time),
+                        )),
                     );
                 }
                 // `target_time` is out of bounds.
@@ -214,7 +585,7 @@ impl<W: 'static> Timeline<W> {
                     self.queue_cache.cache(
                         *key,
                         clip.id,
-                        sample_mode,
+                        clip.orient(sample_mode),
                     );
                 }
             }
@@ -238,6 +609,25 @@ impl<W: 'static> Timeline<W> {
         registry: &Registry,
         subject_world: &mut W,
     ) {
+        self.blend_queued_actions(registry, subject_world, 1.0);
+    }
+
+    /// Samples the queued actions like [`Self::sample_queued_actions`]
+    /// but only blends `weight` of each value over the current value
+    /// of its field, through the interpolation of its action.
+    ///
+    /// Sampling a timeline at full weight and then another one at a
+    /// weight rising from `0.0` to `1.0` crossfades between them.
+    pub fn blend_queued_actions(
+        &self,
+        registry: &Registry,
+        subject_world: &mut W,
+        weight: f32,
+    ) {
+        if weight <= 0.0 {
+            return;
+        }
+
         for (key, samples) in self.sample_queue.iter() {
             if samples.is_empty() {
                 continue;
@@ -249,10 +639,149 @@ impl<W: 'static> Timeline<W> {
                     action_table: &self.action_table,
                     accessor_registry: &registry.accessor,
                     samples,
+                    weight,
+                    track: self.tracks.get(self.curr_index),
+                    restore: false,
+                    bindings: &self.bindings,
+                },
+            );
+            debug_assert!(ok, "pipeline not found for key {key:?}");
+        }
+    }
+
+    /// Moves to `time` in the target track and samples it, like
+    /// [`Self::set_target_time`] followed by [`Self::queue_actions`]
+    /// and [`Self::sample_queued_actions`].
+    ///
+    /// Unlike that fast path, which only samples the last clip passed
+    /// by each sequence, every clip boundary between the current and
+    /// the target time is sampled in order, across every track in
+    /// between. Use this when seeking timelines whose clips have side
+    /// effects that must not be skipped. Sequences warped by a
+    /// [`TimeRemap`](crate::track::TimeRemap) are stepped at their
+    /// unwarped boundaries.
+    pub fn jump_to(
+        &mut self,
+        time: Duration,
+        registry: &Registry,
+        subject_world: &mut W,
+    ) {
+        let target_index = self.target_index;
+
+        while self.curr_index != target_index {
+            let forward = target_index > self.curr_index;
+            let (edge, next) = if forward {
+                (self.curr_track().duration(), self.curr_index + 1)
+            } else {
+                (Duration::ZERO, self.curr_index - 1)
+            };
+            self.step_through(edge, registry, subject_world);
+
+            // Enter the neighbouring track from its near edge.
+            self.target_index = next;
+            self.target_time = if forward {
+                Duration::ZERO
+            } else {
+                self.tracks[next].duration()
+            };
+            self.queue_actions();
+            self.sample_queued_actions(registry, subject_world);
+        }
+
+        self.step_through(time, registry, subject_world);
+    }
+
+    /// Samples every clip boundary of the current track between the
+    /// current time and `time`, then `time` itself.
+    fn step_through(
+        &mut self,
+        time: Duration,
+        registry: &Registry,
+        subject_world: &mut W,
+    ) {
+        let track = &self.tracks[self.curr_index];
+        let from = self.curr_time;
+        let to = time.min(track.duration());
+        let (min, max) = (from.min(to), from.max(to));
+
+        let mut boundaries = track
+            .sequences_spans()
+            .iter()
+            .flat_map(|(_, span)| track.clips(*span))
+            .flat_map(|clip| [clip.start, clip.end()])
+            .map(|local| track.playback_time(local))
+            .filter(|time| *time > min && *time < max)
+            .collect::<Vec<_>>();
+        boundaries.sort_unstable();
+        boundaries.dedup();
+        if to < from {
+            boundaries.reverse();
+        }
+
+        for time in boundaries.into_iter().chain([to]) {
+            self.target_time = time;
+            self.queue_actions();
+            self.sample_queued_actions(registry, subject_world);
+        }
+    }
+
+    /// Restores every animated field to the value it was baked from
+    /// and rewinds the timeline to the start of its first track.
+    ///
+    /// Replaying or scrubbing back to the start after this begins
+    /// from a clean state, regardless of what sampling last wrote.
+    /// Fields first animated by a track that is not baked yet are
+    /// left untouched.
+    pub fn reset_subjects(
+        &mut self,
+        registry: &Registry,
+        subject_world: &mut W,
+    ) {
+        let mut seen = HashSet::new();
+        let mut restores =
+            HashMap::<PipelineKey, Vec<(ActionId, SampleMode)>>::new(
+            );
+
+        for track in self.tracks.iter() {
+            for (key, span) in track.sequences_spans() {
+                let Some(clip) = track.clips(*span).first() else {
+                    continue;
+                };
+                // Only the earliest sequence of a field was baked
+                // from its untouched value.
+                if !seen.insert(*key) {
+                    continue;
+                }
+
+                restores
+                    .entry(PipelineKey::from_action_key::<W>(*key))
+                    .or_default()
+                    .push((clip.id, SampleMode::Start));
+            }
+        }
+
+        for (key, samples) in restores.iter() {
+            let ok = registry.pipeline.sample(
+                key,
+                SampleCtx {
+                    world: subject_world,
+                    action_table: &self.action_table,
+                    accessor_registry: &registry.accessor,
+                    samples,
+                    weight: 1.0,
+                    track: None,
+                    restore: true,
+                    bindings: &self.bindings,
                 },
             );
             debug_assert!(ok, "pipeline not found for key {key:?}");
         }
+
+        self.reset_queues();
+        self.curr_index = 0;
+        self.target_index = 0;
+        self.curr_time = Duration::ZERO;
+        self.target_time = Duration::ZERO;
     }
 
     fn reset_queues(&mut self) {
@@ -296,12 +825,112 @@ impl<W> Timeline<W> {
         self.target_index
     }
 
+    /// Returns the [`ActionTable`] holding every action and its baked
+    /// segment.
+    #[inline]
+    pub fn action_table(&self) -> &ActionTable {
+        &self.action_table
+    }
+
+    /// ## Panic
+    ///
+    /// Panics if the actions are shared, see [`Self::is_shared`].
+    fn action_table_mut(&mut self) -> &mut ActionTable {
+        Arc::get_mut(&mut self.action_table).expect(
+            "Timeline actions are shared by `Timeline::instance`.",
+        )
+    }
+
+    /// Returns the number of actions queued for each pipeline by the
+    /// last [`Timeline::queue_actions`].
+    pub fn sample_queue(
+        &self,
+    ) -> impl Iterator<Item = (&PipelineKey, usize)> + '_ {
+        self.sample_queue
+            .iter()
+            .map(|(key, samples)| (key, samples.len()))
+    }
+
+    /// Returns every [`PipelineKey`] used by this timeline with the
+    /// number of actions going through it.
+    #[inline]
+    pub fn pipeline_counts(&self) -> &[(PipelineKey, u32)] {
+        &self.pipeline_counts
+    }
+
     /// Returns a reference slice to all tracks.
     #[inline]
     pub fn tracks(&self) -> &[Track] {
         &self.tracks
     }
 
+    /// Returns the duration of every track played back to back,
+    /// saturating at [`Duration::MAX`].
+    pub fn total_duration(&self) -> Duration {
+        self.tracks
+            .iter()
+            .map(Track::duration)
+            .fold(Duration::ZERO, Duration::saturating_add)
+    }
+
+    /// Returns the target as a ratio of [`Self::total_duration()`],
+    /// in `0.0..=1.0`. See [`Self::set_global_time`].
+    ///
+    /// A timeline without any duration is always at `1.0`.
+    pub fn global_time(&self) -> f32 {
+        let total = self.total_duration();
+        if total.is_zero() {
+            return 1.0;
+        }
+
+        let elapsed = self.tracks[..self.target_index]
+            .iter()
+            .map(Track::duration)
+            .fold(Duration::ZERO, Duration::saturating_add)
+            .saturating_add(self.target_time);
+
+        (elapsed.as_secs_f64() / total.as_secs_f64()) as f32
+    }
+
+    /// Returns every checkpoint label with the index of its track, in
+    /// the order they were added.
+    pub fn labels(&self) -> impl Iterator<Item = (&str, usize)> + '_ {
+        self.labels
+            .iter()
+            .map(|(label, index)| (label.as_str(), *index))
+    }
+
+    /// Returns the index of the track labeled `label`. See
+    /// [`TimelineBuilder::add_checkpoint`].
+    pub fn label_index(&self, label: &str) -> Option<usize> {
+        self.labels
+            .iter()
+            .find(|(name, _)| name == label)
+            .map(|(_, index)| *index)
+    }
+
+    /// Returns the label of the track at `index`, if it has one.
+    pub fn track_label(&self, index: usize) -> Option<&str> {
+        self.labels
+            .iter()
+            .find(|(_, i)| *i == index)
+            .map(|(label, _)| label.as_str())
+    }
+
+    /// Are the actions shared with other timelines? See
+    /// [`Timeline::instance`].
+    #[inline]
+    pub fn is_shared(&self) -> bool {
+        Arc::strong_count(&self.action_table) > 1
+    }
+
+    /// Has the track at `index` been baked? See
+    /// [`Timeline::bake_track`].
+    #[inline]
+    pub fn is_track_baked(&self, index: usize) -> bool {
+        self.baked.get(index).copied().unwrap_or(false)
+    }
+
     /// Returns a reference the current playing track.
     #[inline]
     pub fn curr_track(&self) -> &Track {
@@ -336,20 +965,120 @@ impl<W> Timeline<W> {
     pub fn is_complete(&self) -> bool {
         self.is_last_track() && self.is_track_end()
     }
-}
-
-// Setter methods.
-impl<W> Timeline<W> {
-    /// Set the target time of the current track, clamping the value
-    /// within \[0.0..=track.duration\]
-    pub fn set_target_time(
-        &mut self,
-        target_time: Duration,
-    ) -> &mut Self {
-        let duration = self.tracks[self.target_index].duration();
 
-        self.target_time = target_time.min(duration);
-        self
+    /// Evaluates every sequence of the current track at `time`,
+    /// without queuing anything or touching the subject world.
+    ///
+    /// Each sequence holds the value of its clip at `time`, or of the
+    /// clip before it, or the start of its first clip. Values only
+    /// exist once the track is baked and ignore the weights and masks
+    /// of the track, e.g. for drawing ghost poses in an editor.
+    pub fn evaluate_at(
+        &self,
+        time: Duration,
+    ) -> impl Iterator<Item = (ActionKey, SampledValue<'_>)> {
+        let track = &self.tracks[self.curr_index];
+        let local_time = track.local_time(time);
+
+        track.sequences_spans().iter().filter_map(
+            move |(key, span)| {
+                let time = track.sequence_time(key, local_time);
+                let value =
+                    self.clips_value(track.clips(*span), time)?;
+                Some((*key, value))
+            },
+        )
+    }
+
+    /// Samples the sequence of `key` in the current track at `points`
+    /// evenly spaced times across its clips, without touching the
+    /// subject world.
+    ///
+    /// Each sample is paired with its playback time in seconds and
+    /// goes through the ease, steps, interpolation and modifiers of
+    /// its action, e.g. for plotting the curve in a graph widget.
+    /// Returns an empty [`Vec`] if the sequence does not exist or
+    /// is not a baked `T` interpolation.
+    pub fn sample_curve<T: Clone + ThreadSafe>(
+        &self,
+        key: &ActionKey,
+        points: usize,
+    ) -> Vec<(f32, T)> {
+        let track = &self.tracks[self.curr_index];
+        let Some((_, span)) =
+            track.sequences_spans().iter().find(|(k, _)| k == key)
+        else {
+            return Vec::new();
+        };
+        let clips = track.clips(*span);
+        let (Some(first), Some(last)) = (clips.first(), clips.last())
+        else {
+            return Vec::new();
+        };
+
+        let (start, end) = (first.start, last.end());
+        let last_point = points.saturating_sub(1).max(1) as f64;
+
+        (0..points)
+            .map_while(|point| {
+                let local = start
+                    + (end - start)
+                        .mul_f64(point as f64 / last_point);
+                let time = track.sequence_time(key, local);
+                let value = self.clips_value(clips, time)?.get()?;
+
+                Some((
+                    track.playback_time(local).as_secs_f32(),
+                    value,
+                ))
+            })
+            .collect()
+    }
+
+    /// The value of the last clip in `clips` starting at or before
+    /// `time`, or the start of the first clip.
+    fn clips_value(
+        &self,
+        clips: &[ActionClip],
+        time: Duration,
+    ) -> Option<SampledValue<'_>> {
+        let index = clips
+            .partition_point(|clip| clip.start <= time)
+            .saturating_sub(1);
+        let clip = clips.get(index)?;
+        let mode = if time < clip.start {
+            SampleMode::Start
+        } else if time >= clip.end() {
+            SampleMode::End
+        } else {
+            SampleMode::Interp(clip.progress(time))
+        };
+
+        let t = match clip.orient(mode) {
+            SampleMode::Start => 0.0,
+            SampleMode::End => 1.0,
+            SampleMode::Interp(t) => t,
+        };
+        Some(SampledValue {
+            action_table: &self.action_table,
+            id: clip.id,
+            t,
+        })
+    }
+}
+
+// Setter methods.
+impl<W> Timeline<W> {
+    /// Set the target time of the current track, clamping the value
+    /// within \[0.0..=track.duration\]
+    pub fn set_target_time(
+        &mut self,
+        target_time: Duration,
+    ) -> &mut Self {
+        let duration = self.tracks[self.target_index].duration();
+
+        self.target_time = target_time.min(duration);
+        self
     }
 
     /// Steps forward, clamping at the track's end.
@@ -379,6 +1108,113 @@ impl<W> Timeline<W> {
         self.target_index = target_index.clamp(0, max_index);
         self
     }
+
+    /// Targets the start of the track labeled `label`, see
+    /// [`TimelineBuilder::add_checkpoint`].
+    ///
+    /// Returns `false` and leaves the target untouched if there is no
+    /// such label.
+    pub fn seek_label(&mut self, label: &str) -> bool {
+        let Some(index) = self.label_index(label) else {
+            return false;
+        };
+
+        self.set_target_track(index).set_target_time(Duration::ZERO);
+        true
+    }
+
+    /// Targets `time` as a ratio of [`Self::total_duration()`],
+    /// clamped to `0.0..=1.0`, e.g. for a scrubber spanning every
+    /// track.
+    ///
+    /// A time on the boundary of two tracks targets the end of the
+    /// earlier one, so every checkpoint is reached at its end.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `time` is NaN.
+    pub fn set_global_time(&mut self, time: f32) -> &mut Self {
+        assert!(!time.is_nan(), "Global time must not be NaN!");
+
+        let mut remaining = self
+            .total_duration()
+            .mul_f64(time.clamp(0.0, 1.0) as f64);
+        let mut index = 0;
+        while index < self.last_track_index() {
+            let duration = self.tracks[index].duration();
+            if remaining <= duration {
+                break;
+            }
+            remaining -= duration;
+            index += 1;
+        }
+
+        // Rounding past the last track is clamped to its end.
+        self.set_target_track(index).set_target_time(remaining)
+    }
+
+    /// Sets the weight of the track at `index`, applied the next time
+    /// it is sampled. See [`Track::set_weight`].
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `index` is out of bounds or `weight` is NaN.
+    pub fn set_track_weight(
+        &mut self,
+        index: usize,
+        weight: f32,
+    ) -> &mut Self {
+        Arc::make_mut(&mut self.tracks)[index].set_weight(weight);
+        self
+    }
+
+    /// Sets the playback rate of the track at `index`. See
+    /// [`Track::set_speed`].
+    ///
+    /// The target time is kept in playback time, so it is clamped to
+    /// the new duration of the track.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `index` is out of bounds or `speed` is not a
+    /// positive finite number.
+    pub fn set_track_speed(
+        &mut self,
+        index: usize,
+        speed: f32,
+    ) -> &mut Self {
+        Arc::make_mut(&mut self.tracks)[index].set_speed(speed);
+        if index == self.target_index {
+            self.set_target_time(self.target_time);
+        }
+        self
+    }
+
+    /// Limits the weight of the track at `index` to `fields`. See
+    /// [`Track::set_mask`].
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_track_mask<F: Into<UntypedField>>(
+        &mut self,
+        index: usize,
+        fields: impl IntoIterator<Item = F>,
+    ) -> &mut Self {
+        Arc::make_mut(&mut self.tracks)[index].set_mask(fields);
+        self
+    }
+
+    /// Applies the weight of the track at `index` to every field
+    /// again.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn clear_track_mask(&mut self, index: usize) -> &mut Self {
+        Arc::make_mut(&mut self.tracks)[index].clear_mask();
+        self
+    }
 }
 
 /// Cached actions that are queued to be sampled.
@@ -399,6 +1235,23 @@ impl QueueCache {
         }
     }
 
+    /// Creates a cache with room for `capacity` actions before it
+    /// reallocates.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            cache: HashMap::with_capacity(capacity),
+        }
+    }
+
+    /// The number of actions the cache holds before it reallocates.
+    pub fn capacity(&self) -> usize {
+        self.cache.capacity()
+    }
+
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
     pub fn is_empty(&self) -> bool {
         self.cache.is_empty()
     }
@@ -418,7 +1271,8 @@ impl QueueCache {
         self.cache.values().map(|(id, _)| *id)
     }
 
-    /// Clear all the cached contents.
+    /// Clear all the cached contents, keeping the allocation for the
+    /// next queue.
     pub fn clear(&mut self) {
         self.cache.clear();
     }
@@ -441,11 +1295,41 @@ impl Default for QueueCache {
     }
 }
 
+/// The value of a sequence at a time, see [`Timeline::evaluate_at`].
+#[derive(Clone, Copy)]
+pub struct SampledValue<'a> {
+    action_table: &'a ActionTable,
+    id: ActionId,
+    t: f32,
+}
+
+impl SampledValue<'_> {
+    /// The action providing the value.
+    #[inline]
+    pub fn id(&self) -> ActionId {
+        self.id
+    }
+
+    /// Progress through the clip of [`Self::id`], in
+    /// \[0.0..=1.0\].
+    #[inline]
+    pub fn progress(&self) -> f32 {
+        self.t
+    }
+
+    /// The value, or `None` if the action is not a baked `T`
+    /// interpolation. See [`ActionTable::evaluate`].
+    pub fn get<T: Clone + ThreadSafe>(&self) -> Option<T> {
+        self.action_table.evaluate(&self.id, self.t)
+    }
+}
+
 pub struct TimelineBuilder<'a, W> {
     registry: &'a mut Registry,
     action_table: ActionTable,
     pipeline_counts: HashMap<PipelineKey, u32>,
     tracks: Vec<Track>,
+    labels: Vec<(String, usize)>,
     _marker: PhantomData<fn() -> W>,
 }
 
@@ -457,6 +1341,7 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
             action_table: ActionTable::new(),
             pipeline_counts: HashMap::new(),
             tracks: Vec::new(),
+            labels: Vec::new(),
             _marker: PhantomData,
         }
     }
@@ -466,7 +1351,7 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
     pub fn act<I, S, T, M>(
         &mut self,
         target: I,
-        field_acc: FieldAccessor<S, T>,
+        field: impl ActionField<S, T>,
         action: impl Action<T>,
     ) -> InterpActionBuilder<'_, T>
     where
@@ -475,15 +1360,96 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
         S: 'static,
         T: Interpolation<M> + Clone + ThreadSafe,
     {
-        self.act_builder(target, field_acc, action)
-            .with_interp(T::interp)
+        self.act_builder(target, field, action)
+            .with_interpolation::<M>()
+    }
+
+    /// Similar to [`Self::act`], but for a group subject: the action
+    /// is interpolated once and its value written to every member,
+    /// e.g. to drive a whole crowd of sprites with a single action.
+    /// See [`Pipeline::grouped`](crate::pipeline::Pipeline::grouped).
+    pub fn act_group<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: FieldAccessor<S, T>,
+        action: impl Action<T>,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: GroupSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+    {
+        self.registry.pipeline.register_grouped::<W, I, S, T>();
+        self.act(target, field, action)
+    }
+
+    /// Adds an action on `field` for every `(target, action)` pair,
+    /// interpolated using [`Interpolation::interp`], and finishes
+    /// each with `play`.
+    ///
+    /// The field and its pipeline are registered once for the whole
+    /// batch instead of once per action, which adds up when building
+    /// an action per letter or particle.
+    ///
+    /// ```ignore
+    /// let letters = b.act_many(
+    ///     path!(<Transform>::scale),
+    ///     glyphs.iter().map(|&e| (e, |_: &Vec3| Vec3::ONE)),
+    ///     |action| {
+    ///         action.with_ease(ease::back::ease_out).play(ms(300))
+    ///     },
+    /// );
+    /// let track = letters.ord_flow(ms(20));
+    /// ```
+    pub fn act_many<I, S, T, M, A, P>(
+        &mut self,
+        field: impl ActionField<S, T>,
+        actions: impl IntoIterator<Item = (I, A)>,
+        mut play: P,
+    ) -> Vec<TrackFragment>
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+        A: Action<T>,
+        P: FnMut(InterpActionBuilder<'_, T>) -> TrackFragment,
+    {
+        let (untyped_field, field_target) = field.target();
+        let policy = field.none_policy();
+        let key = field.register::<W, I>(self.registry);
+
+        let actions = actions.into_iter();
+        let (lower, _) = actions.size_hint();
+        self.reserve::<I>(lower);
+
+        let mut fragments = Vec::with_capacity(lower);
+        for (target, action) in actions {
+            let builder = self
+                .action_table
+                .add_at(target, untyped_field, field_target, action)
+                .with_none_policy(policy)
+                .with_interpolation::<M>();
+            fragments.push(play(builder));
+        }
+
+        *self.pipeline_counts.entry(key).or_default() +=
+            fragments.len() as u32;
+        fragments
+    }
+
+    /// Reserves room for `actions` more actions on `I` subjects,
+    /// e.g. before adding thousands of them one by one.
+    pub fn reserve<I: SubjectId>(&mut self, actions: usize) {
+        self.action_table.reserve::<I>(actions);
     }
 
     /// Add an [`Action`] using step interpolation.
     pub fn act_step<I, S, T>(
         &mut self,
         target: I,
-        field_acc: FieldAccessor<S, T>,
+        field: impl ActionField<S, T>,
         action: impl Action<T>,
     ) -> InterpActionBuilder<'_, T>
     where
@@ -492,11 +1458,141 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
         S: 'static,
         T: Clone + ThreadSafe,
     {
-        self.act_builder(target, field_acc, action).with_interp(
-            |a, b, t| {
-                if t < 1.0 { a.clone() } else { b.clone() }
-            },
-        )
+        self.act_builder(target, field, action)
+            .with_interp(step_interp)
+    }
+
+    /// Add an absolute [`Action`] that ends at `value` whatever it
+    /// starts from, with interpolation using
+    /// [`Interpolation::interp`].
+    ///
+    /// Records [`ActionKind::Absolute`] and the `value` in the
+    /// [`ActionTable`].
+    ///
+    /// [`ActionKind::Absolute`]: crate::action::ActionKind::Absolute
+    pub fn act_to<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        value: T,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+    {
+        let end = value.clone();
+        self.act_builder(target, field, move |_: &T| end.clone())
+            .with_value(ActionValue::To(value))
+            .with_interpolation::<M>()
+    }
+
+    /// Add a relative [`Action`] that adds `delta` to whatever it
+    /// starts from, with interpolation using
+    /// [`Interpolation::interp`].
+    ///
+    /// Records [`ActionKind::Relative`] and the `delta` in the
+    /// [`ActionTable`], so re-baking from a new start keeps the same
+    /// offset.
+    ///
+    /// [`ActionKind::Relative`]: crate::action::ActionKind::Relative
+    pub fn act_by<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        delta: T,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Add<Output = T> + Clone + ThreadSafe,
+    {
+        let offset = delta.clone();
+        self.act_builder(target, field, move |start: &T| {
+            start.clone() + offset.clone()
+        })
+        .with_value(ActionValue::By(delta))
+        .with_interpolation::<M>()
+    }
+
+    /// Add an action whose value is computed by `procedural` from
+    /// the eased progress of its clip instead of interpolated between
+    /// a start and an end, e.g. a sine wobble:
+    ///
+    /// ```ignore
+    /// b.act_fn(id, path!(<Transform>::rotation), |t| {
+    ///     Quat::from_rotation_z((t * TAU * 4.0).sin() * 0.1)
+    /// })
+    /// .play(s(1));
+    /// ```
+    ///
+    /// The following actions of the sequence start from its value at
+    /// the end of the clip. [`Interpolation::interp`] is only used to
+    /// blend the value with weights.
+    pub fn act_fn<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        procedural: impl Procedural<T>,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+    {
+        let procedural: Arc<dyn Procedural<T>> = Arc::new(procedural);
+        let end = procedural.clone();
+        self.act_builder(target, field, move |_: &T| end(1.0))
+            .with_procedural(procedural)
+            .with_interpolation::<M>()
+    }
+
+    /// Add an action sampling `curve` across its clip, e.g. a
+    /// [`SampleAutoCurve`] or an [`EasingCurve`], see
+    /// [`Self::act_fn`].
+    ///
+    /// A curve with a bounded domain plays through all of it, one
+    /// with an unbounded domain is sampled at the eased progress of
+    /// the clip, past the start of the domain if it has one.
+    ///
+    /// [`SampleAutoCurve`]: bevy_math::curve::SampleAutoCurve
+    /// [`EasingCurve`]: bevy_math::curve::EasingCurve
+    #[cfg(feature = "curve")]
+    pub fn act_curve<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        curve: impl Curve<T> + ThreadSafe,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+    {
+        self.act_fn(target, field, curve_procedural(curve))
+    }
+
+    /// Wiggles an [`f32`] field around the value it holds for
+    /// `duration`, e.g. for idle motion, see [`Wiggle`].
+    pub fn wiggle<I, S>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, f32>,
+        wiggle: Wiggle,
+        duration: Duration,
+    ) -> TrackFragment
+    where
+        W: SubjectSource<I, S> + 'static,
+        I: SubjectId,
+        S: 'static,
+    {
+        self.act(target, field, |x: &f32| *x)
+            .with_modifier(wiggle.modifier(duration))
+            .play(duration)
     }
 
     /// Add an [`Action`] without interpolation, returning an
@@ -504,7 +1600,7 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
     pub fn act_builder<I, S, T>(
         &mut self,
         target: I,
-        field_acc: FieldAccessor<S, T>,
+        field: impl ActionField<S, T>,
         action: impl Action<T>,
     ) -> ActionBuilder<'_, T>
     where
@@ -513,44 +1609,24 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
         S: 'static,
         T: Clone + ThreadSafe,
     {
-        let field = field_acc.field;
-        self.registry.register::<W, I, S, T>(field_acc);
-        let key = PipelineKey::new::<W, I, S, T>();
-
-        match self.pipeline_counts.get_mut(&key) {
-            Some(count) => *count += 1,
-            None => {
-                self.pipeline_counts.insert(key, 1);
-            }
-        }
-
-        self.action_table.add(target, field, action)
+        add_action::<W, _, _, _>(
+            self.registry,
+            &mut self.action_table,
+            &mut self.pipeline_counts,
+            target,
+            field,
+            action,
+        )
     }
 
     /// Remove an [`Action`].
     pub fn unact(&mut self, id: ActionId) -> bool {
-        if let Some(key) = self.action_table.remove(id) {
-            let pipeline_key = PipelineKey::from_action_key::<W>(key);
-
-            let count = self
-                .pipeline_counts
-                .get_mut(&pipeline_key)
-                .unwrap_or_else(|| {
-                    panic!(
-                        "Field counts not registered for {:?}!",
-                        key.field()
-                    )
-                });
-
-            *count -= 1;
-            if *count == 0 {
-                self.pipeline_counts.remove(&pipeline_key);
-            }
-
-            return true;
-        }
-
-        false
+        remove_action::<W>(
+            &mut self.action_table,
+            &mut self.pipeline_counts,
+            id,
+        )
+        .is_some()
     }
 
     /// Add [`Track`]\(s\) to the timeline.
@@ -561,6 +1637,28 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
         self.tracks.extend(tracks);
     }
 
+    /// Add a [`Track`] labeled `name`, so it can be reached with
+    /// [`Timeline::seek_label`] no matter where it ends up in the
+    /// timeline.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if a checkpoint named `name` already exists.
+    pub fn add_checkpoint(
+        &mut self,
+        name: impl Into<String>,
+        track: Track,
+    ) {
+        let name = name.into();
+        assert!(
+            self.labels.iter().all(|(label, _)| *label != name),
+            "Checkpoint {name:?} already exists!"
+        );
+
+        self.labels.push((name, self.tracks.len()));
+        self.tracks.push(track);
+    }
+
     /// Compile into a [`Timeline`].
     ///
     /// ## Panic
@@ -575,15 +1673,27 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
             "Track cannot be empty!"
         );
 
+        // Sized for the busiest track, so queueing never reallocates.
+        let queue_capacity = self
+            .tracks
+            .iter()
+            .map(|track| track.sequences_spans().len())
+            .max()
+            .unwrap_or_default();
+        let pipeline_count = self.pipeline_counts.len();
+
         Timeline {
-            action_table: self.action_table,
+            action_table: Arc::new(self.action_table),
             pipeline_counts: self
                 .pipeline_counts
                 .into_iter()
                 .collect(),
-            tracks: self.tracks.into_boxed_slice(),
-            queue_cache: QueueCache::new(),
-            sample_queue: HashMap::new(),
+            baked: vec![false; self.tracks.len()].into_boxed_slice(),
+            tracks: self.tracks.into(),
+            labels: self.labels.into(),
+            bindings: SubjectBindings::default(),
+            queue_cache: QueueCache::with_capacity(queue_capacity),
+            sample_queue: HashMap::with_capacity(pipeline_count),
             curr_time: Duration::ZERO,
             target_time: Duration::ZERO,
             curr_index: 0,
@@ -599,6 +1709,978 @@ impl<'a, W: 'static> TimelineBuilder<'a, W> {
     }
 }
 
+/// Maps the progress of a clip onto the domain of `curve`.
+#[cfg(feature = "curve")]
+fn curve_procedural<T>(
+    curve: impl Curve<T> + ThreadSafe,
+) -> impl Procedural<T> {
+    let domain = curve.domain();
+
+    move |t: f32| {
+        if domain.is_bounded() {
+            curve.sample_clamped(domain.start() + t * domain.length())
+        } else if domain.has_finite_start() {
+            curve.sample_clamped(domain.start() + t)
+        } else {
+            curve.sample_clamped(t)
+        }
+    }
+}
+
+/// Interpolation of [`TimelineBuilder::act_step`].
+fn step_interp<T: Clone>(a: &T, b: &T, t: f32) -> T {
+    if t < 1.0 { a.clone() } else { b.clone() }
+}
+
+/// Adds an action, counting it towards its pipeline.
+fn add_action<'t, W, I, S, T>(
+    registry: &mut Registry,
+    action_table: &'t mut ActionTable,
+    pipeline_counts: &mut HashMap<PipelineKey, u32>,
+    target: I,
+    field: impl ActionField<S, T>,
+    action: impl Action<T>,
+) -> ActionBuilder<'t, T>
+where
+    W: SubjectSource<I, S> + 'static,
+    I: SubjectId,
+    S: 'static,
+    T: Clone + ThreadSafe,
+{
+    let (untyped_field, field_target) = field.target();
+    let policy = field.none_policy();
+    let key = field.register::<W, I>(registry);
+
+    match pipeline_counts.get_mut(&key) {
+        Some(count) => *count += 1,
+        None => {
+            pipeline_counts.insert(key, 1);
+        }
+    }
+
+    action_table
+        .add_at(target, untyped_field, field_target, action)
+        .with_none_policy(policy)
+}
+
+/// Removes an action, returning its key.
+fn remove_action<W: 'static>(
+    action_table: &mut ActionTable,
+    pipeline_counts: &mut HashMap<PipelineKey, u32>,
+    id: ActionId,
+) -> Option<ActionKey> {
+    let key = action_table.remove(id)?;
+    let pipeline_key = PipelineKey::from_action_key::<W>(key);
+
+    let count =
+        pipeline_counts.get_mut(&pipeline_key).unwrap_or_else(|| {
+            panic!(
+                "Field counts not registered for {:?}!",
+                key.field()
+            )
+        });
+
+    *count -= 1;
+    if *count == 0 {
+        pipeline_counts.remove(&pipeline_key);
+    }
+
+    Some(key)
+}
+
 // TODO: Write some unit tests.
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use core::time::Duration;
+
+    use alloc::vec::Vec;
+
+    use crate::prelude::*;
+
+    struct World(f32);
+
+    impl SubjectSource<u32, f32> for World {
+        fn get_source(&self, _id: u32) -> Option<&f32> {
+            Some(&self.0)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            _id: u32,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            Some(f(&mut self.0))
+        }
+    }
+
+    /// One `f32` per subject id.
+    struct Subjects([f32; 2]);
+
+    impl SubjectSource<u32, f32> for Subjects {
+        fn get_source(&self, id: u32) -> Option<&f32> {
+            self.0.get(id as usize)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            id: u32,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            self.0.get_mut(id as usize).map(f)
+        }
+    }
+
+    /// A single group of `f32` members, started from the first.
+    struct Crowd([f32; 3]);
+
+    impl SubjectSource<u32, f32> for Crowd {
+        fn get_source(&self, _id: u32) -> Option<&f32> {
+            self.0.first()
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            _id: u32,
+            f: impl FnOnce(&mut f32) -> R,
+        ) -> Option<R> {
+            self.0.first_mut().map(f)
+        }
+    }
+
+    impl GroupSource<u32, f32> for Crowd {
+        fn apply_group(&mut self, _id: u32, f: impl FnMut(&mut f32)) {
+            self.0.iter_mut().for_each(f);
+        }
+    }
+
+    #[test]
+    fn act_group_writes_every_member() {
+        let mut registry = Registry::new();
+        let mut world = Crowd([0.0; 3]);
+
+        let mut b = TimelineBuilder::<Crowd>::new(&mut registry);
+        let track =
+            b.act_group(0u32, path!(<f32>), |x| x + 10.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        timeline.set_target_time(ms(500));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        assert_eq!(world.0, [5.0; 3]);
+
+        timeline.reset_subjects(&registry, &mut world);
+        assert_eq!(world.0, [0.0; 3]);
+    }
+
+    #[test]
+    fn act_many_adds_an_action_per_target() {
+        let mut registry = Registry::new();
+        let mut world = Subjects([1.0, 2.0]);
+
+        let mut b = TimelineBuilder::<Subjects>::new(&mut registry);
+        let fragments = b.act_many(
+            path!(<f32>),
+            [0u32, 1].map(|id| (id, |x: &f32| x * 10.0)),
+            |action| action.play(s(1)),
+        );
+        assert_eq!(fragments.len(), 2);
+        b.add_tracks(fragments.ord_all().compile());
+        let mut timeline = b.compile();
+        assert_eq!(timeline.pipeline_counts()[0].1, 2);
+        timeline.bake_actions(&registry, &world);
+
+        timeline.set_target_time(s(1));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        assert_eq!(world.0, [10.0, 20.0]);
+    }
+
+    #[test]
+    fn act_to_and_act_by_record_their_kind() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let by = b.act_by(0u32, path!(<f32>), 5.0);
+        let by_id = by.id();
+        let by = by.play(s(1));
+        let to = b.act_to(0u32, path!(<f32>), 3.0);
+        let to_id = to.id();
+        let track = [by, to.play(s(1))].ord_chain();
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let table = timeline.action_table();
+        assert_eq!(table.kind(&by_id), Some(ActionKind::Relative));
+        assert_eq!(
+            table.value::<f32>(&by_id),
+            Some(&ActionValue::By(5.0))
+        );
+        assert_eq!(table.kind(&to_id), Some(ActionKind::Absolute));
+        assert_eq!(
+            table.value::<f32>(&to_id),
+            Some(&ActionValue::To(3.0))
+        );
+
+        world.0 = 10.0;
+        timeline.rebake_subject(
+            &registry,
+            &world,
+            &0u32,
+            path!(<f32>).field,
+        );
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(value, 15.0);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(2));
+        assert_eq!(value, 3.0);
+    }
+
+    #[test]
+    fn rebake_subject_picks_up_the_new_start() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b.act(0, path!(<f32>), |x| x + 10.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        world.0 = 5.0;
+        assert!(!timeline.rebake_subject(
+            &registry,
+            &world,
+            &1u32,
+            path!(<f32>).field,
+        ));
+        assert!(timeline.rebake_subject(
+            &registry,
+            &world,
+            &0u32,
+            path!(<f32>).field,
+        ));
+
+        timeline.set_target_time(s(1));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        assert_eq!(world.0, 15.0);
+    }
+
+    #[test]
+    fn retarget_rebakes_from_the_new_subject() {
+        let mut registry = Registry::new();
+        let mut world = Subjects([0.0, 5.0]);
+
+        let mut b = TimelineBuilder::<Subjects>::new(&mut registry);
+        let track =
+            b.act(0u32, path!(<f32>), |x| x + 10.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        assert!(!timeline.retarget(&registry, &world, &1u32, 0));
+        assert!(timeline.retarget(&registry, &world, &0u32, 1));
+
+        timeline.set_target_time(s(1));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        assert_eq!(world.0, [0.0, 15.0]);
+    }
+
+    #[test]
+    fn instances_share_actions_and_tracks() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track =
+            b.act(0u32, path!(<f32>), |x| x + 10.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let mut instance = timeline.instance();
+        assert!(timeline.is_shared() && instance.is_shared());
+        assert!(core::ptr::eq(timeline.tracks(), instance.tracks()));
+
+        // Each instance keeps its own playhead.
+        let end =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(end, 10.0);
+        let mut other = World(0.0);
+        let mid =
+            sample_at(&mut instance, &registry, &mut other, ms(500));
+        assert_eq!(mid, 5.0);
+
+        // Tweaking a track copies the tracks of that instance only.
+        instance.set_track_weight(0, 0.5);
+        assert_eq!(timeline.curr_track().weight(), 1.0);
+        assert!(!core::ptr::eq(timeline.tracks(), instance.tracks()));
+
+        drop(instance);
+        assert!(!timeline.is_shared());
+    }
+
+    #[test]
+    fn instances_play_on_their_bound_subjects() {
+        let mut registry = Registry::new();
+        let mut world = Subjects([0.0; 2]);
+
+        let mut b = TimelineBuilder::<Subjects>::new(&mut registry);
+        let track =
+            b.act(0u32, path!(<f32>), |x| x + 10.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let mut instance = timeline.instance();
+        assert!(!instance.bind_subject(&1u32, 0));
+        assert!(instance.bind_subject(&0u32, 1));
+
+        timeline.set_target_time(s(1));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        assert_eq!(world.0, [10.0, 0.0]);
+
+        instance.set_target_time(ms(500));
+        instance.queue_actions();
+        instance.sample_queued_actions(&registry, &mut world);
+        assert_eq!(world.0, [10.0, 5.0]);
+        assert!(timeline.is_shared());
+
+        // Resetting only restores the subjects of that instance.
+        instance.reset_subjects(&registry, &mut world);
+        assert_eq!(world.0, [10.0, 0.0]);
+    }
+
+    #[test]
+    fn queueing_keeps_the_allocations() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act(0u32, path!(<f32>), |x| x + 1.0).play(s(1)),
+            b.act(1u32, path!(<f32>), |x| x + 1.0).play(s(1)),
+        ]
+        .ord_all();
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let capacity = timeline.queue_cache().capacity();
+        assert!(capacity >= 2);
+
+        for time in [ms(250), ms(500), ms(750), ms(250)] {
+            sample_at(&mut timeline, &registry, &mut world, time);
+            assert_eq!(timeline.queue_cache().capacity(), capacity);
+        }
+    }
+
+    fn sample_at(
+        timeline: &mut Timeline<World>,
+        registry: &Registry,
+        world: &mut World,
+        time: Duration,
+    ) -> f32 {
+        timeline.set_target_time(time);
+        timeline.queue_actions();
+        timeline.sample_queued_actions(registry, world);
+        world.0
+    }
+
+    #[test]
+    fn edit_inserts_and_retimes_clips() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let builder = b.act(0u32, path!(<f32>), |x| x + 10.0);
+        let first = builder.id();
+        let track = builder.play(s(1)).compile();
+        b.add_tracks(track);
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let end =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(end, 10.0);
+
+        // Mid playback, the world holds the end of the first clip.
+        let mut editor = timeline.edit(&mut registry);
+        let fragment =
+            editor.act(0u32, path!(<f32>), |x| x + 5.0).play(s(1));
+        editor.insert(0, s(1), fragment);
+        editor.finish(&world);
+
+        assert_eq!(timeline.curr_track().duration(), s(2));
+        let end =
+            sample_at(&mut timeline, &registry, &mut world, s(2));
+        assert_eq!(end, 15.0);
+
+        let mut editor = timeline.edit(&mut registry);
+        assert!(editor.retime(first, ms(500), ms(500)));
+        assert!(!editor.remove(ActionId::PLACEHOLDER));
+        editor.finish(&world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(750));
+        assert_eq!(value, 5.0);
+    }
+
+    #[test]
+    fn remove_subjects_drops_their_clips() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        for _ in 0..2 {
+            let track = [
+                b.act(0u32, path!(<f32>), |x| x + 1.0).play(s(1)),
+                b.act(1u32, path!(<f32>), |x| x + 1.0).play(s(1)),
+            ]
+            .ord_all();
+            b.add_tracks(track.compile());
+        }
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let removed = timeline.remove_subjects(
+            &mut registry,
+            &world,
+            |id: &u32| *id == 1,
+        );
+        assert_eq!(removed, 2);
+        assert_eq!(timeline.action_table().get_uid(&1u32), None);
+        assert!(
+            timeline
+                .tracks()
+                .iter()
+                .all(|track| { track.sequences_spans().len() == 1 })
+        );
+
+        let removed = timeline.remove_subjects(
+            &mut registry,
+            &world,
+            |id: &u32| *id == 1,
+        );
+        assert_eq!(removed, 0);
+        // Subject 0 still plays.
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(value, 1.0);
+    }
+
+    #[test]
+    fn remove_subjects_matches_bound_subjects() {
+        let mut registry = Registry::new();
+        let world = Subjects([0.0; 2]);
+
+        let mut b = TimelineBuilder::<Subjects>::new(&mut registry);
+        let track = b.act(0u32, path!(<f32>), |x| x + 1.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        // Shared actions are left alone instead of panicking.
+        let mut instance = timeline.instance();
+        assert!(instance.bind_subject(&0u32, 1));
+        let gone = |id: &u32| *id == 1;
+        assert_eq!(
+            instance.remove_subjects(&mut registry, &world, gone),
+            0
+        );
+        drop(instance);
+
+        assert!(timeline.bind_subject(&0u32, 1));
+        let removed = timeline.remove_subjects(
+            &mut registry,
+            &world,
+            |id: &u32| *id == 0,
+        );
+        assert_eq!(removed, 0);
+        let removed =
+            timeline.remove_subjects(&mut registry, &world, gone);
+        assert_eq!(removed, 1);
+    }
+
+    #[test]
+    fn blend_queued_actions_crossfades_timelines() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut timelines = [10.0, 20.0].map(|offset| {
+            let mut b = TimelineBuilder::<World>::new(&mut registry);
+            let track = b
+                .act(0u32, path!(<f32>), move |x| x + offset)
+                .play(s(1));
+            b.add_tracks(track.compile());
+            b.compile()
+        });
+        for timeline in timelines.iter_mut() {
+            timeline.bake_actions(&registry, &world);
+            timeline.set_target_time(s(1));
+            timeline.queue_actions();
+        }
+
+        let [base, overlay] = &timelines;
+        base.sample_queued_actions(&registry, &mut world);
+        overlay.blend_queued_actions(&registry, &mut world, 0.0);
+        assert_eq!(world.0, 10.0);
+
+        overlay.blend_queued_actions(&registry, &mut world, 0.25);
+        assert_eq!(world.0, 12.5);
+    }
+
+    #[test]
+    fn track_weight_scales_masked_fields() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track =
+            b.act(0u32, path!(<f32>), |x| x + 10.0).play(s(1));
+        b.add_tracks(track.compile().with_weight(0.5));
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(value, 5.0);
+
+        // Fields outside of the mask ignore the weight.
+        timeline.set_track_mask(0, [path!(<i32>).field]);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(value, 10.0);
+
+        world.0 = 0.0;
+        timeline.clear_track_mask(0).set_track_weight(0, 0.0);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(value, 0.0);
+    }
+
+    #[test]
+    fn remap_time_warps_clip_progress() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b
+            .act(0u32, path!(<f32>), |x| x + 10.0)
+            .play(s(1))
+            .remap_time(|t| t * t);
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(500));
+        assert_eq!(value, 2.5);
+        assert_eq!(timeline.curr_track().duration(), s(1));
+    }
+
+    #[test]
+    fn reversed_fragment_replays_baked_segment() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let intro =
+            b.act(0u32, path!(<f32>), |x| x + 10.0).play(s(1));
+        let track = [intro.clone(), intro.reversed()].ord_chain();
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(value, 10.0);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(1750));
+        assert_eq!(value, 2.5);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(2));
+        assert_eq!(value, 0.0);
+    }
+
+    #[test]
+    fn split_clips_play_their_share() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let fragment =
+            b.act(0u32, path!(<f32>), |x| x + 10.0).play(s(2));
+        let (head, tail) = fragment.clone().split(s(1));
+        b.add_tracks([head, tail].ord_chain().compile());
+        b.add_tracks(fragment.slice(ms(500)..s(1)).compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(1500));
+        assert_eq!(value, 7.5);
+
+        timeline.set_target_track(1);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(250));
+        assert_eq!(value, 3.75);
+    }
+
+    #[test]
+    fn act_fn_samples_the_procedural() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act_fn(0u32, path!(<f32>), |t| t * t * 4.0).play(s(1)),
+            b.act_by(0u32, path!(<f32>), 1.0).play(s(1)),
+        ]
+        .ord_chain();
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(500));
+        assert_eq!(value, 1.0);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(2));
+        assert_eq!(value, 5.0);
+    }
+
+    #[test]
+    fn seek_label_targets_checkpoints() {
+        let mut registry = Registry::new();
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        for name in ["intro", "chapter-2"] {
+            let track =
+                b.act(0u32, path!(<f32>), |x| x + 1.0).play(s(1));
+            b.add_checkpoint(name, track.compile());
+        }
+        let mut timeline = b.compile();
+
+        assert_eq!(timeline.label_index("chapter-2"), Some(1));
+        assert_eq!(timeline.track_label(0), Some("intro"));
+
+        timeline.set_target_time(ms(500));
+        assert!(timeline.seek_label("chapter-2"));
+        assert_eq!(timeline.target_index(), 1);
+        assert_eq!(timeline.target_time(), Duration::ZERO);
+
+        assert!(!timeline.seek_label("missing"));
+        assert_eq!(timeline.target_index(), 1);
+    }
+
+    #[test]
+    fn global_time_spans_every_track() {
+        let mut registry = Registry::new();
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        for duration in [s(1), s(3)] {
+            let track =
+                b.act(0u32, path!(<f32>), |x| x + 1.0).play(duration);
+            b.add_checkpoint(
+                alloc::format!("{duration:?}"),
+                track.compile(),
+            );
+        }
+        let mut timeline = b.compile();
+        assert_eq!(timeline.total_duration(), s(4));
+
+        timeline.set_global_time(0.5);
+        assert_eq!(timeline.target_index(), 1);
+        assert_eq!(timeline.target_time(), s(1));
+        assert_eq!(timeline.global_time(), 0.5);
+
+        // The boundary stays on the end of the first track.
+        timeline.set_global_time(0.25);
+        assert_eq!(timeline.target_index(), 0);
+        assert_eq!(timeline.target_time(), s(1));
+
+        timeline.set_global_time(2.0);
+        assert_eq!(timeline.target_index(), 1);
+        assert_eq!(timeline.target_time(), s(3));
+        assert_eq!(timeline.global_time(), 1.0);
+    }
+
+    #[test]
+    fn global_time_saturates_on_endless_tracks() {
+        let mut registry = Registry::new();
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        for _ in 0..2 {
+            let track = b
+                .act(0u32, path!(<f32>), |x| x + 1.0)
+                .play(Duration::MAX);
+            b.add_tracks(track.compile());
+        }
+        let mut timeline = b.compile();
+        assert_eq!(timeline.total_duration(), Duration::MAX);
+
+        timeline.set_target_track(1).set_target_time(s(1));
+        assert_eq!(timeline.global_time(), 1.0);
+    }
+
+    #[test]
+    fn bake_active_tracks_bakes_on_demand() {
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        for _ in 0..3 {
+            let track =
+                b.act(0, path!(<f32>), |x| x + 1.0).play(s(1));
+            b.add_tracks(track.compile());
+        }
+        let mut timeline = b.compile();
+
+        timeline.bake_active_tracks(&registry, &world);
+        assert!(timeline.is_track_baked(0));
+        assert!(timeline.is_track_baked(1));
+        assert!(!timeline.is_track_baked(2));
+
+        timeline.set_target_track(2);
+        timeline.bake_active_tracks(&registry, &world);
+        assert!(timeline.is_track_baked(2));
+
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        // Every track was baked from the initial value.
+        assert_eq!(world.0, 0.0);
+
+        timeline.set_target_time(s(1));
+        timeline.queue_actions();
+        timeline.sample_queued_actions(&registry, &mut world);
+        assert_eq!(world.0, 1.0);
+    }
+
+    #[test]
+    fn lazy_bake_matches_eager_bake() {
+        fn build(registry: &mut Registry) -> Timeline<World> {
+            let mut b = TimelineBuilder::<World>::new(registry);
+            for _ in 0..3 {
+                let track =
+                    b.act(0, path!(<f32>), |x| x + 1.0).play(s(1));
+                b.add_tracks(track.compile());
+            }
+            b.compile()
+        }
+
+        /// The `(start, end)` of every action, in track order.
+        fn segments(timeline: &Timeline<World>) -> Vec<(f32, f32)> {
+            let table = timeline.action_table();
+            timeline
+                .tracks()
+                .iter()
+                .flat_map(|track| {
+                    track
+                        .sequences_spans()
+                        .iter()
+                        .flat_map(|(_, span)| track.clips(*span))
+                })
+                .map(|clip| {
+                    let at = |t| table.evaluate::<f32>(&clip.id, t);
+                    (at(0.0).unwrap(), at(1.0).unwrap())
+                })
+                .collect()
+        }
+
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let mut eager = build(&mut registry);
+        eager.bake_actions(&registry, &world);
+
+        let mut lazy = build(&mut registry);
+        lazy.snapshot_tracks(&registry, &world);
+        // Play through, so each track is reached with the world left
+        // at the end of the previous one.
+        for index in 0..3 {
+            lazy.set_target_track(index);
+            for time in [Duration::ZERO, s(1)] {
+                lazy.set_target_time(time);
+                lazy.bake_active_tracks(&registry, &world);
+                lazy.queue_actions();
+                lazy.sample_queued_actions(&registry, &mut world);
+            }
+        }
+        assert_eq!(world.0, 1.0);
+
+        assert_eq!(segments(&lazy), segments(&eager));
+        assert_eq!(segments(&eager), [(0.0, 1.0); 3]);
+    }
+
+    #[test]
+    fn reset_subjects_restores_baked_values() {
+        let mut registry = Registry::new();
+        let mut world = World(2.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b.act(0u32, path!(<f32>), |x| x * 3.0).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(500));
+        assert_eq!(value, 4.0);
+
+        timeline.reset_subjects(&registry, &mut world);
+        assert_eq!(world.0, 2.0);
+        assert_eq!(timeline.curr_time(), Duration::ZERO);
+        assert_eq!(timeline.target_time(), Duration::ZERO);
+    }
+
+    #[test]
+    fn jump_to_samples_every_boundary() {
+        struct Counter(f32, u32);
+
+        impl SubjectSource<u32, f32> for Counter {
+            fn get_source(&self, _id: u32) -> Option<&f32> {
+                Some(&self.0)
+            }
+
+            fn apply_source<R>(
+                &mut self,
+                _id: u32,
+                f: impl FnOnce(&mut f32) -> R,
+            ) -> Option<R> {
+                self.1 += 1;
+                Some(f(&mut self.0))
+            }
+        }
+
+        let mut registry = Registry::new();
+        let mut world = Counter(0.0, 0);
+
+        let mut b = TimelineBuilder::<Counter>::new(&mut registry);
+        let track = [
+            b.act(0u32, path!(<f32>), |x| x + 1.0).play(s(1)),
+            b.act(0u32, path!(<f32>), |x| x + 1.0).play(s(1)),
+            b.act(0u32, path!(<f32>), |x| x + 1.0).play(s(1)),
+        ]
+        .ord_chain();
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        timeline.jump_to(ms(2500), &registry, &mut world);
+        // Both boundaries on the way, then the target.
+        assert_eq!(world.1, 3);
+        assert_eq!(world.0, 2.5);
+        assert_eq!(timeline.curr_time(), ms(2500));
+    }
+
+    #[test]
+    fn evaluate_at_leaves_the_world_alone() {
+        let mut registry = Registry::new();
+        let mut world = World(1.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = [
+            b.act(0u32, path!(<f32>), |x| x + 1.0).play(s(1)),
+            b.act(0u32, path!(<f32>), |x| x * 4.0).play(s(1)),
+        ]
+        .ord_chain();
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value = |timeline: &Timeline<World>, time| {
+            let (_, value) =
+                timeline.evaluate_at(time).next().unwrap();
+            value.get::<f32>().unwrap()
+        };
+        assert_eq!(value(&timeline, ms(500)), 1.5);
+        assert_eq!(value(&timeline, ms(1500)), 5.0);
+        assert_eq!(value(&timeline, s(5)), 8.0);
+        assert_eq!(world.0, 1.0);
+
+        let sampled =
+            sample_at(&mut timeline, &registry, &mut world, ms(500));
+        assert_eq!(sampled, value(&timeline, ms(500)));
+    }
+
+    #[test]
+    fn sample_curve_spans_the_sequence() {
+        let mut registry = Registry::new();
+        let world = World(0.0);
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b
+            .act(0u32, path!(<f32>), |x| x + 4.0)
+            .with_ease(ease::quad::ease_in)
+            .play(s(2));
+        let key = *track.keys().next().unwrap();
+        b.add_tracks(crate::track::delay(s(1), track).compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let curve = timeline.sample_curve::<f32>(&key, 3);
+        assert_eq!(curve, [(1.0, 0.0), (2.0, 1.0), (3.0, 4.0)]);
+        assert!(timeline.sample_curve::<u32>(&key, 3).is_empty());
+    }
+
+    #[cfg(feature = "curve")]
+    #[test]
+    fn act_curve_plays_the_whole_domain() {
+        use bevy_math::curve::{FunctionCurve, Interval};
+
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let curve = FunctionCurve::new(
+            Interval::new(2.0, 4.0).unwrap(),
+            |x: f32| x * 10.0,
+        );
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b.act_curve(0u32, path!(<f32>), curve).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(500));
+        assert_eq!(value, 30.0);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(value, 40.0);
+    }
+
+    #[cfg(feature = "curve")]
+    #[test]
+    fn act_curve_starts_half_bounded_domains_at_their_start() {
+        use bevy_math::curve::{FunctionCurve, Interval};
+
+        let mut registry = Registry::new();
+        let mut world = World(0.0);
+
+        let curve = FunctionCurve::new(
+            Interval::new(2.0, f32::INFINITY).unwrap(),
+            |x: f32| x * 10.0,
+        );
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = b.act_curve(0u32, path!(<f32>), curve).play(s(1));
+        b.add_tracks(track.compile());
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, ms(500));
+        assert_eq!(value, 25.0);
+        let value =
+            sample_at(&mut timeline, &registry, &mut world, s(1));
+        assert_eq!(value, 30.0);
+    }
+}
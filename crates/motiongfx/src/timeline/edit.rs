@@ -0,0 +1,392 @@
+use core::any::TypeId;
+use core::ops::Add;
+use core::time::Duration;
+
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use hashbrown::{HashMap, HashSet};
+
+use super::{Timeline, add_action, remove_action, step_interp};
+use crate::ThreadSafe;
+use crate::action::{
+    Action, ActionBuilder, ActionClip, ActionId, ActionKey,
+    ActionValue, InterpActionBuilder, Procedural,
+};
+use crate::field::ActionField;
+use crate::interpolation::Interpolation;
+use crate::pipeline::PipelineKey;
+use crate::registry::Registry;
+use crate::subject::SubjectId;
+use crate::track::{TrackFragment, delay};
+use crate::world::SubjectSource;
+
+impl<W: 'static> Timeline<W> {
+    /// Starts editing the clips of this compiled timeline.
+    ///
+    /// ```ignore
+    /// let mut editor = timeline.edit(&mut registry);
+    /// let fragment = editor.act(id, field, |x| x * 2.0).play(s(1));
+    /// editor.insert(0, s(2), fragment);
+    /// editor.finish(&world);
+    /// ```
+    ///
+    /// ## Panic
+    ///
+    /// Panics if the actions are shared, see [`Self::instance`].
+    pub fn edit<'a>(
+        &'a mut self,
+        registry: &'a mut Registry,
+    ) -> TimelineEditor<'a, W> {
+        assert!(
+            !self.is_shared(),
+            "Cannot edit actions shared by `Timeline::instance`."
+        );
+        let pipeline_counts =
+            self.pipeline_counts.iter().copied().collect();
+
+        TimelineEditor {
+            timeline: self,
+            registry,
+            pipeline_counts,
+            fragments: HashMap::new(),
+            dirty: HashSet::new(),
+        }
+    }
+
+    /// Removes every clip animating an `I` subject matching
+    /// `predicate`, along with its action, e.g. once the subject is
+    /// gone for good. See [`TimelineEditor::remove`].
+    ///
+    /// Subjects are matched after the [`Self::bind_subject`]
+    /// bindings. Nothing is removed while the actions are shared,
+    /// see [`Self::instance`].
+    ///
+    /// Returns the number of removed actions.
+    pub fn remove_subjects<I: SubjectId>(
+        &mut self,
+        registry: &mut Registry,
+        subject_world: &W,
+        mut predicate: impl FnMut(&I) -> bool,
+    ) -> usize {
+        if self.is_shared() {
+            return 0;
+        }
+
+        let mut ids = Vec::new();
+        for track in self.tracks.iter() {
+            for (key, span) in track.sequences_spans() {
+                let subject_id = key.subject_id();
+                let matches = subject_id.type_id()
+                    == TypeId::of::<I>()
+                    && self
+                        .bindings
+                        .resolve::<I>(
+                            &self.action_table,
+                            &subject_id.uid(),
+                        )
+                        .is_some_and(&mut predicate);
+                if matches {
+                    ids.extend(
+                        track.clips(*span).iter().map(|c| c.id),
+                    );
+                }
+            }
+        }
+        if ids.is_empty() {
+            return 0;
+        }
+
+        let mut editor = self.edit(registry);
+        let removed =
+            ids.into_iter().filter(|&id| editor.remove(id)).count();
+        editor.finish(subject_world);
+        removed
+    }
+}
+
+/// Inserts, removes and retimes clips of a compiled [`Timeline`],
+/// see [`Timeline::edit`].
+///
+/// Only the sequences touched by an edit are re-baked, continuing
+/// from their already baked start so edits can happen mid playback.
+#[must_use = "Edits are only applied by `TimelineEditor::finish`."]
+pub struct TimelineEditor<'a, W> {
+    timeline: &'a mut Timeline<W>,
+    registry: &'a mut Registry,
+    pipeline_counts: HashMap<PipelineKey, u32>,
+    /// Decompiled tracks, by index.
+    fragments: HashMap<usize, TrackFragment>,
+    /// Sequences to re-bake, by track index.
+    dirty: HashSet<(usize, ActionKey)>,
+}
+
+impl<W: 'static> TimelineEditor<'_, W> {
+    /// Add an [`Action`] with interpolation using
+    /// [`Interpolation::interp`].
+    ///
+    /// See [`TimelineBuilder::act`](super::TimelineBuilder::act).
+    pub fn act<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        action: impl Action<T>,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+    {
+        self.act_builder(target, field, action)
+            .with_interpolation::<M>()
+    }
+
+    /// Add an [`Action`] using step interpolation.
+    pub fn act_step<I, S, T>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        action: impl Action<T>,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + ThreadSafe,
+    {
+        self.act_builder(target, field, action)
+            .with_interp(step_interp)
+    }
+
+    /// Add an absolute [`Action`] that ends at `value`.
+    ///
+    /// See [`TimelineBuilder::act_to`](super::TimelineBuilder::act_to).
+    pub fn act_to<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        value: T,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+    {
+        let end = value.clone();
+        self.act_builder(target, field, move |_: &T| end.clone())
+            .with_value(ActionValue::To(value))
+            .with_interpolation::<M>()
+    }
+
+    /// Add a relative [`Action`] that adds `delta` to its start.
+    ///
+    /// See [`TimelineBuilder::act_by`](super::TimelineBuilder::act_by).
+    pub fn act_by<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        delta: T,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Add<Output = T> + Clone + ThreadSafe,
+    {
+        let offset = delta.clone();
+        self.act_builder(target, field, move |start: &T| {
+            start.clone() + offset.clone()
+        })
+        .with_value(ActionValue::By(delta))
+        .with_interpolation::<M>()
+    }
+
+    /// Add a [`Procedural`] action.
+    ///
+    /// See [`TimelineBuilder::act_fn`](super::TimelineBuilder::act_fn).
+    pub fn act_fn<I, S, T, M>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        procedural: impl Procedural<T>,
+    ) -> InterpActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Interpolation<M> + Clone + ThreadSafe,
+    {
+        let procedural: Arc<dyn Procedural<T>> = Arc::new(procedural);
+        let end = procedural.clone();
+        self.act_builder(target, field, move |_: &T| end(1.0))
+            .with_procedural(procedural)
+            .with_interpolation::<M>()
+    }
+
+    /// Add an [`Action`] without interpolation, returning an
+    /// [`ActionBuilder`] for manual configuration.
+    pub fn act_builder<I, S, T>(
+        &mut self,
+        target: I,
+        field: impl ActionField<S, T>,
+        action: impl Action<T>,
+    ) -> ActionBuilder<'_, T>
+    where
+        W: SubjectSource<I, S>,
+        I: SubjectId,
+        S: 'static,
+        T: Clone + ThreadSafe,
+    {
+        add_action::<W, _, _, _>(
+            self.registry,
+            self.timeline.action_table_mut(),
+            &mut self.pipeline_counts,
+            target,
+            field,
+            action,
+        )
+    }
+
+    /// Inserts the clips of `fragment` into the track at `index`,
+    /// starting at `at` in the local time of the track.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `index` is out of bounds. Overlapping clips of the
+    /// same [`ActionKey`] panic in debug builds.
+    pub fn insert(
+        &mut self,
+        index: usize,
+        at: Duration,
+        fragment: TrackFragment,
+    ) -> &mut Self {
+        let fragment = delay(at, fragment);
+        self.dirty.extend(fragment.keys().map(|key| (index, *key)));
+
+        let track = self.fragment(index);
+        *track = core::mem::take(track).merge(fragment);
+        self
+    }
+
+    /// Removes the clip of action `id` along with the action.
+    ///
+    /// Returns `false` if no track holds the clip.
+    pub fn remove(&mut self, id: ActionId) -> bool {
+        let Some(index) = self.track_of(id) else {
+            return false;
+        };
+        let Some((key, _)) = self.fragment(index).remove_clip(id)
+        else {
+            return false;
+        };
+
+        remove_action::<W>(
+            self.timeline.action_table_mut(),
+            &mut self.pipeline_counts,
+            id,
+        );
+        self.dirty.insert((index, key));
+        true
+    }
+
+    /// Moves the clip of action `id` to `start` with a new
+    /// `duration`, within the same track.
+    ///
+    /// Returns `false` if no track holds the clip. Overlapping clips
+    /// of the same [`ActionKey`] panic in debug builds.
+    pub fn retime(
+        &mut self,
+        id: ActionId,
+        start: Duration,
+        duration: Duration,
+    ) -> bool {
+        let Some(index) = self.track_of(id) else {
+            return false;
+        };
+        let fragment = self.fragment(index);
+        let Some((key, clip)) = fragment.remove_clip(id) else {
+            return false;
+        };
+
+        fragment.insert_clip(
+            key,
+            ActionClip {
+                start,
+                duration,
+                ..clip
+            },
+        );
+        self.dirty.insert((index, key));
+        true
+    }
+
+    /// Recompiles the edited tracks and re-bakes the touched
+    /// sequences of the tracks that were already baked.
+    pub fn finish(self, subject_world: &W) {
+        let Self {
+            timeline,
+            registry,
+            pipeline_counts,
+            fragments,
+            dirty,
+        } = self;
+
+        for (index, fragment) in fragments {
+            let prev = &timeline.tracks[index];
+            let mut track = prev.dilations().iter().fold(
+                fragment.compile(),
+                |track, dilation| {
+                    track.dilate(
+                        dilation.start,
+                        dilation.end,
+                        dilation.speed,
+                    )
+                },
+            );
+            track.set_weight(prev.weight());
+            if let Some(mask) = prev.mask() {
+                track.set_mask(mask.iter().copied());
+            }
+            Arc::make_mut(&mut timeline.tracks)[index] = track;
+        }
+        timeline.pipeline_counts =
+            pipeline_counts.into_iter().collect();
+
+        for index in 0..timeline.tracks.len() {
+            if !timeline.baked[index] {
+                continue;
+            }
+
+            let filter =
+                |key: &ActionKey| dirty.contains(&(index, *key));
+            timeline.bake_filtered(
+                index,
+                registry,
+                subject_world,
+                Some(&filter),
+                true,
+                false,
+            );
+        }
+    }
+
+    /// Returns the decompiled track at `index`.
+    fn fragment(&mut self, index: usize) -> &mut TrackFragment {
+        self.fragments.entry(index).or_insert_with(|| {
+            self.timeline.tracks[index].to_fragment()
+        })
+    }
+
+    /// Finds the index of the track holding the clip of action `id`.
+    fn track_of(&self, id: ActionId) -> Option<usize> {
+        (0..self.timeline.tracks.len()).find(|index| {
+            match self.fragments.get(index) {
+                Some(fragment) => fragment.contains_clip(id),
+                None => {
+                    self.timeline.tracks[*index].contains_clip(id)
+                }
+            }
+        })
+    }
+}
@@ -1,11 +1,14 @@
+use core::ops::{Bound, RangeBounds};
 use core::time::Duration;
 
 use alloc::boxed::Box;
 use alloc::vec::Vec;
 use field_path::field::UntypedField;
 use hashbrown::HashMap;
+use nonempty::NonEmpty;
 
-use crate::action::{ActionClip, ActionKey};
+use crate::action::{ActionClip, ActionId, ActionKey, EaseFn};
+use crate::modifier::noise;
 use crate::sequence::Sequence;
 
 pub trait TrackOrdering {
@@ -14,6 +17,19 @@ pub trait TrackOrdering {
     fn ord_all(self) -> TrackFragment;
     fn ord_any(self) -> TrackFragment;
     fn ord_flow(self, delay: Duration) -> TrackFragment;
+    fn ord_overlap(self, overlap: Duration) -> TrackFragment;
+    fn ord_align_end(self) -> TrackFragment;
+    fn ord_align_center(self) -> TrackFragment;
+    fn ord_align_to(self, time: Duration) -> TrackFragment;
+    fn ord_flow_with(
+        self,
+        delay: impl FnMut(usize) -> Duration,
+    ) -> TrackFragment;
+    fn ord_flow_eased(
+        self,
+        delay: Duration,
+        ease: EaseFn,
+    ) -> TrackFragment;
 }
 
 impl<T> TrackOrdering for T
@@ -35,6 +51,39 @@ where
     fn ord_flow(self, delay: Duration) -> TrackFragment {
         flow(delay, self)
     }
+
+    fn ord_overlap(self, overlap: Duration) -> TrackFragment {
+        self::overlap(overlap, self)
+    }
+
+    fn ord_align_end(self) -> TrackFragment {
+        align_end(self)
+    }
+
+    fn ord_align_center(self) -> TrackFragment {
+        align_center(self)
+    }
+
+    fn ord_align_to(self, time: Duration) -> TrackFragment {
+        align_to(time, self)
+    }
+
+    fn ord_flow_with(
+        self,
+        delay: impl FnMut(usize) -> Duration,
+    ) -> TrackFragment {
+        flow_with(delay, self)
+    }
+
+    fn ord_flow_eased(
+        self,
+        delay: Duration,
+        ease: EaseFn,
+    ) -> TrackFragment {
+        Stagger::new(delay)
+            .with_ease(ease)
+            .apply(self, |track, _| track)
+    }
 }
 
 /// Run all [`TrackFragment`]s one after another.
@@ -53,6 +102,7 @@ pub fn chain(
             other_sequence.delay(chain_duration);
             track = track.upsert_sequence(key, other_sequence);
         }
+        track.absorb_remaps(other_track.remaps, chain_duration);
 
         chain_duration =
             chain_duration.saturating_add(other_track.duration);
@@ -78,6 +128,7 @@ pub fn all(
         for (key, other_sequence) in other_track.sequences.drain() {
             track = track.upsert_sequence(key, other_sequence);
         }
+        track.absorb_remaps(other_track.remaps, Duration::ZERO);
     }
 
     track.duration = max_duration;
@@ -100,6 +151,7 @@ pub fn any(
         for (key, other_sequence) in other_track.sequences.drain() {
             track = track.upsert_sequence(key, other_sequence);
         }
+        track.absorb_remaps(other_track.remaps, Duration::ZERO);
     }
 
     track.duration = min_duration;
@@ -111,6 +163,114 @@ pub fn any(
 pub fn flow(
     delay: Duration,
     tracks: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    flow_with(|_| delay, tracks)
+}
+
+/// Run one [`Track`] after another, starting each one `overlap`
+/// before the previous one ends, or along with the previous one if
+/// it is shorter than `overlap`.
+#[must_use = "This function consumes all the given tracks and returns a modified one."]
+pub fn overlap(
+    overlap: Duration,
+    tracks: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    let tracks = tracks.into_iter().collect::<Vec<_>>();
+    let delays = tracks
+        .iter()
+        .map(|track| track.duration.saturating_sub(overlap))
+        .collect::<Vec<_>>();
+
+    flow_with(|index| delays[index - 1], tracks)
+}
+
+/// Run all [`Track`]s concurrently, ending them together with the
+/// longest one.
+#[must_use = "This function consumes all the given tracks and returns a modified one."]
+pub fn align_end(
+    tracks: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    align(Alignment::End, tracks)
+}
+
+/// Run all [`Track`]s concurrently, centering them on the middle of
+/// the longest one.
+#[must_use = "This function consumes all the given tracks and returns a modified one."]
+pub fn align_center(
+    tracks: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    align(Alignment::Center, tracks)
+}
+
+/// Run all [`Track`]s concurrently, ending them at `time`. Tracks
+/// longer than `time` start at zero instead.
+#[must_use = "This function consumes all the given tracks and returns a modified one."]
+pub fn align_to(
+    time: Duration,
+    tracks: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    align(Alignment::To(time), tracks)
+}
+
+/// Run all [`Track`]s concurrently, each delayed as the `alignment`
+/// places it.
+#[must_use = "This function consumes all the given tracks and returns a modified one."]
+pub fn align(
+    alignment: Alignment,
+    tracks: impl IntoIterator<Item = TrackFragment>,
+) -> TrackFragment {
+    let tracks = tracks.into_iter().collect::<Vec<_>>();
+    let longest = tracks
+        .iter()
+        .map(|track| track.duration)
+        .max()
+        .unwrap_or_default();
+
+    all(tracks.into_iter().map(|track| {
+        let offset = alignment.offset(track.duration, longest);
+        delay(offset, track)
+    }))
+}
+
+/// Where [`align`] places each track.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize)
+)]
+pub enum Alignment {
+    /// End with the longest track.
+    End,
+    /// Center on the middle of the longest track.
+    Center,
+    /// End at a fixed time.
+    To(Duration),
+}
+
+impl Alignment {
+    /// The start of a track lasting `duration`, aligned among tracks
+    /// of which the `longest` lasts that long.
+    pub fn offset(
+        self,
+        duration: Duration,
+        longest: Duration,
+    ) -> Duration {
+        match self {
+            Self::End => longest.saturating_sub(duration),
+            Self::Center => longest.saturating_sub(duration) / 2,
+            Self::To(time) => time.saturating_sub(duration),
+        }
+    }
+}
+
+/// Run one [`Track`] after another, starting the track at `index`
+/// `delay(index)` after the previous one.
+///
+/// See [`Stagger`] for spacing the tracks along an ease instead.
+#[must_use = "This function consumes all the given tracks and returns a modified one."]
+pub fn flow_with(
+    mut delay: impl FnMut(usize) -> Duration,
+    tracks: impl IntoIterator<Item = TrackFragment>,
 ) -> TrackFragment {
     let mut tracks_iter = tracks.into_iter();
     let mut track = tracks_iter.next().unwrap_or_default();
@@ -118,8 +278,8 @@ pub fn flow(
     let mut flow_delay = Duration::ZERO;
     let mut final_duration = track.duration;
 
-    for other_track in tracks_iter {
-        flow_delay = flow_delay.saturating_add(delay);
+    for (index, other_track) in (1..).zip(tracks_iter) {
+        flow_delay = flow_delay.saturating_add(delay(index));
         final_duration = flow_delay
             .saturating_add(other_track.duration)
             .max(final_duration);
@@ -128,6 +288,7 @@ pub fn flow(
             sequence.delay(flow_delay);
             track = track.upsert_sequence(key, sequence);
         }
+        track.absorb_remaps(other_track.remaps, flow_delay);
     }
 
     track.duration = final_duration;
@@ -143,14 +304,132 @@ pub fn delay(
     for sequence in track.sequences.values_mut() {
         sequence.delay(delay);
     }
+    for remap in track.remaps.iter_mut() {
+        remap.delay(delay);
+    }
 
     track.duration = track.duration.saturating_add(delay);
     track
 }
 
+/// Plays the fragment created by `f` for every item, starting each
+/// one `delay` after the previous. `f` also gets the index of the
+/// item.
+///
+/// See [`Stagger`] for eased or randomized spacing.
+///
+/// ```ignore
+/// let track = stagger(entities, cs(5), |entity, _| {
+///     builder.act(entity, field, |x| x + 1.0).play(s(1))
+/// });
+/// ```
+#[must_use = "This function creates a new track from the given items."]
+pub fn stagger<E>(
+    items: impl IntoIterator<Item = E>,
+    delay: Duration,
+    f: impl FnMut(E, usize) -> TrackFragment,
+) -> TrackFragment {
+    Stagger::new(delay).apply(items, f)
+}
+
+/// The spacing between the starts of a [`stagger`].
+///
+/// ```ignore
+/// let track = Stagger::new(cs(5))
+///     .with_ease(ease::quad::ease_in)
+///     .with_jitter(0.5, 7)
+///     .apply(entities, |entity, _| {
+///         builder.act(entity, field, |x| x + 1.0).play(s(1))
+///     });
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Stagger {
+    /// Average delay between the starts of two items.
+    pub delay: Duration,
+    /// Spreads the starts along this ease over the same total span,
+    /// instead of evenly.
+    pub ease: Option<EaseFn>,
+    /// Offsets each start by noise of up to this fraction of
+    /// [`Self::delay`], in either direction.
+    pub jitter: f32,
+    /// Seed of the [`Self::jitter`] noise.
+    pub seed: u32,
+}
+
+impl Stagger {
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            ease: None,
+            jitter: 0.0,
+            seed: 0,
+        }
+    }
+
+    /// Builder method for setting [`Self::ease`].
+    pub fn with_ease(mut self, ease: EaseFn) -> Self {
+        self.ease = Some(ease);
+        self
+    }
+
+    /// Builder method for setting [`Self::jitter`] and
+    /// [`Self::seed`].
+    pub fn with_jitter(mut self, jitter: f32, seed: u32) -> Self {
+        self.jitter = jitter;
+        self.seed = seed;
+        self
+    }
+
+    /// The start of item `index` out of `count` items, never before
+    /// [`Duration::ZERO`].
+    pub fn offset(&self, index: usize, count: usize) -> Duration {
+        let delay = self.delay.as_secs_f64();
+        let span = delay * count.saturating_sub(1) as f64;
+
+        let mut start = match (self.ease, count) {
+            (Some(ease), 2..) => {
+                let t = index as f32 / (count - 1) as f32;
+                span * ease(t) as f64
+            }
+            _ => delay * index as f64,
+        };
+        if self.jitter != 0.0 {
+            let noise = noise(index as f32, self.seed);
+            start += delay * (self.jitter * noise) as f64;
+        }
+
+        Duration::from_secs_f64(start.max(0.0))
+    }
+
+    /// Plays the fragment created by `f` for every item, starting
+    /// each one at its [`Self::offset`].
+    #[must_use = "This function creates a new track from the given items."]
+    pub fn apply<E>(
+        &self,
+        items: impl IntoIterator<Item = E>,
+        mut f: impl FnMut(E, usize) -> TrackFragment,
+    ) -> TrackFragment {
+        let fragments = items
+            .into_iter()
+            .enumerate()
+            .map(|(index, item)| f(item, index))
+            .collect::<Vec<_>>();
+        let count = fragments.len();
+
+        all(fragments.into_iter().enumerate().map(
+            |(index, fragment)| {
+                delay(self.offset(index, count), fragment)
+            },
+        ))
+    }
+}
+
+#[derive(Clone)]
 pub struct TrackFragment {
     sequences: HashMap<ActionKey, Sequence>,
     duration: Duration,
+    /// Time warps in the order they were applied, inner ones first.
+    remaps: Vec<TimeRemap>,
 }
 
 impl TrackFragment {
@@ -158,6 +437,7 @@ impl TrackFragment {
         Self {
             sequences: HashMap::new(),
             duration: Duration::ZERO,
+            remaps: Vec::new(),
         }
     }
 
@@ -165,7 +445,168 @@ impl TrackFragment {
         Self {
             duration: clip.duration,
             sequences: [(key, Sequence::new(clip))].into(),
+            remaps: Vec::new(),
+        }
+    }
+
+    /// Warps the time of every clip in this fragment along `curve`,
+    /// e.g. [`ease::cubic::ease_in_out`] for a slow-motion ramp at
+    /// both ends, without changing the duration of the fragment.
+    ///
+    /// The warp is applied when sampling, so the progress within
+    /// each clip follows the curve too. It stays with the clips of
+    /// this fragment once combined with others.
+    ///
+    /// ```ignore
+    /// let track = [a, b, c]
+    ///     .ord_chain()
+    ///     .remap_time(ease::cubic::ease_in_out);
+    /// ```
+    ///
+    /// [`ease::cubic::ease_in_out`]: crate::ease::cubic::ease_in_out
+    pub fn remap_time(mut self, curve: EaseFn) -> Self {
+        let mut keys =
+            self.sequences.keys().copied().collect::<Vec<_>>();
+        keys.sort();
+
+        self.remaps.push(TimeRemap {
+            start: Duration::ZERO,
+            end: self.duration,
+            curve,
+            mirrored: false,
+            keys: keys.into_boxed_slice(),
+        });
+        self
+    }
+
+    /// Plays this fragment backwards: the clips run in reverse order
+    /// and each one replays the baked segment of its action from end
+    /// to start, e.g. to reuse an intro as the outro.
+    ///
+    /// ```ignore
+    /// let intro = [a, b].ord_chain();
+    /// let outro = intro.clone().reversed();
+    /// let track = [intro, hold, outro].ord_chain();
+    /// ```
+    ///
+    /// Reversed clips do not run their action, they need the
+    /// segment baked by a forward clip of the same action earlier
+    /// in the timeline and are skipped otherwise.
+    pub fn reversed(mut self) -> Self {
+        let duration = self.duration;
+        self.sequences = self
+            .sequences
+            .into_iter()
+            .map(|(key, sequence)| (key, sequence.reverse(duration)))
+            .collect();
+
+        for remap in self.remaps.iter_mut() {
+            remap.reverse(duration);
+        }
+
+        self
+    }
+
+    /// Cuts this fragment at `at` into the clips before and after
+    /// it, the latter moved to start at zero.
+    ///
+    /// Clips straddling the cut are split in two, each half playing
+    /// its share of the baked segment of the action. Time remaps cut
+    /// by `at` apply their whole curve to the part they keep.
+    pub fn split(self, at: Duration) -> (Self, Self) {
+        let at = at.min(self.duration);
+        let mut before = Self {
+            duration: at,
+            ..Self::new()
+        };
+        let mut after = Self {
+            duration: self.duration - at,
+            ..Self::new()
+        };
+
+        for (key, sequence) in self.sequences {
+            let (head, tail) = sequence.split(at);
+            if let Some(head) = head {
+                before.sequences.insert(key, head);
+            }
+            if let Some(tail) = tail {
+                after.sequences.insert(key, tail);
+            }
+        }
+
+        for remap in self.remaps {
+            before.remaps.extend(remap.crop(Duration::ZERO, at));
+            after.remaps.extend(remap.crop(at, self.duration));
+        }
+
+        (before, after)
+    }
+
+    /// Keeps the part of this fragment within `range`, moved to
+    /// start at zero, see [`Self::split`].
+    ///
+    /// ```ignore
+    /// let highlight = [a, b, c].ord_chain().slice(ms(500)..s(2));
+    /// ```
+    ///
+    /// A clip cut at the start of the range still bakes its action
+    /// from the value before it, as the clips it followed are gone.
+    pub fn slice(self, range: impl RangeBounds<Duration>) -> Self {
+        let end = match range.end_bound() {
+            Bound::Included(end) | Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.duration,
+        };
+        let start = match range.start_bound() {
+            Bound::Included(start) | Bound::Excluded(start) => *start,
+            Bound::Unbounded => Duration::ZERO,
+        };
+
+        let (_, rest) = self.split(start);
+        rest.split(end.saturating_sub(start)).0
+    }
+
+    /// Plays every clip of this fragment at `speed` times the normal
+    /// rate by scaling their start times and durations.
+    ///
+    /// Once compiled, [`Track::with_speed`] plays a whole track at a
+    /// different rate without touching the clips.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `speed` is not a positive finite number.
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        assert!(
+            speed.is_finite() && speed > 0.0,
+            "Speed must be positive and finite, got {speed}!"
+        );
+
+        let scale = |time: Duration| time.div_f64(speed as f64);
+        for sequence in self.sequences.values_mut() {
+            for clip in sequence.clips.iter_mut() {
+                clip.start = scale(clip.start);
+                clip.duration = scale(clip.duration);
+            }
+        }
+        for remap in self.remaps.iter_mut() {
+            remap.start = scale(remap.start);
+            remap.end = scale(remap.end);
         }
+
+        self.duration = scale(self.duration);
+        self
+    }
+
+    /// Takes over the `remaps` of another fragment whose clips were
+    /// delayed by `offset`.
+    fn absorb_remaps(
+        &mut self,
+        remaps: Vec<TimeRemap>,
+        offset: Duration,
+    ) {
+        self.remaps.extend(remaps.into_iter().map(|mut remap| {
+            remap.delay(offset);
+            remap
+        }));
     }
 
     /// Updates or inserts a [`Sequence`] in a track.
@@ -199,6 +640,86 @@ impl TrackFragment {
         self
     }
 
+    /// Merges the clips of `other` into this fragment, keeping each
+    /// sequence in time order.
+    ///
+    /// Unlike [`Self::upsert_sequence`], the clips of `other` may
+    /// start before existing ones as long as they do not overlap.
+    pub fn merge(mut self, other: TrackFragment) -> Self {
+        for (key, sequence) in other.sequences {
+            match self.sequences.get_mut(&key) {
+                Some(existing) => {
+                    for clip in sequence {
+                        existing.insert(clip);
+                    }
+                }
+                None => {
+                    self.sequences.insert(key, sequence);
+                }
+            }
+        }
+
+        self.duration = self.duration.max(other.duration);
+        self.remaps.extend(other.remaps);
+        self
+    }
+
+    /// Iterates over the [`ActionKey`] of every sequence.
+    pub fn keys(&self) -> impl Iterator<Item = &ActionKey> {
+        self.sequences.keys()
+    }
+
+    /// Returns `true` if the fragment holds a clip of action `id`.
+    pub(crate) fn contains_clip(&self, id: ActionId) -> bool {
+        self.sequences.values().any(|sequence| {
+            sequence.clips.iter().any(|clip| clip.id == id)
+        })
+    }
+
+    /// Removes the clip of action `id`, returning its key and clip.
+    pub(crate) fn remove_clip(
+        &mut self,
+        id: ActionId,
+    ) -> Option<(ActionKey, ActionClip)> {
+        let key =
+            *self.sequences.iter().find_map(|(key, sequence)| {
+                sequence
+                    .clips
+                    .iter()
+                    .any(|clip| clip.id == id)
+                    .then_some(key)
+            })?;
+
+        let sequence = self.sequences.remove(&key)?;
+        match sequence.remove(id) {
+            Ok((clip, rest)) => {
+                if let Some(rest) = rest {
+                    self.sequences.insert(key, rest);
+                }
+                Some((key, clip))
+            }
+            Err(sequence) => {
+                self.sequences.insert(key, sequence);
+                None
+            }
+        }
+    }
+
+    /// Inserts a single clip into the sequence of `key`.
+    pub(crate) fn insert_clip(
+        &mut self,
+        key: ActionKey,
+        clip: ActionClip,
+    ) {
+        match self.sequences.get_mut(&key) {
+            Some(sequence) => sequence.insert(clip),
+            None => {
+                self.sequences.insert(key, Sequence::new(clip));
+            }
+        }
+        self.duration = self.duration.max(clip.end());
+    }
+
     pub fn compile(self) -> Track {
         let mut sequences =
             self.sequences.into_iter().collect::<Vec<_>>();
@@ -208,7 +729,13 @@ impl TrackFragment {
                 field_lookups: Box::new([]),
                 sequence_spans: Box::new([]),
                 clip_arena: Box::new([]),
-                duration: self.duration,
+                dilations: Vec::new(),
+                local_duration: self.duration,
+                playback_duration: self.duration,
+                speed: 1.0,
+                weight: 1.0,
+                mask: None,
+                remaps: self.remaps.into_boxed_slice(),
             };
         }
 
@@ -277,7 +804,13 @@ impl TrackFragment {
             field_lookups: field_lookups.into_boxed_slice(),
             sequence_spans: sequence_spans.into_boxed_slice(),
             clip_arena,
-            duration,
+            dilations: Vec::new(),
+            local_duration: duration,
+            playback_duration: duration,
+            speed: 1.0,
+            weight: 1.0,
+            mask: None,
+            remaps: self.remaps.into_boxed_slice(),
         }
     }
 }
@@ -313,11 +846,32 @@ pub struct Track {
     /// Contiguous storage of all action clips.
     clip_arena: Box<[ActionClip]>,
 
-    /// Total duration of the track.
+    /// Non-overlapping [`Dilation`]s sorted by their start time.
+    dilations: Vec<Dilation>,
+
+    /// Total duration of the track in local time.
     ///
     /// Guaranteed to be `>=` the end of every clip in `clip_arena`.
     /// See [`TrackFragment::compile`].
-    duration: Duration,
+    local_duration: Duration,
+
+    /// Total duration of the track in playback time, after applying
+    /// all `dilations` and `speed`.
+    playback_duration: Duration,
+
+    /// Playback rate of the whole track, see [`Self::set_speed`].
+    speed: f32,
+
+    /// Influence of the track on the fields of `mask`, see
+    /// [`Self::set_weight`].
+    weight: f32,
+
+    /// Fields affected by `weight`, all of them if `None`.
+    mask: Option<Box<[UntypedField]>>,
+
+    /// Time warps of some sequences, inner ones first. See
+    /// [`TrackFragment::remap_time`].
+    remaps: Box<[TimeRemap]>,
 }
 
 impl Track {
@@ -337,6 +891,58 @@ impl Track {
         )
     }
 
+    /// Decompiles the track back into a [`TrackFragment`], leaving
+    /// out its [`Dilation`]s and [`Self::speed`].
+    pub fn to_fragment(&self) -> TrackFragment {
+        let sequences = self
+            .sequence_spans
+            .iter()
+            .filter_map(|(key, span)| {
+                let clips = NonEmpty::from_slice(self.clips(*span))?;
+                Some((*key, Sequence { clips }))
+            })
+            .collect();
+
+        TrackFragment {
+            sequences,
+            duration: self.local_duration,
+            remaps: self.remaps.to_vec(),
+        }
+    }
+
+    /// Embeds this track as a fragment of another, starting `offset`
+    /// into its local time and playing at `speed` times the normal
+    /// rate. A compiled sub-animation can then be placed many times
+    /// without re-running the builder code:
+    ///
+    /// ```ignore
+    /// let pop = b.act(button, scale, |s| s * 1.2).play(ms(200));
+    /// let pop = pop.compile();
+    /// let track = [
+    ///     pop.nest(Duration::ZERO, 1.0),
+    ///     pop.nest(ms(100), 2.0),
+    /// ]
+    /// .ord_chain();
+    /// ```
+    ///
+    /// The clips keep pointing at the actions of this track, so it
+    /// must come from the same [`TimelineBuilder`]. Its
+    /// [`Dilation`]s are left out, see [`Self::to_fragment`].
+    ///
+    /// [`TimelineBuilder`]: crate::timeline::TimelineBuilder
+    pub fn nest(
+        &self,
+        offset: Duration,
+        speed: f32,
+    ) -> TrackFragment {
+        self.to_fragment().slice(offset..).with_speed(speed)
+    }
+
+    /// Returns `true` if the track holds a clip of action `id`.
+    pub fn contains_clip(&self, id: ActionId) -> bool {
+        self.clip_arena.iter().any(|clip| clip.id == id)
+    }
+
     #[inline]
     pub fn field_lookups(&self) -> &[(UntypedField, Span)] {
         &self.field_lookups
@@ -352,9 +958,341 @@ impl Track {
         &self.clip_arena[span.offset..span.offset + span.len]
     }
 
+    /// Plays the local time window `start..end` at `speed` times the
+    /// normal rate, e.g. `0.25` for a 4x slow-motion.
+    ///
+    /// Every clip in the window is affected the same way, so the
+    /// track stays in sync without re-authoring any clip durations.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `speed` is not a positive finite number, if
+    /// `start > end`, or if the window overlaps an existing one.
+    pub fn dilate(
+        mut self,
+        start: Duration,
+        end: Duration,
+        speed: f32,
+    ) -> Self {
+        assert!(
+            speed.is_finite() && speed > 0.0,
+            "Dilation speed must be positive and finite, got {speed}!"
+        );
+        assert!(
+            start <= end,
+            "Dilation must not end before it starts!"
+        );
+
+        let index = self
+            .dilations
+            .partition_point(|dilation| dilation.start < start);
+
+        let overlaps_prev = index
+            .checked_sub(1)
+            .is_some_and(|i| self.dilations[i].end > start);
+        let overlaps_next = self
+            .dilations
+            .get(index)
+            .is_some_and(|next| next.start < end);
+        assert!(
+            !overlaps_prev && !overlaps_next,
+            "Dilations cannot overlap!"
+        );
+
+        self.dilations.insert(index, Dilation { start, end, speed });
+        self.playback_duration =
+            self.playback_time(self.local_duration);
+        self
+    }
+
+    /// Maps a playback time to the local time of the clips, clamped
+    /// within \[0.0..=[`Self::local_duration`]\].
+    pub fn local_time(&self, playback_time: Duration) -> Duration {
+        if playback_time >= self.playback_duration {
+            return self.local_duration;
+        }
+
+        let playback_time = playback_time.mul_f32(self.speed);
+        let mut local = Duration::ZERO;
+        let mut playback = Duration::ZERO;
+
+        for dilation in self.dilations.iter() {
+            let gap = dilation.start.saturating_sub(local);
+            if playback_time <= playback + gap {
+                break;
+            }
+            playback += gap;
+            local = dilation.start;
+
+            let window = dilation.playback_len();
+            if playback_time <= playback + window {
+                return local
+                    + (playback_time - playback)
+                        .mul_f32(dilation.speed);
+            }
+            playback += window;
+            local = dilation.end;
+        }
+
+        (local + (playback_time - playback)).min(self.local_duration)
+    }
+
+    /// Maps a local time of the clips to its playback time. The
+    /// inverse of [`Self::local_time`].
+    pub fn playback_time(&self, local_time: Duration) -> Duration {
+        self.dilated_time(local_time).div_f32(self.speed)
+    }
+
+    /// Maps a local time of the clips to its playback time at normal
+    /// [`Self::speed`].
+    fn dilated_time(&self, local_time: Duration) -> Duration {
+        let local_time = local_time.min(self.local_duration);
+        let mut playback = Duration::ZERO;
+        let mut local = Duration::ZERO;
+
+        for dilation in self.dilations.iter() {
+            if local_time <= dilation.start {
+                break;
+            }
+            playback += dilation.start - local;
+
+            if local_time <= dilation.end {
+                return playback
+                    + (local_time - dilation.start)
+                        .div_f32(dilation.speed);
+            }
+            playback += dilation.playback_len();
+            local = dilation.end;
+        }
+
+        playback + (local_time - local)
+    }
+
+    #[inline]
+    pub fn dilations(&self) -> &[Dilation] {
+        &self.dilations
+    }
+
+    /// Total duration of the track in playback time, with all
+    /// [`Dilation`]s and [`Self::speed`] applied.
     #[inline]
     pub fn duration(&self) -> Duration {
-        self.duration
+        self.playback_duration
+    }
+
+    /// Total duration of the track in the local time of the clips.
+    #[inline]
+    pub fn local_duration(&self) -> Duration {
+        self.local_duration
+    }
+
+    #[inline]
+    pub fn remaps(&self) -> &[TimeRemap] {
+        &self.remaps
+    }
+
+    /// Maps a local time of the track to the time the clips of `key`
+    /// are sampled at, through every [`TimeRemap`] of the sequence.
+    pub fn sequence_time(
+        &self,
+        key: &ActionKey,
+        local_time: Duration,
+    ) -> Duration {
+        // Outer remaps were applied last, so they warp first.
+        self.remaps
+            .iter()
+            .rev()
+            .filter(|remap| remap.applies_to(key))
+            .fold(local_time, |time, remap| remap.remap(time))
+    }
+
+    /// Builder method for [`Self::set_speed`].
+    pub fn with_speed(mut self, speed: f32) -> Self {
+        self.set_speed(speed);
+        self
+    }
+
+    /// Plays the whole track at `speed` times the normal rate, on top
+    /// of its [`Dilation`]s, e.g. `0.5` for a background loop running
+    /// at half the rate of the rest of the timeline.
+    ///
+    /// Unlike [`TrackFragment::with_speed`], the clips are left as
+    /// authored and only the mapping from playback time to local
+    /// time changes, so it can change between plays.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `speed` is not a positive finite number.
+    pub fn set_speed(&mut self, speed: f32) {
+        assert!(
+            speed.is_finite() && speed > 0.0,
+            "Track speed must be positive and finite, got {speed}!"
+        );
+
+        self.speed = speed;
+        self.playback_duration =
+            self.playback_time(self.local_duration);
+    }
+
+    #[inline]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Builder method for [`Self::set_weight`].
+    pub fn with_weight(mut self, weight: f32) -> Self {
+        self.set_weight(weight);
+        self
+    }
+
+    /// Builder method for [`Self::set_mask`].
+    pub fn with_mask<F: Into<UntypedField>>(
+        mut self,
+        fields: impl IntoIterator<Item = F>,
+    ) -> Self {
+        self.set_mask(fields);
+        self
+    }
+
+    /// Sets how much the sampled values of this track are blended
+    /// over the current values of the fields of [`Self::mask`],
+    /// clamped within \[0.0..=1.0\]. `0.0` leaves the fields
+    /// untouched and `1.0` overwrites them.
+    ///
+    /// Applied when sampling, so it can change during playback
+    /// without re-baking.
+    ///
+    /// ## Panic
+    ///
+    /// Panics if `weight` is NaN.
+    pub fn set_weight(&mut self, weight: f32) {
+        assert!(!weight.is_nan(), "Track weight must not be NaN!");
+        self.weight = weight.clamp(0.0, 1.0);
+    }
+
+    /// Limits [`Self::weight`] to `fields`. Fields outside of the
+    /// mask are sampled at full weight.
+    pub fn set_mask<F: Into<UntypedField>>(
+        &mut self,
+        fields: impl IntoIterator<Item = F>,
+    ) {
+        self.mask =
+            Some(fields.into_iter().map(Into::into).collect());
+    }
+
+    /// Applies [`Self::weight`] to every field again.
+    pub fn clear_mask(&mut self) {
+        self.mask = None;
+    }
+
+    #[inline]
+    pub fn weight(&self) -> f32 {
+        self.weight
+    }
+
+    #[inline]
+    pub fn mask(&self) -> Option<&[UntypedField]> {
+        self.mask.as_deref()
+    }
+
+    /// The weight this track samples `field` at.
+    pub fn field_weight(&self, field: &UntypedField) -> f32 {
+        match &self.mask {
+            Some(mask) if !mask.contains(field) => 1.0,
+            _ => self.weight,
+        }
+    }
+}
+
+/// A window of a [`Track`] that plays at a different speed.
+///
+/// See [`Track::dilate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dilation {
+    /// Start of the window in local time.
+    pub start: Duration,
+    /// End of the window in local time.
+    pub end: Duration,
+    /// Playback rate within the window, `1.0` being normal speed.
+    pub speed: f32,
+}
+
+impl Dilation {
+    /// The time it takes to play through this window.
+    #[inline]
+    pub fn playback_len(&self) -> Duration {
+        (self.end - self.start).div_f32(self.speed)
+    }
+}
+
+/// A curve warping the local time of some sequences of a [`Track`]
+/// within a window.
+///
+/// See [`TrackFragment::remap_time`].
+#[derive(Debug, Clone)]
+pub struct TimeRemap {
+    /// Start of the window in local time.
+    pub start: Duration,
+    /// End of the window in local time.
+    pub end: Duration,
+    /// Maps the progress through the window to the progress of the
+    /// clips within it.
+    pub curve: EaseFn,
+    /// Applies the [`Self::curve`] from the end of the window, for
+    /// [`TrackFragment::reversed`] fragments.
+    pub mirrored: bool,
+    /// Sorted keys of the warped sequences.
+    keys: Box<[ActionKey]>,
+}
+
+impl TimeRemap {
+    #[inline]
+    pub fn applies_to(&self, key: &ActionKey) -> bool {
+        self.keys.binary_search(key).is_ok()
+    }
+
+    /// Warps `time` if it is within the window.
+    pub fn remap(&self, time: Duration) -> Duration {
+        if time <= self.start || time >= self.end {
+            return time;
+        }
+
+        let len = (self.end - self.start).as_secs_f64();
+        let t = (time - self.start).as_secs_f64() / len;
+        let t = if self.mirrored {
+            1.0 - (self.curve)(1.0 - t as f32)
+        } else {
+            (self.curve)(t as f32)
+        };
+        let warped = t as f64 * len;
+
+        self.start + Duration::from_secs_f64(warped.max(0.0))
+    }
+
+    fn delay(&mut self, duration: Duration) {
+        self.start = self.start.saturating_add(duration);
+        self.end = self.end.saturating_add(duration);
+    }
+
+    /// Returns the part of the window within `[start, end]`, moved
+    /// back by `start`.
+    fn crop(&self, start: Duration, end: Duration) -> Option<Self> {
+        let from = self.start.max(start);
+        let to = self.end.min(end);
+        (from < to).then(|| Self {
+            start: from - start,
+            end: to - start,
+            ..self.clone()
+        })
+    }
+
+    /// Mirrors the window within `[0, duration]`.
+    fn reverse(&mut self, duration: Duration) {
+        (self.start, self.end) = (
+            duration.saturating_sub(self.end),
+            duration.saturating_sub(self.start),
+        );
+        self.mirrored = !self.mirrored;
     }
 }
 
@@ -376,7 +1314,9 @@ pub struct Span {
 
 #[cfg(test)]
 mod tests {
-    use crate::action::{ActionId, IdRegistry, UntypedSubjectId};
+    use crate::action::{
+        ActionId, IdRegistry, SampleMode, UntypedSubjectId,
+    };
     use crate::time::{cs, ms, s};
 
     use super::*;
@@ -479,6 +1419,201 @@ mod tests {
         assert_eq!(seq_b.start(), cs(50));
     }
 
+    #[test]
+    fn overlap_starts_before_the_previous_end() {
+        let tracks = [("a", 100), ("b", 20), ("c", 100)].map(
+            |(path, centis)| {
+                TrackFragment::single(key(path), clip(centis))
+            },
+        );
+
+        let track = tracks.ord_overlap(cs(30));
+
+        assert_eq!(track.sequences[&key("b")].start(), cs(70));
+        // Shorter than the overlap, `c` starts along with `b`.
+        assert_eq!(track.sequences[&key("c")].start(), cs(70));
+        assert_eq!(track.duration, cs(170));
+    }
+
+    #[test]
+    fn align_places_tracks_against_the_longest() {
+        let tracks = || {
+            [("a", 100), ("b", 40)].map(|(path, centis)| {
+                TrackFragment::single(key(path), clip(centis))
+            })
+        };
+        let start = |track: &TrackFragment, path| {
+            track.sequences[&key(path)].start()
+        };
+
+        let end = tracks().ord_align_end();
+        assert_eq!(start(&end, "a"), Duration::ZERO);
+        assert_eq!(start(&end, "b"), cs(60));
+        assert_eq!(end.duration, cs(100));
+
+        let center = tracks().ord_align_center();
+        assert_eq!(start(&center, "b"), cs(30));
+        assert_eq!(center.duration, cs(100));
+
+        let to = tracks().ord_align_to(cs(150));
+        assert_eq!(start(&to, "a"), cs(50));
+        assert_eq!(start(&to, "b"), cs(110));
+        assert_eq!(to.duration, cs(150));
+
+        // `a` is longer than the time, so it starts at zero.
+        let to = tracks().ord_align_to(cs(80));
+        assert_eq!(start(&to, "a"), Duration::ZERO);
+        assert_eq!(start(&to, "b"), cs(40));
+        assert_eq!(to.duration, cs(100));
+    }
+
+    #[test]
+    fn stagger_matches_flow() {
+        let keys = ["a", "b", "c"];
+        let track = stagger(keys, cs(50), |path, _| {
+            TrackFragment::single(key(path), clip(100))
+        });
+
+        assert_eq!(track.duration, cs(200));
+        assert_eq!(track.sequences[&key("b")].start(), cs(50));
+        assert_eq!(track.sequences[&key("c")].start(), cs(100));
+    }
+
+    #[test]
+    fn stagger_eases_and_jitters_spacing() {
+        let eased = Stagger::new(cs(100)).with_ease(|t| t * t);
+        assert_eq!(eased.offset(0, 3), Duration::ZERO);
+        assert_eq!(eased.offset(1, 3), cs(50));
+        assert_eq!(eased.offset(2, 3), cs(200));
+
+        let jittered = Stagger::new(cs(100)).with_jitter(0.5, 7);
+        for index in 0..8 {
+            let offset = jittered.offset(index, 8);
+            let even = cs(100 * index as u64);
+
+            assert_eq!(offset, jittered.offset(index, 8));
+            assert!(offset.abs_diff(even) <= cs(50));
+        }
+    }
+
+    #[test]
+    fn flow_with_per_index_delay() {
+        let tracks = ["a", "b", "c"]
+            .map(|path| TrackFragment::single(key(path), clip(100)));
+
+        let track =
+            tracks.ord_flow_with(|index| cs(10 * index as u64));
+
+        assert_eq!(track.sequences[&key("b")].start(), cs(10));
+        assert_eq!(track.sequences[&key("c")].start(), cs(30));
+        assert_eq!(track.duration, cs(130));
+    }
+
+    #[test]
+    fn flow_eased_keeps_the_total_span() {
+        let tracks = ["a", "b", "c"]
+            .map(|path| TrackFragment::single(key(path), clip(100)));
+
+        let track = tracks.ord_flow_eased(cs(100), |t| t * t);
+
+        assert_eq!(track.sequences[&key("b")].start(), cs(50));
+        assert_eq!(track.sequences[&key("c")].start(), cs(200));
+        assert_eq!(track.duration, cs(300));
+    }
+
+    #[test]
+    fn remap_time_stays_with_its_sequences() {
+        let warped = TrackFragment::single(key("a"), clip(100))
+            .remap_time(|t| t * t);
+        let track =
+            [TrackFragment::single(key("b"), clip(100)), warped]
+                .ord_chain()
+                .compile();
+
+        let remap = &track.remaps()[0];
+        assert_eq!((remap.start, remap.end), (s(1), s(2)));
+
+        assert_eq!(
+            track.sequence_time(&key("a"), ms(1500)),
+            ms(1250)
+        );
+        assert_eq!(track.sequence_time(&key("a"), ms(500)), ms(500));
+        assert_eq!(
+            track.sequence_time(&key("b"), ms(1500)),
+            ms(1500)
+        );
+    }
+
+    #[test]
+    fn reversed_mirrors_clips() {
+        let track = [
+            TrackFragment::single(key("a"), clip(100)),
+            TrackFragment::single(key("b"), clip(200)),
+        ]
+        .ord_chain()
+        .reversed();
+
+        let clip_a = track.sequences[&key("a")].clips.first();
+        let clip_b = track.sequences[&key("b")].clips.first();
+
+        assert_eq!(track.duration, cs(300));
+        assert_eq!((clip_b.start, clip_b.reversed), (cs(0), true));
+        assert_eq!((clip_a.start, clip_a.reversed), (cs(200), true));
+        assert!(matches!(
+            clip_a.orient(SampleMode::End),
+            SampleMode::Start
+        ));
+    }
+
+    #[test]
+    fn split_cuts_straddling_clips() {
+        let fragment = [
+            TrackFragment::single(key("a"), clip(200)),
+            TrackFragment::single(key("b"), clip(100)),
+        ]
+        .ord_chain();
+
+        let (before, after) = fragment.clone().split(cs(50));
+        let head = before.sequences[&key("a")].clips.first();
+        let tail = after.sequences[&key("a")].clips.first();
+
+        assert_eq!(
+            (before.duration, after.duration),
+            (cs(50), cs(250))
+        );
+        assert_eq!((head.duration, head.to), (cs(50), 0.25));
+        assert_eq!((tail.start, tail.from), (cs(0), 0.25));
+        assert!(!before.sequences.contains_key(&key("b")));
+        assert_eq!(after.sequences[&key("b")].start(), cs(150));
+
+        let slice = fragment.slice(cs(100)..cs(250));
+        let clip_a = slice.sequences[&key("a")].clips.first();
+        let clip_b = slice.sequences[&key("b")].clips.first();
+
+        assert_eq!(slice.duration, cs(150));
+        assert_eq!((clip_a.from, clip_a.to), (0.5, 1.0));
+        assert_eq!((clip_b.start, clip_b.to), (cs(100), 0.5));
+    }
+
+    #[test]
+    fn nest_trims_and_scales_the_track() {
+        let track = [
+            TrackFragment::single(key("a"), clip(100)),
+            TrackFragment::single(key("b"), clip(100)),
+        ]
+        .ord_chain()
+        .compile();
+
+        let nested = track.nest(cs(50), 2.0);
+        let clip_a = nested.sequences[&key("a")].clips.first();
+        let clip_b = nested.sequences[&key("b")].clips.first();
+
+        assert_eq!(nested.duration, cs(75));
+        assert_eq!((clip_a.start, clip_a.duration), (cs(0), cs(25)));
+        assert_eq!(clip_a.from, 0.5);
+        assert_eq!((clip_b.start, clip_b.duration), (cs(25), cs(50)));
+    }
+
     #[test]
     fn delay_applies_offset() {
         let track = TrackFragment::single(key("a"), clip(200));
@@ -582,4 +1717,61 @@ mod tests {
         assert_eq!(track.duration(), Duration::ZERO);
         assert!(track.sequences_spans().is_empty());
     }
+
+    #[test]
+    fn dilation_stretches_playback_duration() {
+        let track = TrackFragment::single(key("a"), clip(400))
+            .compile()
+            .dilate(s(2), cs(250), 0.25);
+
+        // The 0.5s window now takes 2s to play through.
+        assert_eq!(track.local_duration(), s(4));
+        assert_eq!(track.duration(), cs(550));
+    }
+
+    #[test]
+    fn dilation_maps_playback_to_local_time() {
+        let track = TrackFragment::single(key("a"), clip(400))
+            .compile()
+            .dilate(s(2), cs(250), 0.25);
+
+        // Before the window.
+        assert_eq!(track.local_time(s(1)), s(1));
+        // Halfway through the window.
+        assert_eq!(track.local_time(s(3)), cs(225));
+        // After the window.
+        assert_eq!(track.local_time(cs(500)), cs(350));
+        assert_eq!(track.local_time(s(10)), s(4));
+
+        for local in [s(1), cs(225), cs(350), s(4)] {
+            assert_eq!(
+                track.local_time(track.playback_time(local)),
+                local
+            );
+        }
+    }
+
+    #[test]
+    fn speed_scales_on_top_of_dilations() {
+        let track = TrackFragment::single(key("a"), clip(400))
+            .compile()
+            .dilate(s(2), cs(250), 0.25)
+            .with_speed(2.0);
+
+        assert_eq!(track.local_duration(), s(4));
+        assert_eq!(track.duration(), cs(275));
+        // Halfway through the window.
+        assert_eq!(track.local_time(cs(150)), cs(225));
+        assert_eq!(track.playback_time(cs(225)), cs(150));
+        assert_eq!(track.to_fragment().compile().speed(), 1.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn overlapping_dilations_panic() {
+        let _ = TrackFragment::single(key("a"), clip(400))
+            .compile()
+            .dilate(s(1), s(2), 0.5)
+            .dilate(cs(150), s(3), 2.0);
+    }
 }
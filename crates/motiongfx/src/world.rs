@@ -10,3 +10,17 @@ pub trait SubjectSource<I: SubjectId, S: 'static> {
         f: impl FnOnce(&mut S) -> R,
     ) -> Option<R>;
 }
+
+/// Write access to the source `S` of every member of a group subject
+/// `I`, so that a single action can drive all of them.
+///
+/// [`SubjectSource`] reads the start value of the group when baking,
+/// typically from its first member. See [`Pipeline::grouped`].
+///
+/// [`Pipeline::grouped`]: crate::pipeline::Pipeline::grouped
+pub trait GroupSource<I: SubjectId, S: 'static>:
+    SubjectSource<I, S>
+{
+    /// Calls `f` on the source of every member of `id`.
+    fn apply_group(&mut self, id: I, f: impl FnMut(&mut S));
+}
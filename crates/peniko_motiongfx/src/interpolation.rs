@@ -1,9 +1,9 @@
 use motiongfx::prelude::Interpolation;
-use peniko::Color;
 use peniko::kurbo::{
-    Circle, CubicBez, Line, Point, QuadBez, Rect, RoundedRect,
-    RoundedRectRadii, Size, Vec2,
+    BezPath, Circle, CubicBez, Dashes, Line, PathEl, Point, QuadBez,
+    Rect, RoundedRect, RoundedRectRadii, Size, Stroke, Vec2,
 };
+use peniko::{Color, Fill};
 
 use crate::Peniko;
 
@@ -139,8 +139,153 @@ impl Interpolation<Peniko> for RoundedRect {
     }
 }
 
+impl Interpolation<Peniko> for BezPath {
+    /// Interpolates the points of two [`BezPath`]s with the same
+    /// sequence of elements.
+    ///
+    /// Paths with different structures can't be matched point to
+    /// point and switch over at the end instead.
+    fn interp(a: &Self, b: &Self, t: f32) -> Self {
+        let elements = a
+            .elements()
+            .iter()
+            .zip(b.elements())
+            .map(|(a, b)| interp_path_el(a, b, t))
+            .collect::<Option<_>>()
+            .filter(|_| a.elements().len() == b.elements().len());
+
+        match elements {
+            Some(elements) => BezPath::from_vec(elements),
+            None if t < 1.0 => a.clone(),
+            None => b.clone(),
+        }
+    }
+}
+
+/// Interpolates two [`PathEl`]s of the same kind, [`None`] otherwise.
+fn interp_path_el(a: &PathEl, b: &PathEl, t: f32) -> Option<PathEl> {
+    let p = |a, b| interp_point(a, b, t);
+
+    Some(match (a, b) {
+        (PathEl::MoveTo(a0), PathEl::MoveTo(b0)) => {
+            PathEl::MoveTo(p(a0, b0))
+        }
+        (PathEl::LineTo(a0), PathEl::LineTo(b0)) => {
+            PathEl::LineTo(p(a0, b0))
+        }
+        (PathEl::QuadTo(a0, a1), PathEl::QuadTo(b0, b1)) => {
+            PathEl::QuadTo(p(a0, b0), p(a1, b1))
+        }
+        (
+            PathEl::CurveTo(a0, a1, a2),
+            PathEl::CurveTo(b0, b1, b2),
+        ) => PathEl::CurveTo(p(a0, b0), p(a1, b1), p(a2, b2)),
+        (PathEl::ClosePath, PathEl::ClosePath) => PathEl::ClosePath,
+        _ => return None,
+    })
+}
+
+impl Interpolation<Peniko> for Fill {
+    /// [`Fill`] rules are discrete and switch over at the end.
+    #[inline]
+    fn interp(a: &Self, b: &Self, t: f32) -> Self {
+        if t < 1.0 { *a } else { *b }
+    }
+}
+
 impl Interpolation<Peniko> for Color {
     fn interp(a: &Self, b: &Self, t: f32) -> Self {
         a.lerp_rect(*b, t)
     }
 }
+
+impl Interpolation<Peniko> for Dashes {
+    /// Interpolates between two dash patterns of any length.
+    ///
+    /// kurbo alternates on/off across repeats of the pattern, so an
+    /// odd-length pattern is doubled first. Both patterns are then
+    /// cycled to a common length and interpolated element-wise. An
+    /// empty pattern is a solid line, matched as the dashes of the
+    /// other pattern with their gaps closed.
+    fn interp(a: &Self, b: &Self, t: f32) -> Self {
+        match (a.is_empty(), b.is_empty()) {
+            (true, true) => Dashes::new(),
+            (true, false) => interp_dashes(&solid_dashes(b), b, t),
+            (false, true) => interp_dashes(a, &solid_dashes(a), t),
+            (false, false) => interp_dashes(a, b, t),
+        }
+    }
+}
+
+impl Interpolation<Peniko> for Stroke {
+    /// Interpolates the width, miter limit, dash pattern and dash
+    /// offset of two [`Stroke`]s.
+    ///
+    /// Joins and caps are discrete and switch over at the end of the
+    /// interpolation. Animate [`Stroke::dash_offset`] on its own for
+    /// a marching-ants effect.
+    fn interp(a: &Self, b: &Self, t: f32) -> Self {
+        let discrete = if t < 1.0 { a } else { b };
+
+        Stroke {
+            width: lerp_f64(a.width, b.width, t),
+            join: discrete.join,
+            miter_limit: lerp_f64(a.miter_limit, b.miter_limit, t),
+            start_cap: discrete.start_cap,
+            end_cap: discrete.end_cap,
+            dash_pattern: Dashes::interp(
+                &a.dash_pattern,
+                &b.dash_pattern,
+                t,
+            ),
+            dash_offset: lerp_f64(a.dash_offset, b.dash_offset, t),
+        }
+    }
+}
+
+/// Element-wise interpolation of two non-empty dash patterns.
+///
+/// Negative lengths are clamped to zero, and a pattern that collapses
+/// to a zero period becomes a solid line, as kurbo cannot dash it.
+fn interp_dashes(a: &[f64], b: &[f64], t: f32) -> Dashes {
+    let a = even_dashes(a);
+    let b = even_dashes(b);
+    let len = lcm(a.len(), b.len());
+
+    let dashes = (0..len)
+        .map(|i| lerp_f64(a[i % a.len()], b[i % b.len()], t).max(0.0))
+        .collect::<Dashes>();
+
+    if dashes.iter().sum::<f64>() > 0.0 {
+        dashes
+    } else {
+        Dashes::new()
+    }
+}
+
+/// A pattern with the same period as `dashes` that draws a solid
+/// line: every dash spans its gap and every gap is zero.
+fn solid_dashes(dashes: &[f64]) -> Dashes {
+    even_dashes(dashes)
+        .chunks_exact(2)
+        .flat_map(|pair| [pair[0] + pair[1], 0.0])
+        .collect()
+}
+
+/// Repeats an odd-length pattern once so that it reads as strict
+/// on/off pairs, matching how kurbo walks it.
+fn even_dashes(dashes: &[f64]) -> Dashes {
+    let mut even = Dashes::from_slice(dashes);
+    if dashes.len() % 2 == 1 {
+        even.extend_from_slice(dashes);
+    }
+    even
+}
+
+const fn lcm(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
@@ -1,16 +1,30 @@
 #![no_std]
 
+extern crate alloc;
+
 pub mod interpolation;
+pub mod morph;
+#[cfg(feature = "svg")]
+pub mod svg;
 pub mod trace;
+#[cfg(feature = "typst")]
+pub mod typst;
 
 pub mod prelude {
     pub use peniko;
     pub use peniko::kurbo;
 
     pub use crate::Peniko;
+    pub use crate::morph::act_morph;
+    #[cfg(feature = "svg")]
+    pub use crate::svg::{SvgPath, draw_on, load_svg};
     pub use crate::trace::{
         CubicTracer, LineTracer, PathTracer, QuadTracer, Trace,
     };
+    #[cfg(feature = "typst")]
+    pub use crate::typst::{
+        compile_typst, load_typst, load_typst_frame,
+    };
 }
 
 pub use motiongfx;
@@ -0,0 +1,379 @@
+//! Shape morphing between arbitrary [`BezPath`]s.
+//!
+//! Two paths rarely share the same sequence of elements, which the
+//! plain [`BezPath`] interpolation needs. [`align_paths`] rewrites
+//! both into matching cubic segments, so every in-between is a smooth
+//! blend of the two shapes:
+//!
+//! ```ignore
+//! act_morph(&mut builder, entity, path!(<Icon>::path), star_path)
+//!     .with_ease(ease::cubic::ease_in_out)
+//!     .play(s(1));
+//! ```
+
+use alloc::vec::Vec;
+
+use motiongfx::prelude::*;
+use motiongfx::subject::SubjectId;
+use peniko::kurbo::{
+    BezPath, CubicBez, ParamCurve, PathEl, Point, QuadBez, Shape,
+};
+
+use crate::interpolation::interp_point;
+
+/// Morphs the field from its current path into `to`.
+///
+/// Both paths are [aligned](align_paths) on every sample, so the
+/// start path can be anything the field holds at bake time.
+pub fn act_morph<'a, W, I, S>(
+    builder: &'a mut TimelineBuilder<'_, W>,
+    target: I,
+    field: impl ActionField<S, BezPath>,
+    to: BezPath,
+) -> InterpActionBuilder<'a, BezPath>
+where
+    W: SubjectSource<I, S> + 'static,
+    I: SubjectId,
+    S: 'static,
+{
+    builder
+        .act_builder(target, field, move |_| to.clone())
+        .with_interp(morph)
+}
+
+/// Interpolates between two arbitrary paths, see [`align_paths`].
+pub fn morph(a: &BezPath, b: &BezPath, t: f32) -> BezPath {
+    if t <= 0.0 {
+        return a.clone();
+    }
+    if t >= 1.0 {
+        return b.clone();
+    }
+
+    let (a, b) = align_subpaths(a, b);
+    let mut path = BezPath::new();
+    for (a, b) in a.iter().zip(b.iter()) {
+        let closed = a.closed && b.closed;
+        push_subpath(
+            &mut path,
+            a.segments.iter().zip(b.segments.iter()).map(|(a, b)| {
+                let p = |a, b| interp_point(a, b, t);
+                CubicBez::new(
+                    p(&a.p0, &b.p0),
+                    p(&a.p1, &b.p1),
+                    p(&a.p2, &b.p2),
+                    p(&a.p3, &b.p3),
+                )
+            }),
+            closed,
+        );
+    }
+    path
+}
+
+/// Rewrites `a` and `b` into paths with the same sequence of
+/// elements, tracing the same shapes.
+///
+/// Subpaths are paired in order, with missing ones grown from a
+/// point at the center of their counterpart. Paired subpaths are
+/// split into the same number of cubic segments, and closed ones are
+/// rotated so that their segments line up as closely as possible.
+pub fn align_paths(a: &BezPath, b: &BezPath) -> (BezPath, BezPath) {
+    let (a, b) = align_subpaths(a, b);
+
+    let mut path_a = BezPath::new();
+    let mut path_b = BezPath::new();
+    for (a, b) in a.into_iter().zip(b) {
+        let closed = a.closed && b.closed;
+        push_subpath(&mut path_a, a.segments, closed);
+        push_subpath(&mut path_b, b.segments, closed);
+    }
+    (path_a, path_b)
+}
+
+/// A subpath as a non-empty list of cubic segments.
+struct Subpath {
+    segments: Vec<CubicBez>,
+    closed: bool,
+}
+
+impl Subpath {
+    /// A single segment collapsed onto `point`.
+    fn point(point: Point) -> Self {
+        Self {
+            segments: alloc::vec![CubicBez::new(
+                point, point, point, point
+            )],
+            closed: false,
+        }
+    }
+
+    fn center(&self) -> Point {
+        let mut path = BezPath::new();
+        push_subpath(&mut path, self.segments.iter().copied(), false);
+        path.bounding_box().center()
+    }
+
+    /// Splits the longest segments until there are `count` of them.
+    fn split_to(&mut self, count: usize) {
+        while self.segments.len() < count {
+            let (i, _) = self
+                .segments
+                .iter()
+                .map(polygon_length)
+                .enumerate()
+                .fold((0, f64::NEG_INFINITY), |max, (i, len)| {
+                    if len > max.1 { (i, len) } else { max }
+                });
+            let (left, right) = self.segments[i].subdivide();
+            self.segments[i] = left;
+            self.segments.insert(i + 1, right);
+        }
+    }
+
+    /// Rotates a closed subpath so its segments start as close as
+    /// possible to the ones of `other`.
+    fn rotate_to(&mut self, other: &Subpath) {
+        let n = self.segments.len();
+        let cost = |offset: usize| {
+            other
+                .segments
+                .iter()
+                .enumerate()
+                .map(|(i, seg)| {
+                    let start = self.segments[(i + offset) % n].p0;
+                    (seg.p0 - start).hypot2()
+                })
+                .sum::<f64>()
+        };
+
+        let best = (0..n).map(|offset| (offset, cost(offset))).fold(
+            (0, f64::INFINITY),
+            |best, curr| {
+                if curr.1 < best.1 { curr } else { best }
+            },
+        );
+        self.segments.rotate_left(best.0);
+    }
+}
+
+fn align_subpaths(
+    a: &BezPath,
+    b: &BezPath,
+) -> (Vec<Subpath>, Vec<Subpath>) {
+    let mut a = subpaths(a);
+    let mut b = subpaths(b);
+
+    // Grow missing subpaths from their counterpart's center.
+    let grow = |s: &Subpath| Subpath::point(s.center());
+    a.extend(b.iter().skip(a.len()).map(grow));
+    b.extend(a.iter().skip(b.len()).map(grow));
+
+    for (a, b) in a.iter_mut().zip(b.iter_mut()) {
+        let count = a.segments.len().max(b.segments.len());
+        a.split_to(count);
+        b.split_to(count);
+
+        if a.closed && b.closed {
+            b.rotate_to(a);
+        }
+    }
+
+    (a, b)
+}
+
+fn subpaths(path: &BezPath) -> Vec<Subpath> {
+    let mut subpaths = Vec::new();
+    let mut segments = Vec::new();
+    let mut start = Point::ZERO;
+    let mut last = Point::ZERO;
+
+    let mut finish = |segments: &mut Vec<CubicBez>, point, closed| {
+        if segments.is_empty() {
+            subpaths.push(Subpath::point(point));
+        } else {
+            subpaths.push(Subpath {
+                segments: core::mem::take(segments),
+                closed,
+            });
+        }
+    };
+
+    let mut open = false;
+    for el in path.elements() {
+        match *el {
+            PathEl::MoveTo(p) => {
+                if open {
+                    finish(&mut segments, start, false);
+                }
+                start = p;
+                last = p;
+                open = true;
+                continue;
+            }
+            PathEl::LineTo(p) => segments.push(line(last, p)),
+            PathEl::QuadTo(p1, p2) => {
+                segments.push(QuadBez::new(last, p1, p2).raise())
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                segments.push(CubicBez::new(last, p1, p2, p3))
+            }
+            PathEl::ClosePath => {
+                if last != start {
+                    segments.push(line(last, start));
+                }
+                finish(&mut segments, start, true);
+                last = start;
+                open = false;
+                continue;
+            }
+        }
+        last = segments.last().map_or(last, |seg| seg.p3);
+        open = true;
+    }
+    if open {
+        finish(&mut segments, start, false);
+    }
+
+    subpaths
+}
+
+fn push_subpath(
+    path: &mut BezPath,
+    segments: impl IntoIterator<Item = CubicBez>,
+    closed: bool,
+) {
+    let mut segments = segments.into_iter().peekable();
+    if let Some(first) = segments.peek() {
+        path.move_to(first.p0);
+    }
+    for seg in segments {
+        path.curve_to(seg.p1, seg.p2, seg.p3);
+    }
+    if closed {
+        path.close_path();
+    }
+}
+
+/// A straight cubic from `p0` to `p3`.
+fn line(p0: Point, p3: Point) -> CubicBez {
+    CubicBez::new(
+        p0,
+        p0.lerp(p3, 1.0 / 3.0),
+        p0.lerp(p3, 2.0 / 3.0),
+        p3,
+    )
+}
+
+/// Length of the control polygon, a cheap upper bound of the arc
+/// length.
+fn polygon_length(seg: &CubicBez) -> f64 {
+    (seg.p1 - seg.p0).hypot()
+        + (seg.p2 - seg.p1).hypot()
+        + (seg.p3 - seg.p2).hypot()
+}
+
+#[cfg(test)]
+mod tests {
+    use peniko::kurbo::Rect;
+
+    use super::*;
+
+    fn square(x: f64, y: f64, size: f64) -> BezPath {
+        Rect::new(x, y, x + size, y + size).to_path(0.1)
+    }
+
+    fn starts(path: &BezPath) -> Vec<Point> {
+        path.elements()
+            .iter()
+            .filter_map(|el| match el {
+                PathEl::MoveTo(p) => Some(*p),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn triangle() -> BezPath {
+        let mut path = BezPath::new();
+        path.move_to((0.0, 0.0));
+        path.line_to((10.0, 0.0));
+        path.quad_to((10.0, 10.0), (5.0, 10.0));
+        path.close_path();
+        path
+    }
+
+    #[test]
+    fn aligned_paths_have_matching_elements() {
+        let (a, b) =
+            align_paths(&square(0.0, 0.0, 10.0), &triangle());
+
+        assert_eq!(a.elements().len(), b.elements().len());
+        // 4 segments each, closed: a move, 4 curves and a close.
+        assert_eq!(a.elements().len(), 6);
+        assert!(
+            a.elements()
+                .iter()
+                .zip(b.elements())
+                .all(|(a, b)| core::mem::discriminant(a)
+                    == core::mem::discriminant(b))
+        );
+        assert_eq!(b.elements().last(), Some(&PathEl::ClosePath));
+    }
+
+    #[test]
+    fn morph_ends_on_the_original_paths() {
+        let a = square(0.0, 0.0, 10.0);
+        let b = triangle();
+
+        assert_eq!(morph(&a, &b, 0.0), a);
+        assert_eq!(morph(&a, &b, 1.0), b);
+    }
+
+    #[test]
+    fn missing_subpaths_grow_from_a_point() {
+        let mut a = square(0.0, 0.0, 10.0);
+        a.extend(square(20.0, 0.0, 10.0));
+        let b = square(0.0, 0.0, 20.0);
+
+        let (_, aligned) = align_paths(&a, &b);
+        let grown = starts(&aligned)[1];
+        assert_eq!(grown, Point::new(25.0, 5.0));
+
+        let halfway = morph(&a, &b, 0.5);
+        assert_eq!(starts(&halfway).len(), 2);
+    }
+
+    #[test]
+    fn split_to_keeps_the_shape() {
+        let mut line = Subpath {
+            segments: alloc::vec![super::line(
+                Point::ZERO,
+                Point::new(8.0, 0.0)
+            )],
+            closed: false,
+        };
+        line.split_to(4);
+
+        assert_eq!(line.segments.len(), 4);
+        assert_eq!(line.segments[0].p0, Point::ZERO);
+        assert_eq!(line.segments[3].p3, Point::new(8.0, 0.0));
+        for pair in line.segments.windows(2) {
+            assert_eq!(pair[0].p3, pair[1].p0);
+        }
+    }
+
+    #[test]
+    fn closed_paths_are_rotated_to_line_up() {
+        let a = square(0.0, 0.0, 10.0);
+        // The same square, starting from its opposite corner.
+        let mut b = BezPath::new();
+        b.move_to((10.0, 10.0));
+        b.line_to((0.0, 10.0));
+        b.line_to((0.0, 0.0));
+        b.line_to((10.0, 0.0));
+        b.close_path();
+
+        let (a, b) = align_paths(&a, &b);
+        assert_eq!(starts(&a), starts(&b));
+    }
+}
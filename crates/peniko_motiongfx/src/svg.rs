@@ -0,0 +1,296 @@
+//! SVG import for drawing paths on over time, enabled with the `svg`
+//! feature.
+//!
+//! [`load_svg`] flattens an SVG document into [`SvgPath`]s. Each one
+//! carries its outline as a [`PathTracer`], so the visible part of
+//! the path can be trimmed from the timeline, and [`draw_on`] staggers
+//! that trim across many subpaths:
+//!
+//! ```ignore
+//! let paths = load_svg(include_str!("logo.svg"))?;
+//!
+//! // Store `paths` in the subject world under ids 0..paths.len().
+//! let track = draw_on(&mut builder, 0..paths.len(), s(1), ms(100));
+//! ```
+
+use alloc::vec::Vec;
+use core::time::Duration;
+
+use motiongfx::prelude::*;
+use motiongfx::subject::SubjectId;
+use peniko::kurbo::{Affine, BezPath, Cap, Join, Point, Stroke};
+use peniko::{Color, Fill};
+
+use crate::trace::PathTracer;
+
+pub use usvg::Error as SvgError;
+
+/// A single path of an SVG document.
+#[derive(Debug, Clone)]
+pub struct SvgPath {
+    /// The outline with the document transforms applied. Animate
+    /// [`PathTracer::t_end`] to draw it on.
+    pub outline: PathTracer,
+    /// The fill, if the path has a solid color fill.
+    pub fill: Option<SvgFill>,
+    /// The stroke, if the path has a solid color stroke.
+    pub stroke: Option<SvgStroke>,
+}
+
+impl SvgPath {
+    /// The visible part of the outline.
+    pub fn trimmed(&self) -> BezPath {
+        self.outline.trace()
+    }
+}
+
+/// The fill of an [`SvgPath`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvgFill {
+    pub rule: Fill,
+    pub color: Color,
+}
+
+/// The stroke of an [`SvgPath`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgStroke {
+    pub style: Stroke,
+    pub color: Color,
+}
+
+/// Parses `svg` and flattens every visible path in document order.
+///
+/// Gradient and pattern paints are not supported and are left out,
+/// as is text.
+pub fn load_svg(svg: &str) -> Result<Vec<SvgPath>, SvgError> {
+    let tree = usvg::Tree::from_str(svg, &usvg::Options::default())?;
+
+    let mut paths = Vec::new();
+    collect_paths(tree.root(), &mut paths);
+    Ok(paths)
+}
+
+/// Draws on every subject's [`SvgPath`] from nothing, one after
+/// another with `delay` in between.
+pub fn draw_on<W, I>(
+    builder: &mut TimelineBuilder<'_, W>,
+    subjects: impl IntoIterator<Item = I>,
+    duration: Duration,
+    delay: Duration,
+) -> TrackFragment
+where
+    W: SubjectSource<I, SvgPath> + 'static,
+    I: SubjectId,
+{
+    let field = motiongfx::path!(<SvgPath>::outline::t_end);
+
+    // Every path is hidden from the start, not only once its turn
+    // comes.
+    let (hide, draw): (Vec<_>, Vec<_>) = subjects
+        .into_iter()
+        .map(|id| {
+            (
+                builder
+                    .act_step(id, field, |_| 0.0)
+                    .play(Duration::ZERO),
+                builder.act(id, field, |_| 1.0).play(duration),
+            )
+        })
+        .unzip();
+
+    [hide.ord_all(), draw.ord_flow(delay)].ord_chain()
+}
+
+fn collect_paths(group: &usvg::Group, paths: &mut Vec<SvgPath>) {
+    for node in group.children() {
+        match node {
+            usvg::Node::Group(group) => collect_paths(group, paths),
+            usvg::Node::Path(path) if path.is_visible() => {
+                paths.push(convert_path(path))
+            }
+            _ => {}
+        }
+    }
+}
+
+fn convert_path(path: &usvg::Path) -> SvgPath {
+    use usvg::tiny_skia_path::PathSegment;
+
+    let p = |p: usvg::tiny_skia_path::Point| {
+        Point::new(p.x as f64, p.y as f64)
+    };
+
+    let mut outline = BezPath::new();
+    for segment in path.data().segments() {
+        match segment {
+            PathSegment::MoveTo(p0) => outline.move_to(p(p0)),
+            PathSegment::LineTo(p0) => outline.line_to(p(p0)),
+            PathSegment::QuadTo(p0, p1) => {
+                outline.quad_to(p(p0), p(p1))
+            }
+            PathSegment::CubicTo(p0, p1, p2) => {
+                outline.curve_to(p(p0), p(p1), p(p2))
+            }
+            PathSegment::Close => outline.close_path(),
+        }
+    }
+    let t = path.abs_transform();
+    outline.apply_affine(Affine::new(
+        [t.sx, t.ky, t.kx, t.sy, t.tx, t.ty].map(f64::from),
+    ));
+
+    let fill = path.fill().and_then(|fill| {
+        Some(SvgFill {
+            rule: match fill.rule() {
+                usvg::FillRule::NonZero => Fill::NonZero,
+                usvg::FillRule::EvenOdd => Fill::EvenOdd,
+            },
+            color: convert_paint(fill.paint(), fill.opacity())?,
+        })
+    });
+
+    let stroke = path.stroke().and_then(|stroke| {
+        let mut style = Stroke::new(stroke.width().get() as f64)
+            .with_miter_limit(stroke.miterlimit().get() as f64)
+            .with_join(match stroke.linejoin() {
+                usvg::LineJoin::Miter | usvg::LineJoin::MiterClip => {
+                    Join::Miter
+                }
+                usvg::LineJoin::Round => Join::Round,
+                usvg::LineJoin::Bevel => Join::Bevel,
+            })
+            .with_caps(match stroke.linecap() {
+                usvg::LineCap::Butt => Cap::Butt,
+                usvg::LineCap::Round => Cap::Round,
+                usvg::LineCap::Square => Cap::Square,
+            });
+        if let Some(dashes) = stroke.dasharray() {
+            style = style.with_dashes(
+                stroke.dashoffset() as f64,
+                dashes.iter().map(|&d| d as f64),
+            );
+        }
+
+        Some(SvgStroke {
+            style,
+            color: convert_paint(stroke.paint(), stroke.opacity())?,
+        })
+    });
+
+    SvgPath {
+        outline: PathTracer {
+            path: outline,
+            t_start: 0.0,
+            t_end: 1.0,
+        },
+        fill,
+        stroke,
+    }
+}
+
+fn convert_paint(
+    paint: &usvg::Paint,
+    opacity: usvg::Opacity,
+) -> Option<Color> {
+    match paint {
+        usvg::Paint::Color(c) => Some(Color::from_rgba8(
+            c.red,
+            c.green,
+            c.blue,
+            (opacity.get() * 255.0).round() as u8,
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use motiongfx::time::{cs, s};
+
+    use super::*;
+
+    const SVG: &str = r##"
+        <svg xmlns="http://www.w3.org/2000/svg"
+            width="20" height="20">
+            <g transform="translate(10 0)">
+                <path d="M0 0 L10 0" stroke="#ff0000"
+                    stroke-width="2" fill="none"/>
+            </g>
+            <rect width="5" height="5" fill="#0000ff"/>
+        </svg>
+    "##;
+
+    struct World(Vec<SvgPath>);
+
+    impl SubjectSource<u32, SvgPath> for World {
+        fn get_source(&self, id: u32) -> Option<&SvgPath> {
+            self.0.get(id as usize)
+        }
+
+        fn apply_source<R>(
+            &mut self,
+            id: u32,
+            f: impl FnOnce(&mut SvgPath) -> R,
+        ) -> Option<R> {
+            self.0.get_mut(id as usize).map(f)
+        }
+    }
+
+    #[test]
+    fn load_svg_flattens_paths_in_order() {
+        let paths = load_svg(SVG).unwrap();
+        assert_eq!(paths.len(), 2);
+
+        let line = &paths[0];
+        let mut expected = BezPath::new();
+        expected.move_to((10.0, 0.0));
+        expected.line_to((20.0, 0.0));
+        assert_eq!(line.outline.path, expected);
+        assert_eq!(line.trimmed(), expected);
+        assert_eq!(line.fill, None);
+
+        let stroke = line.stroke.as_ref().unwrap();
+        assert_eq!(stroke.style.width, 2.0);
+        assert_eq!(stroke.color, Color::from_rgba8(255, 0, 0, 255));
+
+        let rect = &paths[1];
+        assert!(rect.stroke.is_none());
+        assert_eq!(
+            rect.fill.map(|fill| fill.color),
+            Some(Color::from_rgba8(0, 0, 255, 255))
+        );
+    }
+
+    #[test]
+    fn draw_on_staggers_the_trim() {
+        let mut registry = Registry::new();
+        let mut world = World(load_svg(SVG).unwrap());
+
+        let mut b = TimelineBuilder::<World>::new(&mut registry);
+        let track = draw_on(&mut b, 0..2, s(1), cs(50)).compile();
+        assert_eq!(track.duration(), cs(150));
+
+        b.add_tracks(track);
+        let mut timeline = b.compile();
+        timeline.bake_actions(&registry, &world);
+
+        let mut trim_at = |time| {
+            timeline.set_target_time(time);
+            timeline.queue_actions();
+            timeline.sample_queued_actions(&registry, &mut world);
+            [0, 1].map(|i: usize| world.0[i].outline.t_end)
+        };
+
+        // The second path stays hidden until its turn.
+        assert_eq!(trim_at(cs(25)), [0.25, 0.0]);
+        assert_eq!(trim_at(cs(100)), [1.0, 0.5]);
+        assert_eq!(trim_at(cs(150)), [1.0, 1.0]);
+
+        let mut half = BezPath::new();
+        half.move_to((10.0, 0.0));
+        half.line_to((15.0, 0.0));
+        trim_at(cs(50));
+        assert_eq!(world.0[0].trimmed(), half);
+        assert_eq!(world.0[0].outline.t_start, 0.0);
+    }
+}
@@ -0,0 +1,150 @@
+//! Typst document import, enabled with the `typst` feature.
+//!
+//! Typst markup is compiled with [`compile_typst`], then converted
+//! page by page into [`SvgPath`]s, one per glyph or shape in
+//! document order, so titles and equations can be animated like any
+//! other SVG:
+//!
+//! ```ignore
+//! let document = compile_typst("= Hello $x^2$")?;
+//! let pages = load_typst(&document)?;
+//!
+//! // Store the glyphs of `pages[0]` under ids 0..pages[0].len() and
+//! // write the title on, glyph after glyph.
+//! let track = draw_on(&mut builder, 0..pages[0].len(), ms(400), ms(40));
+//! ```
+//!
+//! [`compile_typst`] only knows the fonts bundled with Typst. For
+//! imports, images, packages or other fonts, implement a
+//! [`typst::World`] and compile with [`typst::compile`] instead.
+
+use alloc::vec::Vec;
+
+use comemo::Prehashed;
+use typst::diag::{FileError, FileResult, SourceResult};
+use typst::eval::Tracer;
+use typst::foundations::{Bytes, Datetime};
+use typst::layout::Frame;
+use typst::model::Document;
+use typst::syntax::{FileId, Source};
+use typst::text::{Font, FontBook};
+use typst::{Library, World};
+
+use crate::svg::{SvgError, SvgPath, load_svg};
+
+/// Compiles standalone Typst `markup` with the fonts bundled with
+/// Typst.
+///
+/// Files, packages and `datetime.today()` are not available.
+pub fn compile_typst(markup: &str) -> SourceResult<Document> {
+    let world = MarkupWorld::new(markup);
+    typst::compile(&world, &mut Tracer::new())
+}
+
+/// Converts every page of `document` into its [`SvgPath`]s.
+pub fn load_typst(
+    document: &Document,
+) -> Result<Vec<Vec<SvgPath>>, SvgError> {
+    document
+        .pages
+        .iter()
+        .map(|page| load_typst_frame(&page.frame))
+        .collect()
+}
+
+/// Converts a single laid out `frame`, such as one page or an
+/// equation, into its [`SvgPath`]s.
+pub fn load_typst_frame(
+    frame: &Frame,
+) -> Result<Vec<SvgPath>, SvgError> {
+    load_svg(&typst_svg::svg(frame))
+}
+
+/// A [`World`] of a single source and the bundled fonts.
+struct MarkupWorld {
+    library: Prehashed<Library>,
+    book: Prehashed<FontBook>,
+    fonts: Vec<Font>,
+    main: Source,
+}
+
+impl MarkupWorld {
+    fn new(markup: &str) -> Self {
+        let fonts = typst_assets::fonts()
+            .flat_map(|data| Font::iter(Bytes::from_static(data)))
+            .collect::<Vec<_>>();
+
+        Self {
+            library: Prehashed::new(Library::default()),
+            book: Prehashed::new(FontBook::from_fonts(&fonts)),
+            fonts,
+            main: Source::detached(markup),
+        }
+    }
+}
+
+impl World for MarkupWorld {
+    fn library(&self) -> &Prehashed<Library> {
+        &self.library
+    }
+
+    fn book(&self) -> &Prehashed<FontBook> {
+        &self.book
+    }
+
+    fn main(&self) -> Source {
+        self.main.clone()
+    }
+
+    fn source(&self, id: FileId) -> FileResult<Source> {
+        if id == self.main.id() {
+            Ok(self.main.clone())
+        } else {
+            Err(not_found(id))
+        }
+    }
+
+    fn file(&self, id: FileId) -> FileResult<Bytes> {
+        Err(not_found(id))
+    }
+
+    fn font(&self, index: usize) -> Option<Font> {
+        self.fonts.get(index).cloned()
+    }
+
+    fn today(&self, _offset: Option<i64>) -> Option<Datetime> {
+        None
+    }
+}
+
+fn not_found(id: FileId) -> FileError {
+    FileError::NotFound(id.vpath().as_rootless_path().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn markup_compiles_into_filled_paths() {
+        let document =
+            compile_typst("#set page(width: auto, height: auto)\nHi")
+                .unwrap();
+        assert_eq!(document.pages.len(), 1);
+
+        let paths =
+            load_typst_frame(&document.pages[0].frame).unwrap();
+        // One outline per glyph.
+        assert_eq!(paths.len(), 2);
+        assert!(paths.iter().all(|path| path.fill.is_some()));
+        assert!(
+            paths.iter().all(|path| !path.outline.path.is_empty())
+        );
+    }
+
+    #[test]
+    fn invalid_markup_is_an_error() {
+        assert!(compile_typst("#undefined").is_err());
+        assert!(compile_typst("#image(\"missing.png\")").is_err());
+    }
+}
@@ -1,3 +1,5 @@
+//! Moves a cube with a custom stepped easing function.
+
 use bevy::color::palettes;
 use bevy::prelude::*;
 use bevy_examples::timeline_movement;
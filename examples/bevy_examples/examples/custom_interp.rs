@@ -1,3 +1,5 @@
+//! Moves a cube along an arc using a custom interpolation function.
+
 use core::f32::consts::FRAC_PI_2;
 
 use bevy::color::palettes;
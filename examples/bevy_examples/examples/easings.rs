@@ -1,3 +1,5 @@
+//! Compares every built-in easing function side by side.
+
 use bevy::camera::Hdr;
 use bevy::color::palettes;
 use bevy::light::NotShadowCaster;
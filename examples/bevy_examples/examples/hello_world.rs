@@ -1,3 +1,5 @@
+//! Animates the scale, position and rotation of a grid of cubes.
+
 use bevy::color::palettes;
 use bevy::light::NotShadowCaster;
 use bevy::prelude::*;
@@ -1,3 +1,5 @@
+//! The smallest timeline: moves a cube and logs when it completes.
+
 use bevy::prelude::*;
 use bevy_motiongfx::BevyMotionGfxPlugin;
 use bevy_motiongfx::manager::TimelineComplete;
@@ -1,3 +1,5 @@
+//! Renders a timeline frame by frame into image files.
+
 use core::f32::consts::FRAC_PI_2;
 
 use bevy::color::palettes;
@@ -13,6 +15,8 @@ use crate::pipelines_ready::*;
 const EXIT_TIME: f32 = 0.5;
 /// Directory where recorded frames will be saved.
 const SAVE_DIR: &str = "frames/";
+/// Frame rate of the recording.
+const FPS: u16 = 144;
 
 fn main() {
     App::new()
@@ -31,6 +35,9 @@ fn main() {
             screenshot.run_if(in_state(PipelineState::Ready)),
         )
         .add_systems(Update, check_final_frame)
+        // Step time by exactly one frame per update, regardless of
+        // how long rendering and saving take.
+        .insert_resource(RenderMode::new(FPS))
         .insert_resource(ExitDelayTimer(Timer::from_seconds(
             EXIT_TIME,
             TimerMode::Once,
@@ -120,7 +127,7 @@ fn spawn_timeline(
     let timeline = b.compile();
     commands.spawn((
         motiongfx.add_timeline(timeline),
-        FixedRatePlayer::new(144),
+        FixedRatePlayer::new(FPS),
     ));
 }
 
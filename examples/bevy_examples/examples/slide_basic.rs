@@ -1,4 +1,8 @@
-use std::time::Duration;
+//! Steps through multiple tracks like presentation slides.
+//!
+//! Arrow keys move between slides, space plays the current one
+//! (shift + space backwards), escape pauses, home and end jump to
+//! the first and last slide.
 
 use bevy::camera::Hdr;
 use bevy::color::palettes;
@@ -8,9 +12,12 @@ use bevy_motiongfx::prelude::*;
 
 fn main() {
     App::new()
-        .add_plugins((DefaultPlugins, BevyMotionGfxPlugin))
+        .add_plugins((
+            DefaultPlugins,
+            BevyMotionGfxPlugin,
+            PresenterPlugin,
+        ))
         .add_systems(Startup, (setup, spawn_timeline))
-        .add_systems(Update, slide_movement)
         .run();
 }
 
@@ -86,9 +93,22 @@ fn spawn_timeline(
     b.add_tracks([slide0, slide1]);
 
     let timeline = b.compile();
+    let slides = commands
+        .spawn((
+            motiongfx.add_timeline(timeline),
+            RealtimePlayer::new().with_playing(true),
+            Presenter,
+        ))
+        .id();
+
     commands.spawn((
-        motiongfx.add_timeline(timeline),
-        RealtimePlayer::new().with_playing(true),
+        SlideCounter::new(slides),
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(12.0),
+            bottom: Val::Px(12.0),
+            ..default()
+        },
     ));
 }
 
@@ -109,67 +129,3 @@ fn setup(mut commands: Commands) {
             .looking_at(Vec3::ZERO, Vec3::Y),
     ));
 }
-
-fn slide_movement(
-    mut motiongfx: ResMut<MotionGfxManager>,
-    mut q_timelines: Query<(&TimelineId, &mut RealtimePlayer)>,
-    keys: Res<ButtonInput<KeyCode>>,
-) {
-    for (id, mut player) in q_timelines.iter_mut() {
-        if let Some(timeline) = motiongfx.get_timeline_mut(id) {
-            if keys.just_pressed(KeyCode::ArrowRight) {
-                // Move to the start of the next track.
-                let target_index = timeline.curr_index() + 1;
-                timeline.set_target_track(target_index);
-                timeline.set_target_time(s(0));
-
-                player.set_playing(false);
-            }
-
-            if keys.just_pressed(KeyCode::ArrowLeft) {
-                // Move to the start of the previous track.
-                let target_index =
-                    timeline.curr_index().saturating_sub(1);
-                timeline.set_target_track(target_index);
-                timeline.set_target_time(s(0));
-
-                player.set_playing(false);
-            }
-
-            if keys.just_pressed(KeyCode::Space) {
-                if keys.any_pressed([
-                    KeyCode::ShiftLeft,
-                    KeyCode::ShiftRight,
-                ]) {
-                    player.set_playing(true).set_time_scale(-1.0);
-
-                    if timeline.curr_time() == Duration::ZERO
-                        && timeline.curr_index() > 0
-                    {
-                        // Move to the end of the previous track.
-                        let target_index =
-                            timeline.curr_index().saturating_sub(1);
-                        timeline.set_target_track(target_index);
-                        // Clamped to the new track's duration.
-                        timeline.set_target_time(Duration::MAX);
-                    }
-                } else {
-                    player.set_playing(true).set_time_scale(1.0);
-
-                    if timeline.is_track_end()
-                        && !timeline.is_complete()
-                    {
-                        // Move to the start of the next track.
-                        let target_index = timeline.curr_index() + 1;
-                        timeline.set_target_track(target_index);
-                        timeline.set_target_time(s(0));
-                    }
-                }
-            }
-
-            if keys.just_pressed(KeyCode::Escape) {
-                player.set_playing(false);
-            }
-        }
-    }
-}
@@ -0,0 +1,160 @@
+//! Plays a timeline in the browser and exports it without touching
+//! the filesystem.
+//!
+//! Runs natively as well. For the web, with `wasm-server-runner`
+//! installed as the runner set in `.cargo/config.toml`:
+//!
+//! ```sh
+//! cargo run -p bevy_examples --example web_demo \
+//!     --target wasm32-unknown-unknown
+//! ```
+//!
+//! Space replays the timeline, E exports it as frames. Each frame
+//! is logged here, a web app would draw it onto an `OffscreenCanvas`
+//! or hand it to a WebCodecs encoder.
+
+use core::time::Duration;
+
+use bevy::color::palettes;
+use bevy::prelude::*;
+use bevy_motiongfx::BevyMotionGfxPlugin;
+use bevy_motiongfx::prelude::*;
+
+/// Frame rate of the export.
+const FPS: u16 = 30;
+
+fn main() {
+    App::new()
+        .add_plugins((
+            DefaultPlugins.set(WindowPlugin {
+                primary_window: Some(Window {
+                    title: "MotionGfx on the web".into(),
+                    // Renders into `<canvas id="motiongfx">` if the
+                    // page has one, and follows its size.
+                    canvas: Some("#motiongfx".into()),
+                    fit_canvas_to_parent: true,
+                    ..default()
+                }),
+                ..default()
+            }),
+            BevyMotionGfxPlugin,
+            ExportPlugin,
+        ))
+        .add_systems(Startup, (setup, spawn_timeline))
+        .add_systems(Update, controls)
+        .add_observer(log_frame)
+        .add_observer(resume_playback)
+        .run();
+}
+
+#[derive(Component)]
+struct Demo;
+
+fn spawn_timeline(
+    mut commands: Commands,
+    mut motiongfx: ResMut<MotionGfxManager>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let cube = commands
+        .spawn((
+            Mesh3d(meshes.add(Cuboid::default())),
+            MeshMaterial3d(materials.add(
+                StandardMaterial::from_color(
+                    palettes::tailwind::SKY_400,
+                ),
+            )),
+            Transform::from_xyz(-3.0, 0.0, 0.0),
+        ))
+        .id();
+
+    let mut b = motiongfx.create_builder();
+    let track = [
+        b.act(cube, path!(<Transform>::translation::x), |x| x + 6.0)
+            .with_ease(ease::cubic::ease_in_out)
+            .play(s(1)),
+        b.act(cube, path!(<Transform>::rotation), |r| {
+            r * Quat::from_rotation_y(core::f32::consts::PI)
+        })
+        .play(s(1)),
+    ]
+    .ord_all()
+    .compile();
+
+    b.add_tracks(track);
+    let timeline = b.compile();
+
+    commands.spawn((
+        Demo,
+        motiongfx.add_timeline(timeline),
+        RealtimePlayer::new().with_playing(true),
+    ));
+}
+
+fn controls(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut q_demo: Query<
+        (Entity, Option<&mut RealtimePlayer>),
+        With<Demo>,
+    >,
+) {
+    let Ok((entity, player)) = q_demo.single_mut() else {
+        return;
+    };
+    // Exporting, the export drives the timeline.
+    let Some(mut player) = player else {
+        return;
+    };
+
+    if keys.just_pressed(KeyCode::Space) {
+        player.seek(Duration::ZERO).set_playing(true);
+    }
+
+    if keys.just_pressed(KeyCode::KeyE) {
+        commands.entity(entity).remove::<RealtimePlayer>();
+        commands.insert_resource(ExportSettings::new(
+            entity,
+            FPS,
+            ExportOutput::Frames,
+        ));
+    }
+}
+
+fn log_frame(frame: On<ExportedFrame>) {
+    info!(
+        "Exported frame {} ({}x{}, {} bytes)",
+        frame.frame,
+        frame.width,
+        frame.height,
+        frame.rgba.len()
+    );
+}
+
+fn resume_playback(
+    finished: On<ExportFinished>,
+    mut commands: Commands,
+    q_demo: Query<Entity, With<Demo>>,
+) {
+    info!(
+        "Exported {} frames: {:?}",
+        finished.frames, finished.result
+    );
+
+    if let Ok(entity) = q_demo.single() {
+        commands.entity(entity).insert(RealtimePlayer::new());
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        Camera3d::default(),
+        Transform::from_xyz(0.0, 0.0, 10.0),
+    ));
+
+    commands.spawn((
+        DirectionalLight::default(),
+        Transform::from_xyz(3.0, 10.0, 5.0)
+            .looking_at(Vec3::ZERO, Vec3::Y),
+    ));
+}
@@ -0,0 +1,196 @@
+//! Lists and runs every example in the workspace.
+//!
+//! ```sh
+//! cargo run -p examples_gallery           # pick from a menu
+//! cargo run -p examples_gallery -- list   # list all examples
+//! cargo run -p examples_gallery -- all    # run all in sequence
+//! cargo run -p examples_gallery -- hello_world
+//! ```
+//!
+//! Descriptions are taken from the first paragraph of each example's
+//! module doc comment.
+
+use std::io::{self, BufRead, Write};
+use std::process::{Command, ExitCode};
+
+struct Example {
+    package: &'static str,
+    name: &'static str,
+    source: &'static str,
+}
+
+impl Example {
+    /// First paragraph of the `//!` doc comment of the example.
+    fn description(&self) -> String {
+        self.source
+            .lines()
+            .map_while(|line| line.strip_prefix("//!"))
+            .map(str::trim)
+            .take_while(|line| !line.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Runs the example through cargo, blocking until it exits.
+    fn run(&self) -> io::Result<bool> {
+        println!(
+            "Running `{}` from `{}`...",
+            self.name, self.package
+        );
+
+        let cargo =
+            std::env::var("CARGO").unwrap_or_else(|_| "cargo".into());
+        let status = Command::new(cargo)
+            .args(["run", "-p", self.package, "--example", self.name])
+            .status()?;
+
+        Ok(status.success())
+    }
+}
+
+macro_rules! example {
+    ($dir:literal, $package:literal, $name:literal) => {
+        Example {
+            package: $package,
+            name: $name,
+            source: include_str!(concat!(
+                "../../../",
+                $dir,
+                "/examples/",
+                $name,
+                ".rs"
+            )),
+        }
+    };
+}
+
+const EXAMPLES: &[Example] = &[
+    example!("crates/motiongfx", "motiongfx", "custom_world"),
+    example!("examples/bevy_examples", "bevy_examples", "minimal"),
+    example!(
+        "examples/bevy_examples",
+        "bevy_examples",
+        "hello_world"
+    ),
+    example!("examples/bevy_examples", "bevy_examples", "easings"),
+    example!(
+        "examples/bevy_examples",
+        "bevy_examples",
+        "custom_ease"
+    ),
+    example!(
+        "examples/bevy_examples",
+        "bevy_examples",
+        "custom_interp"
+    ),
+    example!(
+        "examples/bevy_examples",
+        "bevy_examples",
+        "slide_basic"
+    ),
+    example!("examples/bevy_examples", "bevy_examples", "recording"),
+    example!("examples/bevy_examples", "bevy_examples", "dock_demo"),
+    example!("examples/bevy_examples", "bevy_examples", "web_demo"),
+    example!(
+        "examples/vello_winit_example",
+        "vello_winit_example",
+        "lissajous"
+    ),
+];
+
+fn main() -> ExitCode {
+    let result = match std::env::args().nth(1).as_deref() {
+        Some("list") => {
+            list();
+            Ok(true)
+        }
+        Some("all") => run_all(),
+        Some(name) => {
+            match EXAMPLES.iter().find(|e| e.name == name) {
+                Some(example) => example.run(),
+                None => {
+                    eprintln!("Unknown example `{name}`.\n");
+                    list();
+                    Ok(false)
+                }
+            }
+        }
+        None => pick(),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn list() {
+    let width =
+        EXAMPLES.iter().map(|e| e.name.len()).max().unwrap_or(0);
+
+    for (i, example) in EXAMPLES.iter().enumerate() {
+        println!(
+            "{:>2}. {:<width$}  {}",
+            i + 1,
+            example.name,
+            example.description()
+        );
+    }
+}
+
+/// Runs every example one after another, reporting the failed ones
+/// at the end. Useful as a manual regression pass.
+fn run_all() -> io::Result<bool> {
+    let mut failed = Vec::new();
+
+    for example in EXAMPLES {
+        if !example.run()? {
+            failed.push(example.name);
+        }
+    }
+
+    if failed.is_empty() {
+        println!("All {} examples ran successfully.", EXAMPLES.len());
+    } else {
+        println!("Failed examples: {}", failed.join(", "));
+    }
+
+    Ok(failed.is_empty())
+}
+
+/// Prompts for examples to run until the input is empty.
+fn pick() -> io::Result<bool> {
+    let mut lines = io::stdin().lock().lines();
+
+    loop {
+        list();
+        print!("\nPick an example (empty to quit): ");
+        io::stdout().flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            return Ok(true);
+        };
+        let line = line.trim();
+        if line.is_empty() {
+            return Ok(true);
+        }
+
+        let example = line
+            .parse::<usize>()
+            .ok()
+            .and_then(|i| EXAMPLES.get(i.checked_sub(1)?))
+            .or_else(|| EXAMPLES.iter().find(|e| e.name == line));
+
+        match example {
+            Some(example) => {
+                example.run()?;
+            }
+            None => println!("No example matches `{line}`."),
+        }
+        println!();
+    }
+}
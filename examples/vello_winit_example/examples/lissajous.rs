@@ -1,3 +1,5 @@
+//! Draws a table of Lissajous curves with Vello and winit.
+
 use core::f64;
 use std::time::{Duration, Instant};
 
@@ -164,8 +166,7 @@ impl LissajousTableDemo {
 
         // Track 0: one column + one row draw in simultaneously per step.
         let n_steps = vert_entries.len().max(horiz_entries.len());
-        let mut pair_tracks = Vec::with_capacity(n_steps);
-        for i in 0..n_steps {
+        let grid_track = stagger(0..n_steps, cs(5), |i, _| {
             let v = vert_entries.get(i).map(|&(id, p1)| {
                 b.act(id, path!(<GridLine>::line::p1), move |_| p1)
                     .with_ease(ease::cubic::ease_in_out)
@@ -176,15 +177,14 @@ impl LissajousTableDemo {
                     .with_ease(ease::cubic::ease_in_out)
                     .play(cs(60))
             });
-            pair_tracks.push(match (v, h) {
+            match (v, h) {
                 (Some(v), Some(h)) => [v, h].ord_flow(ms(25)),
                 (Some(v), None) => v,
                 (None, Some(h)) => h,
                 (None, None) => unreachable!(),
-            });
-        }
-        let grid_track =
-            pair_tracks.into_iter().ord_flow(cs(5)).compile();
+            }
+        })
+        .compile();
         b.add_tracks(grid_track);
 
         // Track 1: curves draw in and out, looped by the caller.